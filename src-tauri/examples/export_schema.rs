@@ -0,0 +1,9 @@
+//! Print the JSON Schemas the frontend's types are generated from:
+//! `cargo run --example export_schema > ../src/schemas/pueo.schema.json`
+fn main() {
+    let schema = pueo_lib::frontend_schema();
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&schema).expect("schemas serialize to JSON")
+    );
+}
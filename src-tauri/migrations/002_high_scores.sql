@@ -0,0 +1,11 @@
+-- High scores submitted by games using the saveHighScore effect
+CREATE TABLE IF NOT EXISTS high_scores (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    game_id TEXT NOT NULL,
+    score INTEGER NOT NULL,
+    achieved_at TEXT NOT NULL,
+    FOREIGN KEY (game_id) REFERENCES games(id) ON DELETE CASCADE
+);
+
+-- Index for fetching a game's best scores
+CREATE INDEX IF NOT EXISTS idx_high_scores_game_id ON high_scores(game_id, score DESC);
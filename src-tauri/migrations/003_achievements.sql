@@ -0,0 +1,8 @@
+-- Achievements unlocked while playing a game (definitions live in the spec)
+CREATE TABLE IF NOT EXISTS achievements (
+    game_id TEXT NOT NULL,
+    achievement_id TEXT NOT NULL,
+    unlocked_at TEXT NOT NULL,
+    PRIMARY KEY (game_id, achievement_id),
+    FOREIGN KEY (game_id) REFERENCES games(id) ON DELETE CASCADE
+);
@@ -0,0 +1,9 @@
+-- Full-text index over each game's title, description, and spec text
+-- (object ids, action names, key concepts); kept in sync by the app
+CREATE VIRTUAL TABLE IF NOT EXISTS games_fts USING fts5(
+    game_id UNINDEXED,
+    title,
+    description,
+    spec_text,
+    tokenize = 'porter unicode61'
+);
@@ -0,0 +1,9 @@
+-- PNG previews captured from the running game, one per game version
+CREATE TABLE IF NOT EXISTS thumbnails (
+    game_id TEXT NOT NULL,
+    version INTEGER NOT NULL,
+    png BLOB NOT NULL,
+    created_at TEXT NOT NULL,
+    PRIMARY KEY (game_id, version),
+    FOREIGN KEY (game_id) REFERENCES games(id) ON DELETE CASCADE
+);
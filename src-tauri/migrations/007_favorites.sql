@@ -0,0 +1,2 @@
+-- Starred games surface at the top of the library
+ALTER TABLE games ADD COLUMN favorite INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,2 @@
+-- Optional milestone names such as "playtested" on versions
+ALTER TABLE game_versions ADD COLUMN label TEXT;
@@ -0,0 +1,2 @@
+-- Player names shown on local leaderboards
+ALTER TABLE high_scores ADD COLUMN player_name TEXT;
@@ -0,0 +1,15 @@
+-- Library files attached to a game (imported sprites, audio, ...)
+CREATE TABLE IF NOT EXISTS assets (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    game_id TEXT NOT NULL,
+    key TEXT NOT NULL,
+    asset_type TEXT NOT NULL,
+    file_path TEXT NOT NULL,
+    hash TEXT NOT NULL,  -- SHA-256 of the file contents, hex encoded
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (game_id) REFERENCES games(id) ON DELETE CASCADE,
+    UNIQUE(game_id, key)
+);
+
+-- Index for finding which games use an asset
+CREATE INDEX IF NOT EXISTS idx_assets_key ON assets(key);
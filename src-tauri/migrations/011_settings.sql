@@ -0,0 +1,6 @@
+-- App preferences as JSON values keyed by name (e.g. "default_model", "theme")
+CREATE TABLE IF NOT EXISTS settings (
+    key TEXT PRIMARY KEY NOT NULL,
+    value TEXT NOT NULL,  -- JSON encoded
+    updated_at TEXT NOT NULL
+);
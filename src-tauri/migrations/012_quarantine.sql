@@ -0,0 +1,12 @@
+-- Stored specs that failed to load, set aside by check_library for repair or export
+CREATE TABLE IF NOT EXISTS quarantined_specs (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    game_id TEXT NOT NULL,
+    version INTEGER NOT NULL,
+    is_current INTEGER NOT NULL,  -- 1 for the game's current spec, 0 for a history version
+    spec_json BLOB NOT NULL,  -- Raw stored value, decompressed when possible
+    error TEXT NOT NULL,
+    quarantined_at TEXT NOT NULL,
+    FOREIGN KEY (game_id) REFERENCES games(id) ON DELETE CASCADE,
+    UNIQUE(game_id, version, is_current)
+);
@@ -0,0 +1,8 @@
+-- Starter specs new games can be created from
+CREATE TABLE IF NOT EXISTS templates (
+    id TEXT PRIMARY KEY NOT NULL,
+    name TEXT NOT NULL,
+    description TEXT NOT NULL,
+    spec_json BLOB NOT NULL,  -- zstd compressed PhaserGameSpec
+    created_at TEXT NOT NULL
+);
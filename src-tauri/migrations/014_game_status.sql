@@ -0,0 +1,5 @@
+-- Draft games are works in progress; published games are ready for players
+ALTER TABLE games ADD COLUMN status TEXT NOT NULL DEFAULT 'draft';
+
+-- Index for filtering the library by status
+CREATE INDEX IF NOT EXISTS idx_games_status ON games(status);
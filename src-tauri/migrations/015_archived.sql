@@ -0,0 +1,2 @@
+-- Archived games are hidden from the main library but keep their history
+ALTER TABLE games ADD COLUMN archived INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,2 @@
+-- Free-form Markdown notes kept alongside a game, separate from version notes
+ALTER TABLE games ADD COLUMN notes TEXT NOT NULL DEFAULT '';
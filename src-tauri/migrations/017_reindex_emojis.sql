@@ -0,0 +1,3 @@
+-- Emojis are now part of each game's indexed spec text; clearing the index
+-- makes the app rebuild it on startup
+DELETE FROM games_fts;
@@ -0,0 +1,10 @@
+-- Facts extracted from each game's spec when it is saved, for list filters.
+-- NULL until computed; games saved before this migration are filled in on startup.
+ALTER TABLE games ADD COLUMN has_physics INTEGER;
+ALTER TABLE games ADD COLUMN object_count INTEGER;
+ALTER TABLE games ADD COLUMN uses_spawners INTEGER;
+
+-- Indexes for filtering the library by spec metadata
+CREATE INDEX IF NOT EXISTS idx_games_has_physics ON games(has_physics);
+CREATE INDEX IF NOT EXISTS idx_games_object_count ON games(object_count);
+CREATE INDEX IF NOT EXISTS idx_games_uses_spawners ON games(uses_spawners);
@@ -0,0 +1,14 @@
+-- Backend failures (panics, chat stream errors, database errors) kept for support triage
+CREATE TABLE IF NOT EXISTS error_reports (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    kind TEXT NOT NULL,
+    message TEXT NOT NULL,
+    context TEXT,
+    backtrace TEXT,
+    app_version TEXT NOT NULL,
+    platform TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+-- Index for listing the newest reports first
+CREATE INDEX IF NOT EXISTS idx_error_reports_created_at ON error_reports(created_at DESC);
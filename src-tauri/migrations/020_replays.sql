@@ -0,0 +1,15 @@
+-- Recorded runs (seed and timestamped inputs) that can be played back
+CREATE TABLE IF NOT EXISTS replays (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    game_id TEXT NOT NULL,
+    game_version INTEGER NOT NULL,
+    player_name TEXT,
+    score INTEGER,
+    duration_ms INTEGER NOT NULL,
+    replay TEXT NOT NULL,
+    recorded_at TEXT NOT NULL,
+    FOREIGN KEY (game_id) REFERENCES games(id) ON DELETE CASCADE
+);
+
+-- Index for listing a game's newest replays first
+CREATE INDEX IF NOT EXISTS idx_replays_game_id ON replays(game_id, recorded_at DESC);
@@ -0,0 +1,197 @@
+/// Complexity report of a spec: what a game asks of the machine running it,
+/// and how much of the model's context it takes up on every iteration
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::game_builder::{GameObject, PhaserGameSpec, PhysicsBody, Scene, Spawner, Tilemap};
+use crate::validator::{self, Diagnostic, PerformanceBudget};
+
+/// Average bytes of spec JSON per model token; JSON punctuation and short
+/// keys tokenize worse than prose
+const BYTES_PER_TOKEN: usize = 3;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecReport {
+    pub game_id: String,
+    pub scenes: Vec<SceneReport>,
+    /// Size of the spec as compact JSON
+    pub spec_bytes: usize,
+    /// Rough token count of the spec, which the model reads and rewrites in
+    /// full on every change
+    pub estimated_tokens: usize,
+    /// Budget warnings, as the model gets them
+    pub over_budget: Vec<Diagnostic>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SceneReport {
+    pub name: String,
+    /// Objects the scene starts with by `type`, tilemap tiles included.
+    /// Procedural levels aren't counted since their size depends on the seed.
+    pub objects_by_type: BTreeMap<String, usize>,
+    /// Starting objects with a physics body
+    pub physics_bodies: usize,
+    pub spawners: Vec<SpawnerReport>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpawnerReport {
+    pub id: String,
+    /// Objects spawned per second at the starting interval
+    pub per_second: f64,
+    /// Objects spawned in the first minute, after `max_count`
+    pub first_minute: u32,
+    /// Most objects alive at once, when a pool or `max_count` bounds it
+    pub max_live: Option<u32>,
+}
+
+/// Estimated context use of a spec, split so the largest parts stand out
+/// when deciding what to summarize
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenEstimate {
+    pub total: usize,
+    /// Scene objects across all scenes, tilemap templates included
+    pub objects: usize,
+    pub custom_logic: usize,
+    pub assets: usize,
+    /// Game settings, scene settings (camera, HUD, etc.), achievements, and
+    /// the descriptive fields
+    pub other: usize,
+    pub scenes: Vec<SceneTokens>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SceneTokens {
+    pub name: String,
+    pub total: usize,
+    pub objects: usize,
+    pub custom_logic: usize,
+}
+
+/// Build the report for a game's spec
+pub fn analyze_spec(
+    game_id: &str,
+    spec: &PhaserGameSpec,
+    budget: &PerformanceBudget,
+) -> SpecReport {
+    let spec_bytes = serde_json::to_string(spec).map_or(0, |json| json.len());
+    SpecReport {
+        game_id: game_id.to_string(),
+        scenes: spec.scenes.iter().map(analyze_scene).collect(),
+        spec_bytes,
+        estimated_tokens: bytes_to_tokens(spec_bytes),
+        over_budget: validator::check_budget(spec, budget),
+    }
+}
+
+/// Estimate how many tokens each part of the spec takes up in the model's context
+pub fn estimate_tokens(spec: &PhaserGameSpec) -> TokenEstimate {
+    let scenes: Vec<SceneTokens> = spec.scenes.iter().map(scene_tokens).collect();
+    let total = tokens(spec);
+    let objects = scenes.iter().map(|scene| scene.objects).sum();
+    let custom_logic = scenes.iter().map(|scene| scene.custom_logic).sum();
+    let assets = tokens(&spec.assets);
+    TokenEstimate {
+        total,
+        objects,
+        custom_logic,
+        assets,
+        other: total.saturating_sub(objects + custom_logic + assets),
+        scenes,
+    }
+}
+
+fn scene_tokens(scene: &Scene) -> SceneTokens {
+    let tilemap_templates = scene.tilemap.iter().flat_map(|tilemap| &tilemap.legend);
+    SceneTokens {
+        name: scene.name.clone(),
+        total: tokens(scene),
+        objects: tokens(&scene.objects)
+            + tilemap_templates
+                .map(|entry| tokens(&entry.template))
+                .sum::<usize>(),
+        custom_logic: scene.custom_logic.as_ref().map_or(0, tokens),
+    }
+}
+
+/// Tokens of a part of the spec, serialized the way the model sees it
+fn tokens(part: &impl Serialize) -> usize {
+    serde_json::to_value(part)
+        .ok()
+        .filter(|value| !value.is_null())
+        .map_or(0, |value| bytes_to_tokens(value.to_string().len()))
+}
+
+fn bytes_to_tokens(bytes: usize) -> usize {
+    bytes.div_ceil(BYTES_PER_TOKEN)
+}
+
+fn analyze_scene(scene: &Scene) -> SceneReport {
+    let mut objects_by_type = BTreeMap::new();
+    let mut physics_bodies = 0;
+    for (object, count) in starting_objects(scene) {
+        *objects_by_type.entry(type_name(object)).or_default() += count;
+        let has_body = object
+            .physics
+            .as_ref()
+            .is_some_and(|physics| !matches!(physics.body, PhysicsBody::None));
+        if has_body {
+            physics_bodies += count;
+        }
+    }
+
+    let spawners = scene
+        .custom_logic
+        .iter()
+        .flat_map(|logic| logic.spawners.iter().flatten())
+        .map(analyze_spawner)
+        .collect();
+
+    SceneReport {
+        name: scene.name.clone(),
+        objects_by_type,
+        physics_bodies,
+        spawners,
+    }
+}
+
+/// Objects placed when the scene starts, with how many times each is placed
+fn starting_objects(scene: &Scene) -> Vec<(&GameObject, usize)> {
+    let mut objects: Vec<_> = scene.objects.iter().map(|object| (object, 1)).collect();
+    if let Some(tilemap) = &scene.tilemap {
+        for entry in &tilemap.legend {
+            let count = tilemap
+                .rows
+                .iter()
+                .flat_map(|row| row.chars())
+                .filter(|c| *c == entry.symbol && !Tilemap::EMPTY_TILES.contains(c))
+                .count();
+            if count > 0 {
+                objects.push((&*entry.template, count));
+            }
+        }
+    }
+    objects
+}
+
+fn analyze_spawner(spawner: &Spawner) -> SpawnerReport {
+    // Interval 0 is rejected by the validator; treat it as 1ms rather than divide by zero
+    let interval = spawner.interval.max(1);
+    let first_minute = 60_000 / interval;
+    SpawnerReport {
+        id: spawner.id.clone(),
+        per_second: 1000.0 / f64::from(interval),
+        first_minute: spawner
+            .max_count
+            .map_or(first_minute, |max_count| first_minute.min(max_count)),
+        max_live: spawner.max_live(),
+    }
+}
+
+/// The object's `type` as written in the spec
+fn type_name(object: &GameObject) -> String {
+    serde_json::to_value(&object.object_type)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
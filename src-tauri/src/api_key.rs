@@ -0,0 +1,131 @@
+/// Resolution of the Anthropic API key. Sources are tried in order: the OS
+/// keyring, the settings database (where a key entered in the app is kept
+/// when the keyring is unavailable), and last the `ANTHROPIC_API_KEY`
+/// environment variable or `.env` file.
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::db::{Database, DbError};
+use crate::secrets::{self, SecretError};
+
+const API_KEY_ENV: &str = "ANTHROPIC_API_KEY";
+
+/// Setting holding the key when the keyring couldn't store it
+const API_KEY_SETTING: &str = "anthropic_api_key";
+
+/// Cheapest authenticated endpoint, used to check a key
+const MODELS_URL: &str = "https://api.anthropic.com/v1/models";
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+#[derive(Debug, thiserror::Error)]
+pub enum ApiKeyError {
+    #[error("Database error: {0}")]
+    Db(#[from] DbError),
+    #[error("HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("Unexpected response from the API: {0}")]
+    Status(reqwest::StatusCode),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KeySource {
+    Keyring,
+    Settings,
+    Environment,
+}
+
+/// Which key is active and whether the API accepts it
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct KeyStatus {
+    /// Source of the active key; `None` when there is no key
+    pub source: Option<KeySource>,
+    /// Whether the API accepted the key; `None` until it has been checked, or
+    /// when the check couldn't reach the API
+    pub valid: Option<bool>,
+    /// Why the check couldn't tell
+    pub error: Option<String>,
+}
+
+impl KeyStatus {
+    /// Status of a key that hasn't been checked yet
+    pub fn unchecked(source: KeySource) -> Self {
+        Self {
+            source: Some(source),
+            ..Self::default()
+        }
+    }
+}
+
+/// The key from the first source that has one
+pub async fn resolve(db: &Database) -> Result<Option<(String, KeySource)>, ApiKeyError> {
+    // A broken keyring (e.g. no secret service on Linux) falls through to the other sources
+    match secrets::load_api_key() {
+        Ok(Some(key)) if !key.is_empty() => return Ok(Some((key, KeySource::Keyring))),
+        Ok(_) => {}
+        Err(e) => eprintln!("Failed to read API key from keyring: {}", e),
+    }
+    if let Some(key) = db
+        .get_setting::<String>(API_KEY_SETTING)
+        .await?
+        .filter(|key| !key.is_empty())
+    {
+        return Ok(Some((key, KeySource::Settings)));
+    }
+    Ok(std::env::var(API_KEY_ENV)
+        .ok()
+        .filter(|key| !key.is_empty())
+        .map(|key| (key, KeySource::Environment)))
+}
+
+/// Save a key entered in the app, in the keyring if possible
+pub async fn store(db: &Database, key: &str) -> Result<KeySource, ApiKeyError> {
+    match secrets::store_api_key(key) {
+        Ok(()) => {
+            // Don't leave an older key in the database for the keyring to shadow
+            db.delete_setting(API_KEY_SETTING).await?;
+            Ok(KeySource::Keyring)
+        }
+        Err(SecretError::Keyring(e)) => {
+            eprintln!(
+                "Failed to store API key in keyring, keeping it in settings: {}",
+                e
+            );
+            db.set_setting(API_KEY_SETTING, &key).await?;
+            Ok(KeySource::Settings)
+        }
+    }
+}
+
+/// Ask the API whether it accepts the key
+pub async fn validate(key: &str) -> Result<bool, ApiKeyError> {
+    let response = reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()?
+        .get(MODELS_URL)
+        .header("x-api-key", key)
+        .header("anthropic-version", ANTHROPIC_VERSION)
+        .send()
+        .await?;
+    match response.status() {
+        status if status.is_success() => Ok(true),
+        reqwest::StatusCode::UNAUTHORIZED | reqwest::StatusCode::FORBIDDEN => Ok(false),
+        status => Err(ApiKeyError::Status(status)),
+    }
+}
+
+/// Check a key from `source`, keeping the reason when the check can't tell
+pub async fn check(key: &str, source: KeySource) -> KeyStatus {
+    match validate(key).await {
+        Ok(valid) => KeyStatus {
+            source: Some(source),
+            valid: Some(valid),
+            error: None,
+        },
+        Err(e) => KeyStatus {
+            source: Some(source),
+            valid: None,
+            error: Some(e.to_string()),
+        },
+    }
+}
@@ -0,0 +1,238 @@
+/// Local library of user-provided image and audio assets stored in the app data directory
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, thiserror::Error)]
+pub enum AssetError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Unsupported file type: {0}")]
+    UnsupportedType(String),
+    #[error("Invalid asset key: {0}")]
+    InvalidKey(String),
+    #[error("Asset not found: {0}")]
+    NotFound(String),
+}
+
+/// Image file extensions accepted on import
+const IMAGE_EXTENSIONS: [&str; 5] = ["png", "jpg", "jpeg", "gif", "webp"];
+
+/// Audio file extensions accepted on import
+const AUDIO_EXTENSIONS: [&str; 3] = ["mp3", "ogg", "wav"];
+
+/// Lowercased extension of `file_name` if it's a supported image or audio type
+fn supported_extension(file_name: &Path) -> Option<String> {
+    file_name
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_lowercase())
+        .filter(|ext| {
+            IMAGE_EXTENSIONS.contains(&ext.as_str()) || AUDIO_EXTENSIONS.contains(&ext.as_str())
+        })
+}
+
+/// Whether a library file holds audio rather than an image
+pub fn is_audio(file_name: &str) -> bool {
+    supported_extension(Path::new(file_name))
+        .is_some_and(|ext| AUDIO_EXTENSIONS.contains(&ext.as_str()))
+}
+
+/// Scheme of the protocol serving library assets to the webview
+pub const PROTOCOL: &str = "pueo-asset";
+
+/// Asset stored in the library
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LibraryAsset {
+    pub key: String,
+    pub file_name: String,
+    pub size_bytes: u64,
+}
+
+/// App-managed asset directory
+pub struct AssetLibrary {
+    dir: PathBuf,
+}
+
+impl AssetLibrary {
+    /// Open the library, creating its directory if needed
+    pub async fn new(dir: PathBuf) -> Result<Self, AssetError> {
+        tokio::fs::create_dir_all(&dir).await?;
+        Ok(Self { dir })
+    }
+
+    /// Copy an image or audio file into the library under a unique key
+    pub async fn import_file(
+        &self,
+        source: &Path,
+        key: Option<String>,
+    ) -> Result<LibraryAsset, AssetError> {
+        let extension = supported_extension(source)
+            .ok_or_else(|| AssetError::UnsupportedType(source.display().to_string()))?;
+
+        let requested = match key {
+            Some(key) => key,
+            None => source
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .unwrap_or("asset")
+                .to_string(),
+        };
+        let base_key = sanitize_key(&requested);
+        if base_key.is_empty() {
+            return Err(AssetError::InvalidKey(requested));
+        }
+
+        // Suffix the key until it no longer collides with an existing asset
+        let mut key = base_key.clone();
+        let mut suffix = 2;
+        while self.find(&key).await?.is_some() {
+            key = format!("{}_{}", base_key, suffix);
+            suffix += 1;
+        }
+
+        let file_name = format!("{}.{}", key, extension);
+        let size_bytes = tokio::fs::copy(source, self.dir.join(&file_name)).await?;
+
+        Ok(LibraryAsset {
+            key,
+            file_name,
+            size_bytes,
+        })
+    }
+
+    /// Add raw image or audio data under `key`, reusing an asset that already holds the
+    /// same data. Different data under a taken key is added under a suffixed
+    /// key instead of replacing the asset other games use. Returns the key the
+    /// data is stored under.
+    pub async fn add_bytes(
+        &self,
+        key: &str,
+        file_name: &str,
+        data: &[u8],
+    ) -> Result<String, AssetError> {
+        if key.is_empty() || sanitize_key(key) != key {
+            return Err(AssetError::InvalidKey(key.to_string()));
+        }
+        let extension = supported_extension(Path::new(file_name))
+            .ok_or_else(|| AssetError::UnsupportedType(file_name.to_string()))?;
+
+        let hash = format!("{:x}", Sha256::digest(data));
+        let mut candidate = key.to_string();
+        let mut suffix = 2;
+        while self.find(&candidate).await?.is_some() {
+            if self.hash(&candidate).await? == hash {
+                return Ok(candidate);
+            }
+            candidate = format!("{}_{}", key, suffix);
+            suffix += 1;
+        }
+        tokio::fs::write(self.dir.join(format!("{}.{}", candidate, extension)), data).await?;
+        Ok(candidate)
+    }
+
+    /// List all assets in the library, sorted by key
+    pub async fn list(&self) -> Result<Vec<LibraryAsset>, AssetError> {
+        let mut assets = Vec::new();
+        let mut entries = tokio::fs::read_dir(&self.dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let metadata = entry.metadata().await?;
+            if !metadata.is_file() {
+                continue;
+            }
+            let path = entry.path();
+            let (Some(key), Some(file_name)) = (
+                path.file_stem().and_then(|stem| stem.to_str()),
+                path.file_name().and_then(|name| name.to_str()),
+            ) else {
+                continue;
+            };
+            assets.push(LibraryAsset {
+                key: key.to_string(),
+                file_name: file_name.to_string(),
+                size_bytes: metadata.len(),
+            });
+        }
+        assets.sort_by(|a, b| a.key.cmp(&b.key));
+        Ok(assets)
+    }
+
+    /// Look up an asset by key
+    pub async fn find(&self, key: &str) -> Result<Option<LibraryAsset>, AssetError> {
+        Ok(self
+            .list()
+            .await?
+            .into_iter()
+            .find(|asset| asset.key == key))
+    }
+
+    /// Resolve the on-disk path of an asset
+    pub async fn path_of(&self, key: &str) -> Result<PathBuf, AssetError> {
+        let asset = self
+            .find(key)
+            .await?
+            .ok_or_else(|| AssetError::NotFound(key.to_string()))?;
+        Ok(self.dir.join(asset.file_name))
+    }
+
+    /// Read an asset's contents along with its MIME type
+    pub async fn read(&self, key: &str) -> Result<(Vec<u8>, &'static str), AssetError> {
+        let path = self.path_of(key).await?;
+        let content_type = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("png") => "image/png",
+            Some("jpg") | Some("jpeg") => "image/jpeg",
+            Some("gif") => "image/gif",
+            Some("webp") => "image/webp",
+            Some("mp3") => "audio/mpeg",
+            Some("ogg") => "audio/ogg",
+            Some("wav") => "audio/wav",
+            _ => "application/octet-stream",
+        };
+        Ok((tokio::fs::read(path).await?, content_type))
+    }
+
+    /// SHA-256 of an asset's contents, hex encoded
+    pub async fn hash(&self, key: &str) -> Result<String, AssetError> {
+        let data = tokio::fs::read(self.path_of(key).await?).await?;
+        Ok(format!("{:x}", Sha256::digest(&data)))
+    }
+
+    /// Remove an asset from the library
+    pub async fn delete(&self, key: &str) -> Result<(), AssetError> {
+        let path = self.path_of(key).await?;
+        tokio::fs::remove_file(path).await?;
+        Ok(())
+    }
+
+    /// Delete every asset whose key is not referenced, returning the removed keys
+    pub async fn remove_unreferenced(
+        &self,
+        referenced: &HashSet<String>,
+    ) -> Result<Vec<String>, AssetError> {
+        let mut removed = Vec::new();
+        for asset in self.list().await? {
+            if !referenced.contains(&asset.key) {
+                tokio::fs::remove_file(self.dir.join(&asset.file_name)).await?;
+                removed.push(asset.key);
+            }
+        }
+        Ok(removed)
+    }
+}
+
+/// Reduce a user-provided name to a safe key (lowercase alphanumerics, '_' and '-')
+fn sanitize_key(name: &str) -> String {
+    name.trim()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>()
+        .trim_matches('_')
+        .to_string()
+}
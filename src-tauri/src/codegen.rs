@@ -0,0 +1,1881 @@
+/// Translation of a game spec into readable, standalone Phaser 3 JavaScript
+///
+/// The generated code covers the same ground as the spec interpreter in the
+/// frontend (objects, physics, controls, behaviors, spawners, collisions,
+/// timers, and actions) written out as plain scene classes, so learners can
+/// read and edit it. Features only the app's runtime supports are listed in a
+/// comment at the top of each scene instead of being silently dropped.
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+use crate::game_builder::{
+    ActionEffect, Asset, AssetType, BehaviorType, CollisionBoxShape, Controls, CustomLogic,
+    GameObject, ObjectType, PhaserGameSpec, PhysicsBody, RecyclePolicy, Scene, Spawner, TextAlign,
+};
+
+/// Speeds used by the frontend's interpreter, so generated games play the same
+const MOVE_SPEED: u32 = 160;
+const JUMP_SPEED: u32 = 330;
+const SHOOT_COOLDOWN_MS: u32 = 200;
+
+/// Generate a complete Phaser 3 program for `spec`
+pub fn generate_js(spec: &PhaserGameSpec) -> String {
+    let mut w = Writer::default();
+    w.line(format!("// {}", comment_text(&spec.title)));
+    for line in spec.description.lines() {
+        w.line(format!("// {}", comment_text(line)));
+    }
+    w.line("//");
+    w.line("// Generated from a pueo game spec. Load Phaser 3 before this script, e.g.");
+    w.line("// <script src=\"https://cdn.jsdelivr.net/npm/phaser@3/dist/phaser.min.js\"></script>");
+    let game_unsupported = game_unsupported(spec);
+    if !game_unsupported.is_empty() {
+        w.line(format!(
+            "// Not generated (only supported by the pueo runtime): {}",
+            game_unsupported.join(", ")
+        ));
+    }
+
+    let mut class_names = Names::default();
+    let classes: Vec<String> = spec
+        .scenes
+        .iter()
+        .map(|scene| class_names.claim(&format!("{}Scene", pascal_case(&scene.name))))
+        .collect();
+    for (scene, class_name) in spec.scenes.iter().zip(&classes) {
+        w.blank();
+        SceneGen::new(spec, scene).write(&mut w, class_name);
+    }
+
+    w.blank();
+    w.open("const config = {");
+    w.line("type: Phaser.AUTO,");
+    w.line(format!("width: {},", spec.game.width));
+    w.line(format!("height: {},", spec.game.height));
+    w.line(format!(
+        "backgroundColor: {},",
+        js_string(&spec.game.background_color)
+    ));
+    let physics = &spec.game.physics;
+    if physics.enabled {
+        w.open("physics: {");
+        w.line("default: 'arcade',");
+        w.open("arcade: {");
+        w.line(format!(
+            "gravity: {{ x: {}, y: {} }},",
+            num(physics.gravity.x),
+            num(physics.gravity.y)
+        ));
+        w.line(format!("debug: {},", physics.debug));
+        w.close("},");
+        w.close("},");
+    }
+    w.line(format!("scene: [{}],", classes.join(", ")));
+    w.close("}");
+    w.blank();
+    w.line("new Phaser.Game(config)");
+    w.finish()
+}
+
+/// Game-wide features the generator leaves out
+fn game_unsupported(spec: &PhaserGameSpec) -> Vec<&'static str> {
+    let mut unsupported = Vec::new();
+    if spec.game.pause.is_some() {
+        unsupported.push("pause");
+    }
+    if spec.game.restart_key.is_some() {
+        unsupported.push("restart key");
+    }
+    if spec.game.audio.is_some() {
+        unsupported.push("music and volume");
+    }
+    if spec.achievements.is_some() {
+        unsupported.push("achievements");
+    }
+    unsupported
+}
+
+/// Code generation for one scene class
+struct SceneGen<'a> {
+    spec: &'a PhaserGameSpec,
+    scene: &'a Scene,
+    logic: Option<&'a CustomLogic>,
+    actions: BTreeMap<&'a str, &'a ActionEffect>,
+    /// Method name of each action
+    action_methods: BTreeMap<&'a str, String>,
+    /// Method name of each spawner
+    spawner_methods: BTreeMap<&'a str, String>,
+    /// Method name that fires each shooter's projectile
+    shoot_methods: BTreeMap<&'a str, String>,
+    /// Helper methods the generated code calls
+    helpers: BTreeSet<Helper>,
+    unsupported: BTreeSet<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Helper {
+    AddScore,
+    EndGame,
+    Damage,
+    Patrol,
+    Follow,
+    Wander,
+}
+
+impl<'a> SceneGen<'a> {
+    fn new(spec: &'a PhaserGameSpec, scene: &'a Scene) -> Self {
+        let logic = scene.custom_logic.as_ref();
+        let mut methods = Names::reserved();
+        let actions: BTreeMap<&str, &ActionEffect> = logic
+            .iter()
+            .flat_map(|logic| logic.actions.iter().flatten())
+            .map(|action| (action.name.as_str(), &action.effect))
+            .collect();
+        let action_methods = actions
+            .keys()
+            .map(|name| (*name, methods.claim(&format!("run{}", pascal_case(name)))))
+            .collect();
+        let spawner_methods = logic
+            .iter()
+            .flat_map(|logic| logic.spawners.iter().flatten())
+            .map(|spawner| {
+                let method = methods.claim(&format!("spawn{}", pascal_case(&spawner.id)));
+                (spawner.id.as_str(), method)
+            })
+            .collect();
+        let shoot_methods = scene
+            .objects
+            .iter()
+            .filter(|object| shooter(object).is_some())
+            .map(|object| {
+                let method = methods.claim(&format!("shoot{}", pascal_case(&object.id)));
+                (object.id.as_str(), method)
+            })
+            .collect();
+
+        let mut unsupported = BTreeSet::new();
+        let scene_features = [
+            (scene.tilemap.is_some(), "tilemap"),
+            (scene.countdown.is_some(), "countdown"),
+            (scene.procedural.is_some(), "procedural level"),
+            (scene.gravity_zones.is_some(), "gravity zones"),
+        ];
+        for (present, feature) in scene_features {
+            if present {
+                unsupported.insert(feature.to_string());
+            }
+        }
+        if let Some(hud) = &scene.hud {
+            let hud_features = [
+                (hud.best_score.is_some(), "HUD best score"),
+                (hud.health_bar.is_some(), "HUD health bar"),
+                (hud.lives.is_some(), "HUD lives"),
+                (hud.mute_button.is_some(), "HUD mute button"),
+                (hud.timer.is_some(), "HUD timer"),
+                (hud.labels.is_some(), "HUD labels"),
+            ];
+            for (present, feature) in hud_features {
+                if present {
+                    unsupported.insert(feature.to_string());
+                }
+            }
+        }
+        if let Some(logic) = logic {
+            let logic_features = [
+                (logic.portals.is_some(), "portals"),
+                (logic.on_destroyed.is_some(), "destroy triggers"),
+                (logic.collision_matrix.is_some(), "collision matrix"),
+            ];
+            for (present, feature) in logic_features {
+                if present {
+                    unsupported.insert(feature.to_string());
+                }
+            }
+        }
+
+        Self {
+            spec,
+            scene,
+            logic,
+            actions,
+            action_methods,
+            spawner_methods,
+            shoot_methods,
+            helpers: BTreeSet::new(),
+            unsupported,
+        }
+    }
+
+    fn write(mut self, w: &mut Writer, class_name: &str) {
+        // Generate method bodies first so the helpers and unsupported
+        // features they need are known before the class header is written
+        let mut body = Writer::with_indent(1);
+        self.write_preload(&mut body);
+        body.blank();
+        self.write_create(&mut body);
+        body.blank();
+        self.write_update(&mut body);
+        for (name, effect) in self.actions.clone() {
+            body.blank();
+            let method = self.action_methods[name].clone();
+            body.line(format!("// Action '{}'", comment_text(name)));
+            body.open(format!("{}(target, source) {{", method));
+            self.write_effect(&mut body, effect);
+            body.close("}");
+        }
+        for spawner in self.spawners() {
+            body.blank();
+            self.write_spawner(&mut body, spawner);
+        }
+        for object in &self.scene.objects {
+            if let Some((controls, projectile)) = shooter(object) {
+                body.blank();
+                self.write_shoot(&mut body, object, controls, projectile);
+            }
+        }
+        for helper in self.helpers.clone() {
+            body.blank();
+            self.write_helper(&mut body, helper);
+        }
+
+        if !self.unsupported.is_empty() {
+            let features: Vec<&str> = self.unsupported.iter().map(String::as_str).collect();
+            w.line(format!(
+                "// Not generated (only supported by the pueo runtime): {}",
+                comment_text(&features.join(", "))
+            ));
+        }
+        w.open(format!("class {} extends Phaser.Scene {{", class_name));
+        w.open("constructor() {");
+        w.line(format!("super({})", js_string(&self.scene.name)));
+        w.close("}");
+        w.blank();
+        w.append(body);
+        w.close("}");
+    }
+
+    fn spawners(&self) -> Vec<&'a Spawner> {
+        self.logic
+            .iter()
+            .flat_map(|logic| logic.spawners.iter().flatten())
+            .collect()
+    }
+
+    fn write_preload(&self, w: &mut Writer) {
+        w.open("preload() {");
+        for asset in &self.spec.assets {
+            let url = asset_url(asset);
+            let line = match asset.asset_type {
+                AssetType::Image | AssetType::Sprite => {
+                    format!("this.load.image({}, {})", js_string(&asset.key), url)
+                }
+                AssetType::Audio => {
+                    format!("this.load.audio({}, {})", js_string(&asset.key), url)
+                }
+                AssetType::Spritesheet => {
+                    let mut frame = vec![];
+                    if let Some(config) = &asset.frame_config {
+                        frame.push(format!("frameWidth: {}", config.frame_width));
+                        frame.push(format!("frameHeight: {}", config.frame_height));
+                        if let Some(spacing) = config.spacing {
+                            frame.push(format!("spacing: {}", spacing));
+                        }
+                        if let Some(margin) = config.margin {
+                            frame.push(format!("margin: {}", margin));
+                        }
+                    }
+                    format!(
+                        "this.load.spritesheet({}, {}, {{ {} }})",
+                        js_string(&asset.key),
+                        url,
+                        frame.join(", ")
+                    )
+                }
+            };
+            if asset.library_key.is_some() {
+                w.line("// From the pueo asset library: put the file next to this script");
+            }
+            w.line(line);
+        }
+        w.close("}");
+    }
+
+    fn write_create(&mut self, w: &mut Writer) {
+        w.open("create() {");
+        w.line("this.score = 0");
+        let variables: Vec<String> = self
+            .logic
+            .iter()
+            .flat_map(|logic| logic.variables.iter().flatten())
+            .map(|variable| format!("{}: {}", js_key(&variable.name), variable.initial))
+            .collect();
+        if !variables.is_empty() {
+            w.line(format!("this.vars = {{ {} }}", variables.join(", ")));
+        }
+        w.line("this.objects = {}");
+        w.line("this.groups = {}");
+
+        // Animations are global, so a scene that runs twice must not redefine them
+        for asset in &self.spec.assets {
+            for animation in asset.animations.iter().flatten() {
+                w.open(format!(
+                    "if (!this.anims.exists({})) {{",
+                    js_string(&animation.key)
+                ));
+                w.open("this.anims.create({");
+                w.line(format!("key: {},", js_string(&animation.key)));
+                w.line(format!(
+                    "frames: this.anims.generateFrameNumbers({}, {{ start: {}, end: {} }}),",
+                    js_string(&asset.key),
+                    animation.start_frame,
+                    animation.end_frame
+                ));
+                w.line(format!(
+                    "frameRate: {},",
+                    animation.frame_rate.unwrap_or(10)
+                ));
+                w.line(format!("repeat: {},", animation.repeat.unwrap_or(-1)));
+                w.close("})");
+                w.close("}");
+            }
+        }
+
+        let mut locals = Names::reserved();
+        for object in &self.scene.objects {
+            w.blank();
+            w.line(format!("// {}", comment_text(&object.id)));
+            if matches!(object.object_type, ObjectType::Group) {
+                w.line(format!(
+                    "{} = this.physics.add.group()",
+                    member("this.groups", &object.id)
+                ));
+                continue;
+            }
+            let local = locals.claim(&camel_case(&object.id));
+            if self.write_object(w, object, &local, &num(object.x), &num(object.y), None) {
+                w.line(format!(
+                    "{} = {}",
+                    member("this.objects", &object.id),
+                    local
+                ));
+            }
+        }
+
+        if !self.shoot_methods.is_empty() {
+            w.blank();
+            w.line("this.projectiles = this.physics.add.group()");
+            w.line("this.lastShot = {}");
+        }
+
+        let spawners = self.spawners();
+        if !spawners.is_empty() {
+            w.blank();
+            w.line("// Spawners");
+            w.line("this.spawnCounts = {}");
+            w.line("this.spawners = {}");
+            for spawner in spawners {
+                let group = member("this.groups", &spawner.template.id);
+                w.line(format!("{} ??= this.physics.add.group()", group));
+                w.line(format!("{} = 0", member("this.spawnCounts", &spawner.id)));
+                w.line(format!(
+                    "{} = this.time.addEvent({{ delay: {}, loop: true, callback: this.{}, callbackScope: this }})",
+                    member("this.spawners", &spawner.id),
+                    spawner.interval,
+                    self.spawner_methods[spawner.id.as_str()]
+                ));
+            }
+        }
+
+        if let Some(camera) = &self.scene.camera {
+            w.blank();
+            w.line("// Camera");
+            let width = camera.world_width.unwrap_or(self.spec.game.width);
+            let height = camera.world_height.unwrap_or(self.spec.game.height);
+            w.line(format!(
+                "this.cameras.main.setBounds(0, 0, {}, {})",
+                width, height
+            ));
+            w.line(format!(
+                "this.physics.world.setBounds(0, 0, {}, {})",
+                width, height
+            ));
+            if let Some(follow) = &camera.follow {
+                let lerp = num(camera.lerp.unwrap_or(1.0));
+                w.line(format!(
+                    "this.cameras.main.startFollow({}, true, {}, {})",
+                    member("this.objects", follow),
+                    lerp,
+                    lerp
+                ));
+            }
+            if let Some(deadzone) = &camera.deadzone {
+                w.line(format!(
+                    "this.cameras.main.setDeadzone({}, {})",
+                    num(deadzone.width),
+                    num(deadzone.height)
+                ));
+            }
+        }
+
+        if let Some(score) = self.scene.hud.as_ref().and_then(|hud| hud.score.as_ref()) {
+            w.blank();
+            w.line("// Score display");
+            let label = score.label.as_deref().unwrap_or("Score: ");
+            w.line(format!(
+                "this.scoreText = this.add.text({}, {}, {} + this.score, {{ fontSize: {}, color: {} }})",
+                num(score.x),
+                num(score.y),
+                js_string(label),
+                js_string(score.font_size.as_deref().unwrap_or("24px")),
+                js_string(score.fill.as_deref().unwrap_or("#ffffff"))
+            ));
+            w.line("this.scoreText.setScrollFactor(0).setDepth(1000)");
+        }
+
+        self.write_interactions(w);
+
+        let controlled: Vec<(&GameObject, &Controls)> = self
+            .scene
+            .objects
+            .iter()
+            .filter_map(|object| object.controls.as_ref().map(|controls| (object, controls)))
+            .collect();
+        if !controlled.is_empty() {
+            w.blank();
+            w.line("// Keys");
+            w.line("this.keys = {}");
+            for (object, controls) in controlled {
+                let keys: Vec<String> = control_keys(controls)
+                    .into_iter()
+                    .map(|(name, key)| format!("{}: {}", name, js_string(&phaser_key(key))))
+                    .collect();
+                w.line(format!(
+                    "{} = this.input.keyboard.addKeys({{ {} }})",
+                    member("this.keys", &object.id),
+                    keys.join(", ")
+                ));
+            }
+        }
+
+        let on_start: Vec<&String> = self
+            .logic
+            .iter()
+            .flat_map(|logic| logic.on_start.iter().flatten())
+            .collect();
+        if !on_start.is_empty() {
+            w.blank();
+            w.line("// Start actions");
+            for action in on_start {
+                self.write_call(w, action, "undefined, undefined");
+            }
+        }
+        w.close("}");
+    }
+
+    /// Write code creating `object` as `local` at (`x`, `y`), added to
+    /// `group` if given. Returns false when the object type isn't generated.
+    fn write_object(
+        &mut self,
+        w: &mut Writer,
+        object: &GameObject,
+        local: &str,
+        x: &str,
+        y: &str,
+        group: Option<&str>,
+    ) -> bool {
+        let created = match object.object_type {
+            ObjectType::Rectangle => {
+                let shape = object.shape.as_ref();
+                Some(format!(
+                    "this.add.rectangle({}, {}, {}, {}, {})",
+                    x,
+                    y,
+                    num(shape.and_then(|shape| shape.width).unwrap_or(100.0)),
+                    num(shape.and_then(|shape| shape.height).unwrap_or(100.0)),
+                    color(shape.and_then(|shape| shape.color.as_deref()))
+                ))
+            }
+            ObjectType::Circle => {
+                let shape = object.shape.as_ref();
+                Some(format!(
+                    "this.add.circle({}, {}, {}, {})",
+                    x,
+                    y,
+                    num(shape.and_then(|shape| shape.radius).unwrap_or(50.0)),
+                    color(shape.and_then(|shape| shape.color.as_deref()))
+                ))
+            }
+            ObjectType::Text => object.text.as_ref().map(|text| {
+                let mut style = vec![
+                    format!(
+                        "fontSize: {}",
+                        js_string(text.font_size.as_deref().unwrap_or("32px"))
+                    ),
+                    format!(
+                        "color: {}",
+                        js_string(text.fill.as_deref().unwrap_or("#ffffff"))
+                    ),
+                ];
+                if let Some(family) = &text.font_family {
+                    style.push(format!("fontFamily: {}", js_string(family)));
+                }
+                if let Some(stroke) = &text.stroke {
+                    style.push(format!("stroke: {}", js_string(stroke)));
+                    style.push(format!(
+                        "strokeThickness: {}",
+                        num(text.stroke_thickness.unwrap_or(0.0))
+                    ));
+                }
+                if let Some(align) = &text.align {
+                    let align = match align {
+                        TextAlign::Left => "left",
+                        TextAlign::Center => "center",
+                        TextAlign::Right => "right",
+                    };
+                    style.push(format!("align: '{}'", align));
+                }
+                if let Some(width) = text.word_wrap_width {
+                    style.push(format!("wordWrap: {{ width: {} }}", num(width)));
+                }
+                if let Some(background) = &text.background_color {
+                    style.push(format!("backgroundColor: {}", js_string(background)));
+                }
+                if let Some(padding) = text.padding {
+                    style.push(format!("padding: {{ x: {0}, y: {0} }}", num(padding)));
+                }
+                format!(
+                    "this.add.text({}, {}, {}, {{ {} }})",
+                    x,
+                    y,
+                    js_string(&text.text),
+                    style.join(", ")
+                )
+            }),
+            ObjectType::Emoji => object.emoji.as_ref().map(|emoji| {
+                format!(
+                    "this.add.text({}, {}, {}, {{ fontSize: '{}px' }})",
+                    x,
+                    y,
+                    js_string(&emoji.emoji),
+                    num(emoji.size.unwrap_or(32.0))
+                )
+            }),
+            ObjectType::Sprite => object
+                .texture
+                .as_ref()
+                .map(|texture| format!("this.add.sprite({}, {}, {})", x, y, js_string(texture))),
+            ObjectType::Group | ObjectType::Hazard | ObjectType::Zone => None,
+        };
+        let Some(created) = created else {
+            self.unsupported.insert(format!(
+                "{} object '{}'",
+                object_type_name(object),
+                object.id
+            ));
+            return false;
+        };
+        w.line(format!("const {} = {}", local, created));
+
+        let has_body = object
+            .physics
+            .as_ref()
+            .is_some_and(|physics| !matches!(physics.body, PhysicsBody::None));
+        // Emojis, and text with a body, are centered like the shapes
+        if matches!(object.object_type, ObjectType::Emoji)
+            || (matches!(object.object_type, ObjectType::Text) && has_body)
+        {
+            w.line(format!("{}.setOrigin(0.5)", local));
+        }
+        if let Some(depth) = object.depth {
+            w.line(format!("{}.setDepth({})", local, depth));
+        }
+        if let Some(alpha) = object.alpha {
+            w.line(format!("{}.setAlpha({})", local, num(alpha)));
+        }
+        if let Some(rotation) = object.rotation {
+            w.line(format!("{}.setAngle({})", local, num(rotation)));
+        }
+        if let Some(scale) = object.scale {
+            w.line(format!("{}.setScale({})", local, num(scale)));
+        }
+        if let Some(tint) = &object.tint {
+            if matches!(object.object_type, ObjectType::Sprite) {
+                w.line(format!("{}.setTint({})", local, color(Some(tint))));
+            }
+        }
+        if let Some(health) = object.health {
+            w.line(format!("{}.setData('health', {})", local, health));
+        }
+        if let Some(idle) = object
+            .animations
+            .as_ref()
+            .and_then(|anims| anims.idle.as_ref())
+        {
+            w.line(format!("{}.play({})", local, js_string(idle)));
+        }
+        if object.emoji.iter().any(|emoji| emoji.layers.is_some()) {
+            self.unsupported.insert("emoji layers".to_string());
+        }
+        if object.on_click.is_some() {
+            self.unsupported.insert("click actions".to_string());
+        }
+        if object.checkpoint.is_some() {
+            self.unsupported.insert("checkpoint objects".to_string());
+        }
+
+        match group {
+            // Adding to a physics group gives the object a body and resets
+            // its settings, so the body is configured afterwards
+            Some(group) => w.line(format!("{}.add({})", group, local)),
+            None if has_body => {
+                let is_static = object
+                    .physics
+                    .as_ref()
+                    .is_some_and(|physics| matches!(physics.body, PhysicsBody::Static));
+                if is_static {
+                    w.line(format!("this.physics.add.existing({}, true)", local));
+                } else {
+                    w.line(format!("this.physics.add.existing({})", local));
+                }
+            }
+            None => return true,
+        }
+        self.write_body(w, object, local);
+        true
+    }
+
+    fn write_body(&mut self, w: &mut Writer, object: &GameObject, local: &str) {
+        let body = format!("{}.body", local);
+        if let Some(collision_box) = object.emoji.as_ref().map(|emoji| &emoji.collision_box) {
+            // Center the collision box on the emoji
+            match collision_box.shape {
+                CollisionBoxShape::Rectangle => {
+                    if let (Some(width), Some(height)) = (collision_box.width, collision_box.height)
+                    {
+                        w.line(format!("{}.setSize({}, {})", body, num(width), num(height)));
+                        w.line(format!(
+                            "{}.setOffset(({}.width - {}) / 2, ({}.height - {}) / 2)",
+                            body,
+                            local,
+                            num(width),
+                            local,
+                            num(height)
+                        ));
+                    }
+                }
+                CollisionBoxShape::Circle => {
+                    if let Some(radius) = collision_box.radius {
+                        w.line(format!(
+                            "{}.setCircle({}, ({}.width - {}) / 2, ({}.height - {}) / 2)",
+                            body,
+                            num(radius),
+                            local,
+                            num(radius * 2.0),
+                            local,
+                            num(radius * 2.0)
+                        ));
+                    }
+                }
+            }
+        }
+        let Some(physics) = &object.physics else {
+            return;
+        };
+        if matches!(physics.body, PhysicsBody::Static) {
+            if physics.one_way.is_some() || physics.surface.is_some() {
+                self.unsupported
+                    .insert("one-way platforms and surfaces".to_string());
+            }
+            return;
+        }
+        if let Some(bounce) = physics.bounce {
+            w.line(format!("{}.setBounce({})", body, num(bounce)));
+        }
+        if let Some(collide) = physics.collide_world_bounds {
+            w.line(format!("{}.setCollideWorldBounds({})", body, collide));
+        }
+        if let Some(velocity) = &physics.velocity {
+            w.line(format!(
+                "{}.setVelocity({}, {})",
+                body,
+                num(velocity.x),
+                num(velocity.y)
+            ));
+        }
+        if let Some(drag) = physics.drag {
+            w.line(format!("{}.setDrag({})", body, num(drag)));
+        }
+        if let Some(angular_velocity) = physics.angular_velocity {
+            w.line(format!(
+                "{}.setAngularVelocity({})",
+                body,
+                num(angular_velocity)
+            ));
+        }
+        if let Some(max_velocity) = physics.max_velocity {
+            w.line(format!("{}.setMaxVelocity({})", body, num(max_velocity)));
+        }
+        if physics.immovable == Some(true) {
+            w.line(format!("{}.setImmovable(true)", body));
+        }
+        if let Some(mass) = physics.mass {
+            w.line(format!("{}.mass = {}", body, num(mass)));
+        }
+        if let Some(gravity) = &physics.gravity {
+            // Body gravity adds to the world's, so subtract it to replace it
+            let world = &self.spec.game.physics.gravity;
+            w.line(format!(
+                "{}.setGravity({}, {})",
+                body,
+                num(gravity.x - world.x),
+                num(gravity.y - world.y)
+            ));
+        }
+    }
+
+    fn write_interactions(&mut self, w: &mut Writer) {
+        let Some(logic) = self.logic else {
+            return;
+        };
+        let handlers = logic
+            .on_collision
+            .iter()
+            .flatten()
+            .map(|handler| ("collider", handler))
+            .chain(
+                logic
+                    .on_overlap
+                    .iter()
+                    .flatten()
+                    .map(|handler| ("overlap", handler)),
+            );
+        let mut wrote_header = false;
+        for (kind, handler) in handlers {
+            if !wrote_header {
+                w.blank();
+                w.line("// Collisions and overlaps");
+                wrote_header = true;
+            }
+            let parsed = handler.split_once("->").and_then(|(pair, callback)| {
+                let (a, b) = pair.split_once(',')?;
+                Some((a.trim(), b.trim(), callback.trim()))
+            });
+            let Some((a, b, callback)) = parsed else {
+                w.line(format!(
+                    "// Skipped invalid handler {}",
+                    comment_text(&js_string(handler))
+                ));
+                continue;
+            };
+            let (first, second) = match (self.target(a), self.target(b)) {
+                (Some(first), Some(second)) => (first, second),
+                (None, _) | (_, None) => {
+                    let missing = if self.target(a).is_none() { a } else { b };
+                    w.line(format!(
+                        "// Skipped {}: no object or group named '{}'",
+                        comment_text(&js_string(handler)),
+                        comment_text(missing)
+                    ));
+                    continue;
+                }
+            };
+            let callback = match callback {
+                "null" => None,
+                _ if self.actions.contains_key(callback) => Some(format!(
+                    "(first, second) => this.{}(second, first)",
+                    self.action_methods[callback]
+                )),
+                "gameOver" => {
+                    self.helpers.insert(Helper::EndGame);
+                    Some("() => this.endGame('GAME OVER', '#ff0000')".to_string())
+                }
+                "destroy" => Some("(first, second) => second.destroy()".to_string()),
+                _ => {
+                    w.line(format!("// Unknown callback '{}'", comment_text(callback)));
+                    None
+                }
+            };
+            match callback {
+                Some(callback) => w.line(format!(
+                    "this.physics.add.{}({}, {}, {})",
+                    kind, first, second, callback
+                )),
+                None => w.line(format!("this.physics.add.{}({}, {})", kind, first, second)),
+            }
+        }
+
+        let timers = logic.timers.iter().flatten();
+        let mut wrote_header = false;
+        for timer in timers {
+            if !wrote_header {
+                w.blank();
+                w.line("// Timers");
+                wrote_header = true;
+            }
+            let parsed = timer.split_once("->").and_then(|(when, callback)| {
+                let when = when.trim();
+                let (repeat, delay) = if let Some(delay) = when.strip_prefix("every") {
+                    (true, delay)
+                } else {
+                    (false, when.strip_prefix("after")?)
+                };
+                let delay: u32 = delay.trim().strip_suffix("ms")?.trim().parse().ok()?;
+                Some((repeat, delay, callback.trim()))
+            });
+            match parsed {
+                Some((repeat, delay, callback)) if self.actions.contains_key(callback) => {
+                    w.line(format!(
+                        "this.time.addEvent({{ delay: {}, loop: {}, callback: () => this.{}() }})",
+                        delay, repeat, self.action_methods[callback]
+                    ))
+                }
+                Some((_, _, callback)) => w.line(format!(
+                    "// Timer calls unknown action '{}'",
+                    comment_text(callback)
+                )),
+                None => w.line(format!(
+                    "// Skipped invalid timer {}",
+                    comment_text(&js_string(timer))
+                )),
+            }
+        }
+
+        let key_triggers: Vec<_> = logic.on_key_press.iter().flatten().collect();
+        if !key_triggers.is_empty() {
+            w.blank();
+            w.line("// Key triggers");
+            for trigger in key_triggers {
+                match self.action_methods.get(trigger.action.as_str()) {
+                    Some(method) => w.line(format!(
+                        "this.input.keyboard.on({}, () => this.{}())",
+                        js_string(&format!("keydown-{}", phaser_key(&trigger.key))),
+                        method
+                    )),
+                    None => w.line(format!(
+                        "// Key '{}' calls unknown action '{}'",
+                        comment_text(&trigger.key),
+                        comment_text(&trigger.action)
+                    )),
+                }
+            }
+        }
+    }
+
+    /// Expression for an object or group named in a handler
+    fn target(&self, id: &str) -> Option<String> {
+        if id == "projectiles" && !self.shoot_methods.is_empty() {
+            return Some("this.projectiles".to_string());
+        }
+        let is_template = self
+            .spawners()
+            .iter()
+            .any(|spawner| spawner.template.id == id);
+        match self.scene.objects.iter().find(|object| object.id == id) {
+            Some(object) if matches!(object.object_type, ObjectType::Group) => {
+                Some(member("this.groups", id))
+            }
+            Some(_) => Some(member("this.objects", id)),
+            None if is_template => Some(member("this.groups", id)),
+            None => None,
+        }
+    }
+
+    fn write_update(&mut self, w: &mut Writer) {
+        w.open("update() {");
+        let mut locals = Names::reserved();
+        for object in &self.scene.objects {
+            let Some(controls) = &object.controls else {
+                continue;
+            };
+            let local = locals.claim(&camel_case(&object.id));
+            w.line(format!("// Controls for {}", comment_text(&object.id)));
+            w.line(format!(
+                "const {} = {}",
+                local,
+                member("this.objects", &object.id)
+            ));
+            w.line(format!(
+                "const {}Keys = {}",
+                local,
+                member("this.keys", &object.id)
+            ));
+            w.open(format!("if ({}?.body) {{", local));
+            let keys = format!("{}Keys", local);
+            if controls.left.is_some() || controls.right.is_some() {
+                w.line(format!("{}.body.setVelocityX(0)", local));
+                if controls.left.is_some() {
+                    w.line(format!(
+                        "if ({}.left.isDown) {}.body.setVelocityX(-{})",
+                        keys, local, MOVE_SPEED
+                    ));
+                }
+                if controls.right.is_some() {
+                    w.line(format!(
+                        "if ({}.right.isDown) {}.body.setVelocityX({})",
+                        keys, local, MOVE_SPEED
+                    ));
+                }
+            }
+            if controls.up.is_some() || controls.down.is_some() {
+                w.line(format!("{}.body.setVelocityY(0)", local));
+                if controls.up.is_some() {
+                    w.line(format!(
+                        "if ({}.up.isDown) {}.body.setVelocityY(-{})",
+                        keys, local, MOVE_SPEED
+                    ));
+                }
+                if controls.down.is_some() {
+                    w.line(format!(
+                        "if ({}.down.isDown) {}.body.setVelocityY({})",
+                        keys, local, MOVE_SPEED
+                    ));
+                }
+            }
+            if controls.jump.is_some() {
+                w.line("// Jump only from the ground");
+                w.line(format!(
+                    "if ({}.jump.isDown && {}.body.touching.down) {}.body.setVelocityY(-{})",
+                    keys, local, local, JUMP_SPEED
+                ));
+            }
+            if let Some(method) = self.shoot_methods.get(object.id.as_str()) {
+                w.line(format!(
+                    "const {}LastShot = {} ?? 0",
+                    local,
+                    member("this.lastShot", &object.id)
+                ));
+                w.open(format!(
+                    "if ({}.shoot.isDown && this.time.now - {}LastShot > {}) {{",
+                    keys, local, SHOOT_COOLDOWN_MS
+                ));
+                w.line(format!(
+                    "{} = this.time.now",
+                    member("this.lastShot", &object.id)
+                ));
+                w.line(format!("this.{}()", method));
+                w.close("}");
+            }
+            if let Some(animations) = &object.animations {
+                let mut branches = Vec::new();
+                if let Some(jump) = &animations.jump {
+                    branches.push((format!("!{}.body.touching.down", local), jump));
+                }
+                if let Some(walk) = &animations.walk {
+                    branches.push((format!("{}.body.velocity.x !== 0", local), walk));
+                }
+                if !branches.is_empty() || animations.idle.is_some() {
+                    w.line("// Animation for the current movement");
+                }
+                for (i, (condition, animation)) in branches.iter().enumerate() {
+                    let keyword = if i == 0 { "if" } else { "} else if" };
+                    if i > 0 {
+                        w.dedent();
+                    }
+                    w.open(format!("{} ({}) {{", keyword, condition));
+                    w.line(format!(
+                        "{}.anims.play({}, true)",
+                        local,
+                        js_string(animation)
+                    ));
+                }
+                match (&animations.idle, branches.is_empty()) {
+                    (Some(idle), true) => {
+                        w.line(format!("{}.anims.play({}, true)", local, js_string(idle)))
+                    }
+                    (Some(idle), false) => {
+                        w.dedent();
+                        w.open("} else {");
+                        w.line(format!("{}.anims.play({}, true)", local, js_string(idle)));
+                        w.close("}");
+                    }
+                    (None, false) => w.close("}"),
+                    (None, true) => (),
+                }
+            }
+            w.close("}");
+        }
+
+        for object in &self.scene.objects {
+            if let Some(behavior) = &object.behavior {
+                let target = member("this.objects", &object.id);
+                if let Some(call) = self.behavior_call(behavior, object, &target) {
+                    w.line(format!(
+                        "// {} behavior for {}",
+                        behavior_name(behavior),
+                        comment_text(&object.id)
+                    ));
+                    w.line(format!("if ({}?.body) {}", target, call));
+                }
+            }
+        }
+        for spawner in self.spawners() {
+            let template = &spawner.template;
+            if let Some(behavior) = &template.behavior {
+                if let Some(call) = self.behavior_call(behavior, template, "obj") {
+                    w.line(format!(
+                        "// {} behavior for spawned {}",
+                        behavior_name(behavior),
+                        comment_text(&template.id)
+                    ));
+                    w.line(format!(
+                        "for (const obj of {}.getChildren()) {}",
+                        member("this.groups", &template.id),
+                        call
+                    ));
+                }
+            }
+        }
+
+        if !self.shoot_methods.is_empty() {
+            w.line("// Projectiles are removed once they leave the world");
+            w.open("for (const projectile of [...this.projectiles.getChildren()]) {");
+            w.open("if (!this.physics.world.bounds.contains(projectile.x, projectile.y)) {");
+            w.line("projectile.destroy()");
+            w.close("}");
+            w.close("}");
+        }
+
+        let wrap_all = self.spec.game.physics.wrap_world;
+        for object in &self.scene.objects {
+            let Some(physics) = &object.physics else {
+                continue;
+            };
+            if matches!(physics.body, PhysicsBody::Dynamic)
+                && physics.wrap_world.unwrap_or(wrap_all)
+            {
+                w.line(format!(
+                    "this.physics.world.wrap({})",
+                    member("this.objects", &object.id)
+                ));
+            }
+        }
+        for spawner in self.spawners() {
+            let wraps = spawner.template.physics.as_ref().is_some_and(|physics| {
+                matches!(physics.body, PhysicsBody::Dynamic)
+                    && physics.wrap_world.unwrap_or(wrap_all)
+            });
+            if wraps {
+                w.line(format!(
+                    "this.physics.world.wrap({})",
+                    member("this.groups", &spawner.template.id)
+                ));
+            }
+        }
+        w.close("}");
+    }
+
+    /// Call of the helper running `behavior` for `target`
+    fn behavior_call(
+        &mut self,
+        behavior: &BehaviorType,
+        object: &GameObject,
+        target: &str,
+    ) -> Option<String> {
+        let param = |name: &str| {
+            object
+                .behavior_params
+                .as_ref()
+                .and_then(|params| params.get(name))
+        };
+        let number = |name: &str, default: f64| {
+            param(name)
+                .and_then(Value::as_f64)
+                .unwrap_or(default)
+                .to_string()
+        };
+        match behavior {
+            BehaviorType::Patrol => {
+                self.helpers.insert(Helper::Patrol);
+                Some(format!(
+                    "this.patrol({}, {}, {})",
+                    target,
+                    number("range", 200.0),
+                    number("speed", 50.0)
+                ))
+            }
+            BehaviorType::Follow => {
+                self.helpers.insert(Helper::Follow);
+                let followed = param("target").and_then(Value::as_str).unwrap_or("player");
+                Some(format!(
+                    "this.follow({}, {}, {})",
+                    target,
+                    member("this.objects", followed),
+                    number("speed", 80.0)
+                ))
+            }
+            BehaviorType::Random => {
+                self.helpers.insert(Helper::Wander);
+                Some(format!(
+                    "this.wander({}, {}, {})",
+                    target,
+                    number("speed", 100.0),
+                    number("interval", 1000.0)
+                ))
+            }
+            BehaviorType::Static => None,
+        }
+    }
+
+    fn write_spawner(&mut self, w: &mut Writer, spawner: &Spawner) {
+        let method = self.spawner_methods[spawner.id.as_str()].clone();
+        let group = member("this.groups", &spawner.template.id);
+        let count = member("this.spawnCounts", &spawner.id);
+        w.line(format!("// Spawner '{}'", comment_text(&spawner.id)));
+        w.open(format!("{}() {{", method));
+        if let Some(max_count) = spawner.max_count {
+            w.line(format!("if ({} >= {}) return", count, max_count));
+        }
+        if let Some(pool) = &spawner.pool {
+            write_pool_check(w, &group, pool.max_live, pool.recycle);
+        }
+
+        let width = self.spec.game.width;
+        let height = self.spec.game.height;
+        let variance = spawner.position_variance.as_ref();
+        let (x, y) = match spawner.spawn_area.to_lowercase().as_str() {
+            "top" => (
+                format!("Phaser.Math.Between(0, {})", width),
+                "0".to_string(),
+            ),
+            "bottom" => (
+                format!("Phaser.Math.Between(0, {})", width),
+                height.to_string(),
+            ),
+            "left" => (
+                "0".to_string(),
+                format!("Phaser.Math.Between(0, {})", height),
+            ),
+            "right" => (
+                width.to_string(),
+                format!("Phaser.Math.Between(0, {})", height),
+            ),
+            "random" => (
+                format!("Phaser.Math.Between(0, {})", width),
+                format!("Phaser.Math.Between(0, {})", height),
+            ),
+            _ => ("0".to_string(), "0".to_string()),
+        };
+        // Position variance overrides the spawn area
+        let x = match variance {
+            Some(variance) => format!(
+                "Phaser.Math.Between({}, {})",
+                num(variance.x_min),
+                num(variance.x_max)
+            ),
+            None => x,
+        };
+        let y = match variance.and_then(|variance| variance.y_min.zip(variance.y_max)) {
+            Some((min, max)) => format!("Phaser.Math.Between({}, {})", num(min), num(max)),
+            None => y,
+        };
+        w.line(format!("const x = {}", x));
+        w.line(format!("const y = {}", y));
+        if self.write_object(w, &spawner.template, "obj", "x", "y", Some(&group)) {
+            w.line(format!("{}++", count));
+        }
+        w.close("}");
+    }
+
+    fn write_shoot(
+        &mut self,
+        w: &mut Writer,
+        object: &GameObject,
+        controls: &Controls,
+        projectile: &GameObject,
+    ) {
+        let method = self.shoot_methods[object.id.as_str()].clone();
+        w.line(format!(
+            "// Fire {}'s projectile from its position",
+            comment_text(&object.id)
+        ));
+        w.open(format!("{}() {{", method));
+        w.line(format!(
+            "const shooter = {}",
+            member("this.objects", &object.id)
+        ));
+        w.line("if (!shooter) return");
+        if let Some(pool) = &controls.projectile_pool {
+            write_pool_check(w, "this.projectiles", pool.max_live, pool.recycle);
+        }
+        if self.write_object(
+            w,
+            projectile,
+            "obj",
+            "shooter.x",
+            "shooter.y",
+            Some("this.projectiles"),
+        ) {
+            w.line("obj.body.setCollideWorldBounds(false)");
+        }
+        w.close("}");
+    }
+
+    /// Call an action by name, or note that it doesn't exist
+    fn write_call(&self, w: &mut Writer, action: &str, args: &str) {
+        match self.action_methods.get(action) {
+            Some(method) => w.line(format!("this.{}({})", method, args)),
+            None => w.line(format!("// Unknown action '{}'", comment_text(action))),
+        }
+    }
+
+    /// Body of an action method. `target` is the second object of a handler
+    /// pair and `source` the first, as in the frontend's interpreter.
+    fn write_effect(&mut self, w: &mut Writer, effect: &ActionEffect) {
+        let object = |object_id: &Option<String>, default: &str| match object_id {
+            Some(id) => member("this.objects", id),
+            None => default.to_string(),
+        };
+        match effect {
+            ActionEffect::UpdateScore { points } => {
+                self.helpers.insert(Helper::AddScore);
+                w.line(format!("this.addScore({})", points));
+                w.line("target?.destroy()");
+                if !self.shoot_methods.is_empty() {
+                    w.line("// Projectiles are used up when they score");
+                    w.line("if (source && this.projectiles.contains(source)) source.destroy()");
+                }
+            }
+            ActionEffect::GameOver { message, .. } => {
+                self.helpers.insert(Helper::EndGame);
+                w.line(format!(
+                    "this.endGame({}, '#ff0000')",
+                    js_string(message.as_deref().unwrap_or("GAME OVER"))
+                ));
+            }
+            ActionEffect::Win {
+                message,
+                next_scene,
+                ..
+            } => {
+                self.helpers.insert(Helper::EndGame);
+                w.line(format!(
+                    "this.endGame({}, '#00ff00')",
+                    js_string(message.as_deref().unwrap_or("YOU WIN!"))
+                ));
+                if let Some(next_scene) = next_scene {
+                    w.line("// Click to continue to the next level");
+                    w.line(format!(
+                        "this.input.once('pointerdown', () => this.scene.start({}))",
+                        js_string(next_scene)
+                    ));
+                }
+            }
+            ActionEffect::Destroy => w.line("target?.destroy()"),
+            ActionEffect::RestartScene => w.line("this.scene.restart()"),
+            ActionEffect::UpdateText { object_id, text } => w.line(format!(
+                "{}?.setText({})",
+                member("this.objects", object_id),
+                js_string(text)
+            )),
+            ActionEffect::UpdateVariable {
+                name,
+                change,
+                value,
+            } => {
+                let variable = member("this.vars", name);
+                match (change, value) {
+                    (_, Some(value)) => w.line(format!("{} = {}", variable, value)),
+                    (Some(change), None) => w.line(format!("{} += {}", variable, change)),
+                    (None, None) => (),
+                }
+            }
+            ActionEffect::Damage { amount, object_id } => {
+                self.helpers.insert(Helper::Damage);
+                w.line(format!(
+                    "this.damage({}, {})",
+                    object(object_id, "source"),
+                    amount
+                ));
+            }
+            ActionEffect::SetAlpha {
+                alpha,
+                object_id,
+                duration,
+            } => {
+                let target = object(object_id, "source");
+                match duration {
+                    Some(duration) => w.line(format!(
+                        "if ({0}) this.tweens.add({{ targets: {0}, alpha: {1}, duration: {2} }})",
+                        target,
+                        num(*alpha),
+                        duration
+                    )),
+                    None => w.line(format!("{}?.setAlpha({})", target, num(*alpha))),
+                }
+            }
+            ActionEffect::SetTint {
+                color: tint,
+                object_id,
+                duration,
+            } => {
+                let target = object(object_id, "source");
+                match tint {
+                    Some(tint) => {
+                        w.line(format!("{}?.setTint?.({})", target, color(Some(tint))));
+                        if let Some(duration) = duration {
+                            w.line(format!(
+                                "this.time.delayedCall({}, () => {}?.clearTint?.())",
+                                duration, target
+                            ));
+                        }
+                    }
+                    None => w.line(format!("{}?.clearTint?.()", target)),
+                }
+            }
+            ActionEffect::Rotate {
+                degrees,
+                object_id,
+                duration,
+            } => {
+                let target = object(object_id, "source");
+                match duration {
+                    Some(duration) => w.line(format!(
+                        "if ({0}) this.tweens.add({{ targets: {0}, angle: {0}.angle + {1}, duration: {2} }})",
+                        target,
+                        num(*degrees),
+                        duration
+                    )),
+                    None => w.line(format!("if ({0}) {0}.angle += {1}", target, num(*degrees))),
+                }
+            }
+            ActionEffect::Scale {
+                scale,
+                object_id,
+                duration,
+            } => {
+                let target = object(object_id, "source");
+                match duration {
+                    Some(duration) => w.line(format!(
+                        "if ({0}) this.tweens.add({{ targets: {0}, scale: {1}, duration: {2} }})",
+                        target,
+                        num(*scale),
+                        duration
+                    )),
+                    None => w.line(format!("{}?.setScale({})", target, num(*scale))),
+                }
+            }
+            ActionEffect::CameraShake {
+                duration,
+                intensity,
+            } => w.line(format!(
+                "this.cameras.main.shake({}, {})",
+                duration,
+                num(intensity.unwrap_or(0.01))
+            )),
+            ActionEffect::CameraFlash {
+                duration,
+                color: flash,
+            } => {
+                let (r, g, b) = rgb(flash.as_deref().unwrap_or("#ffffff"));
+                w.line(format!(
+                    "this.cameras.main.flash({}, {}, {}, {})",
+                    duration, r, g, b
+                ));
+            }
+            ActionEffect::CameraZoom { zoom, duration } => w.line(format!(
+                "this.cameras.main.zoomTo({}, {})",
+                num(*zoom),
+                duration.unwrap_or(500)
+            )),
+            ActionEffect::StartSpawner { spawner_id } => w.line(format!(
+                "if ({0}) {0}.paused = false",
+                member("this.spawners", spawner_id)
+            )),
+            ActionEffect::StopSpawner { spawner_id } => w.line(format!(
+                "if ({0}) {0}.paused = true",
+                member("this.spawners", spawner_id)
+            )),
+            ActionEffect::SetSpawnerInterval {
+                spawner_id,
+                interval,
+            } => match self.spawner_methods.get(spawner_id.as_str()) {
+                Some(method) => w.line(format!(
+                    "{}.reset({{ delay: {}, loop: true, callback: this.{}, callbackScope: this }})",
+                    member("this.spawners", spawner_id),
+                    interval,
+                    method
+                )),
+                None => w.line(format!("// Unknown spawner '{}'", comment_text(spawner_id))),
+            },
+            ActionEffect::FlashScreen {
+                color: overlay,
+                duration,
+                alpha,
+            } => {
+                w.line("const { width, height } = this.scale");
+                w.line(format!(
+                    "const overlay = this.add.rectangle(0, 0, width, height, {}, {})",
+                    color(Some(overlay)),
+                    num(alpha.unwrap_or(0.5))
+                ));
+                w.line("overlay.setOrigin(0).setScrollFactor(0).setDepth(1000)");
+                w.line(format!(
+                    "this.tweens.add({{ targets: overlay, alpha: 0, duration: {}, onComplete: () => overlay.destroy() }})",
+                    duration
+                ));
+            }
+            ActionEffect::SetBackgroundColor {
+                color: background, ..
+            } => w.line(format!(
+                "this.cameras.main.setBackgroundColor({})",
+                js_string(background)
+            )),
+            ActionEffect::SetTimeScale { scale, duration } => {
+                // Arcade physics time scale is inverted: 2 runs at half speed
+                w.line(format!("this.time.timeScale = {}", num(*scale)));
+                w.line(format!(
+                    "this.physics.world.timeScale = {}",
+                    num(1.0 / scale)
+                ));
+                if let Some(duration) = duration {
+                    // Real time, since the scene clock is scaled
+                    w.open("setTimeout(() => {");
+                    w.line("this.time.timeScale = 1");
+                    w.line("this.physics.world.timeScale = 1");
+                    w.close(format!("}}, {})", duration));
+                }
+            }
+            ActionEffect::SaveHighScore { .. }
+            | ActionEffect::LoseLife
+            | ActionEffect::SetCheckpoint { .. }
+            | ActionEffect::RespawnAtCheckpoint { .. } => {
+                let name = effect_name(effect);
+                w.line(format!("// {} is only supported by the pueo runtime", name));
+                self.unsupported.insert(format!("{} actions", name));
+            }
+        }
+    }
+
+    fn write_helper(&self, w: &mut Writer, helper: Helper) {
+        match helper {
+            Helper::AddScore => {
+                w.open("addScore(points) {");
+                let triggers: Vec<_> = self
+                    .logic
+                    .iter()
+                    .flat_map(|logic| logic.on_score_reached.iter().flatten())
+                    .collect();
+                if !triggers.is_empty() {
+                    w.line("const previous = this.score");
+                }
+                w.line("this.score += points");
+                if let Some(score) = self.scene.hud.as_ref().and_then(|hud| hud.score.as_ref()) {
+                    w.line(format!(
+                        "this.scoreText.setText({} + this.score)",
+                        js_string(score.label.as_deref().unwrap_or("Score: "))
+                    ));
+                }
+                if self
+                    .scene
+                    .objects
+                    .iter()
+                    .any(|object| object.id == "scoreText")
+                {
+                    w.line("this.objects.scoreText?.setText(`Score: ${this.score}`)");
+                }
+                for trigger in triggers {
+                    let condition = if trigger.repeat {
+                        format!(
+                            "Math.floor(this.score / {0}) > Math.floor(previous / {0})",
+                            trigger.at.max(1)
+                        )
+                    } else {
+                        format!("previous < {0} && this.score >= {0}", trigger.at)
+                    };
+                    match self.action_methods.get(trigger.action.as_str()) {
+                        Some(method) => w.line(format!("if ({}) this.{}()", condition, method)),
+                        None => w.line(format!(
+                            "// Unknown action '{}'",
+                            comment_text(&trigger.action)
+                        )),
+                    }
+                }
+                w.close("}");
+            }
+            Helper::EndGame => {
+                w.line("// Show a message, then freeze the game");
+                w.open("endGame(message, color) {");
+                w.line("const { centerX, centerY } = this.cameras.main");
+                w.line("this.add.text(centerX, centerY, message, { fontSize: '64px', color })");
+                w.line("  .setOrigin(0.5)");
+                w.line("  .setScrollFactor(0)");
+                w.line("  .setDepth(1000)");
+                w.line("this.physics.pause()");
+                w.line("this.time.removeAllEvents()");
+                w.close("}");
+            }
+            Helper::Damage => {
+                w.line("// Objects are destroyed when their health runs out");
+                w.open("damage(obj, amount) {");
+                w.line("if (!obj) return");
+                w.line("const health = (obj.getData('health') ?? 0) - amount");
+                w.line("obj.setData('health', health)");
+                w.line("if (health <= 0) obj.destroy()");
+                w.close("}");
+            }
+            Helper::Patrol => {
+                w.line("// Move back and forth across `range` pixels");
+                w.open("patrol(obj, range, speed) {");
+                w.line("if (obj.getData('patrolStart') === undefined) obj.setData('patrolStart', obj.body.x)");
+                w.open("if (Math.abs(obj.body.x - obj.getData('patrolStart')) >= range) {");
+                w.line("obj.body.setVelocityX(-obj.body.velocity.x || speed)");
+                w.dedent();
+                w.open("} else if (obj.body.velocity.x === 0) {");
+                w.line("obj.body.setVelocityX(speed)");
+                w.close("}");
+                w.close("}");
+            }
+            Helper::Follow => {
+                w.line("// Head straight for the target");
+                w.open("follow(obj, target, speed) {");
+                w.line("if (!target?.body) return");
+                w.line("const angle = Phaser.Math.Angle.Between(obj.body.x, obj.body.y, target.body.x, target.body.y)");
+                w.line("obj.body.setVelocity(Math.cos(angle) * speed, Math.sin(angle) * speed)");
+                w.close("}");
+            }
+            Helper::Wander => {
+                w.line("// Pick a new random direction every `interval` milliseconds");
+                w.open("wander(obj, speed, interval) {");
+                w.line("const lastTurn = obj.getData('lastTurn') ?? 0");
+                w.open("if (this.time.now - lastTurn > interval) {");
+                w.line("const angle = Phaser.Math.FloatBetween(0, Math.PI * 2)");
+                w.line("obj.body.setVelocity(Math.cos(angle) * speed, Math.sin(angle) * speed)");
+                w.line("obj.setData('lastTurn', this.time.now)");
+                w.close("}");
+                w.close("}");
+            }
+        }
+    }
+}
+
+/// Stop `group` from growing past `max_live`, by skipping or by recycling the oldest
+fn write_pool_check(w: &mut Writer, group: &str, max_live: u32, recycle: RecyclePolicy) {
+    match recycle {
+        RecyclePolicy::Skip => w.line(format!(
+            "if ({}.countActive() >= {}) return",
+            group, max_live
+        )),
+        RecyclePolicy::Oldest => w.line(format!(
+            "if ({0}.countActive() >= {1}) {0}.getFirst(true)?.destroy()",
+            group, max_live
+        )),
+    }
+}
+
+/// The controls and projectile of an object that can shoot
+fn shooter(object: &GameObject) -> Option<(&Controls, &GameObject)> {
+    let controls = object.controls.as_ref()?;
+    controls.shoot.as_ref()?;
+    Some((controls, controls.projectile.as_deref()?))
+}
+
+fn control_keys(controls: &Controls) -> Vec<(&'static str, &str)> {
+    [
+        ("left", &controls.left),
+        ("right", &controls.right),
+        ("up", &controls.up),
+        ("down", &controls.down),
+        ("jump", &controls.jump),
+        ("shoot", &controls.shoot),
+    ]
+    .into_iter()
+    .filter_map(|(name, key)| key.as_deref().map(|key| (name, key)))
+    .collect()
+}
+
+/// Phaser key code name for a key from a spec (e.g. "ArrowLeft" -> "LEFT")
+fn phaser_key(key: &str) -> String {
+    match key.to_lowercase().as_str() {
+        "arrowleft" | "left" => "LEFT".to_string(),
+        "arrowright" | "right" => "RIGHT".to_string(),
+        "arrowup" | "up" => "UP".to_string(),
+        "arrowdown" | "down" => "DOWN".to_string(),
+        " " | "space" | "spacebar" => "SPACE".to_string(),
+        "enter" | "return" => "ENTER".to_string(),
+        "escape" | "esc" => "ESC".to_string(),
+        _ => key.to_uppercase(),
+    }
+}
+
+fn asset_url(asset: &Asset) -> String {
+    match &asset.library_key {
+        Some(key) => js_string(key),
+        None => js_string(&asset.url),
+    }
+}
+
+fn object_type_name(object: &GameObject) -> &'static str {
+    match object.object_type {
+        ObjectType::Sprite => "sprite",
+        ObjectType::Rectangle => "rectangle",
+        ObjectType::Circle => "circle",
+        ObjectType::Text => "text",
+        ObjectType::Emoji => "emoji",
+        ObjectType::Group => "group",
+        ObjectType::Hazard => "hazard",
+        ObjectType::Zone => "zone",
+    }
+}
+
+fn behavior_name(behavior: &BehaviorType) -> &'static str {
+    match behavior {
+        BehaviorType::Patrol => "Patrol",
+        BehaviorType::Follow => "Follow",
+        BehaviorType::Static => "Static",
+        BehaviorType::Random => "Random",
+    }
+}
+
+fn effect_name(effect: &ActionEffect) -> String {
+    serde_json::to_value(effect)
+        .ok()
+        .and_then(|value| value.get("type")?.as_str().map(String::from))
+        .unwrap_or_default()
+}
+
+/// Phaser color number for a hex color string; anything else becomes white
+fn color(color: Option<&str>) -> String {
+    let (r, g, b) = rgb(color.unwrap_or("#ffffff"));
+    format!("0x{:02x}{:02x}{:02x}", r, g, b)
+}
+
+fn rgb(color: &str) -> (u8, u8, u8) {
+    let hex = color
+        .strip_prefix('#')
+        .or_else(|| color.strip_prefix("0x"))
+        .unwrap_or(color);
+    match u32::from_str_radix(hex, 16) {
+        Ok(value) if hex.len() == 6 => ((value >> 16) as u8, (value >> 8) as u8, value as u8),
+        _ => (255, 255, 255),
+    }
+}
+
+fn num(value: f32) -> String {
+    value.to_string()
+}
+
+fn js_string(text: &str) -> String {
+    serde_json::to_string(text).expect("strings serialize to JSON")
+}
+
+/// Spec text for a `//` comment, with line terminators replaced so the text
+/// can't end the comment and run as code. JSON strings keep U+2028 and
+/// U+2029 unescaped, so this applies to `js_string` output too.
+fn comment_text(text: &str) -> String {
+    text.replace(['\n', '\r', '\u{2028}', '\u{2029}'], " ")
+}
+
+/// `base.key`, or `base["key"]` when the key isn't a plain identifier
+fn member(base: &str, key: &str) -> String {
+    if is_identifier(key) {
+        format!("{}.{}", base, key)
+    } else {
+        format!("{}[{}]", base, js_string(key))
+    }
+}
+
+/// Object literal key, quoted when needed
+fn js_key(key: &str) -> String {
+    if is_identifier(key) {
+        key.to_string()
+    } else {
+        js_string(key)
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+fn words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    for c in name.chars() {
+        if !c.is_ascii_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        // Split camelCase into words
+        if c.is_ascii_uppercase()
+            && current
+                .chars()
+                .last()
+                .is_some_and(|last| last.is_ascii_lowercase())
+        {
+            words.push(std::mem::take(&mut current));
+        }
+        current.push(c);
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+fn pascal_case(name: &str) -> String {
+    words(name)
+        .iter()
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+fn camel_case(name: &str) -> String {
+    let pascal = pascal_case(name);
+    let mut chars = pascal.chars();
+    match chars.next() {
+        Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+/// Unique identifiers within one scope
+#[derive(Default)]
+struct Names {
+    used: HashSet<String>,
+}
+
+impl Names {
+    /// Names taken by JavaScript or by Phaser.Scene members
+    fn reserved() -> Self {
+        let reserved = [
+            "break",
+            "case",
+            "catch",
+            "class",
+            "const",
+            "continue",
+            "debugger",
+            "default",
+            "delete",
+            "do",
+            "else",
+            "export",
+            "extends",
+            "finally",
+            "for",
+            "function",
+            "if",
+            "import",
+            "in",
+            "instanceof",
+            "let",
+            "new",
+            "return",
+            "super",
+            "switch",
+            "this",
+            "throw",
+            "try",
+            "typeof",
+            "var",
+            "void",
+            "while",
+            "with",
+            "yield",
+            "await",
+            "obj",
+            "x",
+            "y",
+            "shooter",
+            "add",
+            "anims",
+            "cameras",
+            "create",
+            "events",
+            "game",
+            "input",
+            "load",
+            "physics",
+            "preload",
+            "scale",
+            "scene",
+            "sys",
+            "time",
+            "tweens",
+            "update",
+        ];
+        Self {
+            used: reserved.iter().map(|name| name.to_string()).collect(),
+        }
+    }
+
+    fn claim(&mut self, name: &str) -> String {
+        let base = match name.chars().next() {
+            None => "_".to_string(),
+            Some(first) if first.is_ascii_digit() => format!("_{}", name),
+            Some(_) => name.to_string(),
+        };
+        let mut candidate = base.clone();
+        let mut suffix = 2;
+        while !self.used.insert(candidate.clone()) {
+            candidate = format!("{}{}", base, suffix);
+            suffix += 1;
+        }
+        candidate
+    }
+}
+
+/// Indented source text
+#[derive(Default)]
+struct Writer {
+    out: String,
+    indent: usize,
+}
+
+impl Writer {
+    fn with_indent(indent: usize) -> Self {
+        Self {
+            out: String::new(),
+            indent,
+        }
+    }
+
+    fn line(&mut self, text: impl AsRef<str>) {
+        for _ in 0..self.indent {
+            self.out.push_str("  ");
+        }
+        self.out.push_str(text.as_ref());
+        self.out.push('\n');
+    }
+
+    fn blank(&mut self) {
+        self.out.push('\n');
+    }
+
+    /// Write a line that opens a block
+    fn open(&mut self, text: impl AsRef<str>) {
+        self.line(text);
+        self.indent += 1;
+    }
+
+    /// Write a line that closes a block
+    fn close(&mut self, text: impl AsRef<str>) {
+        self.dedent();
+        self.line(text);
+    }
+
+    fn dedent(&mut self) {
+        self.indent = self.indent.saturating_sub(1);
+    }
+
+    /// Append text written by another writer
+    fn append(&mut self, other: Writer) {
+        self.out.push_str(&other.out);
+    }
+
+    fn finish(self) -> String {
+        self.out
+    }
+}
@@ -1,12 +1,24 @@
 /// Database module for storing and managing Phaser game specifications
 use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
-use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions};
+use sqlx::sqlite::{
+    SqliteConnectOptions, SqliteConnection, SqliteJournalMode, SqlitePool, SqlitePoolOptions,
+    SqliteRow, SqliteSynchronous,
+};
 use sqlx::Row;
-use std::path::PathBuf;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::time::Duration;
 
-use crate::game_builder::PhaserGameSpec;
+use crate::game_builder::{Achievement, AssetType, PhaserGameSpec};
+use crate::replay::Replay;
+use crate::spec_diff::{self, SpecDiff};
+use crate::spec_migration::{self, MigrationError};
+use crate::templates::StarterTemplate;
+use crate::validator;
 
 #[derive(Debug, thiserror::Error)]
 pub enum DbError {
@@ -20,10 +32,175 @@ pub enum DbError {
     NotFound(String),
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
+    #[error("Spec migration error: {0}")]
+    Migration(#[from] MigrationError),
+    #[error("Invalid tag: {0:?}")]
+    InvalidTag(String),
+    #[error("Thumbnail is not a PNG image")]
+    InvalidThumbnail,
+    #[error("Asset {1} is not attached to game {0}")]
+    AssetNotAttached(String, String),
+    #[error("Invalid setting key: {0:?}")]
+    InvalidSettingKey(String),
+    #[error("Game can't be published: {0}")]
+    NotPublishable(String),
 }
 
+impl DbError {
+    /// Whether the error points at a bug or a broken database rather than
+    /// bad input (unknown ids, invalid tags, ...)
+    pub fn is_internal(&self) -> bool {
+        matches!(
+            self,
+            DbError::Database(_)
+                | DbError::Serialization(_)
+                | DbError::DateTimeParse(_)
+                | DbError::Io(_)
+                | DbError::Migration(_)
+        )
+    }
+}
+
+/// Schema change applied once and recorded in `schema_migrations`
+struct Migration {
+    version: i64,
+    description: &'static str,
+    sql: &'static str,
+    /// Query returning a row when a database from before migrations were
+    /// tracked already has this change, so it is recorded without running
+    already_applied: Option<&'static str>,
+}
+
+/// All schema migrations in order. Never edit a released migration; add a new one.
+const MIGRATIONS: [Migration; 20] = [
+    Migration {
+        version: 1,
+        description: "initial",
+        sql: include_str!("../migrations/001_initial.sql"),
+        already_applied: None,
+    },
+    Migration {
+        version: 2,
+        description: "high_scores",
+        sql: include_str!("../migrations/002_high_scores.sql"),
+        already_applied: None,
+    },
+    Migration {
+        version: 3,
+        description: "achievements",
+        sql: include_str!("../migrations/003_achievements.sql"),
+        already_applied: None,
+    },
+    Migration {
+        version: 4,
+        description: "search_index",
+        sql: include_str!("../migrations/004_search_index.sql"),
+        already_applied: None,
+    },
+    Migration {
+        version: 5,
+        description: "tags",
+        sql: include_str!("../migrations/005_tags.sql"),
+        already_applied: None,
+    },
+    Migration {
+        version: 6,
+        description: "thumbnails",
+        sql: include_str!("../migrations/006_thumbnails.sql"),
+        already_applied: None,
+    },
+    Migration {
+        version: 7,
+        description: "favorites",
+        sql: include_str!("../migrations/007_favorites.sql"),
+        already_applied: Some("SELECT 1 FROM pragma_table_info('games') WHERE name = 'favorite'"),
+    },
+    Migration {
+        version: 8,
+        description: "version_labels",
+        sql: include_str!("../migrations/008_version_labels.sql"),
+        already_applied: Some(
+            "SELECT 1 FROM pragma_table_info('game_versions') WHERE name = 'label'",
+        ),
+    },
+    Migration {
+        version: 9,
+        description: "high_score_players",
+        sql: include_str!("../migrations/009_high_score_players.sql"),
+        already_applied: Some(
+            "SELECT 1 FROM pragma_table_info('high_scores') WHERE name = 'player_name'",
+        ),
+    },
+    Migration {
+        version: 10,
+        description: "game_assets",
+        sql: include_str!("../migrations/010_game_assets.sql"),
+        already_applied: None,
+    },
+    Migration {
+        version: 11,
+        description: "settings",
+        sql: include_str!("../migrations/011_settings.sql"),
+        already_applied: None,
+    },
+    Migration {
+        version: 12,
+        description: "quarantine",
+        sql: include_str!("../migrations/012_quarantine.sql"),
+        already_applied: None,
+    },
+    Migration {
+        version: 13,
+        description: "templates",
+        sql: include_str!("../migrations/013_templates.sql"),
+        already_applied: None,
+    },
+    Migration {
+        version: 14,
+        description: "game_status",
+        sql: include_str!("../migrations/014_game_status.sql"),
+        already_applied: None,
+    },
+    Migration {
+        version: 15,
+        description: "archived",
+        sql: include_str!("../migrations/015_archived.sql"),
+        already_applied: None,
+    },
+    Migration {
+        version: 16,
+        description: "game_notes",
+        sql: include_str!("../migrations/016_game_notes.sql"),
+        already_applied: None,
+    },
+    Migration {
+        version: 17,
+        description: "reindex_emojis",
+        sql: include_str!("../migrations/017_reindex_emojis.sql"),
+        already_applied: None,
+    },
+    Migration {
+        version: 18,
+        description: "game_metadata",
+        sql: include_str!("../migrations/018_game_metadata.sql"),
+        already_applied: None,
+    },
+    Migration {
+        version: 19,
+        description: "error_reports",
+        sql: include_str!("../migrations/019_error_reports.sql"),
+        already_applied: None,
+    },
+    Migration {
+        version: 20,
+        description: "replays",
+        sql: include_str!("../migrations/020_replays.sql"),
+        already_applied: None,
+    },
+];
+
 /// Stored game record with metadata
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct GameRecord {
     pub id: String,
     pub title: String,
@@ -43,8 +220,50 @@ pub struct GameVersion {
     pub spec: PhaserGameSpec,
     pub created_at: DateTime<Utc>,
     pub notes: Option<String>,
+    /// Milestone name such as "playtested" (unique within a game)
+    pub label: Option<String>,
+}
+
+/// High score submitted for a game
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HighScore {
+    pub id: i64,
+    pub game_id: String,
+    pub player_name: Option<String>,
+    pub score: i64,
+    pub achieved_at: DateTime<Utc>,
+}
+
+/// A named player's best score on one game
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerBest {
+    pub game_id: String,
+    pub game_title: String,
+    pub player_name: String,
+    pub best_score: i64,
+    /// Scores the player submitted on the game
+    pub plays: i64,
+    pub first_played: DateTime<Utc>,
+    pub last_played: DateTime<Utc>,
+}
+
+/// Achievement definition from a game's spec with its unlock state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AchievementStatus {
+    #[serde(flatten)]
+    pub achievement: Achievement,
+    pub unlocked_at: Option<DateTime<Utc>>,
 }
 
+/// Magic number at the start of every zstd frame
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+
+/// zstd level for stored specs; the default trades little ratio for fast saves
+const SPEC_COMPRESSION_LEVEL: i32 = 3;
+
+/// Longest player name kept on a high score
+const MAX_PLAYER_NAME_CHARS: usize = 32;
+
 /// Summary of a game (without full spec)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GameSummary {
@@ -54,44 +273,718 @@ pub struct GameSummary {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub version: i64,
+    pub favorite: bool,
+    pub status: GameStatus,
+    pub archived: bool,
+    pub tags: Vec<String>,
+}
+
+/// Whether a game is still being worked on or ready for players
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GameStatus {
+    #[default]
+    Draft,
+    Published,
+}
+
+impl GameStatus {
+    fn as_str(self) -> &'static str {
+        match self {
+            GameStatus::Draft => "draft",
+            GameStatus::Published => "published",
+        }
+    }
+
+    fn from_db(value: &str) -> Self {
+        match value {
+            "published" => GameStatus::Published,
+            _ => GameStatus::Draft,
+        }
+    }
+}
+
+/// Filters for listing games
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GameListFilter {
+    /// Only include games carrying every one of these tags
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Only include starred games
+    #[serde(default)]
+    pub favorites_only: bool,
+    /// Sort starred games before the rest
+    #[serde(default)]
+    pub favorites_first: bool,
+    /// Only include games with this status
+    #[serde(default)]
+    pub status: Option<GameStatus>,
+    /// List archived games instead of the main library
+    #[serde(default)]
+    pub archived: bool,
+    /// Only include games created at or after this time
+    #[serde(default)]
+    pub created_after: Option<DateTime<Utc>>,
+    /// Only include games created before this time
+    #[serde(default)]
+    pub created_before: Option<DateTime<Utc>>,
+    /// Only include games updated at or after this time
+    #[serde(default)]
+    pub updated_after: Option<DateTime<Utc>>,
+    /// Only include games updated before this time
+    #[serde(default)]
+    pub updated_before: Option<DateTime<Utc>>,
+    /// Only include games with physics enabled (true) or disabled (false)
+    #[serde(default)]
+    pub has_physics: Option<bool>,
+    /// Only include games placing at least this many objects across their scenes
+    #[serde(default)]
+    pub min_objects: Option<i64>,
+    /// Only include games placing at most this many objects across their scenes
+    #[serde(default)]
+    pub max_objects: Option<i64>,
+    /// Only include games that use (true) or don't use (false) spawners
+    #[serde(default)]
+    pub uses_spawners: Option<bool>,
+}
+
+/// Which versions survive `prune_versions`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetentionPolicy {
+    /// Number of most recent versions to keep (the current version is always kept)
+    #[serde(default = "default_keep_last")]
+    pub keep_last: i64,
+    /// Keep labeled versions regardless of age
+    #[serde(default = "default_keep_labeled")]
+    pub keep_labeled: bool,
+}
+
+fn default_keep_labeled() -> bool {
+    true
+}
+
+fn default_keep_last() -> i64 {
+    20
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        Self {
+            keep_last: default_keep_last(),
+            keep_labeled: default_keep_labeled(),
+        }
+    }
+}
+
+/// Result of pruning a game's version history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PruneReport {
+    pub removed_versions: Vec<i64>,
+    /// Bytes of stored spec JSON and notes that were deleted
+    pub reclaimed_bytes: i64,
+}
+
+/// Storage used by one game
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StorageStats {
+    pub game_id: String,
+    pub version_count: i64,
+    /// Compressed size of the current spec and every version
+    pub spec_bytes: i64,
+    /// Size the same specs would take as plain JSON
+    pub uncompressed_spec_bytes: i64,
+    pub thumbnail_bytes: i64,
+}
+
+/// Stored spec that failed to load and was set aside by `check_library`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuarantinedSpec {
+    pub id: i64,
+    pub game_id: String,
+    pub version: i64,
+    /// The game's current spec rather than a history version
+    pub is_current: bool,
+    pub error: String,
+    pub quarantined_at: DateTime<Utc>,
+}
+
+/// Result of `check_library`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntegrityReport {
+    /// Problems found by SQLite's integrity check (empty when the file is sound)
+    pub integrity_errors: Vec<String>,
+    /// Number of stored specs that were read
+    pub checked_specs: i64,
+    /// Everything in quarantine, including records from earlier checks
+    pub quarantined: Vec<QuarantinedSpec>,
+}
+
+/// Starter spec a new game can be created from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameTemplate {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+}
+
+/// Identity and freshness of a game, used to decide what to sync
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameStamp {
+    pub id: String,
+    pub version: i64,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Tag with the number of games using it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagCount {
+    pub name: String,
+    pub game_count: i64,
+}
+
+/// Library file attached to a game
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameAsset {
+    pub id: i64,
+    pub game_id: String,
+    pub key: String,
+    pub asset_type: AssetType,
+    pub file_path: String,
+    /// SHA-256 of the file contents, hex encoded
+    pub hash: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// What kind of failure an error report records
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportKind {
+    Panic,
+    Stream,
+    Database,
+}
+
+impl ReportKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            ReportKind::Panic => "panic",
+            ReportKind::Stream => "stream",
+            ReportKind::Database => "database",
+        }
+    }
+
+    fn from_db(value: &str) -> Self {
+        match value {
+            "panic" => ReportKind::Panic,
+            "stream" => ReportKind::Stream,
+            _ => ReportKind::Database,
+        }
+    }
+}
+
+/// Backend failure recorded for support triage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErrorReport {
+    pub id: i64,
+    pub kind: ReportKind,
+    pub message: String,
+    /// What the backend was doing when it failed
+    pub context: Option<String>,
+    pub backtrace: Option<String>,
+    pub app_version: String,
+    /// Operating system and architecture, e.g. "macos-aarch64"
+    pub platform: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Replay as listed, without its inputs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplaySummary {
+    pub id: i64,
+    pub game_id: String,
+    /// Version of the game the run was recorded on; later versions may play it back differently
+    pub game_version: i64,
+    pub player_name: Option<String>,
+    pub score: Option<i64>,
+    pub duration_ms: u64,
+    pub recorded_at: DateTime<Utc>,
+}
+
+/// Stored replay with everything needed to play it back
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedReplay {
+    pub id: i64,
+    pub game_id: String,
+    pub game_version: i64,
+    pub recorded_at: DateTime<Utc>,
+    #[serde(flatten)]
+    pub replay: Replay,
+}
+
+/// Connection tuning for `Database::new`
+#[derive(Debug, Clone)]
+pub struct DatabaseOptions {
+    /// Maximum number of pooled connections
+    pub max_connections: u32,
+    /// How long a statement waits for a locked database before failing
+    pub busy_timeout: Duration,
+}
+
+impl Default for DatabaseOptions {
+    fn default() -> Self {
+        Self {
+            max_connections: 5,
+            busy_timeout: Duration::from_secs(10),
+        }
+    }
 }
 
 /// Database connection and operations
 pub struct Database {
     pool: SqlitePool,
+    path: PathBuf,
 }
 
 impl Database {
-    /// Initialize database with migrations
-    pub async fn new(db_path: PathBuf) -> Result<Self, DbError> {
+    /// Initialize database with migrations, unlocking it with the SQLCipher
+    /// passphrase when encryption is enabled
+    pub async fn new(
+        db_path: PathBuf,
+        key: Option<&str>,
+        options: &DatabaseOptions,
+    ) -> Result<Self, DbError> {
         // Ensure parent directory exists
         if let Some(parent) = db_path.parent() {
             tokio::fs::create_dir_all(parent).await?;
         }
 
-        // Create connection options
-        let options = SqliteConnectOptions::from_str(&format!("sqlite:{}", db_path.display()))?
-            .create_if_missing(true);
+        Self::apply_pending_rekey(&db_path).await?;
+
+        // Create connection options. WAL lets reads continue during a write, and
+        // NORMAL sync is safe in WAL mode while avoiding an fsync per commit.
+        let mut connect_options =
+            SqliteConnectOptions::from_str(&format!("sqlite:{}", db_path.display()))?
+                .create_if_missing(true)
+                .journal_mode(SqliteJournalMode::Wal)
+                .synchronous(SqliteSynchronous::Normal)
+                .busy_timeout(options.busy_timeout);
+        if let Some(key) = key {
+            connect_options =
+                connect_options.pragma("key", format!("'{}'", key.replace('\'', "''")));
+        }
 
         // Create connection pool
         let pool = SqlitePoolOptions::new()
-            .max_connections(5)
-            .connect_with(options)
+            .max_connections(options.max_connections.max(1))
+            .connect_with(connect_options)
             .await?;
 
         // Run migrations
         Self::run_migrations(&pool).await?;
 
-        Ok(Self { pool })
+        let db = Self {
+            pool,
+            path: db_path,
+        };
+        db.compress_legacy_specs().await?;
+        db.backfill_search_index().await?;
+        db.backfill_metadata().await?;
+
+        Ok(db)
+    }
+
+    /// Where a re-keyed copy of the database waits for the next startup
+    fn rekeyed_path(db_path: &Path) -> PathBuf {
+        db_path.with_extension("rekeyed.db")
+    }
+
+    /// Where a re-keyed copy is written before it's committed
+    fn rekeying_path(db_path: &Path) -> PathBuf {
+        db_path.with_extension("rekeying.db")
+    }
+
+    /// Swap in a re-keyed copy committed by `commit_rekeyed`, before any connection is open
+    async fn apply_pending_rekey(db_path: &Path) -> Result<(), DbError> {
+        // A copy that was never committed may be incomplete
+        let partial = Self::rekeying_path(db_path);
+        if tokio::fs::try_exists(&partial).await? {
+            tokio::fs::remove_file(&partial).await?;
+        }
+
+        let pending = Self::rekeyed_path(db_path);
+        if !tokio::fs::try_exists(&pending).await? {
+            return Ok(());
+        }
+        // The export already includes everything from the write-ahead log
+        for suffix in ["-wal", "-shm"] {
+            let sidecar = PathBuf::from(format!("{}{}", db_path.display(), suffix));
+            if tokio::fs::try_exists(&sidecar).await? {
+                tokio::fs::remove_file(&sidecar).await?;
+            }
+        }
+        tokio::fs::rename(&pending, db_path).await?;
+        Ok(())
+    }
+
+    /// Write a copy of the database encrypted with `key` (or unencrypted when
+    /// `None`). It replaces the current file on the next startup once
+    /// `commit_rekeyed` is called.
+    pub async fn export_rekeyed(&self, key: Option<&str>) -> Result<(), DbError> {
+        self.discard_rekeyed().await?;
+
+        let result = self.write_rekeyed(key).await;
+        if result.is_err() {
+            let _ = self.discard_rekeyed().await;
+        }
+        result
+    }
+
+    async fn write_rekeyed(&self, key: Option<&str>) -> Result<(), DbError> {
+        let target = Self::rekeying_path(&self.path);
+
+        // ATTACH is per connection, so keep every statement on one
+        let mut conn = self.pool.acquire().await?;
+        sqlx::query("ATTACH DATABASE ?1 AS rekeyed KEY ?2")
+            .bind(target.display().to_string())
+            .bind(key.unwrap_or(""))
+            .execute(&mut *conn)
+            .await?;
+        let exported = sqlx::query("SELECT sqlcipher_export('rekeyed')")
+            .fetch_all(&mut *conn)
+            .await;
+        sqlx::query("DETACH DATABASE rekeyed")
+            .execute(&mut *conn)
+            .await?;
+        exported?;
+
+        Ok(())
+    }
+
+    /// Mark the copy written by `export_rekeyed` as complete, so the next
+    /// startup swaps it in
+    pub async fn commit_rekeyed(&self) -> Result<(), DbError> {
+        let result = tokio::fs::rename(
+            Self::rekeying_path(&self.path),
+            Self::rekeyed_path(&self.path),
+        )
+        .await;
+        if result.is_err() {
+            let _ = self.discard_rekeyed().await;
+        }
+        Ok(result?)
+    }
+
+    /// Remove a re-keyed copy, whether committed or not
+    pub async fn discard_rekeyed(&self) -> Result<(), DbError> {
+        for target in [
+            Self::rekeying_path(&self.path),
+            Self::rekeyed_path(&self.path),
+        ] {
+            if tokio::fs::try_exists(&target).await? {
+                tokio::fs::remove_file(&target).await?;
+            }
+        }
+        Ok(())
     }
 
-    /// Run database migrations
+    /// Apply every migration the database hasn't recorded yet, each in its own transaction
     async fn run_migrations(pool: &SqlitePool) -> Result<(), DbError> {
-        let migration = include_str!("../migrations/001_initial.sql");
-        sqlx::query(migration).execute(pool).await?;
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS schema_migrations (
+                version INTEGER PRIMARY KEY NOT NULL,
+                description TEXT NOT NULL,
+                applied_at TEXT NOT NULL
+            )
+            "#,
+        )
+        .execute(pool)
+        .await?;
+
+        let applied: HashSet<i64> = sqlx::query("SELECT version FROM schema_migrations")
+            .fetch_all(pool)
+            .await?
+            .iter()
+            .map(|row| row.get("version"))
+            .collect();
+
+        for migration in MIGRATIONS.iter().filter(|m| !applied.contains(&m.version)) {
+            let mut tx = pool.begin().await?;
+            let already_applied = match migration.already_applied {
+                Some(check) => sqlx::query(check).fetch_optional(&mut *tx).await?.is_some(),
+                None => false,
+            };
+            if !already_applied {
+                sqlx::query(migration.sql).execute(&mut *tx).await?;
+            }
+            sqlx::query(
+                r#"
+                INSERT INTO schema_migrations (version, description, applied_at)
+                VALUES (?1, ?2, ?3)
+                "#,
+            )
+            .bind(migration.version)
+            .bind(migration.description)
+            .bind(Utc::now().to_rfc3339())
+            .execute(&mut *tx)
+            .await?;
+            tx.commit().await?;
+        }
+        Ok(())
+    }
+
+    /// Parse a stored RFC 3339 timestamp
+    fn parse_timestamp(value: &str) -> Result<DateTime<Utc>, DbError> {
+        Ok(DateTime::parse_from_rfc3339(value)
+            .map_err(|e| DbError::DateTimeParse(e.to_string()))?
+            .with_timezone(&Utc))
+    }
+
+    /// Serialize a spec for storage, zstd compressed
+    fn compress_spec(spec: &PhaserGameSpec) -> Result<Vec<u8>, DbError> {
+        let json = serde_json::to_vec(spec)?;
+        Ok(zstd::encode_all(json.as_slice(), SPEC_COMPRESSION_LEVEL)?)
+    }
+
+    /// Decompress a stored spec (rows written before compression hold plain JSON)
+    fn stored_spec_json(stored: &[u8]) -> Result<String, DbError> {
+        let bytes = if stored.starts_with(&ZSTD_MAGIC) {
+            zstd::decode_all(stored)?
+        } else {
+            stored.to_vec()
+        };
+        String::from_utf8(bytes)
+            .map_err(|e| DbError::Io(std::io::Error::new(std::io::ErrorKind::InvalidData, e)))
+    }
+
+    /// Decompress and parse a stored spec, upgrading it to the current schema
+    fn load_stored_spec(stored: &[u8]) -> Result<PhaserGameSpec, DbError> {
+        Ok(spec_migration::load_spec(&Self::stored_spec_json(stored)?)?)
+    }
+
+    /// Like `load_stored_spec`, but writes the upgraded spec back to row
+    /// `row_id` of `table` so the upgrade only happens once
+    async fn load_and_backfill_spec(
+        &self,
+        table: &str,
+        row_id: i64,
+        stored: &[u8],
+    ) -> Result<PhaserGameSpec, DbError> {
+        let loaded = spec_migration::load_spec_upgraded(&Self::stored_spec_json(stored)?)?;
+        if loaded.upgraded {
+            // Skipped if the row changed since it was read
+            sqlx::query(&format!(
+                "UPDATE {} SET spec_json = ?1 WHERE rowid = ?2 AND CAST(spec_json AS BLOB) = ?3",
+                table
+            ))
+            .bind(Self::compress_spec(&loaded.spec)?)
+            .bind(row_id)
+            .bind(stored)
+            .execute(&self.pool)
+            .await?;
+        }
+        Ok(loaded.spec)
+    }
+
+    /// Compress specs saved as plain JSON before compression was introduced
+    async fn compress_legacy_specs(&self) -> Result<(), DbError> {
+        let mut tx = self.pool.begin().await?;
+        for table in ["games", "game_versions"] {
+            let rows = sqlx::query(&format!(
+                "SELECT rowid AS row_id, spec_json FROM {} WHERE typeof(spec_json) = 'text'",
+                table
+            ))
+            .fetch_all(&mut *tx)
+            .await?;
+
+            for row in rows {
+                let json: String = row.get("spec_json");
+                let compressed = zstd::encode_all(json.as_bytes(), SPEC_COMPRESSION_LEVEL)?;
+                sqlx::query(&format!(
+                    "UPDATE {} SET spec_json = ?1 WHERE rowid = ?2",
+                    table
+                ))
+                .bind(compressed)
+                .bind(row.get::<i64, _>("row_id"))
+                .execute(&mut *tx)
+                .await?;
+            }
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Fail with NotFound unless the game exists
+    async fn ensure_game_exists(&self, id: &str) -> Result<(), DbError> {
+        sqlx::query("SELECT 1 FROM games WHERE id = ?1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?
+            .ok_or_else(|| DbError::NotFound(id.to_string()))?;
+        Ok(())
+    }
+
+    /// Text indexed for search besides title and description
+    fn search_text(spec: &PhaserGameSpec) -> String {
+        let mut words: Vec<String> = Vec::new();
+        for scene in &spec.scenes {
+            words.push(scene.name.clone());
+            for object in scene.all_objects() {
+                words.push(object.id.clone());
+                let Some(emoji) = &object.emoji else {
+                    continue;
+                };
+                words.extend(Self::symbol_token(&emoji.emoji));
+                let layers = emoji.layers.iter().flatten();
+                words.extend(layers.filter_map(|layer| Self::symbol_token(&layer.emoji)));
+            }
+            let actions = scene
+                .custom_logic
+                .iter()
+                .flat_map(|logic| logic.actions.iter().flatten());
+            words.extend(actions.map(|action| action.name.clone()));
+        }
+        words.extend(spec.key_concepts.iter().cloned());
+        words.join(" ")
+    }
+
+    /// The tokenizer treats emojis as separators, so they are indexed (and
+    /// searched) as a word spelled from their code points, e.g. 🐉 -> "sym1f409"
+    fn symbol_token(text: &str) -> Option<String> {
+        let codes: Vec<String> = text
+            .chars()
+            .filter(|c| {
+                !c.is_alphanumeric()
+                    && !c.is_whitespace()
+                    && !c.is_ascii_punctuation()
+                    && !matches!(c, '\u{fe0e}' | '\u{fe0f}')
+            })
+            .map(|c| format!("{:x}", c as u32))
+            .collect();
+        if codes.is_empty() {
+            None
+        } else {
+            Some(format!("sym{}", codes.join("x")))
+        }
+    }
+
+    /// Replace a game's entry in the search index, as part of the caller's transaction
+    async fn index_game(
+        conn: &mut SqliteConnection,
+        id: &str,
+        title: &str,
+        description: &str,
+        spec_text: &str,
+    ) -> Result<(), DbError> {
+        sqlx::query("DELETE FROM games_fts WHERE game_id = ?1")
+            .bind(id)
+            .execute(&mut *conn)
+            .await?;
+        sqlx::query(
+            r#"
+            INSERT INTO games_fts (game_id, title, description, spec_text)
+            VALUES (?1, ?2, ?3, ?4)
+            "#,
+        )
+        .bind(id)
+        .bind(title)
+        .bind(description)
+        .bind(spec_text)
+        .execute(&mut *conn)
+        .await?;
+        Ok(())
+    }
+
+    /// Store the spec facts `list_games` filters on, as part of the caller's transaction
+    async fn save_metadata(
+        conn: &mut SqliteConnection,
+        id: &str,
+        spec: &PhaserGameSpec,
+    ) -> Result<(), DbError> {
+        let object_count: usize = spec.scenes.iter().map(|scene| scene.objects.len()).sum();
+        let uses_spawners = spec.scenes.iter().any(|scene| {
+            scene
+                .custom_logic
+                .as_ref()
+                .and_then(|logic| logic.spawners.as_ref())
+                .is_some_and(|spawners| !spawners.is_empty())
+        });
+
+        sqlx::query(
+            "UPDATE games SET has_physics = ?1, object_count = ?2, uses_spawners = ?3 WHERE id = ?4",
+        )
+        .bind(spec.game.physics.enabled)
+        .bind(object_count as i64)
+        .bind(uses_spawners)
+        .bind(id)
+        .execute(&mut *conn)
+        .await?;
+        Ok(())
+    }
+
+    /// Fill in metadata for games saved before it was tracked. Games whose
+    /// spec can't be read are left out of metadata filters.
+    async fn backfill_metadata(&self) -> Result<(), DbError> {
+        let rows = sqlx::query("SELECT id, spec_json FROM games WHERE object_count IS NULL")
+            .fetch_all(&self.pool)
+            .await?;
+
+        let mut tx = self.pool.begin().await?;
+        for row in rows {
+            let stored: Vec<u8> = row.get("spec_json");
+            if let Ok(spec) = Self::load_stored_spec(&stored) {
+                let id: String = row.get("id");
+                Self::save_metadata(&mut tx, &id, &spec).await?;
+            }
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Index games saved before the search index existed
+    async fn backfill_search_index(&self) -> Result<(), DbError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, title, description, spec_json
+            FROM games
+            WHERE id NOT IN (SELECT game_id FROM games_fts)
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut tx = self.pool.begin().await?;
+        for row in rows {
+            let stored: Vec<u8> = row.get("spec_json");
+            // Still index title and description if the spec can't be read
+            let spec_text = Self::load_stored_spec(&stored)
+                .map(|spec| Self::search_text(&spec))
+                .unwrap_or_default();
+            let id: String = row.get("id");
+            let title: String = row.get("title");
+            let description: String = row.get("description");
+            Self::index_game(&mut tx, &id, &title, &description, &spec_text).await?;
+        }
+        tx.commit().await?;
         Ok(())
     }
 
+    /// Turn free text into an FTS5 query that ranks games matching any term,
+    /// quoting each term so user input can't inject query syntax
+    fn fts_query(query: &str) -> String {
+        query
+            .split_whitespace()
+            .map(|term| {
+                let term = match Self::symbol_token(term) {
+                    Some(token) if !term.chars().any(char::is_alphanumeric) => token,
+                    _ => term.to_string(),
+                };
+                format!("\"{}\"*", term.replace('"', "\"\""))
+            })
+            .collect::<Vec<_>>()
+            .join(" OR ")
+    }
+
     /// Generate a unique ID for a new game
     fn generate_id() -> String {
         use std::time::{SystemTime, UNIX_EPOCH};
@@ -106,8 +999,9 @@ impl Database {
     pub async fn create_game(&self, spec: PhaserGameSpec) -> Result<GameRecord, DbError> {
         let id = Self::generate_id();
         let now = Utc::now();
-        let spec_json = serde_json::to_string(&spec)?;
+        let spec_json = Self::compress_spec(&spec)?;
 
+        let mut tx = self.pool.begin().await?;
         sqlx::query(
             r#"
             INSERT INTO games (id, title, description, spec_json, created_at, updated_at, version)
@@ -120,7 +1014,7 @@ impl Database {
         .bind(&spec_json)
         .bind(now.to_rfc3339())
         .bind(now.to_rfc3339())
-        .execute(&self.pool)
+        .execute(&mut *tx)
         .await?;
 
         // Create initial version record
@@ -134,9 +1028,20 @@ impl Database {
         .bind(&spec_json)
         .bind(now.to_rfc3339())
         .bind("Initial version")
-        .execute(&self.pool)
+        .execute(&mut *tx)
         .await?;
 
+        Self::index_game(
+            &mut tx,
+            &id,
+            &spec.title,
+            &spec.description,
+            &Self::search_text(&spec),
+        )
+        .await?;
+        Self::save_metadata(&mut tx, &id, &spec).await?;
+        tx.commit().await?;
+
         Ok(GameRecord {
             id,
             title: spec.title.clone(),
@@ -152,7 +1057,7 @@ impl Database {
     pub async fn get_game(&self, id: &str) -> Result<GameRecord, DbError> {
         let row = sqlx::query(
             r#"
-            SELECT id, title, description, spec_json, created_at, updated_at, version
+            SELECT rowid AS row_id, id, title, description, spec_json, created_at, updated_at, version
             FROM games
             WHERE id = ?1
             "#,
@@ -162,8 +1067,10 @@ impl Database {
         .await?
         .ok_or_else(|| DbError::NotFound(id.to_string()))?;
 
-        let spec_json: String = row.get("spec_json");
-        let spec: PhaserGameSpec = serde_json::from_str(&spec_json)?;
+        let stored: Vec<u8> = row.get("spec_json");
+        let spec = self
+            .load_and_backfill_spec("games", row.get("row_id"), &stored)
+            .await?;
         let created_at: String = row.get("created_at");
         let updated_at: String = row.get("updated_at");
 
@@ -189,28 +1096,31 @@ impl Database {
         spec: PhaserGameSpec,
         notes: Option<String>,
     ) -> Result<GameRecord, DbError> {
-        // Get current version
-        let current = self.get_game(id).await?;
-        let new_version = current.version + 1;
         let now = Utc::now();
-        let spec_json = serde_json::to_string(&spec)?;
+        let spec_json = Self::compress_spec(&spec)?;
 
-        // Update main record
-        sqlx::query(
+        let mut tx = self.pool.begin().await?;
+        // Update main record, bumping the version in the same statement so
+        // concurrent saves can't claim the same one. The old spec isn't read,
+        // so a damaged one can be replaced.
+        let updated = sqlx::query(
             r#"
             UPDATE games
-            SET title = ?1, description = ?2, spec_json = ?3, updated_at = ?4, version = ?5
-            WHERE id = ?6
+            SET title = ?1, description = ?2, spec_json = ?3, updated_at = ?4, version = version + 1
+            WHERE id = ?5
+            RETURNING version, created_at
             "#,
         )
         .bind(&spec.title)
         .bind(&spec.description)
         .bind(&spec_json)
         .bind(now.to_rfc3339())
-        .bind(new_version)
         .bind(id)
-        .execute(&self.pool)
-        .await?;
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| DbError::NotFound(id.to_string()))?;
+        let created_at: String = updated.get("created_at");
+        let new_version: i64 = updated.get("version");
 
         // Create version record
         sqlx::query(
@@ -224,43 +1134,274 @@ impl Database {
         .bind(&spec_json)
         .bind(now.to_rfc3339())
         .bind(notes)
-        .execute(&self.pool)
+        .execute(&mut *tx)
+        .await?;
+
+        Self::index_game(
+            &mut tx,
+            id,
+            &spec.title,
+            &spec.description,
+            &Self::search_text(&spec),
+        )
         .await?;
+        Self::save_metadata(&mut tx, id, &spec).await?;
+        tx.commit().await?;
 
         Ok(GameRecord {
             id: id.to_string(),
             title: spec.title.clone(),
             description: spec.description.clone(),
             spec,
-            created_at: current.created_at,
+            created_at: Self::parse_timestamp(&created_at)?,
             updated_at: now,
             version: new_version,
         })
     }
 
-    /// Delete a game and all its versions
-    pub async fn delete_game(&self, id: &str) -> Result<(), DbError> {
-        let result = sqlx::query("DELETE FROM games WHERE id = ?1")
-            .bind(id)
-            .execute(&self.pool)
-            .await?;
-
-        if result.rows_affected() == 0 {
-            return Err(DbError::NotFound(id.to_string()));
-        }
+    /// Roll a game back by saving an old version's spec as a new version
+    pub async fn revert_game(&self, id: &str, version: i64) -> Result<GameRecord, DbError> {
+        let old = self.get_game_version(id, version).await?;
+        self.update_game(
+            id,
+            old.spec,
+            Some(format!("Reverted to version {}", version)),
+        )
+        .await
+    }
+
+    /// Copy a game's latest spec into a new game under a new title, optionally
+    /// carrying over its earlier versions. Tags and notes are copied too.
+    pub async fn duplicate_game(
+        &self,
+        id: &str,
+        new_title: &str,
+        include_history: bool,
+    ) -> Result<GameRecord, DbError> {
+        let source = self.get_game(id).await?;
+        let notes = self.get_notes(id).await?;
+        let new_id = Self::generate_id();
+        let now = Utc::now();
+        let version = if include_history { source.version } else { 1 };
+        let mut spec = source.spec;
+        spec.title = new_title.to_string();
+        let spec_json = Self::compress_spec(&spec)?;
+
+        let mut tx = self.pool.begin().await?;
+        sqlx::query(
+            r#"
+            INSERT INTO games (id, title, description, spec_json, created_at, updated_at, version, notes)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+            "#,
+        )
+        .bind(&new_id)
+        .bind(&spec.title)
+        .bind(&spec.description)
+        .bind(&spec_json)
+        .bind(now.to_rfc3339())
+        .bind(now.to_rfc3339())
+        .bind(version)
+        .bind(&notes)
+        .execute(&mut *tx)
+        .await?;
+
+        if include_history {
+            sqlx::query(
+                r#"
+                INSERT INTO game_versions (game_id, version, spec_json, created_at, notes)
+                SELECT ?1, version, spec_json, created_at, notes
+                FROM game_versions
+                WHERE game_id = ?2 AND version < ?3
+                "#,
+            )
+            .bind(&new_id)
+            .bind(id)
+            .bind(version)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO game_versions (game_id, version, spec_json, created_at, notes)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            "#,
+        )
+        .bind(&new_id)
+        .bind(version)
+        .bind(&spec_json)
+        .bind(now.to_rfc3339())
+        .bind(format!("Duplicated from '{}'", source.title))
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO game_tags (game_id, tag_id)
+            SELECT ?1, tag_id FROM game_tags WHERE game_id = ?2
+            "#,
+        )
+        .bind(&new_id)
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+
+        Self::index_game(
+            &mut tx,
+            &new_id,
+            &spec.title,
+            &spec.description,
+            &Self::search_text(&spec),
+        )
+        .await?;
+        Self::save_metadata(&mut tx, &new_id, &spec).await?;
+        tx.commit().await?;
+
+        Ok(GameRecord {
+            id: new_id,
+            title: spec.title.clone(),
+            description: spec.description.clone(),
+            spec,
+            created_at: now,
+            updated_at: now,
+            version,
+        })
+    }
+
+    /// Overwrite a game and its whole history with a copy from elsewhere (e.g.
+    /// a sync remote), keeping its id. Creates the game if it doesn't exist.
+    pub async fn replace_game(
+        &self,
+        game: &GameRecord,
+        versions: &[GameVersion],
+    ) -> Result<(), DbError> {
+        let spec_json = Self::compress_spec(&game.spec)?;
+
+        let mut tx = self.pool.begin().await?;
+        sqlx::query(
+            r#"
+            INSERT INTO games (id, title, description, spec_json, created_at, updated_at, version)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+            ON CONFLICT(id) DO UPDATE SET
+                title = excluded.title,
+                description = excluded.description,
+                spec_json = excluded.spec_json,
+                created_at = excluded.created_at,
+                updated_at = excluded.updated_at,
+                version = excluded.version
+            "#,
+        )
+        .bind(&game.id)
+        .bind(&game.title)
+        .bind(&game.description)
+        .bind(&spec_json)
+        .bind(game.created_at.to_rfc3339())
+        .bind(game.updated_at.to_rfc3339())
+        .bind(game.version)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query("DELETE FROM game_versions WHERE game_id = ?1")
+            .bind(&game.id)
+            .execute(&mut *tx)
+            .await?;
+        for version in versions {
+            sqlx::query(
+                r#"
+                INSERT INTO game_versions (game_id, version, spec_json, created_at, notes, label)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+                "#,
+            )
+            .bind(&game.id)
+            .bind(version.version)
+            .bind(Self::compress_spec(&version.spec)?)
+            .bind(version.created_at.to_rfc3339())
+            .bind(&version.notes)
+            .bind(&version.label)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        Self::index_game(
+            &mut tx,
+            &game.id,
+            &game.title,
+            &game.description,
+            &Self::search_text(&game.spec),
+        )
+        .await?;
+        Self::save_metadata(&mut tx, &game.id, &game.spec).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Delete a game and all its versions
+    pub async fn delete_game(&self, id: &str) -> Result<(), DbError> {
+        let mut tx = self.pool.begin().await?;
+        let result = sqlx::query("DELETE FROM games WHERE id = ?1")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound(id.to_string()));
+        }
+
+        sqlx::query("DELETE FROM games_fts WHERE game_id = ?1")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
 
         Ok(())
     }
 
-    /// List all games (summaries only)
-    pub async fn list_games(&self) -> Result<Vec<GameSummary>, DbError> {
+    /// List games (summaries only), optionally filtered
+    pub async fn list_games(&self, filter: &GameListFilter) -> Result<Vec<GameSummary>, DbError> {
+        // Tag names compare without case, so "Space" and "space" are one filter tag
+        let tags: BTreeSet<String> = filter.tags.iter().map(|tag| tag.to_lowercase()).collect();
         let rows = sqlx::query(
             r#"
-            SELECT id, title, description, created_at, updated_at, version
+            SELECT id, title, description, created_at, updated_at, version, favorite, status,
+                archived
             FROM games
-            ORDER BY updated_at DESC
+            WHERE archived = ?6
+            AND (?3 = 0 OR favorite = 1)
+            AND (?5 IS NULL OR status = ?5)
+            AND (?7 IS NULL OR created_at >= ?7)
+            AND (?8 IS NULL OR created_at < ?8)
+            AND (?9 IS NULL OR updated_at >= ?9)
+            AND (?10 IS NULL OR updated_at < ?10)
+            AND (?11 IS NULL OR has_physics = ?11)
+            AND (?12 IS NULL OR object_count >= ?12)
+            AND (?13 IS NULL OR object_count <= ?13)
+            AND (?14 IS NULL OR uses_spawners = ?14)
+            AND (?1 = 0 OR id IN (
+                SELECT gt.game_id
+                FROM game_tags gt
+                JOIN tags t ON t.id = gt.tag_id
+                WHERE t.name IN (SELECT value FROM json_each(?2))
+                GROUP BY gt.game_id
+                HAVING COUNT(*) = (SELECT COUNT(DISTINCT value) FROM json_each(?2))
+            ))
+            ORDER BY (?4 AND favorite) DESC, updated_at DESC
             "#,
         )
+        .bind(tags.len() as i64)
+        .bind(serde_json::to_string(&tags)?)
+        .bind(filter.favorites_only)
+        .bind(filter.favorites_first)
+        .bind(filter.status.map(GameStatus::as_str))
+        .bind(filter.archived)
+        // Timestamps are stored as UTC RFC 3339 text, which sorts chronologically
+        .bind(filter.created_after.map(|time| time.to_rfc3339()))
+        .bind(filter.created_before.map(|time| time.to_rfc3339()))
+        .bind(filter.updated_after.map(|time| time.to_rfc3339()))
+        .bind(filter.updated_before.map(|time| time.to_rfc3339()))
+        .bind(filter.has_physics)
+        .bind(filter.min_objects)
+        .bind(filter.max_objects)
+        .bind(filter.uses_spawners)
         .fetch_all(&self.pool)
         .await?;
 
@@ -280,17 +1421,40 @@ impl Database {
                     .map_err(|e| DbError::DateTimeParse(e.to_string()))?
                     .with_timezone(&Utc),
                 version: row.get("version"),
+                favorite: row.get("favorite"),
+                status: GameStatus::from_db(row.get("status")),
+                archived: row.get("archived"),
+                tags: Vec::new(),
             });
         }
 
+        self.attach_tags(&mut summaries).await?;
         Ok(summaries)
     }
 
+    /// Id, version, and last update of every game, including archived ones
+    pub async fn list_game_stamps(&self) -> Result<Vec<GameStamp>, DbError> {
+        let rows = sqlx::query("SELECT id, version, updated_at FROM games")
+            .fetch_all(&self.pool)
+            .await?;
+
+        let mut stamps = Vec::new();
+        for row in rows {
+            let updated_at: String = row.get("updated_at");
+            stamps.push(GameStamp {
+                id: row.get("id"),
+                version: row.get("version"),
+                updated_at: Self::parse_timestamp(&updated_at)?,
+            });
+        }
+        Ok(stamps)
+    }
+
     /// Get all versions of a game
     pub async fn get_game_versions(&self, game_id: &str) -> Result<Vec<GameVersion>, DbError> {
         let rows = sqlx::query(
             r#"
-            SELECT id, game_id, version, spec_json, created_at, notes
+            SELECT id, game_id, version, spec_json, created_at, notes, label
             FROM game_versions
             WHERE game_id = ?1
             ORDER BY version DESC
@@ -302,8 +1466,10 @@ impl Database {
 
         let mut versions = Vec::new();
         for row in rows {
-            let spec_json: String = row.get("spec_json");
-            let spec: PhaserGameSpec = serde_json::from_str(&spec_json)?;
+            let stored: Vec<u8> = row.get("spec_json");
+            let spec = self
+                .load_and_backfill_spec("game_versions", row.get("id"), &stored)
+                .await?;
             let created_at: String = row.get("created_at");
 
             versions.push(GameVersion {
@@ -315,6 +1481,7 @@ impl Database {
                     .map_err(|e| DbError::DateTimeParse(e.to_string()))?
                     .with_timezone(&Utc),
                 notes: row.get("notes"),
+                label: row.get("label"),
             });
         }
 
@@ -329,7 +1496,7 @@ impl Database {
     ) -> Result<GameVersion, DbError> {
         let row = sqlx::query(
             r#"
-            SELECT id, game_id, version, spec_json, created_at, notes
+            SELECT id, game_id, version, spec_json, created_at, notes, label
             FROM game_versions
             WHERE game_id = ?1 AND version = ?2
             "#,
@@ -340,8 +1507,10 @@ impl Database {
         .await?
         .ok_or_else(|| DbError::NotFound(format!("{} v{}", game_id, version)))?;
 
-        let spec_json: String = row.get("spec_json");
-        let spec: PhaserGameSpec = serde_json::from_str(&spec_json)?;
+        let stored: Vec<u8> = row.get("spec_json");
+        let spec = self
+            .load_and_backfill_spec("game_versions", row.get("id"), &stored)
+            .await?;
         let created_at: String = row.get("created_at");
 
         Ok(GameVersion {
@@ -353,21 +1522,218 @@ impl Database {
                 .map_err(|e| DbError::DateTimeParse(e.to_string()))?
                 .with_timezone(&Utc),
             notes: row.get("notes"),
+            label: row.get("label"),
+        })
+    }
+
+    /// Delete old versions of a game that fall outside the retention policy
+    pub async fn prune_versions(
+        &self,
+        game_id: &str,
+        policy: &RetentionPolicy,
+    ) -> Result<PruneReport, DbError> {
+        self.ensure_game_exists(game_id).await?;
+        let rows = sqlx::query(
+            r#"
+            SELECT version, label, LENGTH(spec_json) + COALESCE(LENGTH(notes), 0) AS size
+            FROM game_versions
+            WHERE game_id = ?1
+            ORDER BY version DESC
+            "#,
+        )
+        .bind(game_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut removed_versions = Vec::new();
+        let mut reclaimed_bytes = 0;
+        let old_rows = rows.iter().skip(policy.keep_last.max(1) as usize);
+        for row in old_rows {
+            if policy.keep_labeled && row.get::<Option<String>, _>("label").is_some() {
+                continue;
+            }
+            removed_versions.push(row.get::<i64, _>("version"));
+            reclaimed_bytes += row.get::<i64, _>("size");
+        }
+
+        sqlx::query(
+            r#"
+            DELETE FROM game_versions
+            WHERE game_id = ?1 AND version IN (SELECT value FROM json_each(?2))
+            "#,
+        )
+        .bind(game_id)
+        .bind(serde_json::to_string(&removed_versions)?)
+        .execute(&self.pool)
+        .await?;
+
+        removed_versions.reverse();
+        Ok(PruneReport {
+            removed_versions,
+            reclaimed_bytes,
         })
     }
 
-    /// Search games by title
-    pub async fn search_games(&self, query: &str) -> Result<Vec<GameSummary>, DbError> {
-        let search_pattern = format!("%{}%", query);
+    /// Report how much space a game's specs and thumbnails take
+    pub async fn get_storage_stats(&self, game_id: &str) -> Result<StorageStats, DbError> {
+        self.ensure_game_exists(game_id).await?;
         let rows = sqlx::query(
             r#"
-            SELECT id, title, description, created_at, updated_at, version
-            FROM games
-            WHERE title LIKE ?1 OR description LIKE ?1
-            ORDER BY updated_at DESC
+            SELECT spec_json FROM games WHERE id = ?1
+            UNION ALL
+            SELECT spec_json FROM game_versions WHERE game_id = ?1
+            "#,
+        )
+        .bind(game_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut spec_bytes = 0;
+        let mut uncompressed_spec_bytes = 0;
+        for row in &rows {
+            let stored: Vec<u8> = row.get("spec_json");
+            spec_bytes += stored.len() as i64;
+            uncompressed_spec_bytes += Self::stored_spec_json(&stored)?.len() as i64;
+        }
+
+        let thumbnail_bytes: i64 = sqlx::query(
+            "SELECT COALESCE(SUM(LENGTH(png)), 0) AS bytes FROM thumbnails WHERE game_id = ?1",
+        )
+        .bind(game_id)
+        .fetch_one(&self.pool)
+        .await?
+        .get("bytes");
+
+        Ok(StorageStats {
+            game_id: game_id.to_string(),
+            version_count: rows.len() as i64 - 1,
+            spec_bytes,
+            uncompressed_spec_bytes,
+            thumbnail_bytes,
+        })
+    }
+
+    /// Set or clear a version's label; the label moves off any other version of the game
+    pub async fn label_version(
+        &self,
+        game_id: &str,
+        version: i64,
+        label: Option<&str>,
+    ) -> Result<GameVersion, DbError> {
+        let label = label.map(str::trim).filter(|label| !label.is_empty());
+        // Make sure the version exists before touching other labels
+        self.get_game_version(game_id, version).await?;
+
+        let mut tx = self.pool.begin().await?;
+        if let Some(label) = label {
+            sqlx::query("UPDATE game_versions SET label = NULL WHERE game_id = ?1 AND label = ?2")
+                .bind(game_id)
+                .bind(label)
+                .execute(&mut *tx)
+                .await?;
+        }
+        sqlx::query("UPDATE game_versions SET label = ?1 WHERE game_id = ?2 AND version = ?3")
+            .bind(label)
+            .bind(game_id)
+            .bind(version)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+
+        self.get_game_version(game_id, version).await
+    }
+
+    /// Get the version of a game carrying a label
+    pub async fn get_version_by_label(
+        &self,
+        game_id: &str,
+        label: &str,
+    ) -> Result<GameVersion, DbError> {
+        let version: i64 =
+            sqlx::query("SELECT version FROM game_versions WHERE game_id = ?1 AND label = ?2")
+                .bind(game_id)
+                .bind(label.trim())
+                .fetch_optional(&self.pool)
+                .await?
+                .ok_or_else(|| DbError::NotFound(format!("{} label {}", game_id, label)))?
+                .get("version");
+
+        self.get_game_version(game_id, version).await
+    }
+
+    /// Compare two versions of a game
+    pub async fn diff_game_versions(
+        &self,
+        game_id: &str,
+        from: i64,
+        to: i64,
+    ) -> Result<SpecDiff, DbError> {
+        let from = self.get_game_version(game_id, from).await?;
+        let to = self.get_game_version(game_id, to).await?;
+        Ok(spec_diff::diff_specs(&from.spec, &to.spec)?)
+    }
+
+    /// Keys of library assets still in use, either referenced by a stored spec
+    /// or attached to a game
+    pub async fn referenced_asset_keys(&self) -> Result<HashSet<String>, DbError> {
+        let mut keys: HashSet<String> = self
+            .list_all_specs()
+            .await?
+            .iter()
+            .flat_map(|spec| spec.library_asset_keys())
+            .collect();
+        let rows = sqlx::query("SELECT DISTINCT key FROM assets")
+            .fetch_all(&self.pool)
+            .await?;
+        keys.extend(rows.iter().map(|row| row.get::<String, _>("key")));
+        Ok(keys)
+    }
+
+    /// Load every stored spec, including all historical versions
+    pub async fn list_all_specs(&self) -> Result<Vec<PhaserGameSpec>, DbError> {
+        let rows = sqlx::query("SELECT spec_json FROM game_versions")
+            .fetch_all(&self.pool)
+            .await?;
+
+        let mut specs = Vec::new();
+        for row in rows {
+            let stored: Vec<u8> = row.get("spec_json");
+            specs.push(Self::load_stored_spec(&stored)?);
+        }
+
+        Ok(specs)
+    }
+
+    /// Search games outside the archive by title and description, best matches first. With
+    /// `include_spec`, object ids, emojis, action names, and key concepts match too.
+    pub async fn search_games(
+        &self,
+        query: &str,
+        include_spec: bool,
+    ) -> Result<Vec<GameSummary>, DbError> {
+        let fts_query = Self::fts_query(query);
+        if fts_query.is_empty() {
+            return self.list_games(&GameListFilter::default()).await;
+        }
+        let fts_query = if include_spec {
+            fts_query
+        } else {
+            format!("{{title description}} : ({})", fts_query)
+        };
+
+        // bm25 weights: game_id (unindexed), title, description, spec_text
+        let rows = sqlx::query(
+            r#"
+            SELECT g.id, g.title, g.description, g.created_at, g.updated_at, g.version, g.favorite,
+                g.status, g.archived
+            FROM games_fts
+            JOIN games g ON g.id = games_fts.game_id
+            WHERE games_fts MATCH ?1
+            AND g.archived = 0
+            ORDER BY bm25(games_fts, 0.0, 10.0, 4.0, 1.0), g.updated_at DESC
             "#,
         )
-        .bind(&search_pattern)
+        .bind(&fts_query)
         .fetch_all(&self.pool)
         .await?;
 
@@ -387,9 +1753,1009 @@ impl Database {
                     .map_err(|e| DbError::DateTimeParse(e.to_string()))?
                     .with_timezone(&Utc),
                 version: row.get("version"),
+                favorite: row.get("favorite"),
+                status: GameStatus::from_db(row.get("status")),
+                archived: row.get("archived"),
+                tags: Vec::new(),
             });
         }
 
+        self.attach_tags(&mut summaries).await?;
         Ok(summaries)
     }
+
+    /// Record a high score for a game, optionally under a player name
+    pub async fn submit_high_score(
+        &self,
+        game_id: &str,
+        score: i64,
+        player_name: Option<&str>,
+    ) -> Result<HighScore, DbError> {
+        self.ensure_game_exists(game_id).await?;
+        let now = Utc::now();
+        let player_name = Self::clean_player_name(player_name);
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO high_scores (game_id, player_name, score, achieved_at)
+            VALUES (?1, ?2, ?3, ?4)
+            "#,
+        )
+        .bind(game_id)
+        .bind(&player_name)
+        .bind(score)
+        .bind(now.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(HighScore {
+            id: result.last_insert_rowid(),
+            game_id: game_id.to_string(),
+            player_name,
+            score,
+            achieved_at: now,
+        })
+    }
+
+    /// Player name trimmed to the stored length, or `None` if blank
+    fn clean_player_name(player_name: Option<&str>) -> Option<String> {
+        player_name
+            .map(|name| {
+                name.trim()
+                    .chars()
+                    .take(MAX_PLAYER_NAME_CHARS)
+                    .collect::<String>()
+            })
+            .filter(|name| !name.is_empty())
+    }
+
+    /// Store a recorded run of a game's current version
+    pub async fn save_replay(
+        &self,
+        game_id: &str,
+        mut replay: Replay,
+    ) -> Result<SavedReplay, DbError> {
+        let game_version: i64 = sqlx::query("SELECT version FROM games WHERE id = ?1")
+            .bind(game_id)
+            .fetch_optional(&self.pool)
+            .await?
+            .ok_or_else(|| DbError::NotFound(game_id.to_string()))?
+            .get("version");
+        let now = Utc::now();
+        replay.player_name = Self::clean_player_name(replay.player_name.as_deref());
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO replays (game_id, game_version, player_name, score, duration_ms, replay, recorded_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+            "#,
+        )
+        .bind(game_id)
+        .bind(game_version)
+        .bind(&replay.player_name)
+        .bind(replay.score)
+        .bind(replay.duration_ms as i64)
+        .bind(serde_json::to_string(&replay)?)
+        .bind(now.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(SavedReplay {
+            id: result.last_insert_rowid(),
+            game_id: game_id.to_string(),
+            game_version,
+            recorded_at: now,
+            replay,
+        })
+    }
+
+    /// A game's replays, newest first
+    pub async fn list_replays(&self, game_id: &str) -> Result<Vec<ReplaySummary>, DbError> {
+        self.ensure_game_exists(game_id).await?;
+        let rows = sqlx::query(
+            r#"
+            SELECT id, game_id, game_version, player_name, score, duration_ms, recorded_at
+            FROM replays
+            WHERE game_id = ?1
+            ORDER BY recorded_at DESC, id DESC
+            "#,
+        )
+        .bind(game_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut replays = Vec::new();
+        for row in rows {
+            let recorded_at: String = row.get("recorded_at");
+            let duration_ms: i64 = row.get("duration_ms");
+            replays.push(ReplaySummary {
+                id: row.get("id"),
+                game_id: row.get("game_id"),
+                game_version: row.get("game_version"),
+                player_name: row.get("player_name"),
+                score: row.get("score"),
+                duration_ms: duration_ms as u64,
+                recorded_at: Self::parse_timestamp(&recorded_at)?,
+            });
+        }
+
+        Ok(replays)
+    }
+
+    /// Get a replay with its inputs, to play it back
+    pub async fn get_replay(&self, id: i64) -> Result<SavedReplay, DbError> {
+        let row = sqlx::query(
+            "SELECT id, game_id, game_version, replay, recorded_at FROM replays WHERE id = ?1",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| DbError::NotFound(format!("replay {}", id)))?;
+
+        let replay: String = row.get("replay");
+        let recorded_at: String = row.get("recorded_at");
+        Ok(SavedReplay {
+            id: row.get("id"),
+            game_id: row.get("game_id"),
+            game_version: row.get("game_version"),
+            recorded_at: Self::parse_timestamp(&recorded_at)?,
+            replay: serde_json::from_str(&replay)?,
+        })
+    }
+
+    /// Get the best scores for a game, highest first
+    pub async fn get_high_scores(
+        &self,
+        game_id: &str,
+        limit: i64,
+    ) -> Result<Vec<HighScore>, DbError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, game_id, player_name, score, achieved_at
+            FROM high_scores
+            WHERE game_id = ?1
+            ORDER BY score DESC, achieved_at ASC
+            LIMIT ?2
+            "#,
+        )
+        .bind(game_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut scores = Vec::new();
+        for row in rows {
+            let achieved_at: String = row.get("achieved_at");
+            scores.push(HighScore {
+                id: row.get("id"),
+                game_id: row.get("game_id"),
+                player_name: row.get("player_name"),
+                score: row.get("score"),
+                achieved_at: Self::parse_timestamp(&achieved_at)?,
+            });
+        }
+
+        Ok(scores)
+    }
+
+    /// Every named player's best score on every game; names differing only
+    /// in case are the same player
+    pub async fn get_player_bests(&self) -> Result<Vec<PlayerBest>, DbError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT h.game_id, g.title, MIN(h.player_name) AS player_name,
+                   MAX(h.score) AS best_score, COUNT(*) AS plays,
+                   MIN(h.achieved_at) AS first_played, MAX(h.achieved_at) AS last_played
+            FROM high_scores h
+            JOIN games g ON g.id = h.game_id
+            WHERE h.player_name IS NOT NULL
+            GROUP BY h.game_id, h.player_name COLLATE NOCASE
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut bests = Vec::new();
+        for row in rows {
+            let first_played: String = row.get("first_played");
+            let last_played: String = row.get("last_played");
+            bests.push(PlayerBest {
+                game_id: row.get("game_id"),
+                game_title: row.get("title"),
+                player_name: row.get("player_name"),
+                best_score: row.get("best_score"),
+                plays: row.get("plays"),
+                first_played: Self::parse_timestamp(&first_played)?,
+                last_played: Self::parse_timestamp(&last_played)?,
+            });
+        }
+
+        Ok(bests)
+    }
+
+    /// Mark an achievement from the game's current spec as unlocked, keeping
+    /// the original unlock time if it was already earned
+    pub async fn unlock_achievement(
+        &self,
+        game_id: &str,
+        achievement_id: &str,
+    ) -> Result<AchievementStatus, DbError> {
+        let game = self.get_game(game_id).await?;
+        let achievement = game
+            .spec
+            .achievements
+            .into_iter()
+            .flatten()
+            .find(|achievement| achievement.id == achievement_id)
+            .ok_or_else(|| {
+                DbError::NotFound(format!("{} achievement {}", game_id, achievement_id))
+            })?;
+
+        sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO achievements (game_id, achievement_id, unlocked_at)
+            VALUES (?1, ?2, ?3)
+            "#,
+        )
+        .bind(game_id)
+        .bind(achievement_id)
+        .bind(Utc::now().to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+
+        let unlocked_at: String = sqlx::query(
+            "SELECT unlocked_at FROM achievements WHERE game_id = ?1 AND achievement_id = ?2",
+        )
+        .bind(game_id)
+        .bind(achievement_id)
+        .fetch_one(&self.pool)
+        .await?
+        .get("unlocked_at");
+
+        Ok(AchievementStatus {
+            achievement,
+            unlocked_at: Some(Self::parse_timestamp(&unlocked_at)?),
+        })
+    }
+
+    /// Get every achievement defined by the game's current spec and when it was unlocked
+    pub async fn get_achievements(&self, game_id: &str) -> Result<Vec<AchievementStatus>, DbError> {
+        let game = self.get_game(game_id).await?;
+        let rows = sqlx::query(
+            r#"
+            SELECT achievement_id, unlocked_at
+            FROM achievements
+            WHERE game_id = ?1
+            "#,
+        )
+        .bind(game_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut unlocked = HashMap::new();
+        for row in rows {
+            let achievement_id: String = row.get("achievement_id");
+            let unlocked_at: String = row.get("unlocked_at");
+            unlocked.insert(achievement_id, Self::parse_timestamp(&unlocked_at)?);
+        }
+
+        Ok(game
+            .spec
+            .achievements
+            .into_iter()
+            .flatten()
+            .map(|achievement| AchievementStatus {
+                unlocked_at: unlocked.get(&achievement.id).copied(),
+                achievement,
+            })
+            .collect())
+    }
+
+    /// Fill in the tags of each summary
+    async fn attach_tags(&self, summaries: &mut [GameSummary]) -> Result<(), DbError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT gt.game_id, t.name
+            FROM game_tags gt
+            JOIN tags t ON t.id = gt.tag_id
+            ORDER BY t.name
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut tags: HashMap<String, Vec<String>> = HashMap::new();
+        for row in rows {
+            tags.entry(row.get("game_id"))
+                .or_default()
+                .push(row.get("name"));
+        }
+        for summary in summaries {
+            summary.tags = tags.remove(&summary.id).unwrap_or_default();
+        }
+        Ok(())
+    }
+
+    /// Star or unstar a game
+    pub async fn set_favorite(&self, id: &str, favorite: bool) -> Result<(), DbError> {
+        let result = sqlx::query("UPDATE games SET favorite = ?1 WHERE id = ?2")
+            .bind(favorite)
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound(id.to_string()));
+        }
+
+        Ok(())
+    }
+
+    /// A game's free-form Markdown notes (empty if none were written)
+    pub async fn get_notes(&self, id: &str) -> Result<String, DbError> {
+        let row = sqlx::query("SELECT notes FROM games WHERE id = ?1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?
+            .ok_or_else(|| DbError::NotFound(id.to_string()))?;
+
+        Ok(row.get("notes"))
+    }
+
+    /// Replace a game's notes. Notes aren't versioned, so this doesn't create a version.
+    pub async fn set_notes(&self, id: &str, notes: &str) -> Result<(), DbError> {
+        let result = sqlx::query("UPDATE games SET notes = ?1 WHERE id = ?2")
+            .bind(notes)
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound(id.to_string()));
+        }
+
+        Ok(())
+    }
+
+    /// Hide a game from the main library, or bring it back
+    pub async fn set_archived(&self, id: &str, archived: bool) -> Result<(), DbError> {
+        let result = sqlx::query("UPDATE games SET archived = ?1 WHERE id = ?2")
+            .bind(archived)
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound(id.to_string()));
+        }
+
+        Ok(())
+    }
+
+    /// Mark a game as ready for players. Games whose spec has validation
+    /// errors stay drafts.
+    pub async fn publish_game(&self, id: &str) -> Result<(), DbError> {
+        let game = self.get_game(id).await?;
+        let errors = validator::error_messages(&game.spec);
+        if !errors.is_empty() {
+            return Err(DbError::NotPublishable(errors.join("; ")));
+        }
+        self.set_status(id, GameStatus::Published).await
+    }
+
+    /// Move a published game back to drafts
+    pub async fn unpublish_game(&self, id: &str) -> Result<(), DbError> {
+        self.set_status(id, GameStatus::Draft).await
+    }
+
+    async fn set_status(&self, id: &str, status: GameStatus) -> Result<(), DbError> {
+        let result = sqlx::query("UPDATE games SET status = ?1 WHERE id = ?2")
+            .bind(status.as_str())
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound(id.to_string()));
+        }
+
+        Ok(())
+    }
+
+    /// Store a PNG preview for a game version (default: the current version)
+    pub async fn set_game_thumbnail(
+        &self,
+        game_id: &str,
+        version: Option<i64>,
+        png: &[u8],
+    ) -> Result<(), DbError> {
+        const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+        if !png.starts_with(&PNG_SIGNATURE) {
+            return Err(DbError::InvalidThumbnail);
+        }
+        let version = match version {
+            Some(version) => self.get_game_version(game_id, version).await?.version,
+            None => self.get_game(game_id).await?.version,
+        };
+
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO thumbnails (game_id, version, png, created_at)
+            VALUES (?1, ?2, ?3, ?4)
+            "#,
+        )
+        .bind(game_id)
+        .bind(version)
+        .bind(png)
+        .bind(Utc::now().to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Get the newest PNG preview at or before a version (default: any version)
+    pub async fn get_game_thumbnail(
+        &self,
+        game_id: &str,
+        version: Option<i64>,
+    ) -> Result<Option<Vec<u8>>, DbError> {
+        let row = sqlx::query(
+            r#"
+            SELECT png
+            FROM thumbnails
+            WHERE game_id = ?1 AND (?2 IS NULL OR version <= ?2)
+            ORDER BY version DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(game_id)
+        .bind(version)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|row| row.get("png")))
+    }
+
+    /// Get a game's tags, sorted by name
+    pub async fn get_game_tags(&self, game_id: &str) -> Result<Vec<String>, DbError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT t.name
+            FROM game_tags gt
+            JOIN tags t ON t.id = gt.tag_id
+            WHERE gt.game_id = ?1
+            ORDER BY t.name
+            "#,
+        )
+        .bind(game_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(|row| row.get("name")).collect())
+    }
+
+    /// Tag a game, creating the tag if needed, and return the game's tags
+    pub async fn add_tag(&self, game_id: &str, tag: &str) -> Result<Vec<String>, DbError> {
+        let tag = tag.trim();
+        if tag.is_empty() {
+            return Err(DbError::InvalidTag(tag.to_string()));
+        }
+        self.ensure_game_exists(game_id).await?;
+
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("INSERT OR IGNORE INTO tags (name) VALUES (?1)")
+            .bind(tag)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO game_tags (game_id, tag_id)
+            SELECT ?1, id FROM tags WHERE name = ?2
+            "#,
+        )
+        .bind(game_id)
+        .bind(tag)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        self.get_game_tags(game_id).await
+    }
+
+    /// Remove a tag from a game, deleting the tag once no game uses it,
+    /// and return the game's remaining tags
+    pub async fn remove_tag(&self, game_id: &str, tag: &str) -> Result<Vec<String>, DbError> {
+        self.ensure_game_exists(game_id).await?;
+
+        let mut tx = self.pool.begin().await?;
+        sqlx::query(
+            r#"
+            DELETE FROM game_tags
+            WHERE game_id = ?1 AND tag_id IN (SELECT id FROM tags WHERE name = ?2)
+            "#,
+        )
+        .bind(game_id)
+        .bind(tag.trim())
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query("DELETE FROM tags WHERE id NOT IN (SELECT tag_id FROM game_tags)")
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+
+        self.get_game_tags(game_id).await
+    }
+
+    /// List every tag with how many games use it
+    pub async fn list_tags(&self) -> Result<Vec<TagCount>, DbError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT t.name, COUNT(gt.game_id) AS game_count
+            FROM tags t
+            LEFT JOIN game_tags gt ON gt.tag_id = t.id
+            GROUP BY t.id
+            ORDER BY t.name
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| TagCount {
+                name: row.get("name"),
+                game_count: row.get("game_count"),
+            })
+            .collect())
+    }
+
+    /// Attach a library file to a game, refreshing its type, path, and hash if
+    /// it is already attached
+    pub async fn attach_asset(
+        &self,
+        game_id: &str,
+        key: &str,
+        asset_type: &AssetType,
+        file_path: &str,
+        hash: &str,
+    ) -> Result<GameAsset, DbError> {
+        self.ensure_game_exists(game_id).await?;
+        let asset_type = serde_json::to_value(asset_type)?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO assets (game_id, key, asset_type, file_path, hash, created_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+            ON CONFLICT(game_id, key) DO UPDATE SET
+                asset_type = excluded.asset_type,
+                file_path = excluded.file_path,
+                hash = excluded.hash
+            "#,
+        )
+        .bind(game_id)
+        .bind(key)
+        .bind(asset_type.as_str())
+        .bind(file_path)
+        .bind(hash)
+        .bind(Utc::now().to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+
+        self.get_game_assets(game_id)
+            .await?
+            .into_iter()
+            .find(|asset| asset.key == key)
+            .ok_or_else(|| DbError::AssetNotAttached(game_id.to_string(), key.to_string()))
+    }
+
+    /// List the assets attached to a game, sorted by key
+    pub async fn get_game_assets(&self, game_id: &str) -> Result<Vec<GameAsset>, DbError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, game_id, key, asset_type, file_path, hash, created_at
+            FROM assets
+            WHERE game_id = ?1
+            ORDER BY key
+            "#,
+        )
+        .bind(game_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut assets = Vec::new();
+        for row in rows {
+            let asset_type: String = row.get("asset_type");
+            let created_at: String = row.get("created_at");
+            assets.push(GameAsset {
+                id: row.get("id"),
+                game_id: row.get("game_id"),
+                key: row.get("key"),
+                asset_type: serde_json::from_value(serde_json::Value::String(asset_type))?,
+                file_path: row.get("file_path"),
+                hash: row.get("hash"),
+                created_at: Self::parse_timestamp(&created_at)?,
+            });
+        }
+        Ok(assets)
+    }
+
+    /// Detach an asset from a game (the library file itself is kept)
+    pub async fn detach_asset(&self, game_id: &str, key: &str) -> Result<(), DbError> {
+        let result = sqlx::query("DELETE FROM assets WHERE game_id = ?1 AND key = ?2")
+            .bind(game_id)
+            .bind(key)
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::AssetNotAttached(
+                game_id.to_string(),
+                key.to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Read a setting, deserialized into the caller's type. Returns None if unset.
+    pub async fn get_setting<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>, DbError> {
+        let row = sqlx::query("SELECT value FROM settings WHERE key = ?1")
+            .bind(key)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        match row {
+            Some(row) => Ok(Some(serde_json::from_str(&row.get::<String, _>("value"))?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Store a setting, replacing any previous value
+    pub async fn set_setting<T: Serialize>(&self, key: &str, value: &T) -> Result<(), DbError> {
+        let key = key.trim();
+        if key.is_empty() {
+            return Err(DbError::InvalidSettingKey(key.to_string()));
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO settings (key, value, updated_at)
+            VALUES (?1, ?2, ?3)
+            ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(key)
+        .bind(serde_json::to_string(value)?)
+        .bind(Utc::now().to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Remove a setting so it reads as unset
+    pub async fn delete_setting(&self, key: &str) -> Result<(), DbError> {
+        sqlx::query("DELETE FROM settings WHERE key = ?1")
+            .bind(key)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Every stored setting, keyed by name
+    pub async fn get_all_settings(&self) -> Result<BTreeMap<String, serde_json::Value>, DbError> {
+        let rows = sqlx::query("SELECT key, value FROM settings")
+            .fetch_all(&self.pool)
+            .await?;
+
+        let mut settings = BTreeMap::new();
+        for row in rows {
+            let value: String = row.get("value");
+            settings.insert(row.get("key"), serde_json::from_str(&value)?);
+        }
+        Ok(settings)
+    }
+
+    /// Run SQLite's integrity check and try loading every stored spec. History
+    /// versions that fail are moved into quarantine so version lists keep
+    /// working; a failing current spec is copied there and can be fixed with
+    /// `repair_game`.
+    pub async fn check_library(&self) -> Result<IntegrityReport, DbError> {
+        let integrity_errors = sqlx::query("PRAGMA integrity_check")
+            .fetch_all(&self.pool)
+            .await?
+            .iter()
+            .map(|row| row.get::<String, _>(0))
+            .filter(|message| message != "ok")
+            .collect();
+
+        let mut tx = self.pool.begin().await?;
+        let rows = sqlx::query(
+            r#"
+            SELECT id AS game_id, version, spec_json, 1 AS is_current FROM games
+            UNION ALL
+            SELECT game_id, version, spec_json, 0 FROM game_versions
+            "#,
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let now = Utc::now().to_rfc3339();
+        for row in &rows {
+            let stored: Vec<u8> = row.get("spec_json");
+            let Err(error) = Self::load_stored_spec(&stored) else {
+                continue;
+            };
+            let game_id: String = row.get("game_id");
+            let version: i64 = row.get("version");
+            let is_current: bool = row.get("is_current");
+            // Keep the decompressed JSON when only parsing failed
+            let raw = Self::stored_spec_json(&stored)
+                .map(String::into_bytes)
+                .unwrap_or(stored);
+
+            sqlx::query(
+                r#"
+                INSERT OR IGNORE INTO quarantined_specs
+                    (game_id, version, is_current, spec_json, error, quarantined_at)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+                "#,
+            )
+            .bind(&game_id)
+            .bind(version)
+            .bind(is_current)
+            .bind(raw)
+            .bind(error.to_string())
+            .bind(&now)
+            .execute(&mut *tx)
+            .await?;
+
+            if !is_current {
+                sqlx::query("DELETE FROM game_versions WHERE game_id = ?1 AND version = ?2")
+                    .bind(&game_id)
+                    .bind(version)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+        }
+        tx.commit().await?;
+
+        Ok(IntegrityReport {
+            integrity_errors,
+            checked_specs: rows.len() as i64,
+            quarantined: self.list_quarantined().await?,
+        })
+    }
+
+    /// List quarantined specs, newest first
+    pub async fn list_quarantined(&self) -> Result<Vec<QuarantinedSpec>, DbError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, game_id, version, is_current, error, quarantined_at
+            FROM quarantined_specs
+            ORDER BY quarantined_at DESC, id DESC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut quarantined = Vec::new();
+        for row in rows {
+            let quarantined_at: String = row.get("quarantined_at");
+            quarantined.push(QuarantinedSpec {
+                id: row.get("id"),
+                game_id: row.get("game_id"),
+                version: row.get("version"),
+                is_current: row.get("is_current"),
+                error: row.get("error"),
+                quarantined_at: Self::parse_timestamp(&quarantined_at)?,
+            });
+        }
+        Ok(quarantined)
+    }
+
+    /// Write a quarantined spec's raw stored value to a file for manual recovery
+    pub async fn export_quarantined(&self, id: i64, path: &Path) -> Result<(), DbError> {
+        let raw: Vec<u8> = sqlx::query("SELECT spec_json FROM quarantined_specs WHERE id = ?1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?
+            .ok_or_else(|| DbError::NotFound(format!("quarantined spec {}", id)))?
+            .get("spec_json");
+        tokio::fs::write(path, raw).await?;
+        Ok(())
+    }
+
+    /// Permanently delete a quarantined spec
+    pub async fn discard_quarantined(&self, id: i64) -> Result<(), DbError> {
+        let result = sqlx::query("DELETE FROM quarantined_specs WHERE id = ?1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::NotFound(format!("quarantined spec {}", id)));
+        }
+        Ok(())
+    }
+
+    /// Replace a game's unreadable current spec with its newest readable
+    /// version, saved as a new version. Games that already load are returned as-is.
+    pub async fn repair_game(&self, id: &str) -> Result<GameRecord, DbError> {
+        match self.get_game(id).await {
+            Err(DbError::Migration(_)) | Err(DbError::Serialization(_)) | Err(DbError::Io(_)) => {}
+            result => return result,
+        }
+
+        let rows = sqlx::query(
+            r#"
+            SELECT version, spec_json
+            FROM game_versions
+            WHERE game_id = ?1
+            ORDER BY version DESC
+            "#,
+        )
+        .bind(id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        for row in rows {
+            let stored: Vec<u8> = row.get("spec_json");
+            if let Ok(spec) = Self::load_stored_spec(&stored) {
+                let version: i64 = row.get("version");
+                return self
+                    .update_game(
+                        id,
+                        spec,
+                        Some(format!("Recovered from version {}", version)),
+                    )
+                    .await;
+            }
+        }
+        Err(DbError::NotFound(format!("readable version of {}", id)))
+    }
+
+    /// Store the built-in templates. They are refreshed on every start so
+    /// fixes shipped in a new release reach existing installs.
+    pub async fn seed_templates(&self, templates: &[StarterTemplate]) -> Result<(), DbError> {
+        let now = Utc::now().to_rfc3339();
+        let mut tx = self.pool.begin().await?;
+        for template in templates {
+            let spec = spec_migration::load_spec(template.spec_json)?;
+            sqlx::query(
+                r#"
+                INSERT INTO templates (id, name, description, spec_json, created_at)
+                VALUES (?1, ?2, ?3, ?4, ?5)
+                ON CONFLICT(id) DO UPDATE SET
+                    name = excluded.name,
+                    description = excluded.description,
+                    spec_json = excluded.spec_json
+                "#,
+            )
+            .bind(template.id)
+            .bind(template.name)
+            .bind(template.description)
+            .bind(Self::compress_spec(&spec)?)
+            .bind(&now)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// List the available templates by name
+    pub async fn list_templates(&self) -> Result<Vec<GameTemplate>, DbError> {
+        let rows = sqlx::query("SELECT id, name, description FROM templates ORDER BY name")
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| GameTemplate {
+                id: row.get("id"),
+                name: row.get("name"),
+                description: row.get("description"),
+            })
+            .collect())
+    }
+
+    /// Create a new game from a template, optionally under a different title
+    pub async fn create_game_from_template(
+        &self,
+        template_id: &str,
+        title: Option<&str>,
+    ) -> Result<GameRecord, DbError> {
+        let stored: Vec<u8> = sqlx::query("SELECT spec_json FROM templates WHERE id = ?1")
+            .bind(template_id)
+            .fetch_optional(&self.pool)
+            .await?
+            .ok_or_else(|| DbError::NotFound(format!("template {}", template_id)))?
+            .get("spec_json");
+
+        let mut spec = Self::load_stored_spec(&stored)?;
+        if let Some(title) = title.map(str::trim).filter(|title| !title.is_empty()) {
+            spec.title = title.to_string();
+        }
+        self.create_game(spec).await
+    }
+
+    /// Store an error report, returning its id
+    pub async fn record_error(
+        &self,
+        kind: ReportKind,
+        message: &str,
+        context: Option<&str>,
+        backtrace: Option<&str>,
+    ) -> Result<i64, DbError> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO error_reports (kind, message, context, backtrace, app_version, platform, created_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+            "#,
+        )
+        .bind(kind.as_str())
+        .bind(message)
+        .bind(context)
+        .bind(backtrace)
+        .bind(env!("CARGO_PKG_VERSION"))
+        .bind(format!(
+            "{}-{}",
+            std::env::consts::OS,
+            std::env::consts::ARCH
+        ))
+        .bind(Utc::now().to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+        Ok(result.last_insert_rowid())
+    }
+
+    /// Most recent error reports, newest first
+    pub async fn list_error_reports(&self, limit: i64) -> Result<Vec<ErrorReport>, DbError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, kind, message, context, backtrace, app_version, platform, created_at
+            FROM error_reports
+            ORDER BY created_at DESC, id DESC
+            LIMIT ?1
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(Self::error_report_from_row).collect()
+    }
+
+    /// Write an error report as JSON, e.g. to attach to a support request
+    pub async fn export_error_report(&self, id: i64, path: &Path) -> Result<(), DbError> {
+        let row = sqlx::query(
+            r#"
+            SELECT id, kind, message, context, backtrace, app_version, platform, created_at
+            FROM error_reports
+            WHERE id = ?1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| DbError::NotFound(format!("error report {}", id)))?;
+
+        let report = Self::error_report_from_row(&row)?;
+        tokio::fs::write(path, serde_json::to_vec_pretty(&report)?).await?;
+        Ok(())
+    }
+
+    fn error_report_from_row(row: &SqliteRow) -> Result<ErrorReport, DbError> {
+        let created_at: String = row.get("created_at");
+        Ok(ErrorReport {
+            id: row.get("id"),
+            kind: ReportKind::from_db(row.get("kind")),
+            message: row.get("message"),
+            context: row.get("context"),
+            backtrace: row.get("backtrace"),
+            app_version: row.get("app_version"),
+            platform: row.get("platform"),
+            created_at: Self::parse_timestamp(&created_at)?,
+        })
+    }
 }
@@ -0,0 +1,88 @@
+/// Developer mode for prompt engineering: the game builder's system prompt is
+/// read from a markdown file instead of the one compiled into the app. The
+/// file is read again whenever it changes, so edits apply to the next message
+/// without rebuilding the backend.
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+/// Setting holding the path of the prompt file; unset for the built-in prompt
+pub const DEV_PROMPT_SETTING: &str = "dev_prompt_path";
+
+#[derive(Debug, thiserror::Error)]
+pub enum DevPromptError {
+    #[error("Can't read prompt file {0}: {1}")]
+    Read(PathBuf, std::io::Error),
+    #[error("Prompt file {0} is empty")]
+    Empty(PathBuf),
+}
+
+/// Which prompt is in use
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DevPromptStatus {
+    /// Prompt file; `None` when the built-in prompt is used
+    pub path: Option<PathBuf>,
+    /// Size of the prompt read from the file
+    pub bytes: Option<usize>,
+    /// When the file was last changed
+    pub modified: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Default)]
+pub struct DevPrompt {
+    path: Option<PathBuf>,
+    loaded: Option<Loaded>,
+}
+
+#[derive(Debug)]
+struct Loaded {
+    modified: SystemTime,
+    text: String,
+}
+
+impl DevPrompt {
+    pub fn new(path: Option<PathBuf>) -> Self {
+        Self { path, loaded: None }
+    }
+
+    /// The prompt from the file, read again if the file changed since the
+    /// last read; `None` without a file
+    pub fn current(&mut self) -> Result<Option<String>, DevPromptError> {
+        let Some(path) = &self.path else {
+            return Ok(None);
+        };
+        let read_error = |e| DevPromptError::Read(path.clone(), e);
+        let modified = fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .map_err(read_error)?;
+        if self.loaded.as_ref().map(|loaded| loaded.modified) != Some(modified) {
+            let text = fs::read_to_string(path).map_err(read_error)?;
+            if text.trim().is_empty() {
+                return Err(DevPromptError::Empty(path.clone()));
+            }
+            self.loaded = Some(Loaded { modified, text });
+        }
+        Ok(self.loaded.as_ref().map(|loaded| loaded.text.clone()))
+    }
+
+    /// Read the file again even if it looks unchanged, e.g. after editing it
+    /// within the filesystem's timestamp resolution
+    pub fn reload(&mut self) -> Result<DevPromptStatus, DevPromptError> {
+        self.loaded = None;
+        self.current()?;
+        Ok(self.status())
+    }
+
+    pub fn status(&self) -> DevPromptStatus {
+        DevPromptStatus {
+            path: self.path.clone(),
+            bytes: self.loaded.as_ref().map(|loaded| loaded.text.len()),
+            modified: self
+                .loaded
+                .as_ref()
+                .map(|loaded| DateTime::<Utc>::from(loaded.modified)),
+        }
+    }
+}
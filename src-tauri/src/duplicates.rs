@@ -0,0 +1,210 @@
+/// Detection of games that a save would duplicate. Classrooms pile up copies
+/// of the same game quickly, so saving a spec whose title or description
+/// matches an existing game asks how to resolve it instead of creating
+/// another one.
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+use crate::db::{GameRecord, GameSummary};
+use crate::game_builder::PhaserGameSpec;
+
+/// Titles at least this similar (1 - edit distance / length) count as the same game
+const TITLE_SIMILARITY: f64 = 0.8;
+
+/// Descriptions sharing at least this fraction of their words count as the same game
+const DESCRIPTION_OVERLAP: f64 = 0.8;
+
+/// Descriptions shorter than this are too generic to compare
+const MIN_DESCRIPTION_WORDS: usize = 5;
+
+/// Why a game was flagged as a possible duplicate
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchKind {
+    SameTitle,
+    SimilarTitle,
+    SimilarDescription,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimilarGame {
+    pub game: GameSummary,
+    pub kind: MatchKind,
+}
+
+/// Ways to resolve a conflict
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictOption {
+    /// Save the spec as the next version of the existing game
+    Overwrite,
+    /// Save the spec as a separate game anyway
+    SaveAsNew,
+    /// Fold the spec's scenes, assets, and achievements into the existing game
+    Merge,
+}
+
+/// How the caller chose to resolve a conflict
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum SaveResolution {
+    Overwrite { game_id: String },
+    SaveAsNew,
+    Merge { game_id: String },
+}
+
+/// Result of `save_game`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum SaveOutcome {
+    Saved {
+        game: Box<GameRecord>,
+    },
+    /// Nothing was saved; call again with a resolution
+    Conflict {
+        similar: Vec<SimilarGame>,
+        options: Vec<ConflictOption>,
+    },
+}
+
+impl SaveOutcome {
+    pub fn conflict(similar: Vec<SimilarGame>) -> Self {
+        SaveOutcome::Conflict {
+            similar,
+            options: vec![
+                ConflictOption::Overwrite,
+                ConflictOption::SaveAsNew,
+                ConflictOption::Merge,
+            ],
+        }
+    }
+}
+
+/// Games that look like the same game as `spec`, closest matches first
+pub fn find_similar(spec: &PhaserGameSpec, games: Vec<GameSummary>) -> Vec<SimilarGame> {
+    let title = normalize(&spec.title);
+    let description = words(&spec.description);
+    let mut similar: Vec<SimilarGame> = games
+        .into_iter()
+        .filter_map(|game| {
+            let kind = match_kind(&title, &description, &game)?;
+            Some(SimilarGame { game, kind })
+        })
+        .collect();
+    similar.sort_by_key(|similar| match similar.kind {
+        MatchKind::SameTitle => 0,
+        MatchKind::SimilarTitle => 1,
+        MatchKind::SimilarDescription => 2,
+    });
+    similar
+}
+
+fn match_kind(
+    title: &str,
+    description: &BTreeSet<String>,
+    game: &GameSummary,
+) -> Option<MatchKind> {
+    let other_title = normalize(&game.title);
+    if !title.is_empty() && title == other_title {
+        return Some(MatchKind::SameTitle);
+    }
+    if edit_similarity(title, &other_title) >= TITLE_SIMILARITY {
+        return Some(MatchKind::SimilarTitle);
+    }
+    let other_description = words(&game.description);
+    if description.len().min(other_description.len()) >= MIN_DESCRIPTION_WORDS {
+        let shared = description.intersection(&other_description).count();
+        let total = description.union(&other_description).count();
+        if shared as f64 / total as f64 >= DESCRIPTION_OVERLAP {
+            return Some(MatchKind::SimilarDescription);
+        }
+    }
+    None
+}
+
+/// Lowercase words separated by single spaces, punctuation dropped
+fn normalize(text: &str) -> String {
+    let text: String = text
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect();
+    text.to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn words(text: &str) -> BTreeSet<String> {
+    normalize(text)
+        .split_whitespace()
+        .map(str::to_string)
+        .collect()
+}
+
+/// 1 for equal strings down to 0 for entirely different ones
+fn edit_similarity(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 0.0;
+    }
+    // Levenshtein distance, one row at a time
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if ca == cb {
+                diagonal
+            } else {
+                1 + diagonal.min(above).min(row[j])
+            };
+            diagonal = above;
+        }
+    }
+    1.0 - row[b.len()] as f64 / longest as f64
+}
+
+/// `incoming` folded into `existing`: scenes, assets, and achievements replace
+/// those with the same name, key, or id and are otherwise added. The title,
+/// description, and game settings stay the existing game's.
+pub fn merge_specs(existing: PhaserGameSpec, incoming: PhaserGameSpec) -> PhaserGameSpec {
+    let mut merged = existing;
+    for scene in incoming.scenes {
+        match merged.scenes.iter_mut().find(|s| s.name == scene.name) {
+            Some(slot) => *slot = scene,
+            None => merged.scenes.push(scene),
+        }
+    }
+    for asset in incoming.assets {
+        match merged.assets.iter_mut().find(|a| a.key == asset.key) {
+            Some(slot) => *slot = asset,
+            None => merged.assets.push(asset),
+        }
+    }
+    if let Some(achievements) = incoming.achievements {
+        let merged_achievements = merged.achievements.get_or_insert_with(Vec::new);
+        for achievement in achievements {
+            match merged_achievements
+                .iter_mut()
+                .find(|a| a.id == achievement.id)
+            {
+                Some(slot) => *slot = achievement,
+                None => merged_achievements.push(achievement),
+            }
+        }
+    }
+    for line in incoming.controls_description {
+        if !merged.controls_description.contains(&line) {
+            merged.controls_description.push(line);
+        }
+    }
+    for concept in incoming.key_concepts {
+        if !merged.key_concepts.contains(&concept) {
+            merged.key_concepts.push(concept);
+        }
+    }
+    merged
+}
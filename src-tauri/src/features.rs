@@ -0,0 +1,69 @@
+/// Feature flags for experimental subsystems. Experiments ship turned off and
+/// are switched on per user from the settings, so a risky feature can be
+/// tried without changing the app for everyone.
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+pub const FEATURE_FLAGS_SETTING: &str = "feature_flags";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Feature {
+    /// The model edits the current game through `patch_phaser_game`
+    PatchEdits,
+}
+
+impl Feature {
+    pub const ALL: [Feature; 1] = [Feature::PatchEdits];
+
+    /// Name of the flag in the stored setting
+    fn key(self) -> &'static str {
+        match self {
+            Feature::PatchEdits => "patch_edits",
+        }
+    }
+
+    fn description(self) -> &'static str {
+        match self {
+            Feature::PatchEdits => {
+                "Let the game builder change an existing game with small patches instead of rewriting the whole spec"
+            }
+        }
+    }
+}
+
+/// Flags the user has switched; the rest stay off. Stored by name so a flag
+/// removed from a later version is ignored rather than breaking the setting.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct FeatureFlags(BTreeMap<String, bool>);
+
+/// Flag as shown in the settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeatureFlag {
+    pub feature: Feature,
+    pub enabled: bool,
+    pub description: String,
+}
+
+impl FeatureFlags {
+    pub fn is_enabled(&self, feature: Feature) -> bool {
+        self.0.get(feature.key()).copied().unwrap_or(false)
+    }
+
+    pub fn set(&mut self, feature: Feature, enabled: bool) {
+        self.0.insert(feature.key().to_string(), enabled);
+    }
+
+    /// Every known feature with its state
+    pub fn list(&self) -> Vec<FeatureFlag> {
+        Feature::ALL
+            .iter()
+            .map(|&feature| FeatureFlag {
+                feature,
+                enabled: self.is_enabled(feature),
+                description: feature.description().to_string(),
+            })
+            .collect()
+    }
+}
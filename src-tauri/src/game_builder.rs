@@ -3,6 +3,10 @@ use rig::completion::ToolDefinition;
 use rig::tool::Tool;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+
+use crate::{spec_patch, validator};
 
 /// Get the core system prompt for the Phaser game builder agent
 pub fn get_system_prompt() -> String {
@@ -28,6 +32,8 @@ When the user requests that you proceed with building a game, you MUST use the `
 
 **Do NOT generate HTML or JavaScript code.** Use the tool to create a JSON specification instead.
 
+The tool result lists `warnings` for parts of the game that probably won't work as intended, or that are too heavy for the low-end laptops kids often play on (too many objects in a scene, too many spawned objects alive at once). Fix them with another tool call before explaining the game, unless the user asked for exactly what a warning is about.
+
 ## Game Design Guidelines
 
 - **Use emojis for game objects**: Emojis make games visually appealing and easy to recognize (🚗 for cars, 👾 for enemies, ⭐ for collectibles)
@@ -35,8 +41,16 @@ When the user requests that you proceed with building a game, you MUST use the `
 - **Fallback to shapes when needed**: Use rectangles and circles for platforms, walls, or abstract objects
 - **Target 800x600**: Standard resolution works well for most games
 - **Enable physics when needed**: Platformers need gravity, top-down games don't
+- **Vary gravity**: Give an object its own `gravity` in its physics, or add scene `gravity_zones` (rectangles with their own gravity) for underwater areas, moon levels, or upside-down puzzles
+- **Wrap around the screen**: For Asteroids-style games set `"wrap_world": true` in the game physics (or per object) so objects leaving one edge reappear on the other
+- **Tune physics bodies**: Use `mass` and `drag` for pushable crates, `immovable` for moving platforms, `angular_velocity` for spinning obstacles, and `max_velocity` to cap speed
+- **Add platform gimmicks**: Give static platforms a `surface` of `{"type": "ice"}`, `{"type": "conveyor", "speed": 120}`, or `{"type": "bounce", "impulse": 600}`
+- **Use jump-through platforms**: Set `"one_way": true` in a static platform's physics so the player can jump up through it and land on top
 - **Define clear controls**: Use arrow keys, WASD, space bar, or any keyboard key
 - **Add win/lose conditions**: Use actions to trigger gameOver or update score
+- **Style your text**: Titles and instructions look better with `font_family`, `stroke`, `shadow`, or a `background_color`; use `word_wrap_width` for long instructions
+- **Control layering**: Set `depth` on objects when order matters (negative for backgrounds, 100+ for UI text)
+- **Let players retry**: Set `restart_key` (e.g., `"R"`) in the game config so players can restart after GAME OVER
 - **Add shooting mechanics**: Use the shoot control with a projectile template for shooter games
 
 ## Creating Actions
@@ -59,16 +73,65 @@ Actions define what happens during gameplay. Define them in `custom_logic.action
 {
   "name": "hitEnemy",
   "effect": {
-    "type": "gameOver"
+    "type": "gameOver",
+    "message": "SPLAT!",
+    "restart_prompt": "Press R to try again"
+  }
+}
+```
+
+**Example - Victory:**
+```json
+{
+  "name": "reachFlag",
+  "effect": {
+    "type": "win",
+    "message": "You made it!",
+    "stats": ["score", "time"]
   }
 }
 ```
 
 **Available Action Types:**
 - `updateScore` - Add points to score (destroys the target object)
-- `gameOver` - End the game with "GAME OVER" message
+- `gameOver` - End the game (optional `message`, `style`, `show_score`, `restart_prompt`)
+- `win` - Win the game (optional `message`, `style`, `stats`, `next_scene` for a next-level button)
 - `destroy` - Destroy the target object
+- `restartScene` - Restart the scene from the beginning
+- `saveHighScore` - Save the current score to the game's high-score table (use right before `gameOver` or `win`; set `ask_name` for arcade-style name entry)
 - `updateText` - Change text content of an object
+- `updateVariable` - Change a declared variable by `change` or set it to `value`
+- `damage` - Remove `amount` health from the first object of the pair (or `object_id`)
+- `loseLife` - Remove a life shown in the HUD (game over at zero)
+- `setAlpha` - Fade an object (`alpha` 0-1, optional `object_id`, `duration` ms)
+- `setTint` - Tint an object (`color`, omit to clear; optional `object_id`, `duration` ms for a flash)
+- `rotate` - Rotate an object by `degrees` (optional `object_id`, `duration` ms)
+- `scale` - Resize an object to `scale` 0.1-10 (optional `object_id`, `duration` ms)
+- `setCheckpoint` - Save a respawn point (optional `object_id`, default: the touched object)
+- `respawnAtCheckpoint` - Send an object (default `player`) back to the last checkpoint
+- `cameraShake` - Shake the screen (`duration` ms, optional `intensity` 0.001-0.1)
+- `cameraFlash` - Flash the screen (`duration` ms, optional `color`)
+- `cameraZoom` - Zoom in or out (`zoom` 0.25-4, optional `duration` ms)
+- `flashScreen` - Fade a translucent `color` overlay out over `duration` ms (e.g., red on damage)
+- `setBackgroundColor` - Change the background to `color` (optional `duration` ms to blend)
+- `setTimeScale` - Change game speed to `scale` 0.1-4 (optional `duration` ms before returning to normal; e.g., 0.3 for slow motion on a big hit)
+- `startSpawner` / `stopSpawner` - Turn a spawner on or off by `spawner_id` (e.g., stop enemies when the boss appears)
+- `setSpawnerInterval` - Change a spawner's `interval` in ms (e.g., spawn faster as the score rises)
+
+## Variables and Live Text
+
+Declare counters in `custom_logic.variables` and show them with `{name}` placeholders in text objects, HUD `labels`, or `updateText`. `{score}`, `{lives}`, and `{time}` are always available. Text updates automatically when a value changes:
+
+```json
+{
+  "variables": [{ "name": "ammo", "initial": 10 }],
+  "actions": [
+    { "name": "useAmmo", "effect": { "type": "updateVariable", "name": "ammo", "change": -1 } }
+  ]
+}
+```
+
+Then a HUD label `{ "x": 16, "y": 80, "text": "Ammo: {ammo}" }` stays in sync.
 
 ## Using Behaviors
 
@@ -107,6 +170,139 @@ Add autonomous movement to objects with behaviors:
 }
 ```
 
+## Hazards
+
+Use a `hazard` object for spikes, saws, and lava instead of building traps by hand. The visuals, physics, and hit handler are added automatically:
+
+```json
+{
+  "id": "spikes1",
+  "type": "hazard",
+  "x": 400,
+  "y": 560,
+  "hazard": { "kind": "spikes", "width": 120 }
+}
+```
+
+Without `damage` touching the hazard ends the game; with `"damage": 25` it removes health from the target (default `player`), which must define `health`. Saws patrol unless given a different `behavior`.
+
+## Portals
+
+Link two objects as portals in `custom_logic.portals`; anything dynamic that touches one comes out of the other:
+
+```json
+{
+  "portals": [{ "a": "portal_left", "b": "portal_right", "cooldown": 500 }]
+}
+```
+
+Both ids must be objects in the scene (🌀 emojis make good portals).
+
+## Scene Start
+
+Run setup actions as soon as the scene boots with `custom_logic.on_start`, instead of near-zero timers:
+
+```json
+{
+  "on_start": ["showInstructions", "cameraIntro"]
+}
+```
+
+Actions run in the listed order. They have no touched object, so target effects with `object_id`.
+
+## Destruction Triggers
+
+React to objects being destroyed with `custom_logic.on_destroyed`. The destroyed object is the action's target. Set `all` to wait until every member of a group is gone, which makes clear-the-screen win conditions easy:
+
+```json
+{
+  "on_destroyed": [
+    { "target": "boss", "action": "openExit" },
+    { "target": "brick", "action": "levelCleared", "all": true }
+  ]
+}
+```
+
+## Score Triggers
+
+Fire actions at score thresholds with `custom_logic.on_score_reached`. Use `repeat` to fire every `at` points, which is handy for ramping difficulty:
+
+```json
+{
+  "on_score_reached": [
+    { "at": 500, "action": "spawnBoss" },
+    { "at": 100, "action": "speedUp", "repeat": true },
+    { "at": 1000, "action": "victory" }
+  ]
+}
+```
+
+## Key Triggers
+
+Bind any key to an action with `custom_logic.on_key_press`, separate from movement `controls`:
+
+```json
+{
+  "on_key_press": [{ "key": "E", "action": "openDoor" }]
+}
+```
+
+Key-triggered actions have no touched object, so give effects like `destroy`, `setTint`, or `damage` an `object_id`. Don't reuse keys already bound in `controls`.
+
+## Clickable Objects
+
+Set `on_click` on any object to run an action when it is clicked or tapped. The clicked object is the action's target, so `updateScore`, `destroy`, and `setTint` affect it directly:
+
+```json
+{
+  "id": "mole1",
+  "type": "emoji",
+  "x": 200,
+  "y": 300,
+  "emoji": { "emoji": "🐹", "size": 64, "collision_box": { "shape": "circle", "radius": 28 } },
+  "on_click": "whackMole"
+}
+```
+
+Use this for buttons, menus, and whack-a-mole or clicker games; such games may not need `controls` at all.
+
+## Trigger Zones
+
+Script level events with an invisible `zone` object that runs actions when the player enters or leaves it:
+
+```json
+{
+  "id": "bossArena",
+  "type": "zone",
+  "x": 2400,
+  "y": 300,
+  "zone": { "width": 200, "height": 600, "on_enter": "showBossWarning", "once": true }
+}
+```
+
+Use zones instead of transparent rectangles. Set `triggered_by` to another object id or group if something other than the player should trigger it.
+
+## Checkpoints
+
+For longer levels, mark objects with `"checkpoint": true` (🚩 works well). When the player touches one it becomes the respawn point. Use a `respawnAtCheckpoint` action instead of `gameOver` for falls and hazards, optionally paired with `loseLife`.
+
+## Layered Emojis
+
+When a single emoji can't show the object, stack extra emojis with `layers`. Layers move with the object and don't affect its collision box:
+
+```json
+{
+  "emoji": {
+    "emoji": "🚗",
+    "size": 48,
+    "collision_box": { "shape": "rectangle", "width": 48, "height": 30 },
+    "layers": [
+      { "emoji": "👨", "offset_x": 4, "offset_y": -10, "scale": 0.5 }
+    ]
+  }
+}
+```
+
 ## Shooting Mechanics
 
 Add shooting to any object with controls using the `shoot` key and `projectile` template:
@@ -162,6 +358,23 @@ Add shooting to any object with controls using the `shoot` key and `projectile`
 - Projectiles are automatically added to a "projectiles" group for collision detection
 - Use overlap detection to handle projectile hits: `"projectiles,enemy_template -> destroyEnemy"`
 - Rate limited to 200ms between shots
+- For rapid-fire games add `"projectile_pool": { "max_live": 20, "recycle": "oldest" }` next to `projectile` so old bullets are reused instead of piling up
+
+## Collision Layers
+
+When comma-pair handlers get messy (bullets that should hit enemies but not the player), give objects and templates a `collision_group` and describe how groups interact in `custom_logic.collision_matrix`:
+
+```json
+{
+  "collision_matrix": [
+    { "a": "playerBullets", "b": "enemies", "mode": "overlap", "action": "hitEnemy" },
+    { "a": "player", "b": "enemies", "action": "hurtPlayer" },
+    { "a": "playerBullets", "b": "player", "mode": "ignore" }
+  ]
+}
+```
+
+Modes are `collide` (default, bodies push apart), `overlap` (pass through), and `ignore` (no interaction, overriding any handler). Groups without a rule pass through each other.
 
 ## Spawning System
 
@@ -208,6 +421,157 @@ You can define spawners to create objects dynamically during gameplay:
 
 **Important:** Spawned objects are automatically destroyed when they leave the world bounds. They can have physics, behaviors, and will participate in collisions/overlaps defined in custom_logic.
 
+**Pooling:** Spawners with short intervals should cap how many objects are alive at once with `"pool": { "max_live": 30 }`. With `"recycle": "skip"` (default) spawning pauses while the pool is full; `"oldest"` reuses the oldest object instead.
+
+## Tilemaps
+
+For levels with many platforms, walls, or collectibles, use a scene `tilemap` instead of listing every object. Each character in `rows` is one tile, `.` is empty, and the `legend` maps characters to templates:
+
+```json
+{
+  "tilemap": {
+    "tile_size": 40,
+    "rows": [
+      "....................",
+      "......C.....C.......",
+      "....=====..====.....",
+      "===================="
+    ],
+    "legend": [
+      { "symbol": "=", "template": { "id": "ground", "type": "rectangle", "x": 0, "y": 0, "shape": { "width": 40, "height": 40, "color": "#8B4513" }, "physics": { "body": "static" } } },
+      { "symbol": "C", "template": { "id": "coin", "type": "emoji", "x": 0, "y": 0, "emoji": { "emoji": "🪙", "size": 30, "collision_box": { "shape": "circle", "radius": 15 } } } }
+    ]
+  }
+}
+```
+
+Tiles are added to a group named after the template id, so `"player,coin -> collectCoin"` works for every coin in the map.
+
+## HUD
+
+Use the scene `hud` for scores, health, lives, and time instead of text objects. HUD elements stay fixed on screen and update automatically:
+
+```json
+{
+  "hud": {
+    "score": { "x": 16, "y": 16 },
+    "health_bar": { "object_id": "player", "x": 16, "y": 50 },
+    "lives": { "count": 3, "x": 680, "y": 16 },
+    "timer": { "x": 360, "y": 16 }
+  }
+}
+```
+
+The health bar needs an object with `health` (e.g., `"health": 100`); reduce it with the `damage` action.
+
+## Countdown Timers
+
+For "survive for 60 seconds" or "beat the clock" games, add a scene `countdown` that runs an action when time is up:
+
+```json
+{
+  "countdown": { "seconds": 60, "on_expire": "timeUp" }
+}
+```
+
+Set `"display": false` to hide the remaining time. `timers` in custom_logic are still the way to repeat something on an interval.
+
+## Pausing
+
+For longer games, add `"pause": { "key": "P" }` to the `game` config so the player can pause and resume. Timers and spawners stop while paused unless `halt_timers`/`halt_spawners` are set to false.
+
+## Music and Sound
+
+Add an `audio` block to the `game` config for a soundtrack. No asset is needed for a generated tune:
+
+```json
+{
+  "audio": {
+    "background_music": { "source": "generated", "style": "chiptune", "tempo": 140 },
+    "master_volume": 0.8,
+    "mute_key": "M"
+  }
+}
+```
+
+Use `{ "source": "asset", "key": "theme" }` only when an `audio` asset is provided. Add `"mute_button": { "x": 760, "y": 16 }` to the HUD for a clickable mute toggle.
+
+## Procedural Levels
+
+For endless runners or levels that change every play, describe the level with a scene `procedural` block instead of placing objects. It is expanded when the game loads:
+
+```json
+{
+  "procedural": {
+    "length": 6000,
+    "platform": { "id": "ground", "type": "rectangle", "x": 0, "y": 580, "shape": { "width": 200, "height": 40, "color": "#228B22" }, "physics": { "body": "static" } },
+    "obstacle": { "id": "rock", "type": "emoji", "x": 0, "y": 0, "emoji": { "emoji": "🪨", "size": 36, "collision_box": { "shape": "circle", "radius": 16 } }, "physics": { "body": "static" } },
+    "collectible": { "id": "gem", "type": "emoji", "x": 0, "y": 0, "emoji": { "emoji": "💎", "size": 28, "collision_box": { "shape": "circle", "radius": 12 } } },
+    "obstacle_density": 0.25,
+    "gap_width": { "min": 60, "max": 140 },
+    "collectible_frequency": 0.4
+  }
+}
+```
+
+Pair it with a `camera` whose `world_width` matches `length`. Handlers refer to the template ids (e.g., `"player,gem -> collectGem"`). Set `seed` to get the same level every time.
+
+## Camera and Large Levels
+
+By default everything must fit on the canvas. For side-scrollers and exploration games, add a scene `camera` with a world larger than the canvas and a follow target:
+
+```json
+{
+  "camera": {
+    "follow": "player",
+    "world_width": 3200,
+    "world_height": 600,
+    "deadzone": { "width": 200, "height": 150 },
+    "lerp": 0.1
+  }
+}
+```
+
+Objects with `collide_world_bounds` are kept inside the world, not the canvas. Place objects anywhere within the world size.
+
+## Achievements
+
+Reward players with badges they keep across sessions using the top-level `achievements` list. Each one unlocks the first time its `condition` holds:
+
+```json
+{
+  "achievements": [
+    { "id": "first100", "name": "Century", "icon": "💯", "condition": "score >= 100" },
+    { "id": "flawless", "name": "Flawless", "condition": "score >= 500 && lives == 3" }
+  ]
+}
+```
+
+Conditions compare `score`, `lives`, `time`, or declared variables to numbers with `>=`, `<=`, `==`, `!=`, `>`, `<`, joined by `&&`.
+
+## Sprite Sheet Animations
+
+Only use sprites when the user provides their own art. Declare the sheet as a `spritesheet` asset with its frame size and named animations, then reference them from a `sprite` object:
+
+```json
+{
+  "assets": [{
+    "key": "hero",
+    "type": "spritesheet",
+    "url": "hero.png",
+    "frame_config": { "frame_width": 32, "frame_height": 48 },
+    "animations": [
+      { "key": "idle", "start_frame": 0, "end_frame": 3, "frame_rate": 6 },
+      { "key": "walk", "start_frame": 4, "end_frame": 11, "frame_rate": 12 }
+    ]
+  }]
+}
+```
+
+The object sets `"texture": "hero"` and `"animations": { "idle": "idle", "walk": "walk" }`.
+
+Images and sounds (mp3, ogg, wav) the user imported into their local asset library are referenced with `"library_key"` instead of `url` (e.g., `{ "key": "hero", "type": "sprite", "library_key": "my_dragon" }` or `{ "key": "theme", "type": "audio", "library_key": "my_song" }`).
+
 ## Response Format
 
 When creating a game:
@@ -247,6 +611,10 @@ You: [Use the generate_phaser_game tool with a platformer specification, then ex
 pub enum GameBuilderError {
     #[error("Invalid game configuration: {0}")]
     InvalidConfiguration(String),
+    #[error("There is no game to patch yet; use generate_phaser_game first")]
+    NoCurrentGame,
+    #[error("Patch failed: {0}")]
+    Patch(#[from] spec_patch::PatchError),
 }
 
 /// Physics configuration for the game
@@ -261,6 +629,12 @@ pub struct PhysicsConfig {
     #[schemars(description = "Whether to show debug visualization")]
     #[serde(default)]
     pub debug: bool,
+
+    #[schemars(
+        description = "Wrap every dynamic object around the world edges (Asteroids-style); objects can opt out with wrap_world: false"
+    )]
+    #[serde(default)]
+    pub wrap_world: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -288,17 +662,138 @@ pub struct GameConfig {
 
     #[schemars(description = "Physics configuration")]
     pub physics: PhysicsConfig,
+
+    #[schemars(description = "Pause/resume configuration")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pause: Option<PauseConfig>,
+
+    #[schemars(description = "Key that restarts the scene after game over (e.g., 'R')")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub restart_key: Option<String>,
+
+    #[schemars(description = "Background music and volume settings")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audio: Option<AudioConfig>,
 }
 
-fn default_bg_color() -> String {
+pub fn default_bg_color() -> String {
     "#87CEEB".to_string()
 }
 
+/// Pause/resume configuration
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PauseConfig {
+    #[schemars(description = "Key that toggles pause (default: 'P')")]
+    #[serde(default = "default_pause_key")]
+    pub key: String,
+
+    #[schemars(description = "Text shown while paused (default: 'PAUSED')")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub overlay_text: Option<String>,
+
+    #[schemars(description = "Whether timers and the countdown stop while paused (default: true)")]
+    #[serde(default = "default_true")]
+    pub halt_timers: bool,
+
+    #[schemars(description = "Whether spawners stop while paused (default: true)")]
+    #[serde(default = "default_true")]
+    pub halt_spawners: bool,
+}
+
+fn default_pause_key() -> String {
+    "P".to_string()
+}
+
+/// Audio configuration
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AudioConfig {
+    #[schemars(description = "Music that loops while the game runs")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background_music: Option<BackgroundMusic>,
+
+    #[schemars(description = "Master volume (0-1, default: 1)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub master_volume: Option<f32>,
+
+    #[schemars(description = "Key that toggles mute (e.g., 'M')")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mute_key: Option<String>,
+}
+
+/// Background music source
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "source", rename_all = "lowercase")]
+pub enum BackgroundMusic {
+    #[schemars(description = "Loop an audio asset")]
+    Asset {
+        #[schemars(description = "Key of an audio asset")]
+        key: String,
+        #[schemars(description = "Music volume (0-1, default: 0.5)")]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        volume: Option<f32>,
+    },
+
+    #[schemars(description = "Loop a procedurally generated tune (no asset needed)")]
+    Generated {
+        #[schemars(description = "Mood of the generated tune")]
+        style: MusicStyle,
+        #[schemars(description = "Tempo in beats per minute (60-200, default: 120)")]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        tempo: Option<u32>,
+        #[schemars(description = "Music volume (0-1, default: 0.5)")]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        volume: Option<f32>,
+    },
+}
+
+/// Moods for generated music
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum MusicStyle {
+    Chiptune,
+    Upbeat,
+    Calm,
+    Spooky,
+}
+
+impl AudioConfig {
+    pub fn validate(&self, assets: &[Asset]) -> Result<(), String> {
+        let volume = match &self.background_music {
+            Some(BackgroundMusic::Asset { key, volume }) => {
+                let is_audio = assets
+                    .iter()
+                    .any(|asset| &asset.key == key && matches!(asset.asset_type, AssetType::Audio));
+                if !is_audio {
+                    return Err(format!(
+                        "background music references unknown audio asset '{}'",
+                        key
+                    ));
+                }
+                *volume
+            }
+            Some(BackgroundMusic::Generated { tempo, volume, .. }) => {
+                if tempo.is_some_and(|tempo| !(60..=200).contains(&tempo)) {
+                    return Err("music tempo must be between 60 and 200 bpm".to_string());
+                }
+                *volume
+            }
+            None => None,
+        };
+        for volume in [volume, self.master_volume].into_iter().flatten() {
+            if !(0.0..=1.0).contains(&volume) {
+                return Err("volume must be between 0 and 1".to_string());
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Asset types
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum AssetType {
     Sprite,
+    Spritesheet,
     Audio,
     Image,
 }
@@ -313,8 +808,64 @@ pub struct Asset {
     #[serde(rename = "type")]
     pub asset_type: AssetType,
 
-    #[schemars(description = "URL or path to the asset (can be placeholder for shapes)")]
+    #[schemars(
+        description = "URL or path to the asset (can be placeholder for shapes, empty for library assets)"
+    )]
+    #[serde(default)]
     pub url: String,
+
+    #[schemars(
+        description = "Key of an image or sound imported into the local asset library (replaces url)"
+    )]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub library_key: Option<String>,
+
+    #[schemars(description = "Frame dimensions (required for spritesheet assets)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frame_config: Option<FrameConfig>,
+
+    #[schemars(description = "Named animations built from this spritesheet's frames")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub animations: Option<Vec<SpriteAnimation>>,
+}
+
+/// Frame layout of a spritesheet
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FrameConfig {
+    #[schemars(description = "Width of a single frame in pixels")]
+    pub frame_width: u32,
+
+    #[schemars(description = "Height of a single frame in pixels")]
+    pub frame_height: u32,
+
+    #[schemars(description = "Spacing between frames in pixels")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spacing: Option<u32>,
+
+    #[schemars(description = "Margin around the frames in pixels")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub margin: Option<u32>,
+}
+
+/// Named animation defined over a range of spritesheet frames
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SpriteAnimation {
+    #[schemars(description = "Animation name referenced by objects (e.g., 'walk', 'idle')")]
+    pub key: String,
+
+    #[schemars(description = "First frame index (inclusive)")]
+    pub start_frame: u32,
+
+    #[schemars(description = "Last frame index (inclusive)")]
+    pub end_frame: u32,
+
+    #[schemars(description = "Playback speed in frames per second (default: 10)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frame_rate: Option<u32>,
+
+    #[schemars(description = "Number of times to repeat (-1 = loop forever, default: -1)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repeat: Option<i32>,
 }
 
 /// Physics body types
@@ -343,17 +894,89 @@ pub struct ObjectPhysics {
     #[schemars(description = "Initial velocity")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub velocity: Option<VelocityConfig>,
-}
 
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
-pub struct VelocityConfig {
-    #[serde(default)]
-    pub x: f32,
-    #[serde(default)]
-    pub y: f32,
-}
+    #[schemars(
+        description = "Jump-through platform: objects pass through from below and land on top (static bodies only)"
+    )]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub one_way: Option<bool>,
 
-/// Control mapping
+    #[schemars(
+        description = "Special surface applied to objects standing on it (static bodies only)"
+    )]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub surface: Option<Surface>,
+
+    #[schemars(description = "Mass used when dynamic bodies push each other (default: 1)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mass: Option<f32>,
+
+    #[schemars(
+        description = "Deceleration in pixels/second² when not being moved (e.g., 200 for sliding crates)"
+    )]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub drag: Option<f32>,
+
+    #[schemars(description = "Spin speed in degrees/second (positive = clockwise)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub angular_velocity: Option<f32>,
+
+    #[schemars(description = "Speed cap on each axis in pixels/second")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_velocity: Option<f32>,
+
+    #[schemars(
+        description = "Whether collisions can't push this dynamic body (e.g., moving platforms)"
+    )]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub immovable: Option<bool>,
+
+    #[schemars(
+        description = "Reappear on the opposite edge when leaving the world (overrides the global setting)"
+    )]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wrap_world: Option<bool>,
+
+    #[schemars(
+        description = "Gravity for this object, replacing the world gravity (e.g., y: 0 to float, negative y to fall upward)"
+    )]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gravity: Option<GravityConfig>,
+}
+
+/// Surface effects for platforms
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum Surface {
+    #[schemars(description = "Slippery surface where objects keep sliding")]
+    Ice {
+        #[schemars(description = "Friction (0 = frictionless, 1 = normal ground, default: 0.1)")]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        friction: Option<f32>,
+    },
+
+    #[schemars(description = "Moving belt that carries objects along")]
+    Conveyor {
+        #[schemars(description = "Horizontal speed in pixels/second (positive = right)")]
+        speed: f32,
+    },
+
+    #[schemars(description = "Bounce pad that launches objects upward")]
+    Bounce {
+        #[schemars(description = "Upward launch speed in pixels/second")]
+        impulse: f32,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct VelocityConfig {
+    #[serde(default)]
+    pub x: f32,
+    #[serde(default)]
+    pub y: f32,
+}
+
+/// Control mapping
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Controls {
     #[schemars(description = "Key for moving left")]
@@ -383,6 +1006,32 @@ pub struct Controls {
     #[schemars(description = "Projectile template spawned when shoot key is pressed")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub projectile: Option<Box<GameObject>>,
+
+    #[schemars(description = "Limit on live projectiles (recommended for rapid-fire games)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub projectile_pool: Option<PoolConfig>,
+}
+
+/// Cap on how many objects from one source can be alive at once
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PoolConfig {
+    #[schemars(description = "Maximum number of live objects")]
+    pub max_live: u32,
+
+    #[schemars(description = "What happens when the pool is full (default: skip)")]
+    #[serde(default)]
+    pub recycle: RecyclePolicy,
+}
+
+/// Pool behavior once `max_live` is reached
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum RecyclePolicy {
+    /// Don't create a new object until one is destroyed
+    #[default]
+    Skip,
+    /// Reuse the oldest live object for the new one
+    Oldest,
 }
 
 /// Object types
@@ -395,6 +1044,8 @@ pub enum ObjectType {
     Text,
     Emoji,
     Group,
+    Hazard,
+    Zone,
 }
 
 /// Shape-specific properties
@@ -420,7 +1071,9 @@ pub struct ShapeProperties {
 /// Text-specific properties
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct TextProperties {
-    #[schemars(description = "Text content")]
+    #[schemars(
+        description = "Text content. Placeholders like {score}, {lives}, {time}, or {myVariable} update automatically"
+    )]
     pub text: String,
 
     #[schemars(description = "Font size (e.g., '32px')")]
@@ -430,6 +1083,67 @@ pub struct TextProperties {
     #[schemars(description = "Text color")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub fill: Option<String>,
+
+    #[schemars(description = "Font family (e.g., 'Arial', 'Courier New', 'Georgia')")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub font_family: Option<String>,
+
+    #[schemars(description = "Outline color")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stroke: Option<String>,
+
+    #[schemars(description = "Outline thickness in pixels (default: 0)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stroke_thickness: Option<f32>,
+
+    #[schemars(description = "Drop shadow")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shadow: Option<TextShadow>,
+
+    #[schemars(description = "Alignment of multi-line text")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub align: Option<TextAlign>,
+
+    #[schemars(description = "Wrap lines longer than this width in pixels")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub word_wrap_width: Option<f32>,
+
+    #[schemars(description = "Background color behind the text")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background_color: Option<String>,
+
+    #[schemars(description = "Padding around the text in pixels (useful with a background)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub padding: Option<f32>,
+}
+
+/// Text drop shadow
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TextShadow {
+    #[schemars(description = "Horizontal shadow offset in pixels (default: 2)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset_x: Option<f32>,
+
+    #[schemars(description = "Vertical shadow offset in pixels (default: 2)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset_y: Option<f32>,
+
+    #[schemars(description = "Shadow color (default: '#000000')")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+
+    #[schemars(description = "Shadow blur in pixels (default: 0)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blur: Option<f32>,
+}
+
+/// Text alignment
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TextAlign {
+    Left,
+    Center,
+    Right,
 }
 
 /// Collision box shape types
@@ -471,6 +1185,114 @@ pub struct EmojiProperties {
 
     #[schemars(description = "Collision box for physics interactions")]
     pub collision_box: CollisionBox,
+
+    #[schemars(
+        description = "Extra emojis drawn on top of the main emoji (e.g., a driver in a car), moving with it"
+    )]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub layers: Option<Vec<EmojiLayer>>,
+}
+
+/// Additional emoji composed onto an emoji object
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct EmojiLayer {
+    #[schemars(description = "Emoji character for this layer")]
+    pub emoji: String,
+
+    #[schemars(description = "Horizontal offset from the main emoji's center in pixels")]
+    #[serde(default)]
+    pub offset_x: f32,
+
+    #[schemars(description = "Vertical offset from the main emoji's center in pixels")]
+    #[serde(default)]
+    pub offset_y: f32,
+
+    #[schemars(description = "Size relative to the main emoji (default: 1.0)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scale: Option<f32>,
+
+    #[schemars(description = "Draw this layer behind the main emoji instead of in front")]
+    #[serde(default)]
+    pub behind: bool,
+}
+
+/// Animation keys played for an object's movement states
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ObjectAnimations {
+    #[schemars(description = "Animation played when the object is not moving")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub idle: Option<String>,
+
+    #[schemars(description = "Animation played while moving horizontally")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub walk: Option<String>,
+
+    #[schemars(description = "Animation played while in the air")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jump: Option<String>,
+}
+
+/// Invisible area that runs actions when objects enter or leave it
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ZoneProperties {
+    #[schemars(description = "Zone width in pixels")]
+    pub width: f32,
+
+    #[schemars(description = "Zone height in pixels")]
+    pub height: f32,
+
+    #[schemars(description = "Action run when a triggering object enters")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_enter: Option<String>,
+
+    #[schemars(description = "Action run when a triggering object leaves")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_exit: Option<String>,
+
+    #[schemars(description = "Object ID or group that triggers the zone (default: 'player')")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub triggered_by: Option<String>,
+
+    #[schemars(description = "Only trigger the first time (default: false)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub once: Option<bool>,
+}
+
+/// Built-in hazard kinds
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum HazardKind {
+    #[schemars(description = "Row of static spikes")]
+    Spikes,
+    #[schemars(description = "Spinning saw blade that patrols back and forth")]
+    Saw,
+    #[schemars(description = "Pool of static lava")]
+    Lava,
+}
+
+/// Hazard preset: visuals, physics, and the hit handler are created automatically
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct HazardProperties {
+    #[schemars(description = "Kind of hazard")]
+    pub kind: HazardKind,
+
+    #[schemars(description = "Width in pixels (default: 40)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<f32>,
+
+    #[schemars(description = "Height in pixels (default: 40, lava default: 20)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<f32>,
+
+    #[schemars(
+        description = "Health removed on touch; omit to end the game instead (the target needs health)"
+    )]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub damage: Option<u32>,
+
+    #[schemars(description = "ID of the object hurt by this hazard (default: 'player')")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
 }
 
 /// Behavior types for NPCs/enemies
@@ -503,6 +1325,12 @@ pub struct GameObject {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub texture: Option<String>,
 
+    #[schemars(
+        description = "Animations to play for each movement state (for spritesheet sprites)"
+    )]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub animations: Option<ObjectAnimations>,
+
     #[schemars(description = "Shape properties (for geometric shapes)")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub shape: Option<ShapeProperties>,
@@ -515,6 +1343,36 @@ pub struct GameObject {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub emoji: Option<EmojiProperties>,
 
+    #[schemars(description = "Trigger zone properties (for zone objects)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub zone: Option<ZoneProperties>,
+
+    #[schemars(description = "Hazard preset properties (for hazard objects)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hazard: Option<HazardProperties>,
+
+    #[schemars(
+        description = "Draw order (higher is drawn on top, default: 0). Use negative values for backgrounds and 100+ for UI"
+    )]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub depth: Option<i32>,
+
+    #[schemars(description = "Opacity from 0 (invisible) to 1 (opaque), default: 1")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alpha: Option<f32>,
+
+    #[schemars(description = "Tint color multiplied over the object (hex string like '#ff0000')")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tint: Option<String>,
+
+    #[schemars(description = "Initial rotation in degrees (clockwise, default: 0)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rotation: Option<f32>,
+
+    #[schemars(description = "Initial scale multiplier (default: 1)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scale: Option<f32>,
+
     #[schemars(description = "Physics configuration")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub physics: Option<ObjectPhysics>,
@@ -523,6 +1381,28 @@ pub struct GameObject {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub controls: Option<Controls>,
 
+    #[schemars(
+        description = "Named collision layer (e.g., 'player', 'playerBullets', 'enemies') used by the collision matrix"
+    )]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collision_group: Option<String>,
+
+    #[schemars(
+        description = "Action to run when the object is clicked or tapped (buttons, targets, menu items)"
+    )]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_click: Option<String>,
+
+    #[schemars(
+        description = "Whether touching this object saves it as the player's respawn point"
+    )]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checkpoint: Option<bool>,
+
+    #[schemars(description = "Starting health (object is destroyed when it reaches 0)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub health: Option<u32>,
+
     #[schemars(description = "Behavior type (for NPCs/enemies)")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub behavior: Option<BehaviorType>,
@@ -556,6 +1436,12 @@ pub struct Spawner {
     #[schemars(description = "Random position variation")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub position_variance: Option<PositionVariance>,
+
+    #[schemars(
+        description = "Limit on objects from this spawner alive at once (recommended for intervals under 500ms)"
+    )]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pool: Option<PoolConfig>,
 }
 
 /// Position variance for spawning
@@ -576,90 +1462,1049 @@ pub struct PositionVariance {
     pub y_max: Option<f32>,
 }
 
-/// Action effect types
+/// Camera configuration for a scene
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
-#[serde(tag = "type", rename_all = "camelCase")]
-pub enum ActionEffect {
-    #[schemars(description = "Update the score")]
-    UpdateScore { points: i32 },
+pub struct CameraConfig {
+    #[schemars(description = "ID of the object the camera follows (usually the player)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub follow: Option<String>,
 
-    #[schemars(description = "End the game")]
-    GameOver,
+    #[schemars(description = "World width in pixels (default: canvas width)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub world_width: Option<u32>,
 
-    #[schemars(description = "Destroy the object")]
-    Destroy,
+    #[schemars(description = "World height in pixels (default: canvas height)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub world_height: Option<u32>,
 
-    #[schemars(description = "Update text content")]
-    UpdateText { object_id: String, text: String },
+    #[schemars(
+        description = "Area in the middle of the screen where the target can move without scrolling"
+    )]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deadzone: Option<CameraDeadzone>,
+
+    #[schemars(description = "Follow smoothing from 0 (frozen) to 1 (instant), default: 1")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lerp: Option<f32>,
 }
 
-/// Action definition
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
-pub struct ActionDefinition {
-    #[schemars(description = "Action name to reference in callbacks")]
-    pub name: String,
+pub struct CameraDeadzone {
+    #[schemars(description = "Deadzone width in pixels")]
+    pub width: f32,
 
-    #[schemars(description = "The effect this action performs")]
-    pub effect: ActionEffect,
+    #[schemars(description = "Deadzone height in pixels")]
+    pub height: f32,
 }
 
-/// Custom logic/interactions
+/// Heads-up display fixed to the screen above the scene
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
-pub struct CustomLogic {
-    #[schemars(description = "Collision handlers (e.g., 'player,enemy -> gameOver')")]
+pub struct HudConfig {
+    #[schemars(description = "Score display")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub on_collision: Option<Vec<String>>,
+    pub score: Option<HudScore>,
 
-    #[schemars(description = "Overlap handlers (e.g., 'player,coin -> collectCoin')")]
+    #[schemars(description = "Best saved score for this game (see the saveHighScore action)")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub on_overlap: Option<Vec<String>>,
+    pub best_score: Option<HudScore>,
 
-    #[schemars(description = "Timer events (e.g., 'every 2000ms -> spawnEnemy')")]
+    #[schemars(description = "Health bar bound to an object's health")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub timers: Option<Vec<String>>,
+    pub health_bar: Option<HudHealthBar>,
 
-    #[schemars(description = "Object spawners for dynamic object creation")]
+    #[schemars(description = "Remaining lives shown as icons")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub spawners: Option<Vec<Spawner>>,
+    pub lives: Option<HudLives>,
 
-    #[schemars(description = "Reusable action definitions")]
+    #[schemars(description = "Clickable speaker icon that toggles mute")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub actions: Option<Vec<ActionDefinition>>,
+    pub mute_button: Option<HudMuteButton>,
+
+    #[schemars(
+        description = "Time display (remaining time if the scene has a countdown, else elapsed)"
+    )]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timer: Option<HudTimer>,
+
+    #[schemars(description = "Extra text labels with live placeholders (e.g., 'Ammo: {ammo}')")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub labels: Option<Vec<HudLabel>>,
 }
 
-/// Scene definition
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
-pub struct Scene {
-    #[schemars(description = "Scene name/identifier")]
-    pub name: String,
+pub struct HudLabel {
+    #[schemars(description = "X position on screen")]
+    pub x: f32,
 
-    #[schemars(description = "Objects in this scene")]
-    pub objects: Vec<GameObject>,
+    #[schemars(description = "Y position on screen")]
+    pub y: f32,
 
-    #[schemars(description = "Custom game logic and interactions")]
+    #[schemars(description = "Label text with placeholders (e.g., 'Keys: {keys}/3')")]
+    pub text: String,
+
+    #[schemars(description = "Font size (e.g., '24px')")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub custom_logic: Option<CustomLogic>,
+    pub font_size: Option<String>,
+
+    #[schemars(description = "Text color")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fill: Option<String>,
 }
 
-/// Complete Phaser game specification
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
-pub struct PhaserGameSpec {
-    #[schemars(description = "Game title")]
-    pub title: String,
+pub struct HudScore {
+    #[schemars(description = "X position on screen")]
+    pub x: f32,
 
-    #[schemars(description = "Brief description of game mechanics and objective")]
-    pub description: String,
+    #[schemars(description = "Y position on screen")]
+    pub y: f32,
 
-    #[schemars(description = "Game configuration (canvas size, physics, etc.)")]
-    pub game: GameConfig,
+    #[schemars(description = "Label shown before the value (default: 'Score: ' or 'Best: ')")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
 
-    #[schemars(description = "Assets to load (can be empty for placeholder graphics)")]
-    #[serde(default)]
-    pub assets: Vec<Asset>,
+    #[schemars(description = "Font size (e.g., '24px')")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub font_size: Option<String>,
 
-    #[schemars(description = "Game scenes (at least one required)")]
+    #[schemars(description = "Text color")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fill: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct HudHealthBar {
+    #[schemars(description = "ID of the object whose health is shown (must define health)")]
+    pub object_id: String,
+
+    #[schemars(description = "X position on screen")]
+    pub x: f32,
+
+    #[schemars(description = "Y position on screen")]
+    pub y: f32,
+
+    #[schemars(description = "Bar width in pixels (default: 200)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<f32>,
+
+    #[schemars(description = "Bar height in pixels (default: 20)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<f32>,
+
+    #[schemars(description = "Bar fill color (default: '#00ff00')")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct HudLives {
+    #[schemars(description = "Starting number of lives (game over when they run out)")]
+    pub count: u32,
+
+    #[schemars(description = "Emoji used for each life (default: '❤️')")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+
+    #[schemars(description = "X position on screen")]
+    pub x: f32,
+
+    #[schemars(description = "Y position on screen")]
+    pub y: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct HudTimer {
+    #[schemars(description = "X position on screen")]
+    pub x: f32,
+
+    #[schemars(description = "Y position on screen")]
+    pub y: f32,
+
+    #[schemars(description = "Label shown before the value (default: 'Time: ')")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct HudMuteButton {
+    #[schemars(description = "X position on screen")]
+    pub x: f32,
+
+    #[schemars(description = "Y position on screen")]
+    pub y: f32,
+}
+
+/// Parameters for a level generated at load time
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ProceduralLevel {
+    #[schemars(description = "Random seed (omit for a different level every play)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u32>,
+
+    #[schemars(description = "Level length in pixels (match the camera world_width)")]
+    pub length: u32,
+
+    #[schemars(description = "Template for ground segments")]
+    pub platform: Box<GameObject>,
+
+    #[schemars(description = "Template for obstacles placed on the ground")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub obstacle: Option<Box<GameObject>>,
+
+    #[schemars(description = "Template for collectibles floating above the ground")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collectible: Option<Box<GameObject>>,
+
+    #[schemars(description = "Chance of an obstacle per ground segment (0-1, default: 0.2)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub obstacle_density: Option<f32>,
+
+    #[schemars(description = "Width range of gaps between ground segments in pixels")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gap_width: Option<GapRange>,
+
+    #[schemars(description = "Chance of a collectible per ground segment (0-1, default: 0.3)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collectible_frequency: Option<f32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GapRange {
+    #[schemars(description = "Narrowest gap in pixels")]
+    pub min: f32,
+
+    #[schemars(description = "Widest gap in pixels (keep it jumpable)")]
+    pub max: f32,
+}
+
+impl ProceduralLevel {
+    pub fn validate(&self) -> Result<(), String> {
+        for chance in [self.obstacle_density, self.collectible_frequency]
+            .into_iter()
+            .flatten()
+        {
+            if !(0.0..=1.0).contains(&chance) {
+                return Err(
+                    "obstacle_density and collectible_frequency must be between 0 and 1"
+                        .to_string(),
+                );
+            }
+        }
+        if let Some(gap) = &self.gap_width {
+            if gap.min < 0.0 || gap.max < gap.min {
+                return Err("gap_width must satisfy 0 <= min <= max".to_string());
+            }
+        }
+        if self.length == 0 {
+            return Err("length must be greater than 0".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Area that replaces world gravity for dynamic objects inside it
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GravityZone {
+    #[schemars(description = "Unique identifier for this zone")]
+    pub id: String,
+
+    #[schemars(description = "X position of the zone's center")]
+    pub x: f32,
+
+    #[schemars(description = "Y position of the zone's center")]
+    pub y: f32,
+
+    #[schemars(description = "Zone width in pixels")]
+    pub width: f32,
+
+    #[schemars(description = "Zone height in pixels")]
+    pub height: f32,
+
+    #[schemars(description = "Gravity inside the zone")]
+    pub gravity: GravityConfig,
+
+    #[schemars(description = "Tint color shown over the zone (e.g., '#0000ff' for water)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+}
+
+/// Scene countdown timer
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Countdown {
+    #[schemars(description = "Countdown length in seconds")]
+    pub seconds: u32,
+
+    #[schemars(description = "Whether to show the remaining time on screen (default: true)")]
+    #[serde(default = "default_true")]
+    pub display: bool,
+
+    #[schemars(
+        description = "Action to run when time runs out (e.g., 'gameOver' action or a win action)"
+    )]
+    pub on_expire: String,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Grid-based level definition
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Tilemap {
+    #[schemars(description = "Width and height of each tile in pixels")]
+    pub tile_size: u32,
+
+    #[schemars(
+        description = "Rows of the grid, top to bottom. Each character is one tile; '.' and ' ' are empty"
+    )]
+    pub rows: Vec<String>,
+
+    #[schemars(description = "Mapping from grid characters to object templates")]
+    pub legend: Vec<TileLegendEntry>,
+
+    #[schemars(description = "X offset of the grid's top-left corner (default: 0)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset_x: Option<f32>,
+
+    #[schemars(description = "Y offset of the grid's top-left corner (default: 0)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset_y: Option<f32>,
+}
+
+impl Tilemap {
+    /// Characters that never produce a tile
+    pub const EMPTY_TILES: [char; 2] = ['.', ' '];
+}
+
+/// Legend entry mapping a grid character to an object template
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TileLegendEntry {
+    #[schemars(description = "Single grid character (e.g., '=' for ground, 'C' for coin)")]
+    pub symbol: char,
+
+    #[schemars(
+        description = "Template object placed at each tile center (x/y are ignored, ids get a _row_col suffix)"
+    )]
+    pub template: Box<GameObject>,
+}
+
+/// Action effect types
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum ActionEffect {
+    #[schemars(description = "Update the score")]
+    UpdateScore { points: i32 },
+
+    #[schemars(description = "End the game and show the game-over screen")]
+    GameOver {
+        #[schemars(description = "Message shown on the game-over screen (default: 'GAME OVER')")]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        message: Option<String>,
+        #[schemars(description = "Text and background styling for the screen")]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        style: Option<ScreenStyle>,
+        #[schemars(description = "Whether to show the final score (default: true)")]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        show_score: Option<bool>,
+        #[schemars(
+            description = "Prompt telling the player how to retry (e.g., 'Press R to try again')"
+        )]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        restart_prompt: Option<String>,
+    },
+
+    #[schemars(description = "Win the game and show the victory screen")]
+    Win {
+        #[schemars(description = "Message shown on the victory screen (default: 'YOU WIN!')")]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        message: Option<String>,
+        #[schemars(description = "Text and background styling for the screen")]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        style: Option<ScreenStyle>,
+        #[schemars(description = "Stats listed under the message (default: score only)")]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        stats: Option<Vec<ScreenStat>>,
+        #[schemars(description = "Scene started by the next-level button (omit for no button)")]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        next_scene: Option<String>,
+        #[schemars(description = "Label of the next-level button (default: 'Next Level')")]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        next_button_text: Option<String>,
+    },
+
+    #[schemars(description = "Destroy the object")]
+    Destroy,
+
+    #[schemars(description = "Restart the current scene from the beginning")]
+    RestartScene,
+
+    #[schemars(description = "Save the current score to the game's local high-score table")]
+    SaveHighScore {
+        #[schemars(
+            description = "Ask the player for a name to show on the leaderboard (default: false)"
+        )]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        ask_name: Option<bool>,
+    },
+
+    #[schemars(description = "Update text content")]
+    UpdateText { object_id: String, text: String },
+
+    #[schemars(description = "Change a variable declared in custom_logic.variables")]
+    UpdateVariable {
+        #[schemars(description = "Variable name")]
+        name: String,
+        #[schemars(description = "Amount to add (negative to subtract)")]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        change: Option<i32>,
+        #[schemars(description = "Value to set (used instead of change)")]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        value: Option<i32>,
+    },
+
+    #[schemars(description = "Reduce an object's health")]
+    Damage {
+        #[schemars(description = "Health to remove")]
+        amount: u32,
+        #[schemars(
+            description = "Object to damage (default: the first object of the handler pair)"
+        )]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        object_id: Option<String>,
+    },
+
+    #[schemars(description = "Remove one life (game over when none remain)")]
+    LoseLife,
+
+    #[schemars(description = "Change an object's opacity")]
+    SetAlpha {
+        #[schemars(description = "New opacity (0-1)")]
+        alpha: f32,
+        #[schemars(
+            description = "Object to change (default: the first object of the handler pair)"
+        )]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        object_id: Option<String>,
+        #[schemars(description = "Fade duration in milliseconds (default: instant)")]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        duration: Option<u32>,
+    },
+
+    #[schemars(description = "Tint an object with a color, or clear its tint")]
+    SetTint {
+        #[schemars(description = "Tint color (hex string); omit to clear the tint")]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        color: Option<String>,
+        #[schemars(
+            description = "Object to change (default: the first object of the handler pair)"
+        )]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        object_id: Option<String>,
+        #[schemars(description = "How long the tint lasts in milliseconds (default: permanent)")]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        duration: Option<u32>,
+    },
+
+    #[schemars(description = "Rotate an object by an angle")]
+    Rotate {
+        #[schemars(description = "Degrees to rotate by (positive = clockwise)")]
+        degrees: f32,
+        #[schemars(
+            description = "Object to rotate (default: the first object of the handler pair)"
+        )]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        object_id: Option<String>,
+        #[schemars(description = "Tween duration in milliseconds (default: instant)")]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        duration: Option<u32>,
+    },
+
+    #[schemars(description = "Grow or shrink an object")]
+    Scale {
+        #[schemars(description = "Target scale (0.1-10, 1 = original size)")]
+        scale: f32,
+        #[schemars(
+            description = "Object to scale (default: the first object of the handler pair)"
+        )]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        object_id: Option<String>,
+        #[schemars(description = "Tween duration in milliseconds (default: instant)")]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        duration: Option<u32>,
+    },
+
+    #[schemars(description = "Save a respawn point at an object's current position")]
+    SetCheckpoint {
+        #[schemars(
+            description = "Object whose position is saved (default: the second object of the handler pair)"
+        )]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        object_id: Option<String>,
+    },
+
+    #[schemars(description = "Move an object back to the last checkpoint (or its start position)")]
+    RespawnAtCheckpoint {
+        #[schemars(description = "Object to respawn (default: 'player')")]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        object_id: Option<String>,
+    },
+
+    #[schemars(description = "Shake the camera")]
+    CameraShake {
+        #[schemars(description = "Duration in milliseconds (1-5000)")]
+        duration: u32,
+        #[schemars(description = "Shake strength (0.001-0.1, default: 0.01)")]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        intensity: Option<f32>,
+    },
+
+    #[schemars(description = "Flash the camera with a color")]
+    CameraFlash {
+        #[schemars(description = "Duration in milliseconds (1-5000)")]
+        duration: u32,
+        #[schemars(description = "Flash color (hex string, default: '#ffffff')")]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        color: Option<String>,
+    },
+
+    #[schemars(description = "Zoom the camera to a new level")]
+    CameraZoom {
+        #[schemars(description = "Target zoom level (0.25-4, 1 = normal)")]
+        zoom: f32,
+        #[schemars(description = "Transition duration in milliseconds (0-5000, default: 500)")]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        duration: Option<u32>,
+    },
+
+    #[schemars(description = "Resume a stopped spawner")]
+    StartSpawner {
+        #[schemars(description = "ID of the spawner to start")]
+        spawner_id: String,
+    },
+
+    #[schemars(description = "Stop a spawner from creating new objects")]
+    StopSpawner {
+        #[schemars(description = "ID of the spawner to stop")]
+        spawner_id: String,
+    },
+
+    #[schemars(description = "Change how often a spawner creates objects")]
+    SetSpawnerInterval {
+        #[schemars(description = "ID of the spawner to change")]
+        spawner_id: String,
+        #[schemars(description = "New spawn interval in milliseconds")]
+        interval: u32,
+    },
+
+    #[schemars(
+        description = "Cover the screen with a translucent color overlay that fades out (damage feedback, phase changes)"
+    )]
+    FlashScreen {
+        #[schemars(description = "Overlay color (hex string)")]
+        color: String,
+        #[schemars(description = "Fade-out duration in milliseconds (1-5000)")]
+        duration: u32,
+        #[schemars(description = "Starting overlay opacity (0-1, default: 0.5)")]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        alpha: Option<f32>,
+    },
+
+    #[schemars(description = "Change the scene's background color (day/night switches)")]
+    SetBackgroundColor {
+        #[schemars(description = "New background color (hex string)")]
+        color: String,
+        #[schemars(description = "Transition duration in milliseconds (default: instant)")]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        duration: Option<u32>,
+    },
+
+    #[schemars(description = "Slow down or speed up the whole game (slow motion, bullet time)")]
+    SetTimeScale {
+        #[schemars(description = "Game speed multiplier (0.1-4, 1 = normal, 0.3 = slow motion)")]
+        scale: f32,
+        #[schemars(
+            description = "Real-time milliseconds before returning to normal speed (1-30000, default: permanent)"
+        )]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        duration: Option<u32>,
+    },
+}
+
+/// Styling for full-screen overlays such as the game-over screen
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ScreenStyle {
+    #[schemars(description = "Font size of the message (e.g., '64px')")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub font_size: Option<String>,
+
+    #[schemars(description = "Message text color")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fill: Option<String>,
+
+    #[schemars(description = "Overlay background color (hex string)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background_color: Option<String>,
+
+    #[schemars(description = "Overlay background opacity (0-1, default: 0.7)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background_alpha: Option<f32>,
+}
+
+/// Stats that can be listed on an end screen
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ScreenStat {
+    Score,
+    Time,
+    Lives,
+}
+
+impl ScreenStyle {
+    fn validate(&self) -> Result<(), String> {
+        if self
+            .background_alpha
+            .is_some_and(|alpha| !(0.0..=1.0).contains(&alpha))
+        {
+            return Err("background_alpha must be between 0 and 1".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Placeholders every text can use without declaring a variable
+pub const BUILTIN_VARIABLES: [&str; 3] = ["score", "lives", "time"];
+
+/// Extract the `{name}` placeholders from a text template
+pub fn template_placeholders(text: &str) -> Vec<&str> {
+    let mut placeholders = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('{') {
+        let after = &rest[start + 1..];
+        let Some(end) = after.find('}') else {
+            break;
+        };
+        placeholders.push(after[..end].trim());
+        rest = &after[end + 1..];
+    }
+    placeholders
+}
+
+impl ActionEffect {
+    /// Check effect parameters against sane ranges
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            ActionEffect::CameraShake {
+                duration,
+                intensity,
+            } => {
+                check_duration(*duration, 1)?;
+                if intensity.is_some_and(|intensity| !(0.001..=0.1).contains(&intensity)) {
+                    return Err("shake intensity must be between 0.001 and 0.1".to_string());
+                }
+            }
+            ActionEffect::GameOver { style, .. } | ActionEffect::Win { style, .. } => {
+                style.as_ref().map_or(Ok(()), ScreenStyle::validate)?
+            }
+            ActionEffect::SetAlpha {
+                alpha, duration, ..
+            } => {
+                check_alpha(*alpha)?;
+                if let Some(duration) = duration {
+                    check_duration(*duration, 0)?;
+                }
+            }
+            ActionEffect::SetTint {
+                duration: Some(duration),
+                ..
+            } => check_duration(*duration, 1)?,
+            ActionEffect::Rotate {
+                duration: Some(duration),
+                ..
+            } => check_duration(*duration, 0)?,
+            ActionEffect::Scale {
+                scale, duration, ..
+            } => {
+                check_scale(*scale)?;
+                if let Some(duration) = duration {
+                    check_duration(*duration, 0)?;
+                }
+            }
+            ActionEffect::UpdateVariable { change, value, .. }
+                if change.is_some() == value.is_some() =>
+            {
+                return Err("updateVariable needs exactly one of change or value".to_string());
+            }
+            ActionEffect::CameraFlash { duration, .. } => check_duration(*duration, 1)?,
+            ActionEffect::CameraZoom { zoom, duration } => {
+                if !(0.25..=4.0).contains(zoom) {
+                    return Err("zoom must be between 0.25 and 4".to_string());
+                }
+                if let Some(duration) = duration {
+                    check_duration(*duration, 0)?;
+                }
+            }
+            ActionEffect::FlashScreen {
+                duration, alpha, ..
+            } => {
+                check_duration(*duration, 1)?;
+                if let Some(alpha) = alpha {
+                    check_alpha(*alpha)?;
+                }
+            }
+            ActionEffect::SetBackgroundColor {
+                duration: Some(duration),
+                ..
+            } => check_duration(*duration, 0)?,
+            ActionEffect::SetTimeScale { scale, duration } => {
+                if !(0.1..=4.0).contains(scale) {
+                    return Err("time scale must be between 0.1 and 4".to_string());
+                }
+                if duration.is_some_and(|duration| !(1..=30000).contains(&duration)) {
+                    return Err("time scale duration must be between 1ms and 30000ms".to_string());
+                }
+            }
+            ActionEffect::SetSpawnerInterval { interval: 0, .. } => {
+                return Err("spawner interval must be greater than 0".to_string());
+            }
+            _ => (),
+        }
+        Ok(())
+    }
+}
+
+fn check_alpha(alpha: f32) -> Result<(), String> {
+    if !(0.0..=1.0).contains(&alpha) {
+        return Err("alpha must be between 0 and 1".to_string());
+    }
+    Ok(())
+}
+
+fn check_scale(scale: f32) -> Result<(), String> {
+    if !(0.1..=10.0).contains(&scale) {
+        return Err("scale must be between 0.1 and 10".to_string());
+    }
+    Ok(())
+}
+
+fn check_duration(duration: u32, min: u32) -> Result<(), String> {
+    if !(min..=5000).contains(&duration) {
+        return Err(format!("duration must be between {}ms and 5000ms", min));
+    }
+    Ok(())
+}
+
+/// Action definition
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ActionDefinition {
+    #[schemars(description = "Action name to reference in callbacks")]
+    pub name: String,
+
+    #[schemars(description = "The effect this action performs")]
+    pub effect: ActionEffect,
+}
+
+/// Custom logic/interactions
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CustomLogic {
+    #[schemars(description = "Collision handlers (e.g., 'player,enemy -> gameOver')")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_collision: Option<Vec<String>>,
+
+    #[schemars(description = "Overlap handlers (e.g., 'player,coin -> collectCoin')")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_overlap: Option<Vec<String>>,
+
+    #[schemars(description = "Timer events (e.g., 'every 2000ms -> spawnEnemy')")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timers: Option<Vec<String>>,
+
+    #[schemars(description = "Object spawners for dynamic object creation")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spawners: Option<Vec<Spawner>>,
+
+    #[schemars(description = "Reusable action definitions")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actions: Option<Vec<ActionDefinition>>,
+
+    #[schemars(
+        description = "Named numeric variables that text can display with {name} placeholders"
+    )]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variables: Option<Vec<Variable>>,
+
+    #[schemars(description = "Linked portal pairs that teleport objects both ways")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub portals: Option<Vec<PortalPair>>,
+
+    #[schemars(
+        description = "Keyboard triggers that run an action when a key is pressed (e.g., 'E' to open a door)"
+    )]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_key_press: Option<Vec<KeyTrigger>>,
+
+    #[schemars(
+        description = "Actions run in order when the scene starts (show instructions, set up variables, start effects)"
+    )]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_start: Option<Vec<String>>,
+
+    #[schemars(
+        description = "Triggers that run an action when an object or group member is destroyed"
+    )]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_destroyed: Option<Vec<DestroyTrigger>>,
+
+    #[schemars(
+        description = "Triggers that run an action when the score reaches a threshold (ramp difficulty, spawn a boss, win)"
+    )]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_score_reached: Option<Vec<ScoreTrigger>>,
+
+    #[schemars(
+        description = "Rules for how collision groups interact; groups without a rule pass through each other"
+    )]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collision_matrix: Option<Vec<CollisionRule>>,
+}
+
+/// How two collision groups interact
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CollisionRule {
+    #[schemars(description = "First collision group")]
+    pub a: String,
+
+    #[schemars(description = "Second collision group (may equal the first)")]
+    pub b: String,
+
+    #[schemars(description = "Interaction between the groups (default: collide)")]
+    #[serde(default)]
+    pub mode: CollisionMode,
+
+    #[schemars(
+        description = "Action to run when members touch (the member of group a is the first object)"
+    )]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub action: Option<String>,
+}
+
+/// Collision matrix interaction modes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum CollisionMode {
+    /// Bodies push each other apart
+    #[default]
+    Collide,
+    /// Bodies pass through but still trigger the action
+    Overlap,
+    /// No interaction, even if an on_collision/on_overlap handler pairs them
+    Ignore,
+}
+
+/// Action fired when the score crosses a threshold
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ScoreTrigger {
+    #[schemars(description = "Score that fires the action")]
+    pub at: u32,
+
+    #[schemars(description = "Name of the action to run")]
+    pub action: String,
+
+    #[schemars(
+        description = "Fire again every `at` points (at 100, 200, 300, ...) instead of once (default: false)"
+    )]
+    #[serde(default)]
+    pub repeat: bool,
+}
+
+/// Action fired when an object, a group member, or a whole group is destroyed
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DestroyTrigger {
+    #[schemars(
+        description = "Object ID or group name (spawner/tilemap template ID or 'projectiles') to watch"
+    )]
+    pub target: String,
+
+    #[schemars(description = "Name of the action to run")]
+    pub action: String,
+
+    #[schemars(
+        description = "Only fire once every member of the group has been destroyed (default: false)"
+    )]
+    #[serde(default)]
+    pub all: bool,
+}
+
+/// Keyboard key that fires an action, independent of movement controls
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct KeyTrigger {
+    #[schemars(description = "Key name (e.g., 'E', 'SPACE', 'ENTER', 'ArrowUp')")]
+    pub key: String,
+
+    #[schemars(description = "Name of the action to run when the key is pressed")]
+    pub action: String,
+}
+
+/// Numeric game variable (ammo, keys collected, level, ...)
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Variable {
+    #[schemars(description = "Variable name used in placeholders and updateVariable actions")]
+    pub name: String,
+
+    #[schemars(description = "Starting value")]
+    #[serde(default)]
+    pub initial: i32,
+}
+
+/// Two objects that teleport dynamic objects between each other
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PortalPair {
+    #[schemars(description = "ID of the first portal object")]
+    pub a: String,
+
+    #[schemars(description = "ID of the second portal object")]
+    pub b: String,
+
+    #[schemars(
+        description = "Milliseconds before the same object can teleport again (default: 500)"
+    )]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cooldown: Option<u32>,
+
+    #[schemars(description = "Keep the traveler's velocity after teleporting (default: true)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preserve_velocity: Option<bool>,
+}
+
+/// Scene definition
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Scene {
+    #[schemars(description = "Scene name/identifier")]
+    pub name: String,
+
+    #[schemars(description = "Objects in this scene")]
+    pub objects: Vec<GameObject>,
+
+    #[schemars(description = "Custom game logic and interactions")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub custom_logic: Option<CustomLogic>,
+
+    #[schemars(description = "Grid-based level layout expanded into objects at load time")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tilemap: Option<Tilemap>,
+
+    #[schemars(
+        description = "Camera and world size configuration (for levels larger than the canvas)"
+    )]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub camera: Option<CameraConfig>,
+
+    #[schemars(
+        description = "Heads-up display (score, health, lives, timer) that updates automatically"
+    )]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hud: Option<HudConfig>,
+
+    #[schemars(description = "Countdown that fires an action when it reaches zero")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub countdown: Option<Countdown>,
+
+    #[schemars(
+        description = "Procedurally generated level layout (for endless runners and replayable levels)"
+    )]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub procedural: Option<ProceduralLevel>,
+
+    #[schemars(
+        description = "Rectangular areas with their own gravity (underwater, low-gravity, reversed)"
+    )]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gravity_zones: Option<Vec<GravityZone>>,
+}
+
+/// Badge unlocked the first time its condition holds during play
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Achievement {
+    #[schemars(description = "Unique identifier for this achievement")]
+    pub id: String,
+
+    #[schemars(description = "Name shown when the achievement is unlocked")]
+    pub name: String,
+
+    #[schemars(description = "What the player did to earn it")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    #[schemars(description = "Emoji shown as the badge icon (default: '🏆')")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+
+    #[schemars(
+        description = "Condition comparing variables to numbers, joined with && (e.g., 'score >= 100 && lives == 3')"
+    )]
+    pub condition: String,
+}
+
+/// Comparison operators allowed in achievement conditions, longest first
+const CONDITION_OPERATORS: [&str; 6] = [">=", "<=", "==", "!=", ">", "<"];
+
+/// Parse a condition like `score >= 100 && coins == 10`, returning the variables it reads
+pub fn condition_variables(condition: &str) -> Result<Vec<&str>, String> {
+    condition
+        .split("&&")
+        .map(|clause| {
+            let clause = clause.trim();
+            let (name, value) = CONDITION_OPERATORS
+                .iter()
+                .find_map(|operator| clause.split_once(operator))
+                .ok_or_else(|| format!("'{}' must compare a variable to a number", clause))?;
+            let name = name.trim();
+            if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                return Err(format!("'{}' must start with a variable name", clause));
+            }
+            if value.trim().parse::<f64>().is_err() {
+                return Err(format!("'{}' must compare against a number", clause));
+            }
+            Ok(name)
+        })
+        .collect()
+}
+
+/// Current version of the spec schema. Bump it (and add a migration in
+/// `spec_migration`) whenever the shape of stored specs changes.
+pub const CURRENT_SCHEMA_VERSION: u32 = 2;
+
+fn current_schema_version() -> u32 {
+    CURRENT_SCHEMA_VERSION
+}
+
+/// Complete Phaser game specification
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PhaserGameSpec {
+    /// Filled in by the backend; stored specs without it are migrated on load
+    #[schemars(skip)]
+    #[serde(default = "current_schema_version")]
+    pub schema_version: u32,
+
+    #[schemars(description = "Game title")]
+    pub title: String,
+
+    #[schemars(description = "Brief description of game mechanics and objective")]
+    pub description: String,
+
+    #[schemars(description = "Game configuration (canvas size, physics, etc.)")]
+    pub game: GameConfig,
+
+    #[schemars(description = "Assets to load (can be empty for placeholder graphics)")]
+    #[serde(default)]
+    pub assets: Vec<Asset>,
+
+    #[schemars(description = "Game scenes (at least one required)")]
     pub scenes: Vec<Scene>,
 
+    #[schemars(description = "Badges awarded when a condition is met, remembered across sessions")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub achievements: Option<Vec<Achievement>>,
+
     #[schemars(description = "Player controls description for documentation")]
     pub controls_description: Vec<String>,
 
@@ -667,16 +2512,218 @@ pub struct PhaserGameSpec {
     pub key_concepts: Vec<String>,
 }
 
+impl GameObject {
+    /// Check object properties against sane ranges
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(alpha) = self.alpha {
+            check_alpha(alpha)?;
+        }
+        if let Some(scale) = self.scale {
+            check_scale(scale)?;
+        }
+        if matches!(self.object_type, ObjectType::Hazard) && self.hazard.is_none() {
+            return Err("hazard objects must define hazard properties".to_string());
+        }
+        if matches!(self.object_type, ObjectType::Zone) {
+            let zone = self
+                .zone
+                .as_ref()
+                .ok_or("zone objects must define zone properties")?;
+            if zone.on_enter.is_none() && zone.on_exit.is_none() {
+                return Err("zones need an on_enter or on_exit action".to_string());
+            }
+            if zone.width <= 0.0 || zone.height <= 0.0 {
+                return Err("zones must have a positive size".to_string());
+            }
+        }
+        if let Some(physics) = &self.physics {
+            if physics.one_way == Some(true) && !matches!(physics.body, PhysicsBody::Static) {
+                return Err("one_way platforms must have a static body".to_string());
+            }
+            if physics.wrap_world == Some(true) && physics.collide_world_bounds == Some(true) {
+                return Err(
+                    "wrap_world and collide_world_bounds cannot both be enabled".to_string()
+                );
+            }
+            if physics.mass.is_some_and(|mass| mass <= 0.0) {
+                return Err("mass must be positive".to_string());
+            }
+            if physics.drag.is_some_and(|drag| drag < 0.0) {
+                return Err("drag cannot be negative".to_string());
+            }
+            if physics.max_velocity.is_some_and(|max| max <= 0.0) {
+                return Err("max_velocity must be positive".to_string());
+            }
+            match &physics.surface {
+                Some(_) if !matches!(physics.body, PhysicsBody::Static) => {
+                    return Err("surfaces can only be set on static bodies".to_string());
+                }
+                Some(Surface::Ice {
+                    friction: Some(friction),
+                }) if !(0.0..=1.0).contains(friction) => {
+                    return Err("ice friction must be between 0 and 1".to_string());
+                }
+                Some(Surface::Bounce { impulse }) if *impulse <= 0.0 => {
+                    return Err("bounce impulse must be positive".to_string());
+                }
+                _ => (),
+            }
+        }
+        let layers = self
+            .emoji
+            .iter()
+            .flat_map(|emoji| emoji.layers.iter().flatten());
+        for layer in layers {
+            if layer.scale.is_some_and(|scale| scale <= 0.0) {
+                return Err(format!(
+                    "layer '{}' must have a positive scale",
+                    layer.emoji
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Push this object and any projectile template it fires
+    fn collect_into<'a>(&'a self, objects: &mut Vec<&'a GameObject>) {
+        objects.push(self);
+        if let Some(projectile) = self
+            .controls
+            .as_ref()
+            .and_then(|controls| controls.projectile.as_deref())
+        {
+            projectile.collect_into(objects);
+        }
+    }
+}
+
+impl Spawner {
+    /// Most objects from this spawner alive at once, if a pool or `max_count` bounds it
+    pub fn max_live(&self) -> Option<u32> {
+        match (&self.pool, self.max_count) {
+            (Some(pool), Some(max_count)) => Some(pool.max_live.min(max_count)),
+            (Some(pool), None) => Some(pool.max_live),
+            (None, max_count) => max_count,
+        }
+    }
+}
+
+impl Scene {
+    /// Every object in the scene, including projectile, spawner, and tilemap templates
+    pub fn all_objects(&self) -> Vec<&GameObject> {
+        let mut objects = Vec::new();
+        for object in &self.objects {
+            object.collect_into(&mut objects);
+        }
+        let spawners = self
+            .custom_logic
+            .iter()
+            .flat_map(|logic| logic.spawners.iter().flatten());
+        for spawner in spawners {
+            spawner.template.collect_into(&mut objects);
+        }
+        for entry in self.tilemap.iter().flat_map(|tilemap| &tilemap.legend) {
+            entry.template.collect_into(&mut objects);
+        }
+        if let Some(procedural) = &self.procedural {
+            procedural.platform.collect_into(&mut objects);
+            for template in [&procedural.obstacle, &procedural.collectible]
+                .into_iter()
+                .flatten()
+            {
+                template.collect_into(&mut objects);
+            }
+        }
+        objects
+    }
+}
+
+impl PhaserGameSpec {
+    /// Keys of local library assets used by this spec, either through an asset's
+    /// `library_key` or an object texture that doesn't match a declared asset
+    pub fn library_asset_keys(&self) -> HashSet<String> {
+        let mut keys: HashSet<String> = self
+            .assets
+            .iter()
+            .filter_map(|asset| asset.library_key.clone())
+            .collect();
+        let textures = self
+            .scenes
+            .iter()
+            .flat_map(|scene| scene.all_objects())
+            .filter_map(|object| object.texture.as_ref());
+        for texture in textures {
+            if !self.assets.iter().any(|asset| &asset.key == texture) {
+                keys.insert(texture.clone());
+            }
+        }
+        keys
+    }
+}
+
+/// Latest spec of the game being built, shared by the tools so patches apply
+/// on top of whatever the previous tool call produced
+pub type CurrentSpec = Arc<Mutex<Option<PhaserGameSpec>>>;
+
 /// Tool for generating Phaser games
+#[derive(Debug, Clone)]
+pub struct PhaserGameTool {
+    budget: validator::PerformanceBudget,
+    current: CurrentSpec,
+}
+
+/// Tool for changing the current game through a patch instead of a full spec
+#[derive(Debug, Clone)]
+pub struct PatchGameTool {
+    budget: validator::PerformanceBudget,
+    current: CurrentSpec,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct PatchGameArgs {
+    #[schemars(description = "Edits applied in order to the current game spec")]
+    pub operations: Vec<spec_patch::PatchOperation>,
+}
+
+/// Tool output: the spec, plus validator warnings for the model to act on
 #[derive(Debug, Serialize, Deserialize)]
-pub struct PhaserGameTool;
+pub struct GeneratedGame {
+    #[serde(flatten)]
+    pub spec: PhaserGameSpec,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+/// Reject specs with validation errors, and make a valid spec the current one
+fn accept_spec(
+    spec: PhaserGameSpec,
+    budget: &validator::PerformanceBudget,
+    current: &CurrentSpec,
+) -> Result<GeneratedGame, GameBuilderError> {
+    let (errors, warnings): (Vec<_>, Vec<_>) = validator::validate_spec(&spec)
+        .into_iter()
+        .chain(validator::check_budget(&spec, budget))
+        .partition(|diagnostic| diagnostic.severity == validator::Severity::Error);
+    if !errors.is_empty() {
+        let errors: Vec<String> = errors.iter().map(ToString::to_string).collect();
+        return Err(GameBuilderError::InvalidConfiguration(errors.join("; ")));
+    }
+
+    *current
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(spec.clone());
+    Ok(GeneratedGame {
+        spec,
+        warnings: warnings.iter().map(ToString::to_string).collect(),
+    })
+}
 
 impl Tool for PhaserGameTool {
     const NAME: &'static str = "generate_phaser_game";
 
     type Error = GameBuilderError;
     type Args = PhaserGameSpec;
-    type Output = PhaserGameSpec;
+    type Output = GeneratedGame;
 
     async fn definition(&self, _prompt: String) -> ToolDefinition {
         let schema = schemars::schema_for!(PhaserGameSpec);
@@ -695,24 +2742,70 @@ impl Tool for PhaserGameTool {
     }
 
     async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
-        // Validate the game spec
-        if args.scenes.is_empty() {
-            return Err(GameBuilderError::InvalidConfiguration(
-                "Game must have at least one scene".to_string(),
-            ));
-        }
+        accept_spec(args, &self.budget, &self.current)
+    }
+}
 
-        if args.game.width == 0 || args.game.height == 0 {
-            return Err(GameBuilderError::InvalidConfiguration(
-                "Game dimensions must be greater than 0".to_string(),
-            ));
+impl Tool for PatchGameTool {
+    const NAME: &'static str = "patch_phaser_game";
+
+    type Error = GameBuilderError;
+    type Args = PatchGameArgs;
+    type Output = GeneratedGame;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        let schema = schemars::schema_for!(PatchGameArgs);
+
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description:
+                "Change the current game with JSON Patch operations (add, remove, replace) \
+                         instead of regenerating the whole spec. Paths are JSON Pointers into the \
+                         current spec; array elements can be addressed by index or by their id or \
+                         name, e.g. /scenes/main/objects/player/physics/velocity/x. Returns the \
+                         complete patched spec."
+                    .to_string(),
+            parameters: serde_json::to_value(schema).expect("Failed to serialize schema"),
         }
+    }
 
-        Ok(args)
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let current = self
+            .current
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+            .ok_or(GameBuilderError::NoCurrentGame)?;
+        let patched = spec_patch::apply_patch(&current, &args.operations)?;
+        accept_spec(patched, &self.budget, &self.current)
     }
 }
 
-/// Create an instance of the Phaser game tool
-pub fn create_phaser_game_tool() -> PhaserGameTool {
-    PhaserGameTool
+/// Create an instance of the Phaser game tool that warns about specs over `budget`
+pub fn create_phaser_game_tool(
+    budget: validator::PerformanceBudget,
+    current: CurrentSpec,
+) -> PhaserGameTool {
+    PhaserGameTool { budget, current }
+}
+
+/// Create an instance of the patch tool, editing `current`
+pub fn create_patch_game_tool(
+    budget: validator::PerformanceBudget,
+    current: CurrentSpec,
+) -> PatchGameTool {
+    PatchGameTool { budget, current }
+}
+
+/// Prompt section showing the model the game it is iterating on
+pub fn current_game_prompt(spec: &PhaserGameSpec) -> String {
+    let json = serde_json::to_string(spec).unwrap_or_default();
+    format!(
+        "## Current game\n\nThis is the spec of the game the user is working on. \
+         To change it, call `patch_phaser_game` with just the edits instead of sending \
+         the whole spec to `generate_phaser_game` again. A patch leaves the rest of the \
+         game untouched, so nothing gets dropped by accident. Only regenerate the whole \
+         spec when rebuilding the game from scratch.\n\n```json\n{}\n```",
+        json
+    )
 }
@@ -0,0 +1,446 @@
+/// Printable one-page handout describing a game: what it is, how to play,
+/// what it teaches, and what's in it. Written as HTML with print styles, so
+/// it can be printed or saved as PDF from any browser.
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use std::fmt::Write;
+use std::path::Path;
+
+use crate::db::{Database, DbError};
+use crate::game_builder::{
+    ActionEffect, BehaviorType, CustomLogic, GameObject, HazardKind, ObjectType, PhaserGameSpec,
+    PhysicsBody, Scene,
+};
+use crate::standalone::escape_html;
+use crate::validator;
+
+#[derive(Debug, thiserror::Error)]
+pub enum GameDocsError {
+    #[error("Database error: {0}")]
+    Db(#[from] DbError),
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Write the handout for a game, with its latest thumbnail if it has one
+pub async fn export_game_docs(db: &Database, id: &str, path: &Path) -> Result<(), GameDocsError> {
+    let game = db.get_game(id).await?;
+    let thumbnail = db.get_game_thumbnail(id, None).await?;
+    let path = if path.extension().is_none() {
+        path.with_extension("html")
+    } else {
+        path.to_path_buf()
+    };
+    tokio::fs::write(path, render_game_docs(&game.spec, thumbnail.as_deref())).await?;
+    Ok(())
+}
+
+/// Handout HTML for a spec; `thumbnail` is a PNG screenshot of the game
+pub fn render_game_docs(spec: &PhaserGameSpec, thumbnail: Option<&[u8]>) -> String {
+    let mut body = String::new();
+    let _ = writeln!(body, "<h1>{}</h1>", escape_html(&spec.title));
+    let _ = writeln!(
+        body,
+        "<p class=\"description\">{}</p>",
+        escape_html(&spec.description)
+    );
+    if let Some(png) = thumbnail {
+        let _ = writeln!(
+            body,
+            "<img class=\"screenshot\" src=\"data:image/png;base64,{}\" alt=\"Screenshot of {}\">",
+            BASE64.encode(png),
+            escape_html(&spec.title)
+        );
+    }
+
+    body.push_str("<div class=\"columns\">\n");
+    body.push_str(&section("How to play", &spec.controls_description));
+    body.push_str(&section("What it teaches", &spec.key_concepts));
+    body.push_str("</div>\n");
+
+    for scene in &spec.scenes {
+        // Single-scene games don't need the scene's internal name
+        let heading = if spec.scenes.len() > 1 {
+            format!("In the game: {}", scene.name)
+        } else {
+            "In the game".to_string()
+        };
+        let _ = writeln!(body, "<h2>{}</h2>", escape_html(&heading));
+        body.push_str(&objects_table(scene));
+        body.push_str(&list("Rules", &scene_rules(scene)));
+    }
+
+    let achievements: Vec<String> = spec
+        .achievements
+        .iter()
+        .flatten()
+        .map(|achievement| {
+            let icon = achievement.icon.as_deref().unwrap_or("🏆");
+            match &achievement.description {
+                Some(description) => format!("{} {}: {}", icon, achievement.name, description),
+                None => format!("{} {}", icon, achievement.name),
+            }
+        })
+        .collect();
+    body.push_str(&section("Achievements", &achievements));
+
+    render_page(&spec.title, &body)
+}
+
+/// Heading and bullet list, or nothing if there are no items
+fn section(heading: &str, items: &[String]) -> String {
+    if items.is_empty() {
+        return String::new();
+    }
+    format!(
+        "<section>\n<h2>{}</h2>\n{}</section>\n",
+        heading,
+        bullets(items)
+    )
+}
+
+/// Like `section`, one level down
+fn list(heading: &str, items: &[String]) -> String {
+    if items.is_empty() {
+        return String::new();
+    }
+    format!("<h3>{}</h3>\n{}", heading, bullets(items))
+}
+
+fn bullets(items: &[String]) -> String {
+    let mut html = String::from("<ul>\n");
+    for item in items {
+        let _ = writeln!(html, "<li>{}</li>", escape_html(item));
+    }
+    html.push_str("</ul>\n");
+    html
+}
+
+/// Table of the objects placed in a scene and the ones that appear during play
+fn objects_table(scene: &Scene) -> String {
+    let mut rows: Vec<(&GameObject, String)> = scene
+        .objects
+        .iter()
+        .map(|object| (object, object_role(object, scene)))
+        .collect();
+    for spawner in scene
+        .custom_logic
+        .iter()
+        .flat_map(|logic| logic.spawners.iter().flatten())
+    {
+        let role = format!(
+            "{}, appears every {}",
+            object_role(&spawner.template, scene),
+            seconds(spawner.interval)
+        );
+        rows.push((&spawner.template, role));
+    }
+    for entry in scene.tilemap.iter().flat_map(|tilemap| &tilemap.legend) {
+        let role = format!(
+            "{}, placed around the level",
+            object_role(&entry.template, scene)
+        );
+        rows.push((&entry.template, role));
+    }
+    if rows.is_empty() {
+        return String::new();
+    }
+
+    let mut html = String::from(
+        "<table>\n<thead><tr><th>Object</th><th>Looks like</th><th>What it does</th></tr></thead>\n<tbody>\n",
+    );
+    for (object, role) in rows {
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td>{}</td><td>{}</td></tr>",
+            escape_html(&object.id),
+            escape_html(&appearance(object)),
+            escape_html(&role)
+        );
+    }
+    html.push_str("</tbody>\n</table>\n");
+    html
+}
+
+fn appearance(object: &GameObject) -> String {
+    match object.object_type {
+        ObjectType::Emoji => object
+            .emoji
+            .as_ref()
+            .map(|emoji| emoji.emoji.clone())
+            .unwrap_or_default(),
+        ObjectType::Text => object
+            .text
+            .as_ref()
+            .map(|text| format!("\"{}\"", text.text))
+            .unwrap_or_default(),
+        ObjectType::Sprite => object
+            .texture
+            .clone()
+            .unwrap_or_else(|| "picture".to_string()),
+        ObjectType::Rectangle => "rectangle".to_string(),
+        ObjectType::Circle => "circle".to_string(),
+        ObjectType::Group => "group".to_string(),
+        ObjectType::Hazard => match object.hazard.as_ref().map(|hazard| &hazard.kind) {
+            Some(HazardKind::Spikes) => "spikes".to_string(),
+            Some(HazardKind::Saw) => "saw blade".to_string(),
+            Some(HazardKind::Lava) => "lava".to_string(),
+            None => "hazard".to_string(),
+        },
+        ObjectType::Zone => "invisible area".to_string(),
+    }
+}
+
+fn object_role(object: &GameObject, scene: &Scene) -> String {
+    let role = if object.controls.is_some() {
+        Some("You control it")
+    } else if let Some(behavior) = &object.behavior {
+        Some(match behavior {
+            BehaviorType::Patrol => "Moves back and forth",
+            BehaviorType::Follow => "Chases you",
+            BehaviorType::Random => "Wanders around",
+            BehaviorType::Static => "Stays in place",
+        })
+    } else {
+        match object.object_type {
+            ObjectType::Hazard => Some("Hurts you if you touch it"),
+            ObjectType::Zone => Some("Something happens when you walk into it"),
+            ObjectType::Text => Some("Shows information"),
+            _ if object.on_click.is_some() => Some("Click it"),
+            _ if object.checkpoint == Some(true) => Some("Checkpoint: you come back here"),
+            _ => None,
+        }
+    };
+    match (role, touch_effect(object, scene)) {
+        (Some(role), Some(effect)) => format!("{}; touch it: {}", role, effect),
+        (Some(role), None) => role.to_string(),
+        (None, Some(effect)) => format!("Touch it: {}", effect),
+        (None, None) => match object.physics.as_ref().map(|physics| &physics.body) {
+            Some(PhysicsBody::Static) => "Solid ground or wall".to_string(),
+            Some(PhysicsBody::Dynamic) => "Moves with physics".to_string(),
+            _ => "Scenery".to_string(),
+        },
+    }
+}
+
+/// What happens when the player touches the object, if a handler pairs them
+fn touch_effect(object: &GameObject, scene: &Scene) -> Option<String> {
+    let logic = scene.custom_logic.as_ref()?;
+    let is_player = |id: &str| {
+        scene
+            .objects
+            .iter()
+            .any(|object| object.id == id && object.controls.is_some())
+    };
+    logic
+        .on_collision
+        .iter()
+        .chain(&logic.on_overlap)
+        .flatten()
+        .filter_map(|handler| validator::parse_handler(handler))
+        .filter(|(_, _, callback)| *callback != "null")
+        .find(|(first, second, _)| {
+            (is_player(first) && *second == object.id) || (is_player(second) && *first == object.id)
+        })
+        .map(|(_, _, callback)| describe_action(logic, callback))
+}
+
+/// Plain-language rules: what happens when things touch, over time, and on keys
+fn scene_rules(scene: &Scene) -> Vec<String> {
+    let Some(logic) = &scene.custom_logic else {
+        return scene
+            .countdown
+            .iter()
+            .map(|countdown| format!("You have {} seconds", countdown.seconds))
+            .collect();
+    };
+    let action = |name: &str| describe_action(logic, name);
+
+    let mut rules = Vec::new();
+    for handler in logic.on_collision.iter().chain(&logic.on_overlap).flatten() {
+        match validator::parse_handler(handler) {
+            // "null" handlers only make things solid to each other
+            Some((_, _, "null")) | None => {}
+            Some((first, second, callback)) => rules.push(format!(
+                "When {} and {} touch: {}",
+                first,
+                second,
+                action(callback)
+            )),
+        }
+    }
+    for trigger in logic.on_key_press.iter().flatten() {
+        rules.push(format!(
+            "Press {}: {}",
+            trigger.key,
+            action(&trigger.action)
+        ));
+    }
+    for object in &scene.objects {
+        if let Some(callback) = &object.on_click {
+            rules.push(format!("Click {}: {}", object.id, action(callback)));
+        }
+    }
+    for timer in logic.timers.iter().flatten() {
+        if let Some((delay, callback)) = validator::parse_timer(timer) {
+            let when = if timer.trim_start().starts_with("every") {
+                "Every"
+            } else {
+                "After"
+            };
+            rules.push(format!("{} {}: {}", when, seconds(delay), action(callback)));
+        }
+    }
+    for trigger in logic.on_destroyed.iter().flatten() {
+        let target = if trigger.all {
+            format!("all of {} are gone", trigger.target)
+        } else {
+            format!("{} is destroyed", trigger.target)
+        };
+        rules.push(format!("When {}: {}", target, action(&trigger.action)));
+    }
+    for trigger in logic.on_score_reached.iter().flatten() {
+        let when = if trigger.repeat { "Every" } else { "At" };
+        rules.push(format!(
+            "{} {} points: {}",
+            when,
+            trigger.at,
+            action(&trigger.action)
+        ));
+    }
+    if let Some(countdown) = &scene.countdown {
+        rules.push(format!(
+            "You have {} seconds, then: {}",
+            countdown.seconds,
+            action(&countdown.on_expire)
+        ));
+    }
+    rules
+}
+
+/// What a named action does, or its name if it isn't defined
+fn describe_action(logic: &CustomLogic, name: &str) -> String {
+    logic
+        .actions
+        .iter()
+        .flatten()
+        .find(|action| action.name == name)
+        .map(|action| describe_effect(logic, &action.effect))
+        .unwrap_or_else(|| match name {
+            "gameOver" => "game over".to_string(),
+            "destroy" => "it disappears".to_string(),
+            name => split_camel_case(name),
+        })
+}
+
+fn describe_effect(logic: &CustomLogic, effect: &ActionEffect) -> String {
+    // Spawners are named after what they spawn
+    let spawned = |spawner_id: &str| {
+        logic
+            .spawners
+            .iter()
+            .flatten()
+            .find(|spawner| spawner.id == spawner_id)
+            .map_or(spawner_id, |spawner| spawner.template.id.as_str())
+            .to_string()
+    };
+    match effect {
+        ActionEffect::UpdateScore { points } if *points >= 0 => format!("+{} points", points),
+        ActionEffect::UpdateScore { points } => format!("{} points", points),
+        ActionEffect::GameOver { .. } => "game over".to_string(),
+        ActionEffect::Win { .. } => "you win".to_string(),
+        ActionEffect::Destroy => "it disappears".to_string(),
+        ActionEffect::RestartScene => "the level starts over".to_string(),
+        ActionEffect::SaveHighScore { .. } => "your high score is saved".to_string(),
+        ActionEffect::UpdateVariable {
+            name,
+            change: Some(change),
+            ..
+        } if *change >= 0 => format!("+{} {}", change, name),
+        ActionEffect::UpdateVariable {
+            name,
+            change: Some(change),
+            ..
+        } => format!("{} {}", change, name),
+        ActionEffect::UpdateVariable {
+            name,
+            value: Some(value),
+            ..
+        } => format!("{} becomes {}", name, value),
+        ActionEffect::Damage { amount, .. } => format!("lose {} health", amount),
+        ActionEffect::LoseLife => "lose a life".to_string(),
+        ActionEffect::StartSpawner { spawner_id } => {
+            format!("{} starts appearing", spawned(spawner_id))
+        }
+        ActionEffect::StopSpawner { spawner_id } => {
+            format!("{} stops appearing", spawned(spawner_id))
+        }
+        ActionEffect::SetSpawnerInterval {
+            spawner_id,
+            interval,
+        } => format!(
+            "{} now appears every {}",
+            spawned(spawner_id),
+            seconds(*interval)
+        ),
+        _ => split_camel_case(&effect_name(effect)),
+    }
+}
+
+fn effect_name(effect: &ActionEffect) -> String {
+    serde_json::to_value(effect)
+        .ok()
+        .and_then(|value| value.get("type")?.as_str().map(String::from))
+        .unwrap_or_default()
+}
+
+/// "cameraShake" -> "camera shake"
+fn split_camel_case(name: &str) -> String {
+    let mut words = String::new();
+    for c in name.chars() {
+        if c.is_ascii_uppercase() {
+            words.push(' ');
+        }
+        words.push(c.to_ascii_lowercase());
+    }
+    words
+}
+
+fn seconds(ms: u32) -> String {
+    match ms {
+        1000 => "1 second".to_string(),
+        ms if ms % 1000 == 0 => format!("{} seconds", ms / 1000),
+        ms => format!("{} seconds", ms as f32 / 1000.0),
+    }
+}
+
+fn render_page(title: &str, body: &str) -> String {
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>{title}</title>
+<style>
+@page {{ margin: 15mm; }}
+body {{ font-family: system-ui, sans-serif; color: #222; max-width: 180mm; margin: 0 auto; padding: 10mm 0; line-height: 1.4; }}
+h1 {{ margin: 0 0 4px; font-size: 26pt; }}
+h2 {{ font-size: 15pt; border-bottom: 2px solid #ddd; padding-bottom: 2px; margin-top: 18px; }}
+h3 {{ font-size: 12pt; margin-bottom: 4px; }}
+.description {{ font-size: 13pt; margin-top: 0; }}
+.screenshot {{ display: block; max-width: 100%; max-height: 70mm; margin: 8px auto; border: 1px solid #ccc; }}
+.columns {{ display: flex; gap: 24px; }}
+.columns section {{ flex: 1; }}
+table {{ width: 100%; border-collapse: collapse; font-size: 10.5pt; }}
+th, td {{ text-align: left; border: 1px solid #ccc; padding: 3px 6px; }}
+th {{ background: #f2f2f2; }}
+section, table, tr {{ break-inside: avoid; }}
+</style>
+</head>
+<body>
+{body}</body>
+</html>
+"#,
+        title = escape_html(title),
+    )
+}
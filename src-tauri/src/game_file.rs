@@ -0,0 +1,651 @@
+/// Portable `.pueogame` files for sharing a game with everything it needs
+use base64::engine::general_purpose::{STANDARD as BASE64, URL_SAFE_NO_PAD as BASE64_URL};
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use crate::assets::{AssetError, AssetLibrary};
+use crate::db::{Database, DbError, GameListFilter, GameRecord, GameStatus, GameVersion};
+use crate::game_builder::{AssetType, PhaserGameSpec};
+use crate::spec_migration::{self, MigrationError};
+use crate::validator;
+
+#[derive(Debug, thiserror::Error)]
+pub enum GameFileError {
+    #[error("Database error: {0}")]
+    Db(#[from] DbError),
+    #[error("Asset error: {0}")]
+    Asset(#[from] AssetError),
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+    #[error("Spec migration error: {0}")]
+    Migration(#[from] MigrationError),
+    #[error("Invalid asset data for '{0}'")]
+    AssetData(String),
+    #[error("Invalid game: {0}")]
+    InvalidSpec(String),
+    #[error("Invalid record on line {0}: {1}")]
+    InvalidRecord(usize, String),
+    #[error("Invalid link: {0}")]
+    InvalidLink(String),
+}
+
+/// File extension used for exported games
+pub const EXTENSION: &str = "pueogame";
+
+/// Bump when the layout of `GameFile` changes
+const FORMAT_VERSION: u32 = 1;
+
+/// URL scheme the app is registered to open
+pub const LINK_SCHEME: &str = "pueo";
+
+/// Links trade compression speed for shorter URLs
+const LINK_COMPRESSION_LEVEL: i32 = 19;
+
+/// Largest decompressed spec a link may carry, so a crafted link can't exhaust memory
+const MAX_LINK_SPEC_BYTES: usize = 4 * 1024 * 1024;
+
+/// Self-contained export of a game. Specs are stored as raw JSON so files
+/// written by older versions can be migrated on import.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameFile {
+    pub format_version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub title: String,
+    pub description: String,
+    pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// The game's Markdown notes
+    #[serde(default)]
+    pub notes: String,
+    pub spec: Value,
+    /// Full version history, oldest first
+    #[serde(default)]
+    pub versions: Vec<FileVersion>,
+    /// Library assets referenced by any version or attached to the game, base64 encoded
+    #[serde(default)]
+    pub assets: Vec<FileAsset>,
+}
+
+/// Historical version stored in a game file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileVersion {
+    pub version: i64,
+    pub spec: Value,
+    pub created_at: DateTime<Utc>,
+    pub notes: Option<String>,
+    pub label: Option<String>,
+}
+
+/// Library asset embedded in a game file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileAsset {
+    pub key: String,
+    pub file_name: String,
+    /// Set for assets attached to the game
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub asset_type: Option<AssetType>,
+    /// Left empty in workspace folders, which keep assets as files
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub data: String,
+}
+
+/// Link that imports a copy of `spec`. Without `web_base` it opens the app
+/// directly: `pueo://import?spec=<zstd-compressed spec JSON, base64url encoded>`.
+/// With one, the spec goes in the fragment of a web URL
+/// (`https://example.com/play#spec=...`), which browsers never send to the
+/// server. Only the spec is shared; history and library assets stay behind.
+pub fn share_link(spec: &PhaserGameSpec, web_base: Option<&str>) -> Result<String, GameFileError> {
+    let json = serde_json::to_vec(spec)?;
+    let compressed = zstd::encode_all(json.as_slice(), LINK_COMPRESSION_LEVEL)?;
+    let encoded = BASE64_URL.encode(compressed);
+    match web_base {
+        None => Ok(format!("{}://import?spec={}", LINK_SCHEME, encoded)),
+        Some(base) if is_web_url(base) => {
+            // Any existing fragment would hide the spec parameter
+            let base = base.split('#').next().unwrap_or(base);
+            Ok(format!("{}#spec={}", base, encoded))
+        }
+        Some(base) => Err(GameFileError::InvalidLink(format!(
+            "'{}' is not an http(s) URL",
+            base
+        ))),
+    }
+}
+
+/// Save the game carried by a link made with `share_link` as a new game.
+/// Accepts both `pueo://import` links and web links, e.g. pasted by the user.
+pub async fn import_link(db: &Database, link: &str) -> Result<GameRecord, GameFileError> {
+    Ok(db.create_game(read_link(link)?).await?)
+}
+
+/// Spec in a link made by `share_link`, upgraded and validated but not saved
+pub fn read_link(link: &str) -> Result<PhaserGameSpec, GameFileError> {
+    let link = link.trim();
+    let params = if let Some(query) = link.strip_prefix(&format!("{}://import?", LINK_SCHEME)) {
+        query
+    } else if is_web_url(link) {
+        link.split_once('#')
+            .map(|(_, fragment)| fragment)
+            .ok_or_else(|| GameFileError::InvalidLink("missing spec".to_string()))?
+    } else {
+        return Err(GameFileError::InvalidLink("not an import link".to_string()));
+    };
+    let encoded = params
+        .split('&')
+        .find_map(|param| param.strip_prefix("spec="))
+        .ok_or_else(|| GameFileError::InvalidLink("missing spec".to_string()))?;
+
+    let compressed = BASE64_URL
+        .decode(encoded)
+        .map_err(|e| GameFileError::InvalidLink(e.to_string()))?;
+    let json = zstd::bulk::decompress(&compressed, MAX_LINK_SPEC_BYTES)
+        .map_err(|e| GameFileError::InvalidLink(e.to_string()))?;
+    load_valid_spec(serde_json::from_slice(&json)?)
+}
+
+/// Spec JSON for sharing as text, pretty-printed so it stays readable when pasted
+pub fn spec_text(spec: &PhaserGameSpec) -> Result<String, GameFileError> {
+    Ok(serde_json::to_string_pretty(spec)?)
+}
+
+/// Save a spec pasted as text as a new game. Takes spec JSON (optionally
+/// inside a Markdown code block, as chat apps often wrap it) or a share link.
+pub async fn import_spec_text(db: &Database, text: &str) -> Result<GameRecord, GameFileError> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err(GameFileError::InvalidSpec("no text to import".to_string()));
+    }
+    if text.starts_with(&format!("{}://", LINK_SCHEME)) || is_web_url(text) {
+        return import_link(db, text).await;
+    }
+    let value: Value = serde_json::from_str(strip_code_block(text))
+        .map_err(|e| GameFileError::InvalidSpec(format!("text is not spec JSON: {}", e)))?;
+    Ok(db.create_game(load_valid_spec(value)?).await?)
+}
+
+/// Contents of a fenced Markdown code block, or `text` if it isn't one
+fn strip_code_block(text: &str) -> &str {
+    let Some(rest) = text.strip_prefix("```") else {
+        return text;
+    };
+    // Skip the info string, e.g. "json"
+    let body = rest.split_once('\n').map_or("", |(_, body)| body);
+    body.trim_end().strip_suffix("```").unwrap_or(body).trim()
+}
+
+fn is_web_url(url: &str) -> bool {
+    url.starts_with("https://") || url.starts_with("http://")
+}
+
+/// One game in a newline-delimited JSON library export. Assets are left out
+/// so the export stays small enough to read and edit with other tools.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LibraryRecord {
+    pub id: String,
+    pub title: String,
+    pub description: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub version: i64,
+    #[serde(default)]
+    pub status: GameStatus,
+    #[serde(default)]
+    pub favorite: bool,
+    #[serde(default)]
+    pub archived: bool,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub notes: String,
+    pub spec: Value,
+    /// Full version history, oldest first
+    #[serde(default)]
+    pub versions: Vec<FileVersion>,
+}
+
+/// Write a game, its history, and its library assets to a single file
+pub async fn export_game(
+    db: &Database,
+    assets: &AssetLibrary,
+    id: &str,
+    path: &Path,
+) -> Result<(), GameFileError> {
+    let file = build_game_file(db, assets, id).await?;
+    let path = if path.extension().is_none() {
+        path.with_extension(EXTENSION)
+    } else {
+        path.to_path_buf()
+    };
+    tokio::fs::write(path, serde_json::to_vec_pretty(&file)?).await?;
+    Ok(())
+}
+
+/// Collect a game, its history, and its library assets
+pub async fn build_game_file(
+    db: &Database,
+    assets: &AssetLibrary,
+    id: &str,
+) -> Result<GameFile, GameFileError> {
+    let game = db.get_game(id).await?;
+    let mut versions = db.get_game_versions(id).await?;
+    versions.reverse();
+
+    let attached = db.get_game_assets(id).await?;
+    let mut asset_keys: HashSet<String> = game.spec.library_asset_keys();
+    for version in &versions {
+        asset_keys.extend(version.spec.library_asset_keys());
+    }
+    asset_keys.extend(attached.iter().map(|asset| asset.key.clone()));
+    let mut asset_keys: Vec<String> = asset_keys.into_iter().collect();
+    asset_keys.sort();
+
+    let mut file_assets = Vec::new();
+    for key in asset_keys {
+        // Textures that aren't in the library (e.g. generated placeholders) are skipped
+        let Some(asset) = assets.find(&key).await? else {
+            continue;
+        };
+        let data = tokio::fs::read(assets.path_of(&key).await?).await?;
+        let asset_type = attached
+            .iter()
+            .find(|attached| attached.key == asset.key)
+            .map(|attached| attached.asset_type.clone());
+        file_assets.push(FileAsset {
+            key: asset.key,
+            file_name: asset.file_name,
+            asset_type,
+            data: BASE64.encode(data),
+        });
+    }
+
+    Ok(GameFile {
+        format_version: FORMAT_VERSION,
+        exported_at: Utc::now(),
+        title: game.title,
+        description: game.description,
+        created_at: game.created_at,
+        tags: db.get_game_tags(id).await?,
+        notes: db.get_notes(id).await?,
+        spec: serde_json::to_value(&game.spec)?,
+        versions: versions
+            .into_iter()
+            .map(|version| {
+                Ok(FileVersion {
+                    version: version.version,
+                    spec: serde_json::to_value(&version.spec)?,
+                    created_at: version.created_at,
+                    notes: version.notes,
+                    label: version.label,
+                })
+            })
+            .collect::<Result<_, serde_json::Error>>()?,
+        assets: file_assets,
+    })
+}
+
+/// Import a `.pueogame` file or a raw spec JSON file as a new game. Exported
+/// history is replayed as new versions and embedded assets are added to the
+/// library (under a new key if the library has different data under theirs).
+pub async fn import_game(
+    db: &Database,
+    assets: &AssetLibrary,
+    path: &Path,
+) -> Result<GameRecord, GameFileError> {
+    let value: Value = serde_json::from_slice(&tokio::fs::read(path).await?)?;
+    let is_game_file = value.get("format_version").is_some();
+    if !is_game_file {
+        let spec = load_valid_spec(value)?;
+        return Ok(db.create_game(spec).await?);
+    }
+
+    let mut file: GameFile = serde_json::from_value(value)?;
+    add_file_assets(assets, &mut file).await?;
+
+    let spec = load_valid_spec(file.spec)?;
+    let mut history = file.versions.into_iter();
+    let game = match history.next() {
+        None => db.create_game(spec).await?,
+        Some(first) => {
+            let first_label = first.label;
+            let mut game = db.create_game(load_spec_value(first.spec)?).await?;
+            db.label_version(&game.id, game.version, first_label.as_deref())
+                .await?;
+            for version in history {
+                game = db
+                    .update_game(&game.id, load_spec_value(version.spec)?, version.notes)
+                    .await?;
+                db.label_version(&game.id, game.version, version.label.as_deref())
+                    .await?;
+            }
+            // The exported current spec wins if it drifted from the last version
+            if serde_json::to_value(&game.spec)? != serde_json::to_value(&spec)? {
+                game = db
+                    .update_game(&game.id, spec, Some("Imported".to_string()))
+                    .await?;
+            }
+            game
+        }
+    };
+
+    for tag in &file.tags {
+        db.add_tag(&game.id, tag).await?;
+    }
+    db.set_notes(&game.id, &file.notes).await?;
+    attach_file_assets(db, assets, &game.id, &file.assets).await?;
+    Ok(game)
+}
+
+/// Overwrite the game `id` (creating it if needed) with the contents of a
+/// game file, keeping the id. Used to pull games from a sync remote.
+pub async fn restore_game_file(
+    db: &Database,
+    assets: &AssetLibrary,
+    id: &str,
+    updated_at: DateTime<Utc>,
+    mut file: GameFile,
+) -> Result<GameRecord, GameFileError> {
+    add_file_assets(assets, &mut file).await?;
+
+    let spec = load_spec_value(file.spec)?;
+    let versions = game_history(id, &spec, file.created_at, file.versions)?;
+
+    let game = GameRecord {
+        id: id.to_string(),
+        title: file.title,
+        description: file.description,
+        version: versions
+            .iter()
+            .map(|version| version.version)
+            .max()
+            .unwrap_or(1),
+        spec,
+        created_at: file.created_at,
+        updated_at,
+    };
+    db.replace_game(&game, &versions).await?;
+    set_tags(db, id, &file.tags).await?;
+    db.set_notes(id, &file.notes).await?;
+    attach_file_assets(db, assets, id, &file.assets).await?;
+    Ok(game)
+}
+
+/// Write every game, archived ones included, as one JSON record per line.
+/// Returns the number of games written.
+pub async fn export_library_json(db: &Database, path: &Path) -> Result<usize, GameFileError> {
+    let mut games = db.list_games(&GameListFilter::default()).await?;
+    games.extend(
+        db.list_games(&GameListFilter {
+            archived: true,
+            ..Default::default()
+        })
+        .await?,
+    );
+
+    let mut output = String::new();
+    for summary in &games {
+        let game = db.get_game(&summary.id).await?;
+        let mut versions = db.get_game_versions(&game.id).await?;
+        versions.reverse();
+        let record = LibraryRecord {
+            id: game.id,
+            title: game.title,
+            description: game.description,
+            created_at: game.created_at,
+            updated_at: game.updated_at,
+            version: game.version,
+            status: summary.status,
+            favorite: summary.favorite,
+            archived: summary.archived,
+            tags: summary.tags.clone(),
+            notes: db.get_notes(&summary.id).await?,
+            spec: serde_json::to_value(&game.spec)?,
+            versions: versions
+                .into_iter()
+                .map(|version| {
+                    Ok(FileVersion {
+                        version: version.version,
+                        spec: serde_json::to_value(&version.spec)?,
+                        created_at: version.created_at,
+                        notes: version.notes,
+                        label: version.label,
+                    })
+                })
+                .collect::<Result<_, serde_json::Error>>()?,
+        };
+        output.push_str(&serde_json::to_string(&record)?);
+        output.push('\n');
+    }
+
+    tokio::fs::write(path, output).await?;
+    Ok(games.len())
+}
+
+/// Import a library written by `export_library_json`. A record whose id is
+/// already in the library replaces that game, so an edited export can be
+/// loaded back; other records are added as-is. Every line is checked before
+/// anything is written. Returns the number of games imported.
+pub async fn import_library_json(db: &Database, path: &Path) -> Result<usize, GameFileError> {
+    let content = tokio::fs::read_to_string(path).await?;
+
+    let mut games = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let line_number = index + 1;
+        let invalid = |message: String| GameFileError::InvalidRecord(line_number, message);
+
+        let record: LibraryRecord =
+            serde_json::from_str(line).map_err(|e| invalid(e.to_string()))?;
+        if record.id.trim().is_empty() {
+            return Err(invalid("missing id".to_string()));
+        }
+        let spec = load_valid_spec(record.spec.clone()).map_err(|e| invalid(e.to_string()))?;
+        let versions = game_history(
+            &record.id,
+            &spec,
+            record.created_at,
+            record.versions.clone(),
+        )
+        .map_err(|e| invalid(e.to_string()))?;
+        games.push((record, spec, versions));
+    }
+
+    for (record, spec, versions) in &games {
+        let game = GameRecord {
+            id: record.id.clone(),
+            title: record.title.clone(),
+            description: record.description.clone(),
+            spec: spec.clone(),
+            created_at: record.created_at,
+            updated_at: record.updated_at,
+            version: record.version,
+        };
+        db.replace_game(&game, versions).await?;
+        set_tags(db, &record.id, &record.tags).await?;
+        db.set_notes(&record.id, &record.notes).await?;
+        db.set_favorite(&record.id, record.favorite).await?;
+        db.set_archived(&record.id, record.archived).await?;
+        match record.status {
+            GameStatus::Draft => db.unpublish_game(&record.id).await?,
+            GameStatus::Published => db.publish_game(&record.id).await?,
+        };
+    }
+    Ok(games.len())
+}
+
+/// Version rows for a game being restored from an export. Exports without
+/// history get a single version holding the current spec.
+fn game_history(
+    id: &str,
+    spec: &PhaserGameSpec,
+    created_at: DateTime<Utc>,
+    versions: Vec<FileVersion>,
+) -> Result<Vec<GameVersion>, GameFileError> {
+    let mut history = Vec::new();
+    for version in versions {
+        history.push(GameVersion {
+            id: 0,
+            game_id: id.to_string(),
+            version: version.version,
+            spec: load_spec_value(version.spec)?,
+            created_at: version.created_at,
+            notes: version.notes,
+            label: version.label,
+        });
+    }
+    if history.is_empty() {
+        history.push(GameVersion {
+            id: 0,
+            game_id: id.to_string(),
+            version: 1,
+            spec: spec.clone(),
+            created_at,
+            notes: None,
+            label: None,
+        });
+    }
+    Ok(history)
+}
+
+/// Make a game's tags exactly `tags`
+async fn set_tags(db: &Database, id: &str, tags: &[String]) -> Result<(), GameFileError> {
+    for tag in db.get_game_tags(id).await? {
+        if !tags.contains(&tag) {
+            db.remove_tag(id, &tag).await?;
+        }
+    }
+    for tag in tags {
+        db.add_tag(id, tag).await?;
+    }
+    Ok(())
+}
+
+/// Add embedded assets to the library. An asset whose key the library holds
+/// different data under is added under a new key, and the file's specs are
+/// pointed at that key.
+async fn add_file_assets(assets: &AssetLibrary, file: &mut GameFile) -> Result<(), GameFileError> {
+    let mut renames = HashMap::new();
+    for asset in &mut file.assets {
+        let data = BASE64
+            .decode(&asset.data)
+            .map_err(|_| GameFileError::AssetData(asset.key.clone()))?;
+        let key = assets
+            .add_bytes(&asset.key, &asset.file_name, &data)
+            .await?;
+        if key != asset.key {
+            renames.insert(std::mem::replace(&mut asset.key, key.clone()), key);
+        }
+    }
+    if !renames.is_empty() {
+        rename_library_assets(&mut file.spec, &renames);
+        for version in &mut file.versions {
+            rename_library_assets(&mut version.spec, &renames);
+        }
+    }
+    Ok(())
+}
+
+/// Point a spec's references to library assets at their new keys: asset
+/// `library_key`s, and object textures that don't name a declared asset
+fn rename_library_assets(spec: &mut Value, renames: &HashMap<String, String>) {
+    let declared: HashSet<String> = spec
+        .get("assets")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|asset| asset.get("key").and_then(Value::as_str))
+        .map(str::to_string)
+        .collect();
+    if let Some(list) = spec.get_mut("assets").and_then(Value::as_array_mut) {
+        for asset in list {
+            if let Some(Value::String(key)) = asset.get_mut("library_key") {
+                if let Some(new_key) = renames.get(key.as_str()) {
+                    *key = new_key.clone();
+                }
+            }
+        }
+    }
+    if let Some(scenes) = spec.get_mut("scenes") {
+        rename_textures(scenes, &declared, renames);
+    }
+}
+
+/// Rename `texture` fields anywhere below `value`, which reaches projectile,
+/// spawner, and tilemap templates too
+fn rename_textures(
+    value: &mut Value,
+    declared: &HashSet<String>,
+    renames: &HashMap<String, String>,
+) {
+    match value {
+        Value::Object(map) => {
+            for (name, child) in map.iter_mut() {
+                match child {
+                    Value::String(texture) if name == "texture" => {
+                        if declared.contains(texture.as_str()) {
+                            continue;
+                        }
+                        if let Some(new_key) = renames.get(texture.as_str()) {
+                            *texture = new_key.clone();
+                        }
+                    }
+                    _ => rename_textures(child, declared, renames),
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                rename_textures(item, declared, renames);
+            }
+        }
+        _ => (),
+    }
+}
+
+/// Attach the embedded assets that were attached to the exported game
+async fn attach_file_assets(
+    db: &Database,
+    assets: &AssetLibrary,
+    game_id: &str,
+    files: &[FileAsset],
+) -> Result<(), GameFileError> {
+    for asset in files {
+        let Some(asset_type) = &asset.asset_type else {
+            continue;
+        };
+        let path = assets.path_of(&asset.key).await?;
+        let hash = assets.hash(&asset.key).await?;
+        db.attach_asset(
+            game_id,
+            &asset.key,
+            asset_type,
+            &path.display().to_string(),
+            &hash,
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+/// Upgrade a stored spec value to the current schema
+fn load_spec_value(value: Value) -> Result<PhaserGameSpec, GameFileError> {
+    Ok(serde_json::from_value(spec_migration::migrate(value)?)?)
+}
+
+/// Upgrade a spec and reject it if the validator finds errors
+pub fn load_valid_spec(value: Value) -> Result<PhaserGameSpec, GameFileError> {
+    let spec = load_spec_value(value)?;
+    let errors = validator::error_messages(&spec);
+    if !errors.is_empty() {
+        return Err(GameFileError::InvalidSpec(errors.join("; ")));
+    }
+    Ok(spec)
+}
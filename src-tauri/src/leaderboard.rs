@@ -0,0 +1,159 @@
+/// Leaderboard across the whole library. Scores from different games can't
+/// be compared directly, so players earn medals for their place on each
+/// game's board instead: a family or classroom competes over the library as
+/// a whole. Players are the names entered with high scores, matched without
+/// regard to case.
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::db::PlayerBest;
+
+/// Points for first, second, and third place on a game
+const MEDAL_POINTS: [u32; 3] = [3, 2, 1];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LeaderboardEntry {
+    pub rank: usize,
+    pub player_name: String,
+    /// Sum of medal points over all games
+    pub points: u32,
+    pub gold: u32,
+    pub silver: u32,
+    pub bronze: u32,
+    pub games_played: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerStats {
+    pub player_name: String,
+    pub points: u32,
+    pub games_played: usize,
+    /// Scores submitted across all games
+    pub total_plays: i64,
+    pub first_played: DateTime<Utc>,
+    pub last_played: DateTime<Utc>,
+    /// Standing on each game played, most recently played first
+    pub games: Vec<GameStanding>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameStanding {
+    pub game_id: String,
+    pub game_title: String,
+    pub best_score: i64,
+    /// Place among the game's players (1 = best)
+    pub rank: usize,
+    pub players: usize,
+    pub plays: i64,
+    pub last_played: DateTime<Utc>,
+}
+
+/// Players ranked by medal points, then by golds, silvers, and bronzes
+pub fn global_leaderboard(bests: &[PlayerBest], limit: usize) -> Vec<LeaderboardEntry> {
+    let mut players: HashMap<String, LeaderboardEntry> = HashMap::new();
+    for (best, standing) in standings(bests) {
+        let entry = players
+            .entry(best.player_name.to_lowercase())
+            .or_insert_with(|| LeaderboardEntry {
+                rank: 0,
+                player_name: best.player_name.clone(),
+                points: 0,
+                gold: 0,
+                silver: 0,
+                bronze: 0,
+                games_played: 0,
+            });
+        entry.points += points(standing.rank);
+        match standing.rank {
+            1 => entry.gold += 1,
+            2 => entry.silver += 1,
+            3 => entry.bronze += 1,
+            _ => (),
+        }
+        entry.games_played += 1;
+    }
+
+    let mut entries: Vec<LeaderboardEntry> = players.into_values().collect();
+    entries.sort_by(|a, b| {
+        (b.points, b.gold, b.silver, b.bronze)
+            .cmp(&(a.points, a.gold, a.silver, a.bronze))
+            .then_with(|| a.player_name.cmp(&b.player_name))
+    });
+    entries.truncate(limit);
+    for (i, entry) in entries.iter_mut().enumerate() {
+        entry.rank = i + 1;
+    }
+    entries
+}
+
+/// Stats of one player, or `None` if they never entered a high score
+pub fn player_stats(bests: &[PlayerBest], player_name: &str) -> Option<PlayerStats> {
+    let key = player_name.trim().to_lowercase();
+    let mut games: Vec<(&PlayerBest, GameStanding)> = standings(bests)
+        .into_iter()
+        .filter(|(best, _)| best.player_name.to_lowercase() == key)
+        .collect();
+    let (first, _) = games.first()?;
+    let mut stats = PlayerStats {
+        player_name: first.player_name.clone(),
+        points: 0,
+        games_played: games.len(),
+        total_plays: 0,
+        first_played: first.first_played,
+        last_played: first.last_played,
+        games: Vec::new(),
+    };
+    games.sort_by_key(|(_, standing)| Reverse(standing.last_played));
+    for (best, standing) in games {
+        stats.points += points(standing.rank);
+        stats.total_plays += best.plays;
+        stats.first_played = stats.first_played.min(best.first_played);
+        stats.last_played = stats.last_played.max(best.last_played);
+        stats.games.push(standing);
+    }
+    Some(stats)
+}
+
+/// Each best score with its place on its game's board. Ties share a place,
+/// and the player who got there first is listed first.
+fn standings(bests: &[PlayerBest]) -> Vec<(&PlayerBest, GameStanding)> {
+    let mut by_game: BTreeMap<&str, Vec<&PlayerBest>> = BTreeMap::new();
+    for best in bests {
+        by_game.entry(&best.game_id).or_default().push(best);
+    }
+
+    let mut standings = Vec::new();
+    for mut board in by_game.into_values() {
+        board.sort_by(|a, b| {
+            b.best_score
+                .cmp(&a.best_score)
+                .then_with(|| a.first_played.cmp(&b.first_played))
+        });
+        let players = board.len();
+        let mut rank = 0;
+        for (i, best) in board.iter().enumerate() {
+            if i == 0 || best.best_score != board[i - 1].best_score {
+                rank = i + 1;
+            }
+            standings.push((
+                *best,
+                GameStanding {
+                    game_id: best.game_id.clone(),
+                    game_title: best.game_title.clone(),
+                    best_score: best.best_score,
+                    rank,
+                    players,
+                    plays: best.plays,
+                    last_played: best.last_played,
+                },
+            ));
+        }
+    }
+    standings
+}
+
+fn points(rank: usize) -> u32 {
+    MEDAL_POINTS.get(rank - 1).copied().unwrap_or(0)
+}
@@ -1,3 +1,5 @@
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
 use futures::StreamExt;
 use rig::agent::MultiTurnStreamItem;
 use rig::client::CompletionClient;
@@ -8,17 +10,163 @@ use rig::streaming::{
     StreamedAssistantContent, StreamedUserContent, StreamingChat, StreamingPrompt,
 };
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
-use tauri::{Emitter, Manager, State, Window};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use tauri::{AppHandle, Emitter, Manager, State, WebviewUrl, WebviewWindowBuilder, Window};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+use tauri_plugin_deep_link::DeepLinkExt;
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
+use tauri_plugin_notification::NotificationExt;
 use tokio::sync::Mutex;
 
+mod analysis;
+mod api_key;
+mod assets;
+mod codegen;
 mod db;
+mod dev_prompt;
+mod duplicates;
+mod features;
 mod game_builder;
+mod game_docs;
+mod game_file;
+mod leaderboard;
+mod locale;
+mod normalize;
+mod replay;
+mod reports;
+mod schema;
+mod secrets;
+mod sessions;
+mod shortcuts;
+mod simulation;
+mod spec_diff;
+mod spec_migration;
+mod spec_patch;
+mod standalone;
+mod sync;
+mod templates;
+mod thumbnails;
+mod validator;
+mod workspace;
 
-// Shared state for the LLM client and database
+pub use schema::frontend_schema;
+
+// Shared state for the LLM client, database, asset library, error reports, the
+// conversation of each window, the registered global shortcuts, the workspace
+// directory games are written to (if any), the selected language, and the
+// developer prompt file (if any)
 pub struct AppState {
     client: Arc<Mutex<Option<anthropic::Client>>>,
+    key_status: Arc<Mutex<api_key::KeyStatus>>,
     db: Arc<db::Database>,
+    assets: Arc<assets::AssetLibrary>,
+    reports: reports::ErrorRecorder,
+    sessions: Arc<sessions::Sessions>,
+    shortcuts: Arc<shortcuts::Bindings>,
+    workspace: Arc<Mutex<Option<PathBuf>>>,
+    locale: Arc<RwLock<locale::Locale>>,
+    dev_prompt: Arc<std::sync::Mutex<dev_prompt::DevPrompt>>,
+    // pueo://import links waiting for the user to confirm the import
+    deep_links: Arc<std::sync::Mutex<Vec<String>>>,
+}
+
+impl AppState {
+    // Message for a failed database call; unexpected failures are also recorded
+    // as error reports, in English for whoever triages them
+    fn db_failure(&self, action: &str, error: db::DbError) -> String {
+        if error.is_internal() {
+            let message = format!("Failed to {}: {}", action, error);
+            self.reports
+                .record(db::ReportKind::Database, &message, None);
+        }
+        self.failure(action, error)
+    }
+
+    // "Failed to <action>: <error>" in the user's language
+    fn failure(&self, action: &str, error: impl std::fmt::Display) -> String {
+        self.locale().failure(action, error)
+    }
+
+    // Game builder prompt in the user's language, from the developer prompt
+    // file when one is set
+    fn system_prompt(&self) -> String {
+        let prompt = self
+            .dev_prompt
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .current();
+        match prompt {
+            Ok(Some(prompt)) => self.locale().with_language(prompt),
+            Ok(None) => self.locale().system_prompt(),
+            // Keep chatting while the file is being replaced or fixed
+            Err(e) => {
+                eprintln!("{}; using the built-in prompt", e);
+                self.locale().system_prompt()
+            }
+        }
+    }
+
+    fn locale(&self) -> locale::Locale {
+        *self
+            .locale
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    // Save a spec the game builder produced, as a new version of the game the
+    // conversation is iterating on or as a new game
+    async fn auto_save(
+        &self,
+        game_id: Option<&str>,
+        spec: game_builder::PhaserGameSpec,
+    ) -> Result<db::GameRecord, String> {
+        let saved = match game_id {
+            Some(id) => {
+                let notes = Some("Auto-saved from chat".to_string());
+                match self.db.update_game(id, spec.clone(), notes).await {
+                    // Deleted mid-conversation; keep the work as a new game
+                    Err(db::DbError::NotFound(_)) => self.db.create_game(spec).await,
+                    result => result,
+                }
+            }
+            None => self.db.create_game(spec).await,
+        }
+        .map_err(|e| self.db_failure("save game", e))?;
+        self.save_to_workspace(&saved.id).await?;
+        Ok(saved)
+    }
+
+    // Write a changed game through to the workspace directory, if one is set
+    async fn save_to_workspace(&self, id: &str) -> Result<(), String> {
+        let Some(dir) = self.workspace.lock().await.clone() else {
+            return Ok(());
+        };
+        workspace::write_game(&self.db, &self.assets, &dir, id)
+            .await
+            .map_err(|e| self.failure("write game to workspace", e))
+    }
+
+    async fn remove_from_workspace(&self, id: &str) -> Result<(), String> {
+        let Some(dir) = self.workspace.lock().await.clone() else {
+            return Ok(());
+        };
+        workspace::remove_game(&dir, id)
+            .await
+            .map_err(|e| self.failure("remove game from workspace", e))
+    }
+
+    // Reconcile the index with the workspace directory; None without one
+    async fn scan_workspace(&self) -> Result<Option<workspace::ScanReport>, String> {
+        let Some(dir) = self.workspace.lock().await.clone() else {
+            return Ok(None);
+        };
+        workspace::scan(&self.db, &self.assets, &dir)
+            .await
+            .map(Some)
+            .map_err(|e| self.failure("scan workspace", e))
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -41,37 +189,78 @@ async fn is_ai_initialized(state: State<'_, AppState>) -> Result<bool, String> {
     Ok(client_guard.is_some())
 }
 
-// Initialize the Anthropic client with API key
+// Initialize the Anthropic client with API key, saving the key for next time
 #[tauri::command]
 async fn init_ai(state: State<'_, AppState>, api_key: String) -> Result<String, String> {
     if api_key.is_empty() {
-        return Err("API key cannot be empty".to_string());
+        return Err(state.locale().message("API key cannot be empty"));
     }
+    // Without a connection the key can't be checked; accept it and say so in the status
+    let checked = api_key::check(&api_key, api_key::KeySource::Keyring).await;
+    if checked.valid == Some(false) {
+        return Err(state.locale().message("The API key was rejected"));
+    }
+    let source = api_key::store(&state.db, &api_key)
+        .await
+        .map_err(|e| state.failure("save API key", e))?;
+
     let client = anthropic::Client::new(&api_key);
     let mut client_guard = state.client.lock().await;
     *client_guard = Some(client);
+    *state.key_status.lock().await = api_key::KeyStatus {
+        source: Some(source),
+        ..checked
+    };
     Ok("AI client initialized successfully".to_string())
 }
 
-// Stream chat completion with game builder tool
+// Where the active API key came from and whether the API accepted it
+#[tauri::command]
+async fn get_key_status(state: State<'_, AppState>) -> Result<api_key::KeyStatus, String> {
+    Ok(state.key_status.lock().await.clone())
+}
+
+// Setting that turns on saving every game the builder generates
+const AUTO_SAVE_SETTING: &str = "auto_save_games";
+
+// Stream chat completion with game builder tool. `spec` is the game being
+// iterated on, which the model changes through patches when that experiment
+// is on. With auto-save on, generated games are saved as versions of
+// `game_id`, or as a new game the rest of the response then updates.
 #[tauri::command]
 async fn stream_chat(
     window: Window,
     state: State<'_, AppState>,
     messages: Vec<ChatMessage>,
     model: Option<String>,
+    game_id: Option<String>,
+    spec: Option<game_builder::PhaserGameSpec>,
 ) -> Result<(), String> {
-    // Get the client from state
-    let client_guard = state.client.lock().await;
-    let client = client_guard
-        .as_ref()
-        .ok_or("AI client not initialized. Call init_ai first.")?;
+    // Copy the client out so other windows can chat while this one streams
+    let client = state.client.lock().await.clone().ok_or_else(|| {
+        state
+            .locale()
+            .message("AI client not initialized. Call init_ai first.")
+    })?;
+    let budget = load_budget(&state).await?;
+    let auto_save = load_auto_save(&state).await?;
+    let patch_edits = load_feature_flags(&state)
+        .await?
+        .is_enabled(features::Feature::PatchEdits);
+    let mut game_id = game_id;
+
+    // Events go only to this window, which runs its own conversation
+    let label = window.label();
+    let guard = state
+        .sessions
+        .begin_stream(label)
+        .map_err(|e| state.locale().message(&e.to_string()))?;
 
     // Use specified model or default to claude-sonnet-4-5
     let model_name = model.unwrap_or_else(|| "claude-sonnet-4-5-20250929".to_string());
 
     // Build the chat history - separate system messages, last user message, and history
-    let mut system_prompt = game_builder::get_system_prompt();
+    let mut system_prompt = state.system_prompt();
     let mut history = Vec::new();
     let mut last_user_message = String::new();
 
@@ -92,20 +281,36 @@ async fn stream_chat(
                 }
                 history.push(Message::assistant(&msg.content));
             }
-            _ => return Err(format!("Unknown role: {}", msg.role)),
+            _ => {
+                return Err(state
+                    .locale()
+                    .message("Unknown role: {role}")
+                    .replace("{role}", &msg.role))
+            }
         }
     }
+    let spec = spec.filter(|_| patch_edits);
+    if let Some(spec) = &spec {
+        system_prompt.push_str("\n\n");
+        system_prompt.push_str(&game_builder::current_game_prompt(spec));
+    }
 
-    // Create agent with the Phaser game tool
-    let agent = client
-        .agent(&model_name)
-        .preamble(&system_prompt)
-        .tool(game_builder::create_phaser_game_tool())
-        .build();
+    // Create agent with the Phaser game tools, which share the current spec
+    let current = Arc::new(std::sync::Mutex::new(spec));
+    let builder = client.agent(&model_name).preamble(&system_prompt).tool(
+        game_builder::create_phaser_game_tool(budget.clone(), current.clone()),
+    );
+    let agent = if patch_edits {
+        builder
+            .tool(game_builder::create_patch_game_tool(budget, current))
+            .build()
+    } else {
+        builder.build()
+    };
 
     // Create streaming completion with multi-turn enabled for automatic tool execution
     // Max depth of 5 allows the agent to call tools up to 5 times before requiring a text response
-    let mut stream = if history.is_empty() {
+    let stream = if history.is_empty() {
         // Simple prompt if no history
         agent.stream_prompt(&last_user_message).multi_turn(5).await
     } else {
@@ -115,6 +320,8 @@ async fn stream_chat(
             .multi_turn(5)
             .await
     };
+    // The stop button and the stop shortcut end the stream early
+    let mut stream = stream.take_until(Box::pin(guard.stopped()));
 
     // Stream tokens to frontend and accumulate the full response
     let mut accumulated_response = String::new();
@@ -126,23 +333,24 @@ async fn stream_chat(
                     StreamedAssistantContent::Text(text) => {
                         accumulated_response.push_str(&text.text);
                         window
-                            .emit("chat-token", &text.text)
-                            .map_err(|e| format!("Failed to emit token: {}", e))?;
+                            .emit_to(label, "chat-token", &text.text)
+                            .map_err(|e| state.failure("emit token", e))?;
                     }
                     StreamedAssistantContent::ToolCall(tool_call) => {
                         // With multi_turn enabled, rig automatically executes tools
                         // Emit the tool call event with the game spec
                         window
-                            .emit(
+                            .emit_to(
+                                label,
                                 "tool-call",
-                                serde_json::json!({
-                                    "function": {
-                                        "name": &tool_call.function.name,
-                                        "arguments": &tool_call.function.arguments
-                                    }
-                                }),
+                                schema::ToolCallEvent {
+                                    function: schema::ToolCallFunction {
+                                        name: tool_call.function.name.clone(),
+                                        arguments: tool_call.function.arguments.clone(),
+                                    },
+                                },
                             )
-                            .map_err(|e| format!("Failed to emit tool call: {}", e))?;
+                            .map_err(|e| state.failure("emit tool call", e))?;
                     }
                     _ => (),
                 },
@@ -162,14 +370,29 @@ async fn stream_chat(
                             {
                                 let text_item = serde_json::json!(text_item_raw.text);
                                 window
-                                    .emit("tool-result", text_item)
-                                    .map_err(|e| format!("Failed to emit tool result: {}", e))?;
+                                    .emit_to(label, "tool-result", text_item)
+                                    .map_err(|e| state.failure("emit tool result", e))?;
 
                                 // After tool execution, emit new-turn to signal the frontend
                                 // to save the current streaming content and start a new message
                                 window
-                                    .emit("chat-new-turn", ())
-                                    .map_err(|e| format!("Failed to emit new-turn: {}", e))?;
+                                    .emit_to(label, "chat-new-turn", ())
+                                    .map_err(|e| state.failure("emit new-turn", e))?;
+
+                                // Failed tool calls return plain text, so only specs parse
+                                let spec = serde_json::from_str(&text_item_raw.text).ok();
+                                if let Some(spec) = spec.filter(|_| auto_save) {
+                                    match state.auto_save(game_id.as_deref(), spec).await {
+                                        Ok(game) => {
+                                            game_id = Some(game.id.clone());
+                                            window
+                                                .emit_to(label, "game-saved", &game)
+                                                .map_err(|e| state.failure("emit game saved", e))?;
+                                        }
+                                        // A failed save shouldn't cut the response off
+                                        Err(e) => eprintln!("{}", e),
+                                    }
+                                }
                             };
                         }
                     }
@@ -177,45 +400,344 @@ async fn stream_chat(
                 MultiTurnStreamItem::FinalResponse(response) => {
                     // Emit the final response first
                     window
-                        .emit("chat-final-response", &response.response())
-                        .map_err(|e| format!("Failed to emit final response: {}", e))?;
+                        .emit_to(label, "chat-final-response", &response.response())
+                        .map_err(|e| state.failure("emit final response", e))?;
                 }
                 _ => (),
             },
             Err(e) => {
+                state.reports.record(
+                    db::ReportKind::Stream,
+                    &format!("Stream error: {}", e),
+                    Some(format!("chat with {}", model_name)),
+                );
+                let message = state
+                    .locale()
+                    .message("Stream error: {error}")
+                    .replace("{error}", &e.to_string());
                 window
-                    .emit("chat-error", format!("Stream error: {}", e))
-                    .map_err(|e| format!("Failed to emit error: {}", e))?;
-                return Err(format!("Stream error: {}", e));
+                    .emit_to(label, "chat-error", &message)
+                    .map_err(|e| state.failure("emit error", e))?;
+                notify_if_unfocused(&window, &message);
+                return Err(message);
             }
         }
     }
 
+    if stream.is_stopped() {
+        window
+            .emit_to(label, "chat-stopped", ())
+            .map_err(|e| state.failure("emit stop", e))?;
+    } else {
+        notify_if_unfocused(
+            &window,
+            &state
+                .locale()
+                .message("The game builder has finished its response"),
+        );
+    }
+
     // Signal completion
     window
-        .emit("chat-complete", ())
-        .map_err(|e| format!("Failed to emit completion: {}", e))?;
+        .emit_to(label, "chat-complete", ())
+        .map_err(|e| state.failure("emit completion", e))?;
 
     Ok(())
 }
 
+// Multi-turn responses take a while, so tell users who tabbed away when one ends
+fn notify_if_unfocused(window: &Window, body: &str) {
+    if window.is_focused().unwrap_or(true) {
+        return;
+    }
+    let shown = window
+        .app_handle()
+        .notification()
+        .builder()
+        .title("Pueo")
+        .body(body)
+        .show();
+    if let Err(e) = shown {
+        eprintln!("Failed to show notification: {}", e);
+    }
+}
+
+#[tauri::command]
+async fn get_auto_save(state: State<'_, AppState>) -> Result<bool, String> {
+    load_auto_save(&state).await
+}
+
+#[tauri::command]
+async fn set_auto_save(state: State<'_, AppState>, enabled: bool) -> Result<(), String> {
+    state
+        .db
+        .set_setting(AUTO_SAVE_SETTING, &enabled)
+        .await
+        .map_err(|e| state.db_failure("save setting", e))
+}
+
+async fn load_auto_save(state: &AppState) -> Result<bool, String> {
+    state
+        .db
+        .get_setting(AUTO_SAVE_SETTING)
+        .await
+        .map(Option::unwrap_or_default)
+        .map_err(|e| state.db_failure("get setting", e))
+}
+
+// Experimental features, all off until the user turns them on
+#[tauri::command]
+async fn get_feature_flags(
+    state: State<'_, AppState>,
+) -> Result<Vec<features::FeatureFlag>, String> {
+    Ok(load_feature_flags(&state).await?.list())
+}
+
+#[tauri::command]
+async fn set_feature_flag(
+    state: State<'_, AppState>,
+    feature: features::Feature,
+    enabled: bool,
+) -> Result<Vec<features::FeatureFlag>, String> {
+    let mut flags = load_feature_flags(&state).await?;
+    flags.set(feature, enabled);
+    state
+        .db
+        .set_setting(features::FEATURE_FLAGS_SETTING, &flags)
+        .await
+        .map_err(|e| state.db_failure("save setting", e))?;
+    Ok(flags.list())
+}
+
+async fn load_feature_flags(state: &AppState) -> Result<features::FeatureFlags, String> {
+    state
+        .db
+        .get_setting(features::FEATURE_FLAGS_SETTING)
+        .await
+        .map(Option::unwrap_or_default)
+        .map_err(|e| state.db_failure("get setting", e))
+}
+
+// Stop the response streaming into the calling window; false if nothing was streaming
+#[tauri::command]
+fn stop_generation(window: Window, state: State<'_, AppState>) -> bool {
+    state.sessions.stop(window.label())
+}
+
+// Window commands: more builder windows, each with its own conversation, and
+// windows that play a single game
+#[tauri::command]
+async fn open_chat_window(app: AppHandle, state: State<'_, AppState>) -> Result<String, String> {
+    let label = state.sessions.next_chat_label();
+    WebviewWindowBuilder::new(&app, &label, WebviewUrl::App("index.html".into()))
+        .title("pueo")
+        .inner_size(1400.0, 1050.0)
+        .build()
+        .map_err(|e| state.failure("open window", e))?;
+    Ok(label)
+}
+
+#[tauri::command]
+async fn open_game_window(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    game_id: String,
+) -> Result<String, String> {
+    let game = state
+        .db
+        .get_game(&game_id)
+        .await
+        .map_err(|e| state.db_failure("get game", e))?;
+    let label = sessions::game_window_label(&game.id);
+    // A game that is already open is brought to the front
+    if let Some(window) = app.get_webview_window(&label) {
+        window
+            .set_focus()
+            .map_err(|e| state.failure("focus window", e))?;
+        return Ok(label);
+    }
+    // Room for the renderer's title and controls around the canvas
+    let width = f64::from(game.spec.game.width) + 80.0;
+    let height = f64::from(game.spec.game.height) + 260.0;
+    let url = format!("index.html?play={}", game.id);
+    WebviewWindowBuilder::new(&app, &label, WebviewUrl::App(url.into()))
+        .title(&game.title)
+        .inner_size(width, height)
+        .build()
+        .map_err(|e| state.failure("open window", e))?;
+    Ok(label)
+}
+
+#[tauri::command]
+fn get_session(window: Window, state: State<'_, AppState>) -> sessions::Session {
+    state.sessions.get(window.label())
+}
+
 // Game Builder commands
 #[tauri::command]
-fn get_game_builder_prompt() -> String {
-    game_builder::get_system_prompt()
+fn get_game_builder_prompt(state: State<'_, AppState>) -> String {
+    state.system_prompt()
 }
 
-// Database commands for game persistence
+// Developer mode: read the system prompt from a markdown file, reloaded when it changes
 #[tauri::command]
-async fn save_game(
+fn get_dev_prompt(state: State<'_, AppState>) -> dev_prompt::DevPromptStatus {
+    state
+        .dev_prompt
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .status()
+}
+
+#[tauri::command]
+async fn set_dev_prompt_path(
+    state: State<'_, AppState>,
+    path: Option<String>,
+) -> Result<dev_prompt::DevPromptStatus, String> {
+    let path = path
+        .filter(|path| !path.trim().is_empty())
+        .map(PathBuf::from);
+    // Check the file before switching to it
+    let mut prompt = dev_prompt::DevPrompt::new(path.clone());
+    let status = prompt
+        .reload()
+        .map_err(|e| state.failure("load prompt file", e))?;
+    match &path {
+        Some(path) => {
+            state
+                .db
+                .set_setting(dev_prompt::DEV_PROMPT_SETTING, path)
+                .await
+        }
+        None => {
+            state
+                .db
+                .delete_setting(dev_prompt::DEV_PROMPT_SETTING)
+                .await
+        }
+    }
+    .map_err(|e| state.db_failure("save setting", e))?;
+    *state
+        .dev_prompt
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = prompt;
+    Ok(status)
+}
+
+#[tauri::command]
+fn reload_prompt(state: State<'_, AppState>) -> Result<dev_prompt::DevPromptStatus, String> {
+    state
+        .dev_prompt
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .reload()
+        .map_err(|e| state.failure("load prompt file", e))
+}
+
+#[tauri::command]
+async fn validate_spec(
     state: State<'_, AppState>,
     spec: game_builder::PhaserGameSpec,
-) -> Result<db::GameRecord, String> {
+) -> Result<Vec<validator::Diagnostic>, String> {
+    let budget = load_budget(&state).await?;
+    let mut diagnostics = validator::validate_spec(&spec);
+    diagnostics.extend(validator::check_budget(&spec, &budget));
+    Ok(diagnostics)
+}
+
+#[tauri::command]
+fn lint_spec(spec: game_builder::PhaserGameSpec) -> Vec<validator::Diagnostic> {
+    simulation::lint_spec(&spec)
+}
+
+#[tauri::command]
+fn normalize_spec(
+    state: State<'_, AppState>,
+    spec: serde_json::Value,
+) -> Result<normalize::NormalizedSpec, String> {
+    normalize::normalize_spec(spec).map_err(|e| state.failure("normalize spec", e))
+}
+
+#[tauri::command]
+fn estimate_spec_tokens(spec: game_builder::PhaserGameSpec) -> analysis::TokenEstimate {
+    analysis::estimate_tokens(&spec)
+}
+
+// Limits on how heavy generated games may be; exceeding them warns the model
+#[tauri::command]
+async fn get_performance_budget(
+    state: State<'_, AppState>,
+) -> Result<validator::PerformanceBudget, String> {
+    load_budget(&state).await
+}
+
+#[tauri::command]
+async fn set_performance_budget(
+    state: State<'_, AppState>,
+    budget: validator::PerformanceBudget,
+) -> Result<(), String> {
+    budget.validate()?;
+    state
+        .db
+        .set_setting(validator::BUDGET_SETTING, &budget)
+        .await
+        .map_err(|e| state.db_failure("save performance budget", e))
+}
+
+async fn load_budget(state: &AppState) -> Result<validator::PerformanceBudget, String> {
     state
         .db
-        .create_game(spec)
+        .get_setting(validator::BUDGET_SETTING)
         .await
-        .map_err(|e| format!("Failed to save game: {}", e))
+        .map(Option::unwrap_or_default)
+        .map_err(|e| state.db_failure("get performance budget", e))
+}
+
+// Database commands for game persistence
+#[tauri::command]
+async fn save_game(
+    state: State<'_, AppState>,
+    spec: game_builder::PhaserGameSpec,
+    resolution: Option<duplicates::SaveResolution>,
+) -> Result<duplicates::SaveOutcome, String> {
+    // Without a resolution, a game that looks like an existing one isn't saved
+    let saved = match resolution {
+        None => {
+            let games = state
+                .db
+                .list_games(&db::GameListFilter::default())
+                .await
+                .map_err(|e| state.db_failure("list games", e))?;
+            let similar = duplicates::find_similar(&spec, games);
+            if !similar.is_empty() {
+                return Ok(duplicates::SaveOutcome::conflict(similar));
+            }
+            state.db.create_game(spec).await
+        }
+        Some(duplicates::SaveResolution::SaveAsNew) => state.db.create_game(spec).await,
+        Some(duplicates::SaveResolution::Overwrite { game_id }) => {
+            state.db.update_game(&game_id, spec, None).await
+        }
+        Some(duplicates::SaveResolution::Merge { game_id }) => {
+            let existing = state
+                .db
+                .get_game(&game_id)
+                .await
+                .map_err(|e| state.db_failure("get game", e))?;
+            let merged = duplicates::merge_specs(existing.spec, spec);
+            let errors = validator::error_messages(&merged);
+            if !errors.is_empty() {
+                return Err(state.failure("merge games", errors.join("; ")));
+            }
+            let notes = Some("Merged in a saved game".to_string());
+            state.db.update_game(&game_id, merged, notes).await
+        }
+    };
+    let game = saved.map_err(|e| state.db_failure("save game", e))?;
+    state.save_to_workspace(&game.id).await?;
+    Ok(duplicates::SaveOutcome::Saved {
+        game: Box::new(game),
+    })
 }
 
 #[tauri::command]
@@ -224,7 +746,7 @@ async fn get_game(state: State<'_, AppState>, id: String) -> Result<db::GameReco
         .db
         .get_game(&id)
         .await
-        .map_err(|e| format!("Failed to get game: {}", e))
+        .map_err(|e| state.db_failure("get game", e))
 }
 
 #[tauri::command]
@@ -234,122 +756,1661 @@ async fn update_game(
     spec: game_builder::PhaserGameSpec,
     notes: Option<String>,
 ) -> Result<db::GameRecord, String> {
-    state
+    let game = state
         .db
         .update_game(&id, spec, notes)
         .await
-        .map_err(|e| format!("Failed to update game: {}", e))
+        .map_err(|e| state.db_failure("update game", e))?;
+    state.save_to_workspace(&game.id).await?;
+    Ok(game)
 }
 
 #[tauri::command]
-async fn delete_game(state: State<'_, AppState>, id: String) -> Result<(), String> {
+async fn diff_game_versions(
+    state: State<'_, AppState>,
+    game_id: String,
+    from: i64,
+    to: i64,
+) -> Result<spec_diff::SpecDiff, String> {
     state
         .db
-        .delete_game(&id)
+        .diff_game_versions(&game_id, from, to)
         .await
-        .map_err(|e| format!("Failed to delete game: {}", e))
+        .map_err(|e| state.db_failure("diff game versions", e))
 }
 
 #[tauri::command]
-async fn list_games(state: State<'_, AppState>) -> Result<Vec<db::GameSummary>, String> {
+async fn get_storage_stats(
+    state: State<'_, AppState>,
+    game_id: String,
+) -> Result<db::StorageStats, String> {
     state
         .db
-        .list_games()
+        .get_storage_stats(&game_id)
         .await
-        .map_err(|e| format!("Failed to list games: {}", e))
+        .map_err(|e| state.db_failure("get storage stats", e))
 }
 
 #[tauri::command]
-async fn search_games(
+async fn analyze_spec(
     state: State<'_, AppState>,
-    query: String,
-) -> Result<Vec<db::GameSummary>, String> {
-    state
+    game_id: String,
+) -> Result<analysis::SpecReport, String> {
+    let game = state
         .db
-        .search_games(&query)
+        .get_game(&game_id)
         .await
-        .map_err(|e| format!("Failed to search games: {}", e))
+        .map_err(|e| state.db_failure("get game", e))?;
+    let budget = load_budget(&state).await?;
+    Ok(analysis::analyze_spec(&game_id, &game.spec, &budget))
 }
 
 #[tauri::command]
-async fn get_game_versions(
+async fn label_version(
     state: State<'_, AppState>,
     game_id: String,
-) -> Result<Vec<db::GameVersion>, String> {
-    state
+    version: i64,
+    label: Option<String>,
+) -> Result<db::GameVersion, String> {
+    let version = state
         .db
-        .get_game_versions(&game_id)
+        .label_version(&game_id, version, label.as_deref())
         .await
-        .map_err(|e| format!("Failed to get game versions: {}", e))
+        .map_err(|e| state.db_failure("label version", e))?;
+    state.save_to_workspace(&game_id).await?;
+    Ok(version)
 }
 
 #[tauri::command]
-async fn get_game_version(
+async fn get_version_by_label(
     state: State<'_, AppState>,
     game_id: String,
-    version: i64,
+    label: String,
 ) -> Result<db::GameVersion, String> {
     state
         .db
-        .get_game_version(&game_id, version)
+        .get_version_by_label(&game_id, &label)
         .await
-        .map_err(|e| format!("Failed to get game version: {}", e))
+        .map_err(|e| state.db_failure("get version by label", e))
 }
 
-#[cfg_attr(mobile, tauri::mobile_entry_point)]
-pub fn run() {
-    // Try to load .env file (ignore if it doesn't exist)
-    let _ = dotenvy::dotenv();
+#[tauri::command]
+async fn prune_versions(
+    state: State<'_, AppState>,
+    game_id: String,
+    policy: Option<db::RetentionPolicy>,
+) -> Result<db::PruneReport, String> {
+    let report = state
+        .db
+        .prune_versions(&game_id, &policy.unwrap_or_default())
+        .await
+        .map_err(|e| state.db_failure("prune versions", e))?;
+    state.save_to_workspace(&game_id).await?;
+    Ok(report)
+}
+
+#[tauri::command]
+async fn revert_game(
+    state: State<'_, AppState>,
+    id: String,
+    version: i64,
+) -> Result<db::GameRecord, String> {
+    let game = state
+        .db
+        .revert_game(&id, version)
+        .await
+        .map_err(|e| state.db_failure("revert game", e))?;
+    state.save_to_workspace(&game.id).await?;
+    Ok(game)
+}
 
-    // Try to initialize client from environment variable
-    let initial_client = std::env::var("ANTHROPIC_API_KEY")
-        .ok()
-        .filter(|key| !key.is_empty())
-        .map(|key| anthropic::Client::new(&key));
+#[tauri::command]
+async fn duplicate_game(
+    state: State<'_, AppState>,
+    id: String,
+    new_title: String,
+    include_history: Option<bool>,
+) -> Result<db::GameRecord, String> {
+    let game = state
+        .db
+        .duplicate_game(&id, &new_title, include_history.unwrap_or(false))
+        .await
+        .map_err(|e| state.db_failure("duplicate game", e))?;
+    state.save_to_workspace(&game.id).await?;
+    Ok(game)
+}
 
-    if initial_client.is_some() {
-        println!("Loaded ANTHROPIC_API_KEY from environment");
-    }
+#[tauri::command]
+async fn delete_game(state: State<'_, AppState>, id: String) -> Result<(), String> {
+    let attached = state
+        .db
+        .get_game_assets(&id)
+        .await
+        .map_err(|e| state.db_failure("get game assets", e))?;
+    state
+        .db
+        .delete_game(&id)
+        .await
+        .map_err(|e| state.db_failure("delete game", e))?;
+    state.remove_from_workspace(&id).await?;
 
-    tauri::Builder::default()
-        .plugin(tauri_plugin_opener::init())
-        .setup(|app| {
-            // Initialize database in app data directory
-            let app_handle = app.handle();
-            tauri::async_runtime::block_on(async move {
-                let app_data_dir = app_handle
-                    .path()
-                    .app_data_dir()
-                    .expect("Failed to get app data directory");
+    // Remove library files that no other game uses. The game is already gone,
+    // so a failure here only leaves files for cleanup_unused_assets to collect.
+    let referenced = match state.db.referenced_asset_keys().await {
+        Ok(referenced) => referenced,
+        Err(e) => {
+            eprintln!("Failed to load games for asset cleanup: {}", e);
+            return Ok(());
+        }
+    };
+    for asset in attached
+        .iter()
+        .filter(|asset| !referenced.contains(&asset.key))
+    {
+        match state.assets.delete(&asset.key).await {
+            Ok(()) | Err(assets::AssetError::NotFound(_)) => {}
+            Err(e) => eprintln!("Failed to delete asset {}: {}", asset.key, e),
+        }
+    }
+    Ok(())
+}
 
-                let db_path = app_data_dir.join("games.db");
+#[tauri::command]
+async fn list_games(
+    state: State<'_, AppState>,
+    filter: Option<db::GameListFilter>,
+) -> Result<Vec<db::GameSummary>, String> {
+    state
+        .db
+        .list_games(&filter.unwrap_or_default())
+        .await
+        .map_err(|e| state.db_failure("list games", e))
+}
 
-                let database = db::Database::new(db_path)
-                    .await
-                    .expect("Failed to initialize database");
+#[tauri::command]
+async fn get_notes(state: State<'_, AppState>, id: String) -> Result<String, String> {
+    state
+        .db
+        .get_notes(&id)
+        .await
+        .map_err(|e| state.db_failure("get notes", e))
+}
 
-                app_handle.manage(AppState {
-                    client: Arc::new(Mutex::new(initial_client)),
-                    db: Arc::new(database),
-                });
-            });
+#[tauri::command]
+async fn set_notes(state: State<'_, AppState>, id: String, notes: String) -> Result<(), String> {
+    state
+        .db
+        .set_notes(&id, &notes)
+        .await
+        .map_err(|e| state.db_failure("save notes", e))?;
+    state.save_to_workspace(&id).await
+}
 
-            Ok(())
+#[tauri::command]
+async fn set_favorite(
+    state: State<'_, AppState>,
+    id: String,
+    favorite: bool,
+) -> Result<(), String> {
+    state
+        .db
+        .set_favorite(&id, favorite)
+        .await
+        .map_err(|e| state.db_failure("set favorite", e))
+}
+
+#[tauri::command]
+async fn archive_game(state: State<'_, AppState>, id: String) -> Result<(), String> {
+    state
+        .db
+        .set_archived(&id, true)
+        .await
+        .map_err(|e| state.db_failure("archive game", e))
+}
+
+#[tauri::command]
+async fn unarchive_game(state: State<'_, AppState>, id: String) -> Result<(), String> {
+    state
+        .db
+        .set_archived(&id, false)
+        .await
+        .map_err(|e| state.db_failure("unarchive game", e))
+}
+
+#[tauri::command]
+async fn publish_game(state: State<'_, AppState>, id: String) -> Result<(), String> {
+    state
+        .db
+        .publish_game(&id)
+        .await
+        .map_err(|e| state.db_failure("publish game", e))
+}
+
+#[tauri::command]
+async fn unpublish_game(state: State<'_, AppState>, id: String) -> Result<(), String> {
+    state
+        .db
+        .unpublish_game(&id)
+        .await
+        .map_err(|e| state.db_failure("unpublish game", e))
+}
+
+#[tauri::command]
+async fn set_game_thumbnail(
+    state: State<'_, AppState>,
+    game_id: String,
+    version: Option<i64>,
+    png_data_url: String,
+) -> Result<(), String> {
+    let encoded = png_data_url
+        .strip_prefix("data:image/png;base64,")
+        .unwrap_or(&png_data_url);
+    let png = BASE64
+        .decode(encoded)
+        .map_err(|e| state.failure("decode thumbnail", e))?;
+    state
+        .db
+        .set_game_thumbnail(&game_id, version, &png)
+        .await
+        .map_err(|e| state.db_failure("set thumbnail", e))
+}
+
+// Canvas captures from the frontend, downscaled before they're stored
+#[tauri::command]
+async fn capture_game_thumbnail(
+    state: State<'_, AppState>,
+    game_id: String,
+    png_bytes: Vec<u8>,
+) -> Result<(), String> {
+    let png =
+        tauri::async_runtime::spawn_blocking(move || thumbnails::encode_thumbnail(&png_bytes))
+            .await
+            .map_err(|e| state.failure("encode thumbnail", e))?
+            .map_err(|e| state.failure("encode thumbnail", e))?;
+    state
+        .db
+        .set_game_thumbnail(&game_id, None, &png)
+        .await
+        .map_err(|e| state.db_failure("set thumbnail", e))
+}
+
+#[tauri::command]
+async fn get_game_thumbnail(
+    state: State<'_, AppState>,
+    game_id: String,
+    version: Option<i64>,
+) -> Result<Option<String>, String> {
+    let png = state
+        .db
+        .get_game_thumbnail(&game_id, version)
+        .await
+        .map_err(|e| state.db_failure("get thumbnail", e))?;
+    Ok(png.map(|png| format!("data:image/png;base64,{}", BASE64.encode(png))))
+}
+
+#[tauri::command]
+async fn add_tag(
+    state: State<'_, AppState>,
+    game_id: String,
+    tag: String,
+) -> Result<Vec<String>, String> {
+    let tags = state
+        .db
+        .add_tag(&game_id, &tag)
+        .await
+        .map_err(|e| state.db_failure("add tag", e))?;
+    state.save_to_workspace(&game_id).await?;
+    Ok(tags)
+}
+
+#[tauri::command]
+async fn remove_tag(
+    state: State<'_, AppState>,
+    game_id: String,
+    tag: String,
+) -> Result<Vec<String>, String> {
+    let tags = state
+        .db
+        .remove_tag(&game_id, &tag)
+        .await
+        .map_err(|e| state.db_failure("remove tag", e))?;
+    state.save_to_workspace(&game_id).await?;
+    Ok(tags)
+}
+
+#[tauri::command]
+async fn list_tags(state: State<'_, AppState>) -> Result<Vec<db::TagCount>, String> {
+    state
+        .db
+        .list_tags()
+        .await
+        .map_err(|e| state.db_failure("list tags", e))
+}
+
+#[tauri::command]
+async fn search_games(
+    state: State<'_, AppState>,
+    query: String,
+    include_spec: Option<bool>,
+) -> Result<Vec<db::GameSummary>, String> {
+    // Spec contents (objects, emojis, actions, concepts) are searched unless turned off
+    state
+        .db
+        .search_games(&query, include_spec.unwrap_or(true))
+        .await
+        .map_err(|e| state.db_failure("search games", e))
+}
+
+#[tauri::command]
+async fn get_game_versions(
+    state: State<'_, AppState>,
+    game_id: String,
+) -> Result<Vec<db::GameVersion>, String> {
+    state
+        .db
+        .get_game_versions(&game_id)
+        .await
+        .map_err(|e| state.db_failure("get game versions", e))
+}
+
+#[tauri::command]
+async fn get_game_version(
+    state: State<'_, AppState>,
+    game_id: String,
+    version: i64,
+) -> Result<db::GameVersion, String> {
+    state
+        .db
+        .get_game_version(&game_id, version)
+        .await
+        .map_err(|e| state.db_failure("get game version", e))
+}
+
+#[tauri::command]
+async fn submit_high_score(
+    state: State<'_, AppState>,
+    game_id: String,
+    score: i64,
+    player_name: Option<String>,
+) -> Result<db::HighScore, String> {
+    state
+        .db
+        .submit_high_score(&game_id, score, player_name.as_deref())
+        .await
+        .map_err(|e| state.db_failure("submit high score", e))
+}
+
+#[tauri::command]
+async fn get_high_scores(
+    state: State<'_, AppState>,
+    game_id: String,
+    limit: Option<i64>,
+) -> Result<Vec<db::HighScore>, String> {
+    state
+        .db
+        .get_high_scores(&game_id, limit.unwrap_or(10))
+        .await
+        .map_err(|e| state.db_failure("get high scores", e))
+}
+
+// Leaderboard across all games, by medals for each game's top places
+#[tauri::command]
+async fn get_global_leaderboard(
+    state: State<'_, AppState>,
+    limit: Option<usize>,
+) -> Result<Vec<leaderboard::LeaderboardEntry>, String> {
+    let bests = state
+        .db
+        .get_player_bests()
+        .await
+        .map_err(|e| state.db_failure("get leaderboard", e))?;
+    Ok(leaderboard::global_leaderboard(&bests, limit.unwrap_or(10)))
+}
+
+#[tauri::command]
+async fn get_player_stats(
+    state: State<'_, AppState>,
+    player_name: String,
+) -> Result<Option<leaderboard::PlayerStats>, String> {
+    let bests = state
+        .db
+        .get_player_bests()
+        .await
+        .map_err(|e| state.db_failure("get player stats", e))?;
+    Ok(leaderboard::player_stats(&bests, &player_name))
+}
+
+#[tauri::command]
+async fn save_replay(
+    state: State<'_, AppState>,
+    game_id: String,
+    replay: replay::Replay,
+) -> Result<db::SavedReplay, String> {
+    replay
+        .validate()
+        .map_err(|e| state.failure("save replay", e))?;
+    state
+        .db
+        .save_replay(&game_id, replay)
+        .await
+        .map_err(|e| state.db_failure("save replay", e))
+}
+
+#[tauri::command]
+async fn list_replays(
+    state: State<'_, AppState>,
+    game_id: String,
+) -> Result<Vec<db::ReplaySummary>, String> {
+    state
+        .db
+        .list_replays(&game_id)
+        .await
+        .map_err(|e| state.db_failure("list replays", e))
+}
+
+#[tauri::command]
+async fn get_replay(state: State<'_, AppState>, id: i64) -> Result<db::SavedReplay, String> {
+    state
+        .db
+        .get_replay(id)
+        .await
+        .map_err(|e| state.db_failure("get replay", e))
+}
+
+#[tauri::command]
+async fn unlock_achievement(
+    state: State<'_, AppState>,
+    game_id: String,
+    achievement_id: String,
+) -> Result<db::AchievementStatus, String> {
+    state
+        .db
+        .unlock_achievement(&game_id, &achievement_id)
+        .await
+        .map_err(|e| state.db_failure("unlock achievement", e))
+}
+
+#[tauri::command]
+async fn get_achievements(
+    state: State<'_, AppState>,
+    game_id: String,
+) -> Result<Vec<db::AchievementStatus>, String> {
+    state
+        .db
+        .get_achievements(&game_id)
+        .await
+        .map_err(|e| state.db_failure("get achievements", e))
+}
+
+// Library health checks and recovery of unreadable specs
+#[tauri::command]
+async fn check_library(state: State<'_, AppState>) -> Result<db::IntegrityReport, String> {
+    state
+        .db
+        .check_library()
+        .await
+        .map_err(|e| state.db_failure("check library", e))
+}
+
+#[tauri::command]
+async fn list_quarantined(state: State<'_, AppState>) -> Result<Vec<db::QuarantinedSpec>, String> {
+    state
+        .db
+        .list_quarantined()
+        .await
+        .map_err(|e| state.db_failure("list quarantined specs", e))
+}
+
+#[tauri::command]
+async fn export_quarantined(
+    state: State<'_, AppState>,
+    id: i64,
+    path: String,
+) -> Result<(), String> {
+    state
+        .db
+        .export_quarantined(id, &PathBuf::from(path))
+        .await
+        .map_err(|e| state.db_failure("export quarantined spec", e))
+}
+
+#[tauri::command]
+async fn discard_quarantined(state: State<'_, AppState>, id: i64) -> Result<(), String> {
+    state
+        .db
+        .discard_quarantined(id)
+        .await
+        .map_err(|e| state.db_failure("discard quarantined spec", e))
+}
+
+#[tauri::command]
+async fn repair_game(state: State<'_, AppState>, id: String) -> Result<db::GameRecord, String> {
+    let game = state
+        .db
+        .repair_game(&id)
+        .await
+        .map_err(|e| state.db_failure("repair game", e))?;
+    state.save_to_workspace(&game.id).await?;
+    Ok(game)
+}
+
+// Starter templates
+#[tauri::command]
+async fn list_templates(state: State<'_, AppState>) -> Result<Vec<db::GameTemplate>, String> {
+    state
+        .db
+        .list_templates()
+        .await
+        .map_err(|e| state.db_failure("list templates", e))
+}
+
+#[tauri::command]
+async fn create_game_from_template(
+    state: State<'_, AppState>,
+    template_id: String,
+    title: Option<String>,
+) -> Result<db::GameRecord, String> {
+    let game = state
+        .db
+        .create_game_from_template(&template_id, title.as_deref())
+        .await
+        .map_err(|e| state.db_failure("create game from template", e))?;
+    state.save_to_workspace(&game.id).await?;
+    Ok(game)
+}
+
+// App settings, stored as JSON values so each keeps its type
+#[tauri::command]
+async fn get_setting(
+    state: State<'_, AppState>,
+    key: String,
+) -> Result<Option<serde_json::Value>, String> {
+    state
+        .db
+        .get_setting(&key)
+        .await
+        .map_err(|e| state.db_failure("get setting", e))
+}
+
+#[tauri::command]
+async fn set_setting(
+    state: State<'_, AppState>,
+    key: String,
+    value: serde_json::Value,
+) -> Result<(), String> {
+    state
+        .db
+        .set_setting(&key, &value)
+        .await
+        .map_err(|e| state.db_failure("save setting", e))
+}
+
+#[tauri::command]
+async fn get_all_settings(
+    state: State<'_, AppState>,
+) -> Result<BTreeMap<String, serde_json::Value>, String> {
+    state
+        .db
+        .get_all_settings()
+        .await
+        .map_err(|e| state.db_failure("get settings", e))
+}
+
+// Language of the conversation and of error messages
+#[tauri::command]
+fn list_locales() -> Vec<locale::LocaleInfo> {
+    locale::Locale::all()
+}
+
+#[tauri::command]
+fn get_locale(state: State<'_, AppState>) -> locale::Locale {
+    state.locale()
+}
+
+#[tauri::command]
+async fn set_locale(state: State<'_, AppState>, locale: locale::Locale) -> Result<(), String> {
+    state
+        .db
+        .set_setting(locale::LOCALE_SETTING, &locale)
+        .await
+        .map_err(|e| state.db_failure("save language", e))?;
+    *state
+        .locale
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = locale;
+    Ok(())
+}
+
+// Global shortcuts; they trigger the same actions as the matching UI buttons
+#[tauri::command]
+async fn get_shortcuts(state: State<'_, AppState>) -> Result<shortcuts::ShortcutConfig, String> {
+    load_shortcuts(&state).await
+}
+
+#[tauri::command]
+async fn set_shortcuts(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    config: shortcuts::ShortcutConfig,
+) -> Result<(), String> {
+    let previous = load_shortcuts(&state).await?;
+    if let Err(e) = apply_shortcuts(&app, &state, &config) {
+        // Keep the old shortcuts working rather than leaving none registered
+        let _ = apply_shortcuts(&app, &state, &previous);
+        return Err(e);
+    }
+    state
+        .db
+        .set_setting(shortcuts::SHORTCUTS_SETTING, &config)
+        .await
+        .map_err(|e| state.db_failure("save shortcuts", e))
+}
+
+async fn load_shortcuts(state: &AppState) -> Result<shortcuts::ShortcutConfig, String> {
+    state
+        .db
+        .get_setting(shortcuts::SHORTCUTS_SETTING)
+        .await
+        .map(Option::unwrap_or_default)
+        .map_err(|e| state.db_failure("get shortcuts", e))
+}
+
+// Replace the registered shortcuts with the config's
+fn apply_shortcuts(
+    app: &AppHandle,
+    state: &AppState,
+    config: &shortcuts::ShortcutConfig,
+) -> Result<(), String> {
+    if let Some(accelerator) = config.conflict() {
+        return Err(state
+            .locale()
+            .message("'{shortcut}' is bound to more than one action")
+            .replace("{shortcut}", accelerator));
+    }
+    let mut actions = HashMap::new();
+    for (accelerator, action) in config.bindings() {
+        let shortcut: Shortcut = accelerator.parse().map_err(|e| {
+            state
+                .locale()
+                .message("Invalid shortcut '{shortcut}': {error}")
+                .replace("{shortcut}", accelerator)
+                .replace("{error}", &e.to_string())
+        })?;
+        actions.insert(shortcut.id(), action);
+    }
+
+    let global_shortcut = app.global_shortcut();
+    global_shortcut
+        .unregister_all()
+        .map_err(|e| state.failure("unregister shortcuts", e))?;
+    for (accelerator, _) in config.bindings() {
+        global_shortcut
+            .register(accelerator)
+            .map_err(|e| state.failure("register shortcuts", format!("{}: {}", accelerator, e)))?;
+    }
+    state.shortcuts.replace(actions);
+    Ok(())
+}
+
+fn run_shortcut(app: &AppHandle, shortcut: &Shortcut) {
+    let state = app.state::<AppState>();
+    match state.shortcuts.action(shortcut.id()) {
+        Some(shortcuts::ShortcutAction::ShowWindow) => show_main_window(app),
+        Some(shortcuts::ShortcutAction::StopGeneration) => {
+            // The focused window's response, or all of them from the background
+            let focused = app
+                .webview_windows()
+                .into_values()
+                .find(|window| window.is_focused().unwrap_or(false));
+            match focused {
+                Some(window) => {
+                    state.sessions.stop(window.label());
+                }
+                None => state.sessions.stop_all(),
+            }
+        }
+        None => (),
+    }
+}
+
+fn show_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+// Cloud sync; the backend lives in settings and its secret in the keyring
+const SYNC_BACKEND_SETTING: &str = "sync_backend";
+const SYNC_LAST_SYNCED_SETTING: &str = "sync_last_synced_at";
+
+#[tauri::command]
+async fn configure_sync(
+    state: State<'_, AppState>,
+    backend: sync::SyncBackend,
+    secret: String,
+) -> Result<(), String> {
+    secrets::store_sync_secret(&secret).map_err(|e| state.failure("save sync secret", e))?;
+    state
+        .db
+        .set_setting(SYNC_BACKEND_SETTING, &backend)
+        .await
+        .map_err(|e| state.db_failure("save sync settings", e))
+}
+
+#[tauri::command]
+async fn get_sync_backend(state: State<'_, AppState>) -> Result<Option<sync::SyncBackend>, String> {
+    state
+        .db
+        .get_setting(SYNC_BACKEND_SETTING)
+        .await
+        .map_err(|e| state.db_failure("get sync settings", e))
+}
+
+#[tauri::command]
+async fn disable_sync(state: State<'_, AppState>) -> Result<(), String> {
+    secrets::delete_sync_secret().map_err(|e| state.failure("remove sync secret", e))?;
+    state
+        .db
+        .delete_setting(SYNC_BACKEND_SETTING)
+        .await
+        .map_err(|e| state.db_failure("remove sync settings", e))
+}
+
+#[tauri::command]
+async fn sync_library(state: State<'_, AppState>) -> Result<sync::SyncReport, String> {
+    let backend: sync::SyncBackend = state
+        .db
+        .get_setting(SYNC_BACKEND_SETTING)
+        .await
+        .map_err(|e| state.db_failure("get sync settings", e))?
+        .ok_or_else(|| state.locale().message("Sync is not configured"))?;
+    let secret = secrets::load_sync_secret()
+        .map_err(|e| state.failure("read sync secret", e))?
+        .ok_or_else(|| {
+            state
+                .locale()
+                .message("Sync secret is missing; configure sync again")
+        })?;
+
+    let report = sync::sync_library(&state.db, &state.assets, &backend, &secret)
+        .await
+        .map_err(|e| state.failure("sync library", e))?;
+    if !report.pulled.is_empty() {
+        state.scan_workspace().await?;
+    }
+    state
+        .db
+        .set_setting(SYNC_LAST_SYNCED_SETTING, &chrono::Utc::now())
+        .await
+        .map_err(|e| state.db_failure("save sync time", e))?;
+    Ok(report)
+}
+
+// Workspace directory mode; games live as files in the directory and the
+// database indexes them
+#[tauri::command]
+async fn get_workspace_dir(state: State<'_, AppState>) -> Result<Option<String>, String> {
+    Ok(state
+        .workspace
+        .lock()
+        .await
+        .as_ref()
+        .map(|dir| dir.display().to_string()))
+}
+
+// Pass None to go back to keeping games only in the database; files already in
+// the workspace are left where they are
+#[tauri::command]
+async fn set_workspace_dir(
+    state: State<'_, AppState>,
+    dir: Option<String>,
+) -> Result<Option<workspace::ScanReport>, String> {
+    let dir = match dir {
+        Some(dir) => Some(
+            workspace::resolve_dir(&dir)
+                .await
+                .map_err(|e| state.failure("open workspace", e))?,
+        ),
+        None => None,
+    };
+    workspace::clear_index(&state.db)
+        .await
+        .map_err(|e| state.failure("open workspace", e))?;
+    match &dir {
+        Some(dir) => state
+            .db
+            .set_setting(workspace::WORKSPACE_SETTING, &dir.display().to_string())
+            .await
+            .map_err(|e| state.db_failure("save workspace setting", e))?,
+        None => state
+            .db
+            .delete_setting(workspace::WORKSPACE_SETTING)
+            .await
+            .map_err(|e| state.db_failure("remove workspace setting", e))?,
+    }
+    *state.workspace.lock().await = dir;
+    state.scan_workspace().await
+}
+
+// Pick up games added, edited, or deleted outside the app
+#[tauri::command]
+async fn scan_workspace(
+    state: State<'_, AppState>,
+) -> Result<Option<workspace::ScanReport>, String> {
+    state.scan_workspace().await
+}
+
+// Asset library commands for user-provided sprites and sounds
+#[tauri::command]
+async fn import_asset(
+    state: State<'_, AppState>,
+    path: String,
+    key: Option<String>,
+    game_id: Option<String>,
+    asset_type: Option<game_builder::AssetType>,
+) -> Result<assets::LibraryAsset, String> {
+    let asset = state
+        .assets
+        .import_file(&PathBuf::from(path), key)
+        .await
+        .map_err(|e| state.failure("import asset", e))?;
+    if let Some(game_id) = game_id {
+        attach_asset(
+            state,
+            game_id,
+            asset.key.clone(),
+            asset_type.unwrap_or(if assets::is_audio(&asset.file_name) {
+                game_builder::AssetType::Audio
+            } else {
+                game_builder::AssetType::Image
+            }),
+        )
+        .await?;
+    }
+    Ok(asset)
+}
+
+#[tauri::command]
+async fn list_assets(state: State<'_, AppState>) -> Result<Vec<assets::LibraryAsset>, String> {
+    state
+        .assets
+        .list()
+        .await
+        .map_err(|e| state.failure("list assets", e))
+}
+
+#[tauri::command]
+async fn delete_asset(state: State<'_, AppState>, key: String) -> Result<(), String> {
+    state
+        .assets
+        .delete(&key)
+        .await
+        .map_err(|e| state.failure("delete asset", e))
+}
+
+// URL the webview loads a library asset from, served by the pueo-asset protocol
+#[tauri::command]
+fn get_asset_url(key: String) -> String {
+    // Windows webviews only allow custom protocols through http://<scheme>.localhost
+    if cfg!(windows) {
+        format!("http://{}.localhost/{}", assets::PROTOCOL, key)
+    } else {
+        format!("{}://localhost/{}", assets::PROTOCOL, key)
+    }
+}
+
+// Serve a library asset by key. Keys are looked up in the library listing,
+// so requests can't reach files outside the assets directory.
+async fn serve_asset(app: &AppHandle, path: &str) -> tauri::http::Response<Vec<u8>> {
+    use tauri::http::{header, Response, StatusCode};
+
+    let status = |status: StatusCode| {
+        let mut response = Response::new(Vec::new());
+        *response.status_mut() = status;
+        response
+    };
+    let Some(state) = app.try_state::<AppState>() else {
+        return status(StatusCode::SERVICE_UNAVAILABLE);
+    };
+
+    match state.assets.read(path.trim_start_matches('/')).await {
+        Ok((data, content_type)) => {
+            let mut response = Response::new(data);
+            response.headers_mut().insert(
+                header::CONTENT_TYPE,
+                header::HeaderValue::from_static(content_type),
+            );
+            response
+        }
+        Err(assets::AssetError::NotFound(_)) => status(StatusCode::NOT_FOUND),
+        Err(e) => {
+            eprintln!("Failed to serve asset {}: {}", path, e);
+            status(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+// Assets attached to individual games
+#[tauri::command]
+async fn attach_asset(
+    state: State<'_, AppState>,
+    game_id: String,
+    key: String,
+    asset_type: game_builder::AssetType,
+) -> Result<db::GameAsset, String> {
+    let path = state
+        .assets
+        .path_of(&key)
+        .await
+        .map_err(|e| state.failure("attach asset", e))?;
+    let hash = state
+        .assets
+        .hash(&key)
+        .await
+        .map_err(|e| state.failure("attach asset", e))?;
+    let asset = state
+        .db
+        .attach_asset(
+            &game_id,
+            &key,
+            &asset_type,
+            &path.display().to_string(),
+            &hash,
+        )
+        .await
+        .map_err(|e| state.db_failure("attach asset", e))?;
+    state.save_to_workspace(&game_id).await?;
+    Ok(asset)
+}
+
+#[tauri::command]
+async fn list_game_assets(
+    state: State<'_, AppState>,
+    game_id: String,
+) -> Result<Vec<db::GameAsset>, String> {
+    state
+        .db
+        .get_game_assets(&game_id)
+        .await
+        .map_err(|e| state.db_failure("get game assets", e))
+}
+
+#[tauri::command]
+async fn detach_asset(
+    state: State<'_, AppState>,
+    game_id: String,
+    key: String,
+) -> Result<(), String> {
+    state
+        .db
+        .detach_asset(&game_id, &key)
+        .await
+        .map_err(|e| state.db_failure("detach asset", e))?;
+    state.save_to_workspace(&game_id).await
+}
+
+// Portable game files
+#[tauri::command]
+async fn export_game(state: State<'_, AppState>, id: String, path: String) -> Result<(), String> {
+    game_file::export_game(&state.db, &state.assets, &id, &PathBuf::from(path))
+        .await
+        .map_err(|e| state.failure("export game", e))
+}
+
+#[tauri::command]
+async fn import_game(state: State<'_, AppState>, path: String) -> Result<db::GameRecord, String> {
+    let game = game_file::import_game(&state.db, &state.assets, &PathBuf::from(path))
+        .await
+        .map_err(|e| state.failure("import game", e))?;
+    state.save_to_workspace(&game.id).await?;
+    Ok(game)
+}
+
+// Links that share a game's spec, as a pueo:// deep link or, given a base
+// URL, a web link the user can paste back into the app
+#[tauri::command]
+async fn create_share_link(
+    state: State<'_, AppState>,
+    game_id: String,
+    web_base_url: Option<String>,
+) -> Result<String, String> {
+    let game = state
+        .db
+        .get_game(&game_id)
+        .await
+        .map_err(|e| state.db_failure("get game", e))?;
+    game_file::share_link(&game.spec, web_base_url.as_deref())
+        .map_err(|e| state.failure("create share link", e))
+}
+
+#[tauri::command]
+async fn import_share_link(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    link: String,
+) -> Result<db::GameRecord, String> {
+    let game = game_file::import_link(&state.db, &link)
+        .await
+        .map_err(|e| state.failure("import link", e))?;
+    state.save_to_workspace(&game.id).await?;
+    if let Err(e) = app.emit("game-imported", &game) {
+        eprintln!("Failed to emit game-imported: {}", e);
+    }
+    Ok(game)
+}
+
+// Game in a share link, for confirming the import before saving it
+#[tauri::command]
+fn read_share_link(
+    state: State<'_, AppState>,
+    link: String,
+) -> Result<game_builder::PhaserGameSpec, String> {
+    game_file::read_link(&link).map_err(|e| state.failure("read link", e))
+}
+
+// Links opened since the last call; the main window asks before importing each
+#[tauri::command]
+fn take_deep_links(state: State<'_, AppState>) -> Vec<String> {
+    std::mem::take(
+        &mut *state
+            .deep_links
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()),
+    )
+}
+
+// Spec JSON on the system clipboard, for pasting into chat apps and forums
+#[tauri::command]
+async fn copy_spec_to_clipboard(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    game_id: String,
+) -> Result<(), String> {
+    let game = state
+        .db
+        .get_game(&game_id)
+        .await
+        .map_err(|e| state.db_failure("get game", e))?;
+    let text = game_file::spec_text(&game.spec).map_err(|e| state.failure("copy spec", e))?;
+    app.clipboard()
+        .write_text(text)
+        .map_err(|e| state.failure("copy spec", e))
+}
+
+#[tauri::command]
+async fn import_spec_from_clipboard(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<db::GameRecord, String> {
+    let text = app
+        .clipboard()
+        .read_text()
+        .map_err(|e| state.failure("read clipboard", e))?;
+    let game = game_file::import_spec_text(&state.db, &text)
+        .await
+        .map_err(|e| state.failure("import spec", e))?;
+    state.save_to_workspace(&game.id).await?;
+    Ok(game)
+}
+
+// Queue a pueo://import link for the main window, which asks the user before
+// importing it. Links that launched the app wait until the window loads.
+fn handle_deep_link(app: &AppHandle, url: String) {
+    let state = app.state::<AppState>();
+    state
+        .deep_links
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .push(url);
+    show_main_window(app);
+    if let Err(e) = app.emit_to("main", "deep-link-received", ()) {
+        eprintln!("Failed to emit deep-link-received: {}", e);
+    }
+}
+
+// Single-file HTML export that plays in any browser
+#[tauri::command]
+async fn export_standalone_html(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    game_id: String,
+    path: String,
+) -> Result<(), String> {
+    let runtime = load_game_runtime(&app).await?;
+    standalone::export_standalone_html(
+        &state.db,
+        &state.assets,
+        &game_id,
+        &runtime,
+        &PathBuf::from(path),
+    )
+    .await
+    .map_err(|e| state.failure("export game", e))
+}
+
+// Printable handout of a game (HTML with print styles) for classrooms
+#[tauri::command]
+async fn export_game_docs(
+    state: State<'_, AppState>,
+    game_id: String,
+    path: String,
+) -> Result<(), String> {
+    game_docs::export_game_docs(&state.db, &game_id, &PathBuf::from(path))
+        .await
+        .map_err(|e| state.failure("export game docs", e))
+}
+
+// Zip of the page, runtime, spec, and assets for static hosting
+#[tauri::command]
+async fn export_zip_bundle(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    game_id: String,
+    path: String,
+) -> Result<(), String> {
+    let runtime = load_game_runtime(&app).await?;
+    standalone::export_zip_bundle(
+        &state.db,
+        &state.assets,
+        &game_id,
+        &runtime,
+        &PathBuf::from(path),
+    )
+    .await
+    .map_err(|e| state.failure("export game", e))
+}
+
+// The runtime ships with the frontend files; debug builds use the dev server
+// instead of embedded files, so they read it from public/
+async fn load_game_runtime(app: &AppHandle) -> Result<String, String> {
+    if let Some(asset) = app
+        .asset_resolver()
+        .get(standalone::RUNTIME_PATH.to_string())
+    {
+        return Ok(String::from_utf8_lossy(asset.bytes()).into_owned());
+    }
+    if cfg!(debug_assertions) {
+        let dev_path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../public")
+            .join(standalone::RUNTIME_PATH);
+        if let Ok(runtime) = tokio::fs::read_to_string(dev_path).await {
+            return Ok(runtime);
+        }
+    }
+    Err(app
+        .state::<AppState>()
+        .locale()
+        .message("Game runtime is missing; build it with `npm run build:runtime`"))
+}
+
+// Schemas for generating the frontend's TypeScript types
+#[tauri::command]
+fn export_schema() -> serde_json::Value {
+    schema::frontend_schema()
+}
+
+// Readable Phaser 3 JavaScript for a game, for learners moving from the spec to code
+#[tauri::command]
+async fn generate_js_code(state: State<'_, AppState>, game_id: String) -> Result<String, String> {
+    let game = state
+        .db
+        .get_game(&game_id)
+        .await
+        .map_err(|e| state.db_failure("get game", e))?;
+    Ok(codegen::generate_js(&game.spec))
+}
+
+#[tauri::command]
+async fn export_library_json(state: State<'_, AppState>, path: String) -> Result<usize, String> {
+    game_file::export_library_json(&state.db, &PathBuf::from(path))
+        .await
+        .map_err(|e| state.failure("export library", e))
+}
+
+#[tauri::command]
+async fn import_library_json(state: State<'_, AppState>, path: String) -> Result<usize, String> {
+    let count = game_file::import_library_json(&state.db, &PathBuf::from(path))
+        .await
+        .map_err(|e| state.failure("import library", e))?;
+    state.scan_workspace().await?;
+    Ok(count)
+}
+
+// Error reports for support triage
+#[tauri::command]
+async fn list_error_reports(
+    state: State<'_, AppState>,
+    limit: Option<i64>,
+) -> Result<Vec<db::ErrorReport>, String> {
+    state
+        .db
+        .list_error_reports(limit.unwrap_or(50))
+        .await
+        .map_err(|e| state.failure("list error reports", e))
+}
+
+#[tauri::command]
+async fn export_error_report(
+    state: State<'_, AppState>,
+    id: i64,
+    path: String,
+) -> Result<(), String> {
+    state
+        .db
+        .export_error_report(id, &PathBuf::from(path))
+        .await
+        .map_err(|e| state.failure("export error report", e))
+}
+
+// Database encryption, applied by re-keying a copy and restarting the app
+#[tauri::command]
+fn is_database_encrypted(state: State<'_, AppState>) -> Result<bool, String> {
+    secrets::load_db_key()
+        .map(|key| key.is_some())
+        .map_err(|e| state.failure("read database key", e))
+}
+
+#[tauri::command]
+async fn enable_database_encryption(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    passphrase: String,
+) -> Result<(), String> {
+    if passphrase.is_empty() {
+        return Err(state.locale().message("Passphrase cannot be empty"));
+    }
+    // Only store the key once the encrypted copy exists, and only commit the
+    // copy once the key is stored, so the database that opens next startup
+    // always matches the keyring
+    state
+        .db
+        .export_rekeyed(Some(&passphrase))
+        .await
+        .map_err(|e| state.db_failure("encrypt database", e))?;
+
+    if let Err(e) = secrets::store_db_key(&passphrase) {
+        let _ = state.db.discard_rekeyed().await;
+        return Err(state.failure("store database key", e));
+    }
+
+    if let Err(e) = state.db.commit_rekeyed().await {
+        let _ = secrets::delete_db_key();
+        return Err(state.db_failure("encrypt database", e));
+    }
+
+    app.restart()
+}
+
+#[tauri::command]
+async fn disable_database_encryption(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    // Kept to put back if the decrypted copy can't be committed
+    let previous_key = secrets::load_db_key().map_err(|e| state.failure("read database key", e))?;
+
+    state
+        .db
+        .export_rekeyed(None)
+        .await
+        .map_err(|e| state.db_failure("decrypt database", e))?;
+
+    if let Err(e) = secrets::delete_db_key() {
+        let _ = state.db.discard_rekeyed().await;
+        return Err(state.failure("remove database key", e));
+    }
+
+    if let Err(e) = state.db.commit_rekeyed().await {
+        if let Some(key) = previous_key {
+            let _ = secrets::store_db_key(&key);
+        }
+        return Err(state.db_failure("decrypt database", e));
+    }
+
+    app.restart()
+}
+
+// Remove library assets that no stored game or version references or attaches
+#[tauri::command]
+async fn cleanup_unused_assets(state: State<'_, AppState>) -> Result<Vec<String>, String> {
+    let referenced = state
+        .db
+        .referenced_asset_keys()
+        .await
+        .map_err(|e| state.db_failure("load games", e))?;
+
+    state
+        .assets
+        .remove_unreferenced(&referenced)
+        .await
+        .map_err(|e| state.failure("clean up assets", e))
+}
+
+#[cfg_attr(mobile, tauri::mobile_entry_point)]
+pub fn run() {
+    // Try to load .env file (ignore if it doesn't exist)
+    let _ = dotenvy::dotenv();
+
+    tauri::Builder::default()
+        // Must come first so links opened while the app runs reach this instance
+        .plugin(tauri_plugin_single_instance::init(|app, _args, _cwd| {
+            show_main_window(app);
+        }))
+        .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_deep_link::init())
+        .plugin(tauri_plugin_clipboard_manager::init())
+        .plugin(tauri_plugin_notification::init())
+        .plugin(
+            tauri_plugin_global_shortcut::Builder::new()
+                .with_handler(|app, shortcut, event| {
+                    if event.state() == ShortcutState::Pressed {
+                        run_shortcut(app, shortcut);
+                    }
+                })
+                .build(),
+        )
+        .register_asynchronous_uri_scheme_protocol(assets::PROTOCOL, |ctx, request, responder| {
+            let app = ctx.app_handle().clone();
+            tauri::async_runtime::spawn(async move {
+                responder.respond(serve_asset(&app, request.uri().path()).await);
+            });
+        })
+        .setup(|app| {
+            // Encryption is opt-in; without a keyring entry the database is
+            // plain SQLite. A key that can't be read stops startup rather than
+            // opening an encrypted database without it.
+            let db_key = secrets::load_db_key()
+                .map_err(|e| format!("Failed to read the database key from the keyring: {}", e))?;
+
+            // Initialize database in app data directory
+            let app_handle = app.handle();
+            tauri::async_runtime::block_on(async move {
+                let app_data_dir = app_handle
+                    .path()
+                    .app_data_dir()
+                    .expect("Failed to get app data directory");
+
+                let db_path = app_data_dir.join("games.db");
+
+                // Pool size can be raised for heavy use, e.g. PUEO_DB_MAX_CONNECTIONS=10
+                let mut db_options = db::DatabaseOptions::default();
+                if let Some(max) = std::env::var("PUEO_DB_MAX_CONNECTIONS")
+                    .ok()
+                    .and_then(|value| value.parse().ok())
+                {
+                    db_options.max_connections = max;
+                }
+
+                let database = db::Database::new(db_path, db_key.as_deref(), &db_options)
+                    .await
+                    .expect("Failed to initialize database");
+                let database = Arc::new(database);
+
+                // Panics and failures from here on are kept for support triage
+                let error_recorder = reports::ErrorRecorder::start(
+                    database.clone(),
+                    app_data_dir.join("crash-reports.jsonl"),
+                );
+                error_recorder.install_panic_hook();
+
+                if let Err(e) = database.seed_templates(&templates::STARTER_TEMPLATES).await {
+                    eprintln!("Failed to seed starter templates: {}", e);
+                    error_recorder.record(
+                        db::ReportKind::Database,
+                        &format!("Failed to seed starter templates: {}", e),
+                        None,
+                    );
+                }
+
+                let asset_library = assets::AssetLibrary::new(app_data_dir.join("assets"))
+                    .await
+                    .expect("Failed to initialize asset library");
+
+                let locale = database
+                    .get_setting(locale::LOCALE_SETTING)
+                    .await
+                    .unwrap_or_else(|e| {
+                        eprintln!("Failed to read language setting: {}", e);
+                        None
+                    })
+                    .unwrap_or_default();
+                let dev_prompt_path = database
+                    .get_setting(dev_prompt::DEV_PROMPT_SETTING)
+                    .await
+                    .unwrap_or_else(|e| {
+                        eprintln!("Failed to read developer prompt setting: {}", e);
+                        None
+                    });
+
+                // The key is checked in the background so startup doesn't wait on the network
+                let key = api_key::resolve(&database).await.unwrap_or_else(|e| {
+                    eprintln!("Failed to read API key: {}", e);
+                    None
+                });
+                let key_status = match &key {
+                    Some((key, source)) => {
+                        println!("Loaded API key from {:?}", source);
+                        let status = Arc::new(Mutex::new(api_key::KeyStatus::unchecked(*source)));
+                        let checked = status.clone();
+                        let (key, source) = (key.clone(), *source);
+                        tauri::async_runtime::spawn(async move {
+                            let result = api_key::check(&key, source).await;
+                            let mut status = checked.lock().await;
+                            // init_ai may have replaced the key meanwhile
+                            if status.source == Some(source)
+                                && status.valid.is_none()
+                                && status.error.is_none()
+                            {
+                                *status = result;
+                            }
+                        });
+                        status
+                    }
+                    None => Arc::new(Mutex::new(api_key::KeyStatus::default())),
+                };
+
+                app_handle.manage(AppState {
+                    client: Arc::new(Mutex::new(key.map(|(key, _)| anthropic::Client::new(&key)))),
+                    key_status,
+                    db: database,
+                    assets: Arc::new(asset_library),
+                    reports: error_recorder,
+                    sessions: Arc::new(sessions::Sessions::default()),
+                    shortcuts: Arc::new(shortcuts::Bindings::default()),
+                    workspace: Arc::new(Mutex::new(None)),
+                    locale: Arc::new(RwLock::new(locale)),
+                    dev_prompt: Arc::new(std::sync::Mutex::new(dev_prompt::DevPrompt::new(
+                        dev_prompt_path,
+                    ))),
+                    deep_links: Arc::new(std::sync::Mutex::new(Vec::new())),
+                });
+            });
+
+            // Catch up on workspace changes made while the app was closed
+            let handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                let state = handle.state::<AppState>();
+                let dir = match state
+                    .db
+                    .get_setting::<String>(workspace::WORKSPACE_SETTING)
+                    .await
+                {
+                    Ok(dir) => dir,
+                    Err(e) => {
+                        eprintln!("{}", state.db_failure("get workspace setting", e));
+                        return;
+                    }
+                };
+                let Some(dir) = dir else {
+                    return;
+                };
+                *state.workspace.lock().await = Some(PathBuf::from(dir));
+                match state.scan_workspace().await {
+                    Ok(Some(report)) => {
+                        if let Err(e) = handle.emit("workspace-scanned", report) {
+                            eprintln!("Failed to emit workspace scan: {}", e);
+                        }
+                    }
+                    Ok(None) => {}
+                    Err(e) => eprintln!("{}", e),
+                }
+            });
+
+            // A bad saved shortcut shouldn't keep the app from starting
+            let handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                let state = handle.state::<AppState>();
+                let config = load_shortcuts(&state).await.unwrap_or_else(|e| {
+                    eprintln!("{}", e);
+                    shortcuts::ShortcutConfig::default()
+                });
+                if let Err(e) = apply_shortcuts(&handle, &state, &config) {
+                    eprintln!("{}", e);
+                }
+            });
+
+            // Schemes are registered by the installer; dev builds on Linux and
+            // Windows register at runtime instead
+            #[cfg(all(debug_assertions, any(target_os = "linux", windows)))]
+            app.deep_link().register_all()?;
+
+            let handle = app.handle().clone();
+            app.deep_link().on_open_url(move |event| {
+                for url in event.urls() {
+                    handle_deep_link(&handle, url.to_string());
+                }
+            });
+            // A link that launched the app
+            if let Some(urls) = app.deep_link().get_current()? {
+                for url in urls {
+                    handle_deep_link(app.handle(), url.to_string());
+                }
+            }
+
+            Ok(())
+        })
+        .on_window_event(|window, event| {
+            // A closed window's conversation goes with it
+            if let tauri::WindowEvent::Destroyed = event {
+                if let Some(state) = window.try_state::<AppState>() {
+                    state.sessions.remove(window.label());
+                }
+            }
         })
         .invoke_handler(tauri::generate_handler![
             greet,
             is_ai_initialized,
             init_ai,
+            get_key_status,
             stream_chat,
+            get_auto_save,
+            set_auto_save,
+            get_feature_flags,
+            set_feature_flag,
+            stop_generation,
+            open_chat_window,
+            open_game_window,
+            get_session,
             get_game_builder_prompt,
+            get_dev_prompt,
+            set_dev_prompt_path,
+            reload_prompt,
+            validate_spec,
+            lint_spec,
+            normalize_spec,
+            estimate_spec_tokens,
+            get_performance_budget,
+            set_performance_budget,
             save_game,
             get_game,
             update_game,
+            diff_game_versions,
+            get_storage_stats,
+            analyze_spec,
+            label_version,
+            get_version_by_label,
+            prune_versions,
+            revert_game,
+            duplicate_game,
             delete_game,
             list_games,
+            get_notes,
+            set_notes,
+            set_favorite,
+            archive_game,
+            unarchive_game,
+            publish_game,
+            unpublish_game,
+            set_game_thumbnail,
+            capture_game_thumbnail,
+            get_game_thumbnail,
+            add_tag,
+            remove_tag,
+            list_tags,
             search_games,
             get_game_versions,
-            get_game_version
+            get_game_version,
+            submit_high_score,
+            get_high_scores,
+            get_global_leaderboard,
+            get_player_stats,
+            save_replay,
+            list_replays,
+            get_replay,
+            unlock_achievement,
+            get_achievements,
+            list_templates,
+            create_game_from_template,
+            check_library,
+            list_quarantined,
+            export_quarantined,
+            discard_quarantined,
+            repair_game,
+            get_setting,
+            set_setting,
+            get_all_settings,
+            list_locales,
+            get_locale,
+            set_locale,
+            get_shortcuts,
+            set_shortcuts,
+            configure_sync,
+            get_sync_backend,
+            disable_sync,
+            sync_library,
+            get_workspace_dir,
+            set_workspace_dir,
+            scan_workspace,
+            import_asset,
+            list_assets,
+            delete_asset,
+            attach_asset,
+            list_game_assets,
+            detach_asset,
+            cleanup_unused_assets,
+            get_asset_url,
+            export_game,
+            import_game,
+            export_library_json,
+            import_library_json,
+            export_standalone_html,
+            export_zip_bundle,
+            export_game_docs,
+            export_schema,
+            generate_js_code,
+            create_share_link,
+            import_share_link,
+            read_share_link,
+            take_deep_links,
+            copy_spec_to_clipboard,
+            import_spec_from_clipboard,
+            list_error_reports,
+            export_error_report,
+            is_database_encrypted,
+            enable_database_encryption,
+            disable_database_encryption
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,136 @@
+/// Language the game builder talks in and that command errors are reported in
+///
+/// Each non-English locale has a catalog in `locales/<code>.json` holding an
+/// addition to the system prompt (asking the model to converse, and to write
+/// the game's visible text, in that language) and translations of error
+/// messages keyed by their English text. Anything without a translation is
+/// reported in English, so a new message never shows up half-translated.
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::OnceLock;
+
+use crate::game_builder;
+
+/// Setting holding the selected locale
+pub const LOCALE_SETTING: &str = "locale";
+
+/// English template of command failures; `{action}` is a phrase like "save game"
+const FAILURE: &str = "Failed to {action}: {error}";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    En,
+    Es,
+    Fr,
+    De,
+    Pt,
+}
+
+pub const LOCALES: [Locale; 5] = [Locale::En, Locale::Es, Locale::Fr, Locale::De, Locale::Pt];
+
+/// Locale offered in the language picker
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocaleInfo {
+    pub locale: Locale,
+    /// Name of the language in that language, e.g. "Español"
+    pub name: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Catalog {
+    name: String,
+    prompt: String,
+    /// Translations of whole messages, keyed by the English message
+    #[serde(default)]
+    messages: HashMap<String, String>,
+    /// Translations of the action phrases in "Failed to {action}" messages
+    #[serde(default)]
+    actions: HashMap<String, String>,
+}
+
+impl Locale {
+    /// Every locale with its native name
+    pub fn all() -> Vec<LocaleInfo> {
+        LOCALES
+            .iter()
+            .map(|&locale| LocaleInfo {
+                locale,
+                name: locale.name().to_string(),
+            })
+            .collect()
+    }
+
+    pub fn name(self) -> &'static str {
+        self.catalog()
+            .map_or("English", |catalog| catalog.name.as_str())
+    }
+
+    /// Game builder system prompt for conversations in this language
+    pub fn system_prompt(self) -> String {
+        self.with_language(game_builder::get_system_prompt())
+    }
+
+    /// `prompt` followed by the instructions for answering in this language
+    pub fn with_language(self, mut prompt: String) -> String {
+        if let Some(catalog) = self.catalog() {
+            prompt.push_str("\n\n");
+            prompt.push_str(&catalog.prompt);
+        }
+        prompt
+    }
+
+    /// Translation of a fixed message, or the message itself
+    pub fn message(self, english: &str) -> String {
+        self.catalog()
+            .and_then(|catalog| catalog.messages.get(english))
+            .cloned()
+            .unwrap_or_else(|| english.to_string())
+    }
+
+    /// "Failed to {action}: {error}", translated when the action phrase is.
+    /// The error detail itself stays as the underlying library reported it.
+    pub fn failure(self, action: &str, error: impl Display) -> String {
+        let translated = self.catalog().and_then(|catalog| {
+            let action = catalog.actions.get(action)?;
+            let template = catalog.messages.get(FAILURE)?;
+            Some(template.replace("{action}", action))
+        });
+        translated
+            .unwrap_or_else(|| FAILURE.replace("{action}", action))
+            .replace("{error}", &error.to_string())
+    }
+
+    fn catalog(self) -> Option<&'static Catalog> {
+        static CATALOGS: OnceLock<HashMap<Locale, Catalog>> = OnceLock::new();
+        CATALOGS
+            .get_or_init(|| {
+                LOCALES
+                    .iter()
+                    .filter_map(|&locale| {
+                        let source = locale.catalog_source()?;
+                        match serde_json::from_str(source) {
+                            Ok(catalog) => Some((locale, catalog)),
+                            Err(e) => {
+                                eprintln!("Invalid catalog for locale {:?}: {}", locale, e);
+                                None
+                            }
+                        }
+                    })
+                    .collect()
+            })
+            .get(&self)
+    }
+
+    fn catalog_source(self) -> Option<&'static str> {
+        match self {
+            Locale::En => None,
+            Locale::Es => Some(include_str!("../locales/es.json")),
+            Locale::Fr => Some(include_str!("../locales/fr.json")),
+            Locale::De => Some(include_str!("../locales/de.json")),
+            Locale::Pt => Some(include_str!("../locales/pt.json")),
+        }
+    }
+}
@@ -0,0 +1,415 @@
+/// Safe automatic fixes for specs that were imported or edited by hand:
+/// out-of-range values are clamped, missing emoji collision boxes and
+/// background colors are filled in, and duplicate ids get a suffix. Fixes
+/// never change what a valid spec does.
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::collections::HashSet;
+
+use crate::game_builder::{
+    default_bg_color, CollisionBox, CollisionBoxShape, GameObject, ObjectType, PhaserGameSpec,
+    Scene, Surface,
+};
+use crate::spec_migration::{self, MigrationError};
+use crate::validator::{self, Diagnostic, Severity};
+
+/// Emoji size the renderer uses when none is given
+const DEFAULT_EMOJI_SIZE: f64 = 32.0;
+
+/// Collision boxes filled in for emojis cover this fraction of the glyph,
+/// which leaves out the transparent margin around it
+const COLLISION_BOX_FRACTION: f64 = 0.8;
+
+/// Canvas size used when the spec's is zero
+const DEFAULT_WIDTH: u32 = 800;
+const DEFAULT_HEIGHT: u32 = 600;
+
+#[derive(Debug, thiserror::Error)]
+pub enum NormalizeError {
+    #[error("{0}")]
+    Migration(#[from] MigrationError),
+    #[error("Spec can't be read even after fixes: {0}")]
+    Invalid(#[from] serde_json::Error),
+}
+
+/// Change made to a spec
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Fix {
+    /// Location in the spec, e.g. `scenes[main].objects[player]`
+    pub path: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NormalizedSpec {
+    pub spec: PhaserGameSpec,
+    pub fixes: Vec<Fix>,
+    /// Errors the fixes couldn't resolve
+    pub remaining: Vec<Diagnostic>,
+}
+
+#[derive(Default)]
+struct Fixes(Vec<Fix>);
+
+impl Fixes {
+    fn push(&mut self, path: impl Into<String>, message: impl Into<String>) {
+        self.0.push(Fix {
+            path: path.into(),
+            message: message.into(),
+        });
+    }
+}
+
+/// Apply every fix to a spec in JSON form
+pub fn normalize_spec(value: Value) -> Result<NormalizedSpec, NormalizeError> {
+    let mut fixes = Fixes::default();
+
+    // Fixes for fields whose absence keeps the spec from parsing at all
+    let mut value = spec_migration::migrate(value)?;
+    fill_background_color(&mut value, &mut fixes);
+    fill_collision_boxes(&mut value, "", &mut fixes);
+
+    let mut spec: PhaserGameSpec = serde_json::from_value(value)?;
+    fix_game_size(&mut spec, &mut fixes);
+    dedupe_scene_names(&mut spec, &mut fixes);
+    dedupe_achievement_ids(&mut spec, &mut fixes);
+    for scene in &mut spec.scenes {
+        fix_scene(scene, &mut fixes);
+    }
+
+    let remaining = validator::validate_spec(&spec)
+        .into_iter()
+        .filter(|diagnostic| diagnostic.severity == Severity::Error)
+        .collect();
+    Ok(NormalizedSpec {
+        spec,
+        fixes: fixes.0,
+        remaining,
+    })
+}
+
+fn fill_background_color(value: &mut Value, fixes: &mut Fixes) {
+    let Some(game) = value.get_mut("game").and_then(Value::as_object_mut) else {
+        return;
+    };
+    let has_color = game
+        .get("background_color")
+        .and_then(Value::as_str)
+        .is_some_and(|color| !color.trim().is_empty());
+    if !has_color {
+        let color = default_bg_color();
+        fixes.push(
+            "game.background_color",
+            format!("Set the missing background color to {}", color),
+        );
+        game.insert("background_color".to_string(), Value::String(color));
+    }
+}
+
+/// Give every emoji object without a collision box one sized to the emoji
+fn fill_collision_boxes(value: &mut Value, path: &str, fixes: &mut Fixes) {
+    match value {
+        Value::Object(map) => {
+            if map.get("type").and_then(Value::as_str) == Some("emoji") {
+                if let Some(emoji) = map.get_mut("emoji").and_then(Value::as_object_mut) {
+                    fill_collision_box(emoji, path, fixes);
+                }
+            }
+            for (key, child) in map.iter_mut() {
+                fill_collision_boxes(child, &format!("{}.{}", path, key), fixes);
+            }
+        }
+        Value::Array(items) => {
+            for (i, item) in items.iter_mut().enumerate() {
+                // Name elements like the validator does, falling back to the index
+                let name = ["id", "name"]
+                    .iter()
+                    .find_map(|key| item.get(key).and_then(Value::as_str))
+                    .map_or_else(|| i.to_string(), str::to_string);
+                fill_collision_boxes(item, &format!("{}[{}]", path, name), fixes);
+            }
+        }
+        _ => (),
+    }
+}
+
+fn fill_collision_box(emoji: &mut Map<String, Value>, path: &str, fixes: &mut Fixes) {
+    if emoji.get("collision_box").is_some_and(Value::is_object) {
+        return;
+    }
+    let size = emoji
+        .get("size")
+        .and_then(Value::as_f64)
+        .filter(|size| *size > 0.0)
+        .unwrap_or(DEFAULT_EMOJI_SIZE);
+    let radius = (size * COLLISION_BOX_FRACTION / 2.0).round();
+    emoji.insert(
+        "collision_box".to_string(),
+        json!({ "shape": "circle", "radius": radius }),
+    );
+    fixes.push(
+        path.trim_start_matches('.'),
+        format!("Added a circle collision box with radius {}", radius),
+    );
+}
+
+fn fix_game_size(spec: &mut PhaserGameSpec, fixes: &mut Fixes) {
+    if spec.game.width == 0 {
+        spec.game.width = DEFAULT_WIDTH;
+        fixes.push("game.width", format!("Set zero width to {}", DEFAULT_WIDTH));
+    }
+    if spec.game.height == 0 {
+        spec.game.height = DEFAULT_HEIGHT;
+        fixes.push(
+            "game.height",
+            format!("Set zero height to {}", DEFAULT_HEIGHT),
+        );
+    }
+}
+
+fn dedupe_scene_names(spec: &mut PhaserGameSpec, fixes: &mut Fixes) {
+    let mut seen = HashSet::new();
+    for scene in &mut spec.scenes {
+        if let Some(name) = unique(&scene.name, &mut seen) {
+            fixes.push(
+                format!("scenes[{}]", scene.name),
+                format!("Renamed duplicate scene to '{}'", name),
+            );
+            scene.name = name;
+        }
+    }
+}
+
+fn dedupe_achievement_ids(spec: &mut PhaserGameSpec, fixes: &mut Fixes) {
+    let mut seen = HashSet::new();
+    for achievement in spec.achievements.iter_mut().flatten() {
+        if let Some(id) = unique(&achievement.id, &mut seen) {
+            fixes.push(
+                format!("achievements[{}]", achievement.id),
+                format!("Renamed duplicate id to '{}'", id),
+            );
+            achievement.id = id;
+        }
+    }
+}
+
+fn fix_scene(scene: &mut Scene, fixes: &mut Fixes) {
+    let scene_path = format!("scenes[{}]", scene.name);
+
+    let mut object_ids = HashSet::new();
+    for object in &mut scene.objects {
+        if let Some(id) = unique(&object.id, &mut object_ids) {
+            fixes.push(
+                format!("{}.objects[{}]", scene_path, object.id),
+                format!("Renamed duplicate id to '{}'", id),
+            );
+            object.id = id;
+        }
+    }
+    let spawners = scene
+        .custom_logic
+        .iter_mut()
+        .flat_map(|logic| logic.spawners.iter_mut().flatten());
+    let mut spawner_ids = HashSet::new();
+    for spawner in spawners {
+        if let Some(id) = unique(&spawner.id, &mut spawner_ids) {
+            fixes.push(
+                format!("{}.spawners[{}]", scene_path, spawner.id),
+                format!("Renamed duplicate id to '{}'", id),
+            );
+            spawner.id = id;
+        }
+    }
+
+    if let Some(camera) = &mut scene.camera {
+        if let Some(lerp) = &mut camera.lerp {
+            clamp(
+                lerp,
+                0.0,
+                1.0,
+                &format!("{}.camera.lerp", scene_path),
+                fixes,
+            );
+        }
+    }
+    if let Some(procedural) = &mut scene.procedural {
+        let chances = [
+            ("obstacle_density", &mut procedural.obstacle_density),
+            (
+                "collectible_frequency",
+                &mut procedural.collectible_frequency,
+            ),
+        ];
+        for (name, chance) in chances {
+            if let Some(chance) = chance {
+                let path = format!("{}.procedural.{}", scene_path, name);
+                clamp(chance, 0.0, 1.0, &path, fixes);
+            }
+        }
+    }
+
+    for object in &mut scene.objects {
+        let path = format!("{}.objects[{}]", scene_path, object.id);
+        fix_object(object, &path, fixes);
+    }
+    let spawners = scene
+        .custom_logic
+        .iter_mut()
+        .flat_map(|logic| logic.spawners.iter_mut().flatten());
+    for spawner in spawners {
+        let path = format!("{}.spawners[{}].template", scene_path, spawner.id);
+        fix_object(&mut spawner.template, &path, fixes);
+    }
+    for entry in scene
+        .tilemap
+        .iter_mut()
+        .flat_map(|tilemap| &mut tilemap.legend)
+    {
+        let path = format!("{}.tilemap.legend[{}].template", scene_path, entry.symbol);
+        fix_object(&mut entry.template, &path, fixes);
+    }
+    if let Some(procedural) = &mut scene.procedural {
+        let path = format!("{}.procedural", scene_path);
+        fix_object(
+            &mut procedural.platform,
+            &format!("{}.platform", path),
+            fixes,
+        );
+        if let Some(obstacle) = &mut procedural.obstacle {
+            fix_object(obstacle, &format!("{}.obstacle", path), fixes);
+        }
+        if let Some(collectible) = &mut procedural.collectible {
+            fix_object(collectible, &format!("{}.collectible", path), fixes);
+        }
+    }
+}
+
+/// Fix an object and any projectile template it fires
+fn fix_object(object: &mut GameObject, path: &str, fixes: &mut Fixes) {
+    if let Some(alpha) = &mut object.alpha {
+        clamp(alpha, 0.0, 1.0, &format!("{}.alpha", path), fixes);
+    }
+    if let Some(scale) = &mut object.scale {
+        clamp(scale, 0.1, 10.0, &format!("{}.scale", path), fixes);
+    }
+
+    if let Some(shape) = &mut object.shape {
+        let sizes = match object.object_type {
+            ObjectType::Rectangle => {
+                vec![("width", &mut shape.width), ("height", &mut shape.height)]
+            }
+            ObjectType::Circle => vec![("radius", &mut shape.radius)],
+            _ => Vec::new(),
+        };
+        for (name, size) in sizes {
+            if let Some(size) = size.as_mut().filter(|size| **size < 0.0) {
+                *size = size.abs();
+                fixes.push(
+                    format!("{}.shape.{}", path, name),
+                    format!("Made negative {} positive", name),
+                );
+            }
+        }
+    }
+
+    if let Some(emoji) = &mut object.emoji {
+        let size = emoji.size.unwrap_or(DEFAULT_EMOJI_SIZE as f32);
+        if !has_valid_extent(&emoji.collision_box) {
+            let radius = (size * COLLISION_BOX_FRACTION as f32 / 2.0).round();
+            emoji.collision_box = CollisionBox {
+                shape: CollisionBoxShape::Circle,
+                width: None,
+                height: None,
+                radius: Some(radius),
+            };
+            fixes.push(
+                format!("{}.emoji.collision_box", path),
+                format!(
+                    "Replaced unusable collision box with a circle of radius {}",
+                    radius
+                ),
+            );
+        }
+        for layer in emoji.layers.iter_mut().flatten() {
+            if layer.scale.is_some_and(|scale| scale <= 0.0) {
+                layer.scale = None;
+                fixes.push(
+                    format!("{}.emoji.layers[{}]", path, layer.emoji),
+                    "Removed non-positive layer scale",
+                );
+            }
+        }
+    }
+
+    if let Some(physics) = &mut object.physics {
+        let physics_path = format!("{}.physics", path);
+        if physics.mass.is_some_and(|mass| mass <= 0.0) {
+            physics.mass = None;
+            fixes.push(&physics_path, "Removed non-positive mass");
+        }
+        if let Some(drag) = physics.drag.as_mut().filter(|drag| **drag < 0.0) {
+            *drag = 0.0;
+            fixes.push(&physics_path, "Raised negative drag to 0");
+        }
+        if physics.max_velocity.is_some_and(|max| max <= 0.0) {
+            physics.max_velocity = None;
+            fixes.push(&physics_path, "Removed non-positive max_velocity");
+        }
+        if let Some(Surface::Ice {
+            friction: Some(friction),
+        }) = &mut physics.surface
+        {
+            clamp(
+                friction,
+                0.0,
+                1.0,
+                &format!("{}.surface.friction", physics_path),
+                fixes,
+            );
+        }
+    }
+
+    let projectile = object
+        .controls
+        .as_mut()
+        .and_then(|controls| controls.projectile.as_deref_mut());
+    if let Some(projectile) = projectile {
+        fix_object(projectile, &format!("{}.controls.projectile", path), fixes);
+    }
+}
+
+fn has_valid_extent(collision_box: &CollisionBox) -> bool {
+    match collision_box.shape {
+        CollisionBoxShape::Rectangle => {
+            collision_box.width.is_some_and(|width| width > 0.0)
+                && collision_box.height.is_some_and(|height| height > 0.0)
+        }
+        CollisionBoxShape::Circle => collision_box.radius.is_some_and(|radius| radius > 0.0),
+    }
+}
+
+fn clamp(value: &mut f32, min: f32, max: f32, path: &str, fixes: &mut Fixes) {
+    let clamped = value.clamp(min, max);
+    if clamped != *value {
+        fixes.push(
+            path,
+            format!(
+                "Clamped {} to {} (allowed: {} to {})",
+                value, clamped, min, max
+            ),
+        );
+        *value = clamped;
+    }
+}
+
+/// `None` for a name not seen yet; otherwise the first free `name_2`, `name_3`, ...
+fn unique(name: &str, seen: &mut HashSet<String>) -> Option<String> {
+    if seen.insert(name.to_string()) {
+        return None;
+    }
+    let renamed = (2..)
+        .map(|n| format!("{}_{}", name, n))
+        .find(|candidate| !seen.contains(candidate))?;
+    seen.insert(renamed.clone());
+    Some(renamed)
+}
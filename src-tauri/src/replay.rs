@@ -0,0 +1,117 @@
+/// Recorded runs of a game: the random seed the run started with and every
+/// input with its time. Games that only draw randomness from the seed play
+/// out the same way when the inputs are fed back, so a replay can be shared
+/// or used to check that a change didn't break a game.
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Version of the replay format written by this build
+pub const REPLAY_FORMAT_VERSION: u32 = 1;
+
+/// Longest run that can be saved (one hour)
+const MAX_DURATION_MS: u64 = 60 * 60 * 1000;
+
+/// Most inputs a replay may hold; a held key repeats far less often than this
+const MAX_INPUTS: usize = 100_000;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ReplayError {
+    #[error("Replay format version {0} is newer than supported version {REPLAY_FORMAT_VERSION}")]
+    UnsupportedVersion(u32),
+    #[error("Replay is longer than the one hour limit")]
+    TooLong,
+    #[error("Replay has more than {MAX_INPUTS} inputs")]
+    TooManyInputs,
+    #[error("Input {0} happens before the one preceding it")]
+    OutOfOrder(usize),
+    #[error("Input {0} happens after the replay ends")]
+    AfterEnd(usize),
+    #[error("Input {0} has no key")]
+    MissingKey(usize),
+}
+
+fn current_format_version() -> u32 {
+    REPLAY_FORMAT_VERSION
+}
+
+/// Recorded run of a game
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Replay {
+    #[schemars(description = "Replay format version (defaults to the current one)")]
+    #[serde(default = "current_format_version")]
+    pub format_version: u32,
+
+    #[schemars(description = "Seed of the game's random number generator for this run")]
+    pub seed: u32,
+
+    #[schemars(description = "Length of the run in milliseconds")]
+    pub duration_ms: u64,
+
+    #[schemars(description = "Inputs in the order they happened")]
+    pub inputs: Vec<InputEvent>,
+
+    #[schemars(description = "Who played the run")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub player_name: Option<String>,
+
+    #[schemars(description = "Score at the end of the run")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score: Option<i64>,
+}
+
+/// Input at a point in the run
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct InputEvent {
+    #[schemars(description = "Milliseconds since the run started")]
+    pub at_ms: u64,
+
+    #[serde(flatten)]
+    pub input: Input,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Input {
+    #[schemars(description = "Key pressed, named like in controls (e.g., 'LEFT', 'SPACE', 'W')")]
+    KeyDown { key: String },
+
+    #[schemars(description = "Key released")]
+    KeyUp { key: String },
+
+    #[schemars(description = "Click or tap started, in game coordinates")]
+    PointerDown { x: f32, y: f32 },
+
+    #[schemars(description = "Click or tap ended, in game coordinates")]
+    PointerUp { x: f32, y: f32 },
+}
+
+impl Replay {
+    /// Check that the replay can be played back
+    pub fn validate(&self) -> Result<(), ReplayError> {
+        if self.format_version > REPLAY_FORMAT_VERSION {
+            return Err(ReplayError::UnsupportedVersion(self.format_version));
+        }
+        if self.duration_ms > MAX_DURATION_MS {
+            return Err(ReplayError::TooLong);
+        }
+        if self.inputs.len() > MAX_INPUTS {
+            return Err(ReplayError::TooManyInputs);
+        }
+        let mut previous = 0;
+        for (i, event) in self.inputs.iter().enumerate() {
+            if event.at_ms < previous {
+                return Err(ReplayError::OutOfOrder(i));
+            }
+            if event.at_ms > self.duration_ms {
+                return Err(ReplayError::AfterEnd(i));
+            }
+            if let Input::KeyDown { key } | Input::KeyUp { key } = &event.input {
+                if key.trim().is_empty() {
+                    return Err(ReplayError::MissingKey(i));
+                }
+            }
+            previous = event.at_ms;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,111 @@
+/// Records backend failures in the database so they can be exported for support
+use serde::{Deserialize, Serialize};
+use std::backtrace::Backtrace;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+use crate::db::{Database, ReportKind};
+
+/// Failure waiting to be written
+#[derive(Serialize, Deserialize)]
+struct PendingReport {
+    kind: ReportKind,
+    message: String,
+    context: Option<String>,
+    backtrace: Option<String>,
+}
+
+/// Handle for recording failures. Reports are written by a background task,
+/// so recording never blocks the caller.
+#[derive(Clone)]
+pub struct ErrorRecorder {
+    sender: mpsc::UnboundedSender<PendingReport>,
+    /// Panic reports are appended here (one JSON object per line) and moved
+    /// into the database on the next start, since release builds abort on panic
+    crash_log: PathBuf,
+}
+
+impl ErrorRecorder {
+    /// Start writing reports to `db`, first importing panics logged to
+    /// `crash_log` by an earlier run. Must be called inside the async runtime.
+    pub fn start(db: Arc<Database>, crash_log: PathBuf) -> Self {
+        let (sender, mut receiver) = mpsc::unbounded_channel::<PendingReport>();
+        let pending_log = crash_log.clone();
+        tokio::spawn(async move {
+            if let Ok(content) = tokio::fs::read_to_string(&pending_log).await {
+                let reports = content
+                    .lines()
+                    .filter_map(|line| serde_json::from_str::<PendingReport>(line).ok());
+                for report in reports {
+                    save(&db, report).await;
+                }
+                let _ = tokio::fs::remove_file(&pending_log).await;
+            }
+            while let Some(report) = receiver.recv().await {
+                save(&db, report).await;
+            }
+        });
+        Self { sender, crash_log }
+    }
+
+    /// Record a failure, with optional context about what was being done
+    pub fn record(&self, kind: ReportKind, message: &str, context: Option<String>) {
+        self.send(PendingReport {
+            kind,
+            message: message.to_string(),
+            context,
+            backtrace: None,
+        });
+    }
+
+    /// Log panics with a backtrace to the crash log, then run the previous
+    /// hook so they are still printed
+    pub fn install_panic_hook(&self) {
+        let crash_log = self.crash_log.clone();
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            let thread = std::thread::current();
+            let report = PendingReport {
+                kind: ReportKind::Panic,
+                message: info.to_string(),
+                context: Some(format!("thread {}", thread.name().unwrap_or("<unnamed>"))),
+                backtrace: Some(Backtrace::force_capture().to_string()),
+            };
+            if let Err(e) = append_to_log(&crash_log, &report) {
+                eprintln!("Failed to log panic: {}", e);
+            }
+            previous(info);
+        }));
+    }
+
+    fn send(&self, report: PendingReport) {
+        // Only fails once the writer task is gone, when there's nowhere to record to
+        let _ = self.sender.send(report);
+    }
+}
+
+async fn save(db: &Database, report: PendingReport) {
+    let result = db
+        .record_error(
+            report.kind,
+            &report.message,
+            report.context.as_deref(),
+            report.backtrace.as_deref(),
+        )
+        .await;
+    if let Err(e) = result {
+        eprintln!("Failed to record error report: {}", e);
+    }
+}
+
+fn append_to_log(path: &Path, report: &PendingReport) -> std::io::Result<()> {
+    let mut line = serde_json::to_string(report)?;
+    line.push('\n');
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?
+        .write_all(line.as_bytes())
+}
@@ -0,0 +1,53 @@
+/// JSON Schemas of the data the frontend receives, so its TypeScript types
+/// can be generated from the Rust structs instead of kept in sync by hand
+use schemars::{schema_for, JsonSchema, Schema};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+
+use crate::db::GameRecord;
+use crate::game_builder::PhaserGameSpec;
+use crate::replay::Replay;
+use crate::workspace::ScanReport;
+
+/// Payload of the "tool-call" event, sent when the model calls a tool
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ToolCallEvent {
+    pub function: ToolCallFunction,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ToolCallFunction {
+    pub name: String,
+    /// Arguments as sent by the model, e.g. a game spec
+    pub arguments: Value,
+}
+
+/// Schemas of the game spec, the game record commands return, the replay
+/// format, and the payload of every event emitted to the frontend (keyed by
+/// event name)
+pub fn frontend_schema() -> Value {
+    let events: BTreeMap<&str, Schema> = BTreeMap::from([
+        ("chat-token", schema_for!(String)),
+        ("tool-call", schema_for!(ToolCallEvent)),
+        // The tool's result: the generated spec, as JSON text
+        ("tool-result", schema_for!(String)),
+        ("chat-new-turn", schema_for!(())),
+        ("chat-final-response", schema_for!(String)),
+        ("chat-error", schema_for!(String)),
+        ("chat-stopped", schema_for!(())),
+        ("chat-complete", schema_for!(())),
+        // Sent when auto-save stores a generated game
+        ("game-saved", schema_for!(GameRecord)),
+        ("game-imported", schema_for!(GameRecord)),
+        // Sent to the main window when a pueo://import link is opened
+        ("deep-link-received", schema_for!(())),
+        ("workspace-scanned", schema_for!(ScanReport)),
+    ]);
+    json!({
+        "spec": schema_for!(PhaserGameSpec),
+        "game": schema_for!(GameRecord),
+        "replay": schema_for!(Replay),
+        "events": events,
+    })
+}
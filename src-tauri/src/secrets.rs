@@ -0,0 +1,90 @@
+/// Secrets kept in the operating system keyring instead of on disk
+const SERVICE: &str = "pueo";
+
+/// Keyring entry holding the passphrase SQLCipher derives the database key from
+const DB_KEY_USER: &str = "database-key";
+
+/// Keyring entry holding the sync backend's password or secret access key
+const SYNC_SECRET_USER: &str = "sync-secret";
+
+/// Keyring entry holding the Anthropic API key entered in the app
+const API_KEY_USER: &str = "anthropic-api-key";
+
+#[derive(Debug, thiserror::Error)]
+pub enum SecretError {
+    #[error("Keyring error: {0}")]
+    Keyring(#[from] keyring::Error),
+}
+
+fn db_key_entry() -> Result<keyring::Entry, SecretError> {
+    Ok(keyring::Entry::new(SERVICE, DB_KEY_USER)?)
+}
+
+fn sync_secret_entry() -> Result<keyring::Entry, SecretError> {
+    Ok(keyring::Entry::new(SERVICE, SYNC_SECRET_USER)?)
+}
+
+fn api_key_entry() -> Result<keyring::Entry, SecretError> {
+    Ok(keyring::Entry::new(SERVICE, API_KEY_USER)?)
+}
+
+/// Read the database passphrase, if encryption is enabled
+pub fn load_db_key() -> Result<Option<String>, SecretError> {
+    match db_key_entry()?.get_password() {
+        Ok(key) => Ok(Some(key)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Save the database passphrase
+pub fn store_db_key(key: &str) -> Result<(), SecretError> {
+    db_key_entry()?.set_password(key)?;
+    Ok(())
+}
+
+/// Forget the database passphrase
+pub fn delete_db_key() -> Result<(), SecretError> {
+    match db_key_entry()?.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Read the sync backend secret, if sync is configured
+pub fn load_sync_secret() -> Result<Option<String>, SecretError> {
+    match sync_secret_entry()?.get_password() {
+        Ok(secret) => Ok(Some(secret)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Save the sync backend secret
+pub fn store_sync_secret(secret: &str) -> Result<(), SecretError> {
+    sync_secret_entry()?.set_password(secret)?;
+    Ok(())
+}
+
+/// Forget the sync backend secret
+pub fn delete_sync_secret() -> Result<(), SecretError> {
+    match sync_secret_entry()?.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Read the API key, if one was entered in the app
+pub fn load_api_key() -> Result<Option<String>, SecretError> {
+    match api_key_entry()?.get_password() {
+        Ok(key) => Ok(Some(key)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Save the API key
+pub fn store_api_key(key: &str) -> Result<(), SecretError> {
+    api_key_entry()?.set_password(key)?;
+    Ok(())
+}
@@ -0,0 +1,138 @@
+/// Conversation state of each app window. Windows share the database and AI
+/// client, but each runs its own chat, so one window streaming a response
+/// never blocks or leaks into another.
+use serde::Serialize;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::Notify;
+
+/// Label prefix of extra builder windows
+pub const CHAT_WINDOW_PREFIX: &str = "chat-";
+
+/// Label prefix of windows that play a single game
+pub const GAME_WINDOW_PREFIX: &str = "game-";
+
+#[derive(Debug, thiserror::Error)]
+pub enum SessionError {
+    #[error("A response is already streaming in this window")]
+    Busy,
+}
+
+/// What a window is doing
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Session {
+    /// Whether a chat response is streaming into the window
+    pub streaming: bool,
+}
+
+#[derive(Default)]
+struct Entry {
+    session: Session,
+    /// Wakes the running stream when it should stop
+    stop: Option<Arc<Notify>>,
+}
+
+/// Sessions keyed by window label
+#[derive(Default)]
+pub struct Sessions {
+    sessions: Mutex<HashMap<String, Entry>>,
+    next_window: AtomicUsize,
+}
+
+impl Sessions {
+    /// Session of the window, or an idle one if it hasn't started anything
+    pub fn get(&self, label: &str) -> Session {
+        self.lock()
+            .get(label)
+            .map(|entry| entry.session.clone())
+            .unwrap_or_default()
+    }
+
+    /// Mark the window as streaming until the returned guard is dropped
+    pub fn begin_stream(self: &Arc<Self>, label: &str) -> Result<StreamGuard, SessionError> {
+        let mut sessions = self.lock();
+        let entry = sessions.entry(label.to_string()).or_default();
+        if entry.session.streaming {
+            return Err(SessionError::Busy);
+        }
+        let stop = Arc::new(Notify::new());
+        entry.session.streaming = true;
+        entry.stop = Some(stop.clone());
+        Ok(StreamGuard {
+            sessions: self.clone(),
+            label: label.to_string(),
+            stop,
+        })
+    }
+
+    /// Stop the window's stream. Returns false if nothing was streaming.
+    pub fn stop(&self, label: &str) -> bool {
+        match self.lock().get(label).and_then(|entry| entry.stop.as_ref()) {
+            Some(stop) => {
+                stop.notify_one();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Stop every window's stream
+    pub fn stop_all(&self) {
+        for stop in self.lock().values().filter_map(|entry| entry.stop.as_ref()) {
+            stop.notify_one();
+        }
+    }
+
+    /// Forget a closed window
+    pub fn remove(&self, label: &str) {
+        self.lock().remove(label);
+    }
+
+    /// Label for a new builder window
+    pub fn next_chat_label(&self) -> String {
+        let n = self.next_window.fetch_add(1, Ordering::Relaxed) + 1;
+        format!("{}{}", CHAT_WINDOW_PREFIX, n)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Entry>> {
+        // A panic while holding the lock leaves the map itself intact
+        self.sessions
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Ends a window's stream when dropped, including on early returns
+pub struct StreamGuard {
+    sessions: Arc<Sessions>,
+    label: String,
+    stop: Arc<Notify>,
+}
+
+impl StreamGuard {
+    /// Resolves once the stream is asked to stop
+    pub fn stopped(&self) -> impl Future<Output = ()> + Send + 'static {
+        let stop = self.stop.clone();
+        async move { stop.notified().await }
+    }
+}
+
+impl Drop for StreamGuard {
+    fn drop(&mut self) {
+        if let Some(entry) = self.sessions.lock().get_mut(&self.label) {
+            entry.session.streaming = false;
+            entry.stop = None;
+        }
+    }
+}
+
+/// Label of the window playing `game_id`; only characters Tauri allows in labels are kept
+pub fn game_window_label(game_id: &str) -> String {
+    let id: String = game_id
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_'))
+        .collect();
+    format!("{}{}", GAME_WINDOW_PREFIX, id)
+}
@@ -0,0 +1,75 @@
+/// Global keyboard shortcuts, which work even while the app is in the background
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+
+/// Setting holding the user's `ShortcutConfig`
+pub const SHORTCUTS_SETTING: &str = "global_shortcuts";
+
+/// What a shortcut does
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShortcutAction {
+    /// Bring the main window to the front
+    ShowWindow,
+    /// Stop the response streaming into the focused window, or every window
+    /// when the app is in the background
+    StopGeneration,
+}
+
+/// Accelerator bound to each action, e.g. `CommandOrControl+Shift+P`; `None`
+/// leaves the action unbound. Nothing is bound until the user picks a
+/// shortcut, since a global shortcut takes the keys from every other app.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ShortcutConfig {
+    pub show_window: Option<String>,
+    pub stop_generation: Option<String>,
+}
+
+impl ShortcutConfig {
+    /// Bound accelerators and their actions
+    pub fn bindings(&self) -> Vec<(&str, ShortcutAction)> {
+        [
+            (&self.show_window, ShortcutAction::ShowWindow),
+            (&self.stop_generation, ShortcutAction::StopGeneration),
+        ]
+        .into_iter()
+        .filter_map(|(accelerator, action)| {
+            let accelerator = accelerator.as_deref()?.trim();
+            (!accelerator.is_empty()).then_some((accelerator, action))
+        })
+        .collect()
+    }
+
+    /// First accelerator bound to more than one action
+    pub fn conflict(&self) -> Option<&str> {
+        let mut seen = HashSet::new();
+        self.bindings()
+            .into_iter()
+            .map(|(accelerator, _)| accelerator)
+            .find(|accelerator| !seen.insert(accelerator.to_lowercase().replace(' ', "")))
+    }
+}
+
+/// Actions of the registered shortcuts, by shortcut id
+#[derive(Default)]
+pub struct Bindings {
+    actions: Mutex<HashMap<u32, ShortcutAction>>,
+}
+
+impl Bindings {
+    pub fn replace(&self, actions: HashMap<u32, ShortcutAction>) {
+        *self.lock() = actions;
+    }
+
+    pub fn action(&self, id: u32) -> Option<ShortcutAction> {
+        self.lock().get(&id).copied()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<u32, ShortcutAction>> {
+        self.actions
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
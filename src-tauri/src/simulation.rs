@@ -0,0 +1,875 @@
+/// Dry runs of a game spec without a browser: builds the scene's object graph,
+/// steps spawners and timers on a logical clock, and reports problems that only
+/// show up while the game runs
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BinaryHeap, HashMap, HashSet};
+
+use crate::game_builder::{
+    ActionEffect, BehaviorType, CollisionMode, CustomLogic, GameObject, ObjectType, PhaserGameSpec,
+    PhysicsBody, RecyclePolicy, Scene, Spawner,
+};
+use crate::validator::{group_names, parse_handler, parse_timer, Diagnostic, Severity};
+
+/// Length of the dry run behind `lint_spec`, in ms
+pub const LINT_DURATION_MS: u32 = 60_000;
+
+/// Live spawned objects past this count, with nothing removing them, are reported
+const PILE_UP_LIMIT: u32 = 100;
+
+/// Score triggers may run actions that score again; chains deeper than this stop
+const MAX_TRIGGER_DEPTH: usize = 16;
+
+/// Outcome of dry running every scene of a spec
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Simulation {
+    pub scenes: Vec<SceneRun>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// What happened in one scene without any player input
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SceneRun {
+    pub scene: String,
+    /// Objects created by each spawner
+    pub spawned: BTreeMap<String, u32>,
+    /// Times each action ran from start actions, timers, and score triggers
+    pub actions_run: BTreeMap<String, u32>,
+    /// Score earned by those actions
+    pub score: i64,
+    /// Time (ms) and action that ended the scene, if one did
+    pub ended: Option<(u32, String)>,
+}
+
+/// Dry run every scene for `duration_ms` and report anything that can't work
+pub fn simulate(spec: &PhaserGameSpec, duration_ms: u32) -> Simulation {
+    let mut diagnostics = Vec::new();
+    let scenes = spec
+        .scenes
+        .iter()
+        .map(|scene| SceneSim::new(spec, scene).run(duration_ms, &mut diagnostics))
+        .collect();
+    Simulation {
+        scenes,
+        diagnostics,
+    }
+}
+
+/// Problems found by a one-minute dry run of the spec
+pub fn lint_spec(spec: &PhaserGameSpec) -> Vec<Diagnostic> {
+    simulate(spec, LINT_DURATION_MS).diagnostics
+}
+
+/// Something a handler can name: a scene object or a group
+#[derive(Debug, Clone, Copy)]
+enum Party<'a> {
+    Object(&'a GameObject),
+    /// Spawned copies of a template
+    Spawned(&'a GameObject),
+    /// Tiles placed from a legend template
+    Tiles(&'a GameObject, u32),
+    /// Platforms, obstacles, or collectibles of a procedural level
+    Procedural(&'a GameObject),
+    Projectiles,
+}
+
+/// How many times something can happen; `None` is unbounded
+type Count = Option<u64>;
+
+fn add_counts(a: Count, b: Count) -> Count {
+    Some(a?.saturating_add(b?))
+}
+
+struct SceneSim<'a> {
+    spec: &'a PhaserGameSpec,
+    scene: &'a Scene,
+    path: String,
+    actions: HashMap<&'a str, &'a ActionEffect>,
+    spawners: Vec<&'a Spawner>,
+}
+
+/// Spawner state on the logical clock
+struct SpawnerClock {
+    interval: u32,
+    /// Bumped when the interval changes, so stale spawn events are dropped
+    generation: u32,
+    paused: bool,
+    spawned: u32,
+    live: u32,
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Event {
+    Timer { index: usize, every: Option<u32> },
+    Spawn { index: usize, generation: u32 },
+}
+
+impl<'a> SceneSim<'a> {
+    fn new(spec: &'a PhaserGameSpec, scene: &'a Scene) -> Self {
+        let logic = scene.custom_logic.as_ref();
+        let actions = logic
+            .iter()
+            .flat_map(|logic| logic.actions.iter().flatten())
+            .map(|action| (action.name.as_str(), &action.effect))
+            .collect();
+        let spawners = logic
+            .iter()
+            .flat_map(|logic| logic.spawners.iter().flatten())
+            .collect();
+        Self {
+            spec,
+            scene,
+            path: format!("scenes[{}]", scene.name),
+            actions,
+            spawners,
+        }
+    }
+
+    fn run(&self, duration_ms: u32, diagnostics: &mut Vec<Diagnostic>) -> SceneRun {
+        let mut warn = |path: String, message: String| {
+            diagnostics.push(Diagnostic {
+                severity: Severity::Warning,
+                path,
+                message,
+            })
+        };
+
+        let run = self.step_clock(duration_ms);
+        let reachable = self.reachable_actions(&run);
+        for spawner in &self.spawners {
+            let path = format!("{}.spawners[{}]", self.path, spawner.id);
+            if let Some(message) = self.check_spawn_position(spawner) {
+                warn(path.clone(), message);
+            }
+            let never_spawns = run.spawned.get(&spawner.id) == Some(&0)
+                && !self.started_by(spawner, &reachable)
+                && run.ended.is_none();
+            if never_spawns {
+                warn(
+                    path,
+                    format!("Never spawns in the first {} seconds", duration_ms / 1000),
+                );
+            }
+        }
+
+        for (kind, handler) in self.handlers() {
+            let path = format!("{}.{}", self.path, kind);
+            let Some((first, second, _)) = parse_handler(handler) else {
+                continue;
+            };
+            if let Some(reason) = self.why_never_fires(first, second, &run, &reachable) {
+                warn(
+                    path,
+                    format!("Handler '{}' can never fire: {}", handler, reason),
+                );
+            }
+        }
+
+        let max_score = self.max_score(&reachable);
+        for trigger in self
+            .scene
+            .custom_logic
+            .iter()
+            .flat_map(|logic| logic.on_score_reached.iter().flatten())
+        {
+            if let Some(max) = max_score {
+                if u64::from(trigger.at) > max {
+                    warn(
+                        format!("{}.on_score_reached", self.path),
+                        format!(
+                            "Score {} is never reached; at most {} points can be scored",
+                            trigger.at, max
+                        ),
+                    );
+                }
+            }
+        }
+
+        let mut wins: Vec<&str> = self
+            .actions
+            .iter()
+            .filter(|(_, effect)| matches!(effect, ActionEffect::Win { .. }))
+            .map(|(name, _)| *name)
+            .collect();
+        wins.sort();
+        for name in wins {
+            if !reachable.contains(name) {
+                warn(
+                    format!("{}.actions[{}]", self.path, name),
+                    "Win condition can never be reached; nothing that can happen runs it"
+                        .to_string(),
+                );
+            }
+        }
+
+        for (index, spawner) in self.spawners.iter().enumerate() {
+            let live = run.live[index];
+            let template = spawner.template.id.as_str();
+            if live > PILE_UP_LIMIT && !self.can_remove(template, &run, &reachable) {
+                warn(
+                    format!("{}.spawners[{}]", self.path, spawner.id),
+                    format!(
+                        "{} '{}' objects are alive after {} seconds and nothing removes them",
+                        live,
+                        template,
+                        duration_ms / 1000
+                    ),
+                );
+            }
+        }
+
+        SceneRun {
+            scene: self.scene.name.clone(),
+            spawned: run.spawned,
+            actions_run: run.actions_run,
+            score: run.score,
+            ended: run.ended,
+        }
+    }
+
+    fn handlers(&self) -> Vec<(&'static str, &'a String)> {
+        self.scene
+            .custom_logic
+            .iter()
+            .flat_map(|logic| {
+                let collisions = logic.on_collision.iter().flatten();
+                let overlaps = logic.on_overlap.iter().flatten();
+                collisions
+                    .map(|handler| ("on_collision", handler))
+                    .chain(overlaps.map(|handler| ("on_overlap", handler)))
+            })
+            .collect()
+    }
+
+    /// Step start actions, timers, and spawners until `duration_ms` or until an
+    /// action ends the scene
+    fn step_clock(&self, duration_ms: u32) -> ClockRun {
+        let logic = self.scene.custom_logic.as_ref();
+        let timers: Vec<(u32, bool, &str)> = logic
+            .iter()
+            .flat_map(|logic| logic.timers.iter().flatten())
+            .filter_map(|timer| {
+                let (delay, action) = parse_timer(timer)?;
+                let every = timer.trim_start().starts_with("every");
+                (delay > 0).then_some((delay, every, action))
+            })
+            .collect();
+        let mut run = ClockRun {
+            clocks: self
+                .spawners
+                .iter()
+                .map(|spawner| SpawnerClock {
+                    interval: spawner.interval,
+                    generation: 0,
+                    paused: false,
+                    spawned: 0,
+                    live: 0,
+                })
+                .collect(),
+            ..ClockRun::default()
+        };
+
+        let mut queue = BinaryHeap::new();
+        for (index, (delay, every, _)) in timers.iter().enumerate() {
+            let every = every.then_some(*delay);
+            queue.push(Reverse((*delay, Event::Timer { index, every })));
+        }
+        for (index, clock) in run.clocks.iter().enumerate() {
+            if clock.interval > 0 {
+                queue.push(Reverse((
+                    clock.interval,
+                    Event::Spawn {
+                        index,
+                        generation: 0,
+                    },
+                )));
+            }
+        }
+
+        for action in logic
+            .iter()
+            .flat_map(|logic| logic.on_start.iter().flatten())
+        {
+            self.run_action(action, 0, &mut run, &mut queue, 0);
+        }
+        while let Some(Reverse((time, event))) = queue.pop() {
+            if time > duration_ms || run.ended.is_some() {
+                break;
+            }
+            match event {
+                Event::Timer { index, every } => {
+                    self.run_action(timers[index].2, time, &mut run, &mut queue, 0);
+                    // A time past u32 is past any duration, so the event is dropped
+                    if let Some(next) = every.and_then(|every| time.checked_add(every)) {
+                        queue.push(Reverse((next, Event::Timer { index, every })));
+                    }
+                }
+                Event::Spawn { index, generation } => {
+                    let clock = &mut run.clocks[index];
+                    if generation != clock.generation {
+                        continue;
+                    }
+                    let spawner = self.spawners[index];
+                    let under_max = spawner.max_count.is_none_or(|max| clock.spawned < max);
+                    let pool = spawner.pool.as_ref();
+                    let pool_full = pool.is_some_and(|pool| clock.live >= pool.max_live);
+                    let recycles =
+                        pool.is_some_and(|pool| matches!(pool.recycle, RecyclePolicy::Oldest));
+                    if !clock.paused && under_max && (!pool_full || recycles) {
+                        clock.spawned += 1;
+                        if !pool_full {
+                            clock.live += 1;
+                        }
+                    }
+                    if let Some(next) = time.checked_add(clock.interval) {
+                        queue.push(Reverse((next, Event::Spawn { index, generation })));
+                    }
+                }
+            }
+        }
+
+        for (spawner, clock) in self.spawners.iter().zip(&run.clocks) {
+            run.spawned.insert(spawner.id.clone(), clock.spawned);
+            run.live.push(clock.live);
+        }
+        run
+    }
+
+    fn run_action(
+        &self,
+        name: &str,
+        time: u32,
+        run: &mut ClockRun,
+        queue: &mut BinaryHeap<Reverse<(u32, Event)>>,
+        depth: usize,
+    ) {
+        let Some(effect) = self.actions.get(name) else {
+            return;
+        };
+        if run.ended.is_some() || depth > MAX_TRIGGER_DEPTH {
+            return;
+        }
+        *run.actions_run.entry(name.to_string()).or_default() += 1;
+        let spawner_index = |id: &str| self.spawners.iter().position(|spawner| spawner.id == id);
+        match effect {
+            ActionEffect::UpdateScore { points } => {
+                let previous = run.score;
+                run.score += i64::from(*points);
+                let triggers = self
+                    .scene
+                    .custom_logic
+                    .iter()
+                    .flat_map(|logic| logic.on_score_reached.iter().flatten());
+                for trigger in triggers {
+                    let at = i64::from(trigger.at.max(1));
+                    let fires = if trigger.repeat {
+                        run.score.div_euclid(at) > previous.div_euclid(at)
+                    } else {
+                        previous < at && run.score >= at
+                    };
+                    if fires {
+                        self.run_action(&trigger.action, time, run, queue, depth + 1);
+                    }
+                }
+            }
+            ActionEffect::GameOver { .. }
+            | ActionEffect::Win { .. }
+            | ActionEffect::RestartScene => {
+                run.ended = Some((time, name.to_string()));
+            }
+            ActionEffect::StartSpawner { spawner_id } => {
+                if let Some(index) = spawner_index(spawner_id) {
+                    run.clocks[index].paused = false;
+                }
+            }
+            ActionEffect::StopSpawner { spawner_id } => {
+                if let Some(index) = spawner_index(spawner_id) {
+                    run.clocks[index].paused = true;
+                }
+            }
+            ActionEffect::SetSpawnerInterval {
+                spawner_id,
+                interval,
+            } => {
+                if let Some(index) = spawner_index(spawner_id) {
+                    let clock = &mut run.clocks[index];
+                    clock.generation += 1;
+                    clock.interval = *interval;
+                    if let Some(next) = time.checked_add(*interval).filter(|_| *interval > 0) {
+                        queue.push(Reverse((
+                            next,
+                            Event::Spawn {
+                                index,
+                                generation: clock.generation,
+                            },
+                        )));
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /// Resolve a name used by a handler
+    fn party(&self, name: &str) -> Option<Party<'a>> {
+        if let Some(object) = self.scene.objects.iter().find(|object| object.id == name) {
+            return Some(Party::Object(object));
+        }
+        if let Some(spawner) = self
+            .spawners
+            .iter()
+            .find(|spawner| spawner.template.id == name)
+        {
+            return Some(Party::Spawned(&spawner.template));
+        }
+        if let Some(tilemap) = &self.scene.tilemap {
+            if let Some(entry) = tilemap
+                .legend
+                .iter()
+                .find(|entry| entry.template.id == name)
+            {
+                let tiles = tilemap
+                    .rows
+                    .iter()
+                    .flat_map(|row| row.chars())
+                    .filter(|symbol| *symbol == entry.symbol)
+                    .count();
+                return Some(Party::Tiles(&entry.template, tiles as u32));
+            }
+        }
+        if let Some(procedural) = &self.scene.procedural {
+            let templates = [
+                Some(&procedural.platform),
+                procedural.obstacle.as_ref(),
+                procedural.collectible.as_ref(),
+            ];
+            if let Some(template) = templates
+                .into_iter()
+                .flatten()
+                .find(|template| template.id == name)
+            {
+                return Some(Party::Procedural(template));
+            }
+        }
+        (name == "projectiles" && group_names(self.scene).contains("projectiles"))
+            .then_some(Party::Projectiles)
+    }
+
+    /// Why a handler between `first` and `second` can never fire, if it can't
+    fn why_never_fires(
+        &self,
+        first: &str,
+        second: &str,
+        run: &ClockRun,
+        reachable: &HashSet<&str>,
+    ) -> Option<String> {
+        // Unknown names are already reported by the validator
+        let parties = [(first, self.party(first)?), (second, self.party(second)?)];
+        for (name, party) in parties {
+            if let Some(template) = party_template(party) {
+                if !has_body(template) {
+                    return Some(format!("'{}' has no physics body", name));
+                }
+            }
+            match party {
+                Party::Spawned(template) if !self.ever_spawns(template, run, reachable) => {
+                    return Some(format!("no '{}' is ever spawned", name));
+                }
+                Party::Tiles(_, 0) => {
+                    return Some(format!("the tilemap places no '{}' tiles", name));
+                }
+                _ => (),
+            }
+        }
+        let moves = |party: Party| match party {
+            // Group members are added at runtime, so they may move
+            Party::Projectiles => true,
+            Party::Object(object) if matches!(object.object_type, ObjectType::Group) => true,
+            Party::Object(object)
+            | Party::Spawned(object)
+            | Party::Tiles(object, _)
+            | Party::Procedural(object) => self.moves(object),
+        };
+        if !moves(parties[0].1) && !moves(parties[1].1) {
+            return Some(format!("neither '{}' nor '{}' ever moves", first, second));
+        }
+        None
+    }
+
+    fn ever_spawns(
+        &self,
+        template: &GameObject,
+        run: &ClockRun,
+        reachable: &HashSet<&str>,
+    ) -> bool {
+        self.spawners
+            .iter()
+            .filter(|spawner| spawner.template.id == template.id)
+            .any(|spawner| {
+                run.spawned.get(&spawner.id).is_some_and(|count| *count > 0)
+                    || (spawner.max_count != Some(0) && self.started_by(spawner, reachable))
+            })
+    }
+
+    /// Whether a reachable action starts the spawner
+    fn started_by(&self, spawner: &Spawner, reachable: &HashSet<&str>) -> bool {
+        reachable.iter().any(|name| {
+            matches!(
+                self.actions.get(name),
+                Some(ActionEffect::StartSpawner { spawner_id }) if *spawner_id == spawner.id
+            )
+        })
+    }
+
+    /// Whether the object can move on its own, under gravity, or by the player
+    fn moves(&self, object: &GameObject) -> bool {
+        let Some(physics) = &object.physics else {
+            return false;
+        };
+        if !matches!(physics.body, PhysicsBody::Dynamic) {
+            return false;
+        }
+        let world = &self.spec.game.physics.gravity;
+        let gravity = physics.gravity.as_ref().unwrap_or(world);
+        let has_velocity = physics
+            .velocity
+            .as_ref()
+            .is_some_and(|velocity| velocity.x != 0.0 || velocity.y != 0.0);
+        let has_behavior = object
+            .behavior
+            .as_ref()
+            .is_some_and(|behavior| !matches!(behavior, BehaviorType::Static));
+        has_velocity
+            || gravity.x != 0.0
+            || gravity.y != 0.0
+            || object.controls.is_some()
+            || has_behavior
+    }
+
+    /// Actions that something in the scene can run, found by following
+    /// triggers until nothing new becomes reachable
+    fn reachable_actions(&self, run: &ClockRun) -> HashSet<&'a str> {
+        let Some(logic) = &self.scene.custom_logic else {
+            return HashSet::new();
+        };
+        let mut reachable: HashSet<&str> = HashSet::new();
+        let direct = logic
+            .on_start
+            .iter()
+            .flatten()
+            .map(String::as_str)
+            .chain(
+                logic
+                    .timers
+                    .iter()
+                    .flatten()
+                    .filter_map(|timer| parse_timer(timer).map(|(_, action)| action)),
+            )
+            .chain(
+                logic
+                    .on_key_press
+                    .iter()
+                    .flatten()
+                    .map(|trigger| trigger.action.as_str()),
+            )
+            .chain(
+                self.scene
+                    .countdown
+                    .iter()
+                    .map(|countdown| countdown.on_expire.as_str()),
+            )
+            .chain(self.scene.all_objects().into_iter().flat_map(|object| {
+                let zone = object
+                    .zone
+                    .iter()
+                    .flat_map(|zone| zone.on_enter.iter().chain(zone.on_exit.iter()));
+                object.on_click.iter().chain(zone).map(String::as_str)
+            }))
+            .chain(
+                logic
+                    .collision_matrix
+                    .iter()
+                    .flatten()
+                    .filter(|rule| rule.mode != CollisionMode::Ignore)
+                    .filter_map(|rule| rule.action.as_deref()),
+            );
+        reachable.extend(direct);
+
+        loop {
+            let before = reachable.len();
+            for (_, handler) in self.handlers() {
+                if let Some((first, second, callback)) = parse_handler(handler) {
+                    if self
+                        .why_never_fires(first, second, run, &reachable)
+                        .is_none()
+                    {
+                        reachable.insert(callback);
+                    }
+                }
+            }
+            let max_score = self.max_score(&reachable);
+            for trigger in logic.on_score_reached.iter().flatten() {
+                if max_score.is_none_or(|max| u64::from(trigger.at) <= max) {
+                    reachable.insert(trigger.action.as_str());
+                }
+            }
+            let destroys = reachable.iter().any(|name| {
+                matches!(
+                    self.actions.get(name),
+                    Some(
+                        ActionEffect::Destroy
+                            | ActionEffect::UpdateScore { .. }
+                            | ActionEffect::Damage { .. }
+                    )
+                )
+            }) || reachable.contains("destroy");
+            if destroys {
+                for trigger in logic.on_destroyed.iter().flatten() {
+                    reachable.insert(trigger.action.as_str());
+                }
+            }
+            if reachable.len() == before {
+                break;
+            }
+        }
+        reachable.retain(|name| self.actions.contains_key(name));
+        reachable
+    }
+
+    /// Most points the reachable actions can award, or `None` if unbounded
+    fn max_score(&self, reachable: &HashSet<&str>) -> Count {
+        let Some(logic) = &self.scene.custom_logic else {
+            return Some(0);
+        };
+        let mut total = Some(0);
+        for name in reachable {
+            let Some(ActionEffect::UpdateScore { points }) = self.actions.get(name) else {
+                continue;
+            };
+            let Ok(points) = u64::try_from(*points) else {
+                continue;
+            };
+            if points == 0 {
+                continue;
+            }
+            let runs = self.max_runs(name, logic);
+            total = add_counts(total, runs.map(|runs| runs.saturating_mul(points)));
+        }
+        total
+    }
+
+    /// Most times an action can run, or `None` if unbounded
+    fn max_runs(&self, name: &str, logic: &CustomLogic) -> Count {
+        let mut runs = Some(0);
+        let named = |action: &str| action == name;
+        runs = add_counts(
+            runs,
+            Some(
+                logic
+                    .on_start
+                    .iter()
+                    .flatten()
+                    .filter(|action| named(action))
+                    .count() as u64,
+            ),
+        );
+        for timer in logic.timers.iter().flatten() {
+            if let Some((_, action)) = parse_timer(timer) {
+                if named(action) {
+                    let every = timer.trim_start().starts_with("every");
+                    runs = add_counts(runs, if every { None } else { Some(1) });
+                }
+            }
+        }
+        if self
+            .scene
+            .countdown
+            .iter()
+            .any(|countdown| named(&countdown.on_expire))
+        {
+            runs = add_counts(runs, Some(1));
+        }
+        let repeats = logic
+            .on_key_press
+            .iter()
+            .flatten()
+            .any(|trigger| named(&trigger.action))
+            || logic
+                .collision_matrix
+                .iter()
+                .flatten()
+                .any(|rule| rule.action.as_deref() == Some(name))
+            || self.scene.all_objects().into_iter().any(|object| {
+                object.on_click.as_deref() == Some(name)
+                    || object.zone.as_ref().is_some_and(|zone| {
+                        zone.on_enter.as_deref() == Some(name)
+                            || zone.on_exit.as_deref() == Some(name)
+                    })
+            });
+        if repeats {
+            return None;
+        }
+        for trigger in logic.on_score_reached.iter().flatten() {
+            if named(&trigger.action) {
+                runs = add_counts(runs, if trigger.repeat { None } else { Some(1) });
+            }
+        }
+        for trigger in logic.on_destroyed.iter().flatten() {
+            if named(&trigger.action) {
+                let count = if trigger.all {
+                    Some(1)
+                } else {
+                    self.party(&trigger.target)
+                        .and_then(|party| self.party_size(party))
+                };
+                runs = add_counts(runs, count);
+            }
+        }
+        // Scoring destroys the second object of a handler, so each can score once
+        for (_, handler) in self.handlers() {
+            if let Some((_, second, callback)) = parse_handler(handler) {
+                if named(callback) {
+                    let count = self.party(second).and_then(|party| self.party_size(party));
+                    runs = add_counts(runs, count);
+                }
+            }
+        }
+        runs
+    }
+
+    /// Most objects a party can ever hold, or `None` if unbounded
+    fn party_size(&self, party: Party) -> Count {
+        match party {
+            Party::Object(_) => Some(1),
+            Party::Tiles(_, count) => Some(u64::from(count)),
+            Party::Spawned(template) => self
+                .spawners
+                .iter()
+                .filter(|spawner| spawner.template.id == template.id)
+                .map(|spawner| spawner.max_count.map(u64::from))
+                .fold(Some(0), add_counts),
+            Party::Procedural(_) | Party::Projectiles => None,
+        }
+    }
+
+    /// Whether a handler that can fire removes objects spawned from `template`
+    fn can_remove(&self, template: &str, run: &ClockRun, reachable: &HashSet<&str>) -> bool {
+        self.handlers().into_iter().any(|(_, handler)| {
+            let Some((first, second, callback)) = parse_handler(handler) else {
+                return false;
+            };
+            let removes = callback == "destroy"
+                || matches!(
+                    self.actions.get(callback),
+                    Some(
+                        ActionEffect::Destroy
+                            | ActionEffect::UpdateScore { .. }
+                            | ActionEffect::Damage { .. }
+                    )
+                );
+            (first == template || second == template)
+                && removes
+                && self
+                    .why_never_fires(first, second, run, reachable)
+                    .is_none()
+        })
+    }
+
+    /// Warning for a spawner whose objects appear outside the world or leave
+    /// it as soon as they appear
+    fn check_spawn_position(&self, spawner: &Spawner) -> Option<String> {
+        let (width, height) = world_size(self.spec, self.scene);
+        let (width, height) = (width as f32, height as f32);
+        let (mut x, mut y) = match spawner.spawn_area.to_lowercase().as_str() {
+            "top" => ((0.0, width), (0.0, 0.0)),
+            "bottom" => ((0.0, width), (height, height)),
+            "left" => ((0.0, 0.0), (0.0, height)),
+            "right" => ((width, width), (0.0, height)),
+            _ => ((0.0, width), (0.0, height)),
+        };
+        if let Some(variance) = &spawner.position_variance {
+            x = (variance.x_min, variance.x_max);
+            if let (Some(min), Some(max)) = (variance.y_min, variance.y_max) {
+                y = (min, max);
+            }
+        }
+        let physics = spawner.template.physics.as_ref();
+        // Objects kept inside the world are pushed back in
+        if physics.is_some_and(|physics| physics.collide_world_bounds == Some(true)) {
+            return None;
+        }
+        if x.1 < 0.0 || x.0 > width || y.1 < 0.0 || y.0 > height {
+            return Some(format!(
+                "Spawns entirely outside the {}x{} world (x {} to {}, y {} to {})",
+                width, height, x.0, x.1, y.0, y.1
+            ));
+        }
+        let velocity = physics
+            .filter(|physics| matches!(physics.body, PhysicsBody::Dynamic))
+            .and_then(|physics| physics.velocity.as_ref());
+        if let Some(velocity) = velocity {
+            let leaves = (x.1 <= 0.0 && velocity.x < 0.0)
+                || (x.0 >= width && velocity.x > 0.0)
+                || (y.1 <= 0.0 && velocity.y < 0.0)
+                || (y.0 >= height && velocity.y > 0.0);
+            if leaves {
+                return Some(
+                    "Spawns on the world's edge moving outward, so it is never seen".to_string(),
+                );
+            }
+        }
+        if x.0 < 0.0 || x.1 > width || y.0 < 0.0 || y.1 > height {
+            return Some(format!(
+                "Can spawn outside the {}x{} world (x {} to {}, y {} to {})",
+                width, height, x.0, x.1, y.0, y.1
+            ));
+        }
+        None
+    }
+}
+
+/// Result of stepping the logical clock
+#[derive(Default)]
+struct ClockRun {
+    clocks: Vec<SpawnerClock>,
+    spawned: BTreeMap<String, u32>,
+    /// Live objects of each spawner at the end, in spawner order
+    live: Vec<u32>,
+    actions_run: BTreeMap<String, u32>,
+    score: i64,
+    ended: Option<(u32, String)>,
+}
+
+fn party_template<'a>(party: Party<'a>) -> Option<&'a GameObject> {
+    match party {
+        Party::Object(object)
+        | Party::Spawned(object)
+        | Party::Tiles(object, _)
+        | Party::Procedural(object) => Some(object),
+        Party::Projectiles => None,
+    }
+}
+
+fn has_body(object: &GameObject) -> bool {
+    // Hazards and zones get their bodies from the renderer
+    matches!(
+        object.object_type,
+        ObjectType::Group | ObjectType::Hazard | ObjectType::Zone
+    ) || object
+        .physics
+        .as_ref()
+        .is_some_and(|physics| !matches!(physics.body, PhysicsBody::None))
+}
+
+fn world_size(spec: &PhaserGameSpec, scene: &Scene) -> (u32, u32) {
+    scene
+        .camera
+        .as_ref()
+        .map(|camera| {
+            (
+                camera.world_width.unwrap_or(spec.game.width),
+                camera.world_height.unwrap_or(spec.game.height),
+            )
+        })
+        .unwrap_or((spec.game.width, spec.game.height))
+}
@@ -0,0 +1,178 @@
+/// Structured differences between two versions of a game spec
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::game_builder::PhaserGameSpec;
+
+/// Object or action identified by its scene
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct ItemKey {
+    pub scene: String,
+    pub id: String,
+}
+
+/// Items that were added, removed, or modified between two versions
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ItemChanges {
+    pub added: Vec<ItemKey>,
+    pub removed: Vec<ItemKey>,
+    pub changed: Vec<ItemKey>,
+}
+
+/// Changed value at a path like `game.physics.gravity.y` or `scenes[main].camera`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldChange {
+    pub path: String,
+    pub from: Option<Value>,
+    pub to: Option<Value>,
+}
+
+/// Everything that differs between two specs
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SpecDiff {
+    /// Scene objects, keyed by id
+    pub objects: ItemChanges,
+    /// Custom logic actions, keyed by name
+    pub actions: ItemChanges,
+    /// All other fields (game config, assets, scene settings, ...)
+    pub fields: Vec<FieldChange>,
+}
+
+/// Compare two specs
+pub fn diff_specs(
+    from: &PhaserGameSpec,
+    to: &PhaserGameSpec,
+) -> Result<SpecDiff, serde_json::Error> {
+    let mut diff = SpecDiff::default();
+    diff_items(
+        &keyed_objects(from)?,
+        &keyed_objects(to)?,
+        &mut diff.objects,
+    );
+    diff_items(
+        &keyed_actions(from)?,
+        &keyed_actions(to)?,
+        &mut diff.actions,
+    );
+
+    let (from_config, from_scenes) = split_config(from)?;
+    let (to_config, to_scenes) = split_config(to)?;
+    diff_values("", Some(&from_config), Some(&to_config), &mut diff.fields);
+    let scene_names: BTreeSet<&String> = from_scenes.keys().chain(to_scenes.keys()).collect();
+    for name in scene_names {
+        diff_values(
+            &format!("scenes[{}]", name),
+            from_scenes.get(name),
+            to_scenes.get(name),
+            &mut diff.fields,
+        );
+    }
+
+    Ok(diff)
+}
+
+fn keyed_objects(spec: &PhaserGameSpec) -> Result<BTreeMap<ItemKey, Value>, serde_json::Error> {
+    let mut objects = BTreeMap::new();
+    for scene in &spec.scenes {
+        for object in &scene.objects {
+            let key = ItemKey {
+                scene: scene.name.clone(),
+                id: object.id.clone(),
+            };
+            objects.insert(key, serde_json::to_value(object)?);
+        }
+    }
+    Ok(objects)
+}
+
+fn keyed_actions(spec: &PhaserGameSpec) -> Result<BTreeMap<ItemKey, Value>, serde_json::Error> {
+    let mut actions = BTreeMap::new();
+    for scene in &spec.scenes {
+        let definitions = scene
+            .custom_logic
+            .iter()
+            .flat_map(|logic| logic.actions.iter().flatten());
+        for action in definitions {
+            let key = ItemKey {
+                scene: scene.name.clone(),
+                id: action.name.clone(),
+            };
+            actions.insert(key, serde_json::to_value(action)?);
+        }
+    }
+    Ok(actions)
+}
+
+fn diff_items(
+    from: &BTreeMap<ItemKey, Value>,
+    to: &BTreeMap<ItemKey, Value>,
+    changes: &mut ItemChanges,
+) {
+    for (key, value) in from {
+        match to.get(key) {
+            None => changes.removed.push(key.clone()),
+            Some(other) if other != value => changes.changed.push(key.clone()),
+            Some(_) => (),
+        }
+    }
+    for key in to.keys().filter(|key| !from.contains_key(key)) {
+        changes.added.push(key.clone());
+    }
+}
+
+/// Split a spec into its top-level config and per-scene settings, leaving out
+/// objects and actions (diffed separately) and the schema version
+fn split_config(
+    spec: &PhaserGameSpec,
+) -> Result<(Value, BTreeMap<String, Value>), serde_json::Error> {
+    let mut config = serde_json::to_value(spec)?;
+    let mut scenes = BTreeMap::new();
+    if let Value::Object(fields) = &mut config {
+        fields.remove("schema_version");
+        fields.remove("scenes");
+    }
+    for scene in &spec.scenes {
+        let mut value = serde_json::to_value(scene)?;
+        if let Value::Object(fields) = &mut value {
+            fields.remove("name");
+            fields.remove("objects");
+            if let Some(Value::Object(logic)) = fields.get_mut("custom_logic") {
+                logic.remove("actions");
+                if logic.is_empty() {
+                    fields.remove("custom_logic");
+                }
+            }
+        }
+        scenes.insert(scene.name.clone(), value);
+    }
+    Ok((config, scenes))
+}
+
+/// Recurse into JSON objects, recording every leaf (or array) that differs
+fn diff_values(
+    path: &str,
+    from: Option<&Value>,
+    to: Option<&Value>,
+    changes: &mut Vec<FieldChange>,
+) {
+    match (from, to) {
+        (Some(Value::Object(from)), Some(Value::Object(to))) => {
+            let keys: BTreeSet<&String> = from.keys().chain(to.keys()).collect();
+            for key in keys {
+                let child = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                diff_values(&child, from.get(key), to.get(key), changes);
+            }
+        }
+        (from, to) if from != to => changes.push(FieldChange {
+            path: path.to_string(),
+            from: from.cloned(),
+            to: to.cloned(),
+        }),
+        _ => (),
+    }
+}
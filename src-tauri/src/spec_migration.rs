@@ -0,0 +1,117 @@
+/// Upgrades stored game specs written by older versions of the schema
+use serde_json::{json, Map, Value};
+
+use crate::game_builder::{PhaserGameSpec, CURRENT_SCHEMA_VERSION};
+
+#[derive(Debug, thiserror::Error)]
+pub enum MigrationError {
+    #[error("Spec is not a JSON object")]
+    NotAnObject,
+    #[error("Spec schema version {0} is newer than supported version {CURRENT_SCHEMA_VERSION}")]
+    UnsupportedVersion(u64),
+    #[error("Invalid spec: {0}")]
+    Invalid(#[from] serde_json::Error),
+}
+
+/// Upgrades a spec from the given version to the next one
+type Migration = fn(&mut Map<String, Value>);
+
+/// Migrations indexed by the version they upgrade from (index 0 upgrades v1 to v2)
+const MIGRATIONS: [Migration; 1] = [migrate_v1_to_v2];
+
+/// Specs saved before versioning was introduced have no schema_version field
+const UNVERSIONED: u64 = 1;
+
+/// Spec parsed from storage
+pub struct LoadedSpec {
+    pub spec: PhaserGameSpec,
+    /// Migrations or defaults changed the JSON, so the stored copy is outdated
+    pub upgraded: bool,
+}
+
+/// Parse a stored spec, upgrading it to the current schema first
+pub fn load_spec(json: &str) -> Result<PhaserGameSpec, MigrationError> {
+    Ok(load_spec_upgraded(json)?.spec)
+}
+
+/// Parse a stored spec, reporting whether it had to be upgraded
+pub fn load_spec_upgraded(json: &str) -> Result<LoadedSpec, MigrationError> {
+    let value: Value = serde_json::from_str(json)?;
+    let upgraded = migrate(value.clone())?;
+    Ok(LoadedSpec {
+        upgraded: upgraded != value,
+        spec: serde_json::from_value(upgraded)?,
+    })
+}
+
+/// Apply every migration between the spec's version and the current one,
+/// then backfill required fields the spec is missing
+pub fn migrate(mut value: Value) -> Result<Value, MigrationError> {
+    let spec = value.as_object_mut().ok_or(MigrationError::NotAnObject)?;
+    let mut version = spec
+        .get("schema_version")
+        .and_then(Value::as_u64)
+        .unwrap_or(UNVERSIONED);
+
+    if version > u64::from(CURRENT_SCHEMA_VERSION) {
+        return Err(MigrationError::UnsupportedVersion(version));
+    }
+
+    while version < u64::from(CURRENT_SCHEMA_VERSION) {
+        MIGRATIONS[(version - 1) as usize](spec);
+        version += 1;
+        spec.insert("schema_version".to_string(), Value::from(version));
+    }
+
+    fill_defaults(spec);
+    Ok(value)
+}
+
+/// Give required fields a default when a spec lacks them (hand-edited specs,
+/// or fields made required after the spec was saved), so one missing field
+/// doesn't make the whole game unreadable. `game` and `scenes` have no
+/// sensible default, so a spec without them still fails to load.
+fn fill_defaults(spec: &mut Map<String, Value>) {
+    insert_missing(spec, "title", json!("Untitled Game"));
+    insert_missing(spec, "description", json!(""));
+    insert_missing(spec, "controls_description", json!([]));
+    insert_missing(spec, "key_concepts", json!([]));
+
+    if let Some(game) = spec.get_mut("game").and_then(Value::as_object_mut) {
+        insert_missing(game, "width", json!(800));
+        insert_missing(game, "height", json!(600));
+        if let Some(physics) = object_entry(game, "physics") {
+            insert_missing(physics, "enabled", json!(false));
+            insert_missing(physics, "gravity", json!({ "x": 0, "y": 0 }));
+        }
+    }
+
+    if let Some(scenes) = spec.get_mut("scenes").and_then(Value::as_array_mut) {
+        for (index, scene) in scenes.iter_mut().enumerate() {
+            if let Some(scene) = scene.as_object_mut() {
+                insert_missing(scene, "name", json!(format!("scene{}", index + 1)));
+                insert_missing(scene, "objects", json!([]));
+            }
+        }
+    }
+}
+
+fn insert_missing(map: &mut Map<String, Value>, key: &str, default: Value) {
+    if !map.contains_key(key) {
+        map.insert(key.to_string(), default);
+    }
+}
+
+/// The object under `key`, created empty if missing. None if it holds something else.
+fn object_entry<'a>(
+    map: &'a mut Map<String, Value>,
+    key: &str,
+) -> Option<&'a mut Map<String, Value>> {
+    map.entry(key)
+        .or_insert_with(|| Value::Object(Map::new()))
+        .as_object_mut()
+}
+
+/// v2 only added optional fields (camera, hud, tilemap, new effects, ...), so
+/// unversioned specs load as-is once stamped
+fn migrate_v1_to_v2(_spec: &mut Map<String, Value>) {}
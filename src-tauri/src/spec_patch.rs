@@ -0,0 +1,144 @@
+/// Edits to a game spec in JSON Patch form (RFC 6902 `add`, `remove`, and
+/// `replace`), so the model can change a game without rewriting all of it.
+/// Besides array indices, path segments may name an array element by its
+/// `id` or `name`: `/scenes/main/objects/player/x`.
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::game_builder::PhaserGameSpec;
+
+#[derive(Debug, thiserror::Error)]
+pub enum PatchError {
+    #[error("Path '{0}' must start with '/'")]
+    InvalidPath(String),
+    #[error("Path '{0}' doesn't exist in the spec")]
+    NotFound(String),
+    #[error("Path '{0}' can't be removed or replaced: it is the whole spec")]
+    Root(String),
+    #[error("Patched spec is invalid: {0}")]
+    Invalid(#[from] serde_json::Error),
+}
+
+/// One edit to the spec
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum PatchOperation {
+    #[schemars(
+        description = "Add a field, or insert into an array before the given index (use '-' to append)"
+    )]
+    Add { path: String, value: Value },
+
+    #[schemars(description = "Remove a field or array element")]
+    Remove { path: String },
+
+    #[schemars(description = "Replace an existing field or array element")]
+    Replace { path: String, value: Value },
+}
+
+/// Apply `operations` in order to a copy of `spec`
+pub fn apply_patch(
+    spec: &PhaserGameSpec,
+    operations: &[PatchOperation],
+) -> Result<PhaserGameSpec, PatchError> {
+    let mut value = serde_json::to_value(spec)?;
+    for operation in operations {
+        apply(&mut value, operation)?;
+    }
+    Ok(serde_json::from_value(value)?)
+}
+
+fn apply(root: &mut Value, operation: &PatchOperation) -> Result<(), PatchError> {
+    let path = match operation {
+        PatchOperation::Add { path, .. }
+        | PatchOperation::Remove { path }
+        | PatchOperation::Replace { path, .. } => path,
+    };
+    let mut segments = parse_path(path)?;
+    let Some(last) = segments.pop() else {
+        // An empty path means the whole document
+        return match operation {
+            PatchOperation::Add { value, .. } | PatchOperation::Replace { value, .. } => {
+                *root = value.clone();
+                Ok(())
+            }
+            PatchOperation::Remove { .. } => Err(PatchError::Root(path.clone())),
+        };
+    };
+
+    let not_found = || PatchError::NotFound(path.clone());
+    let mut parent = root;
+    for segment in &segments {
+        parent = child(parent, segment).ok_or_else(not_found)?;
+    }
+
+    match (parent, operation) {
+        (Value::Object(map), PatchOperation::Add { value, .. }) => {
+            map.insert(last, value.clone());
+        }
+        (Value::Object(map), PatchOperation::Remove { .. }) => {
+            map.remove(&last).ok_or_else(not_found)?;
+        }
+        (Value::Object(map), PatchOperation::Replace { value, .. }) => {
+            *map.get_mut(&last).ok_or_else(not_found)? = value.clone();
+        }
+        (Value::Array(items), PatchOperation::Add { value, .. }) => {
+            let index = if last == "-" {
+                items.len()
+            } else {
+                element_index(items, &last)
+                    .or_else(|| last.parse().ok().filter(|index| *index == items.len()))
+                    .ok_or_else(not_found)?
+            };
+            items.insert(index, value.clone());
+        }
+        (Value::Array(items), PatchOperation::Remove { .. }) => {
+            let index = element_index(items, &last).ok_or_else(not_found)?;
+            items.remove(index);
+        }
+        (Value::Array(items), PatchOperation::Replace { value, .. }) => {
+            let index = element_index(items, &last).ok_or_else(not_found)?;
+            items[index] = value.clone();
+        }
+        _ => return Err(not_found()),
+    }
+    Ok(())
+}
+
+/// Unescaped segments of a JSON Pointer
+fn parse_path(path: &str) -> Result<Vec<String>, PatchError> {
+    if path.is_empty() {
+        return Ok(Vec::new());
+    }
+    let rest = path
+        .strip_prefix('/')
+        .ok_or_else(|| PatchError::InvalidPath(path.to_string()))?;
+    Ok(rest
+        .split('/')
+        .map(|segment| segment.replace("~1", "/").replace("~0", "~"))
+        .collect())
+}
+
+fn child<'a>(value: &'a mut Value, segment: &str) -> Option<&'a mut Value> {
+    match value {
+        Value::Object(map) => map.get_mut(segment),
+        Value::Array(items) => {
+            let index = element_index(items, segment)?;
+            items.get_mut(index)
+        }
+        _ => None,
+    }
+}
+
+/// Position of the element a segment refers to: an index, or else the
+/// element whose `id` or `name` it is
+fn element_index(items: &[Value], segment: &str) -> Option<usize> {
+    if let Ok(index) = segment.parse::<usize>() {
+        return (index < items.len()).then_some(index);
+    }
+    items.iter().position(|item| {
+        ["id", "name"]
+            .iter()
+            .any(|key| item.get(key).and_then(Value::as_str) == Some(segment))
+    })
+}
@@ -0,0 +1,236 @@
+/// Exports of a game that play in any browser without the app: a single
+/// self-contained HTML file, or a zip bundle for static hosts
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use std::collections::HashMap;
+use std::io::{Cursor, Write};
+use std::path::{Path, PathBuf};
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+use crate::assets::{AssetError, AssetLibrary, LibraryAsset};
+use crate::db::{Database, DbError};
+use crate::game_builder::{Asset, AssetType, PhaserGameSpec};
+
+#[derive(Debug, thiserror::Error)]
+pub enum StandaloneError {
+    #[error("Database error: {0}")]
+    Db(#[from] DbError),
+    #[error("Asset error: {0}")]
+    Asset(#[from] AssetError),
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+    #[error("Zip error: {0}")]
+    Zip(#[from] zip::result::ZipError),
+}
+
+/// Location of the bundled Phaser runtime and spec interpreter among the
+/// frontend files (built by `npm run build:runtime`)
+pub const RUNTIME_PATH: &str = "runtime/pueo-runtime.js";
+
+/// Name of the runtime script inside a zip bundle
+const BUNDLE_RUNTIME: &str = "pueo-runtime.js";
+
+/// Name of the spec inside a zip bundle
+const BUNDLE_SPEC: &str = "spec.json";
+
+/// Directory holding library assets inside a zip bundle
+const BUNDLE_ASSETS_DIR: &str = "assets";
+
+/// Write a game as a single HTML file containing `runtime`, the game's spec,
+/// and every library asset it uses
+pub async fn export_standalone_html(
+    db: &Database,
+    assets: &AssetLibrary,
+    id: &str,
+    runtime: &str,
+    path: &Path,
+) -> Result<(), StandaloneError> {
+    let game = db.get_game(id).await?;
+    // Data URLs, so the file needs nothing else
+    let mut urls = HashMap::new();
+    for asset in used_library_assets(assets, &game.spec).await? {
+        let url = data_url(assets, &asset.key).await?;
+        urls.insert(asset.key, url);
+    }
+    let spec = relink_library_assets(game.spec, &urls);
+    tokio::fs::write(
+        with_default_extension(path, "html"),
+        render_html(&spec, runtime)?,
+    )
+    .await?;
+    Ok(())
+}
+
+/// Write a game as a zip of `index.html`, the runtime, the spec, and every
+/// library asset it uses under `assets/`, ready to upload to a static host
+pub async fn export_zip_bundle(
+    db: &Database,
+    assets: &AssetLibrary,
+    id: &str,
+    runtime: &str,
+    path: &Path,
+) -> Result<(), StandaloneError> {
+    let game = db.get_game(id).await?;
+    let mut files = vec![
+        (
+            "index.html".to_string(),
+            render_bundle_index(&game.spec).into_bytes(),
+        ),
+        (BUNDLE_RUNTIME.to_string(), runtime.as_bytes().to_vec()),
+    ];
+    // Relative URLs, resolved against index.html
+    let mut urls = HashMap::new();
+    for asset in used_library_assets(assets, &game.spec).await? {
+        let name = format!("{}/{}", BUNDLE_ASSETS_DIR, asset.file_name);
+        let (data, _) = assets.read(&asset.key).await?;
+        files.push((name.clone(), data));
+        urls.insert(asset.key, name);
+    }
+    let spec = relink_library_assets(game.spec, &urls);
+    files.push((BUNDLE_SPEC.to_string(), serde_json::to_vec_pretty(&spec)?));
+
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    for (name, data) in files {
+        zip.start_file(name, options)?;
+        zip.write_all(&data)?;
+    }
+    let archive = zip.finish()?.into_inner();
+    tokio::fs::write(with_default_extension(path, "zip"), archive).await?;
+    Ok(())
+}
+
+/// Library assets the spec uses, whether declared or only named by a texture
+async fn used_library_assets(
+    assets: &AssetLibrary,
+    spec: &PhaserGameSpec,
+) -> Result<Vec<LibraryAsset>, StandaloneError> {
+    let mut keys: Vec<String> = spec.library_asset_keys().into_iter().collect();
+    keys.sort();
+    let mut used = Vec::new();
+    for key in keys {
+        let declared = spec
+            .assets
+            .iter()
+            .any(|asset| asset.library_key.as_deref() == Some(key.as_str()));
+        match assets.find(&key).await? {
+            Some(asset) => used.push(asset),
+            None if declared => return Err(AssetError::NotFound(key).into()),
+            // Textures that aren't in the library (e.g. generated placeholders) are skipped
+            None => {}
+        }
+    }
+    Ok(used)
+}
+
+/// Swap library references for `urls`, keyed by library key, so the spec
+/// loads outside the app
+fn relink_library_assets(
+    mut spec: PhaserGameSpec,
+    urls: &HashMap<String, String>,
+) -> PhaserGameSpec {
+    for asset in &mut spec.assets {
+        if let Some(key) = asset.library_key.take() {
+            if let Some(url) = urls.get(&key) {
+                asset.url = url.clone();
+            }
+        }
+    }
+
+    // Textures can name a library asset without declaring it
+    let mut texture_keys: Vec<String> = spec
+        .library_asset_keys()
+        .into_iter()
+        .filter(|key| urls.contains_key(key))
+        .collect();
+    texture_keys.sort();
+    for key in texture_keys {
+        spec.assets.push(Asset {
+            url: urls[&key].clone(),
+            key,
+            asset_type: AssetType::Image,
+            library_key: None,
+            frame_config: None,
+            animations: None,
+        });
+    }
+    spec
+}
+
+fn with_default_extension(path: &Path, extension: &str) -> PathBuf {
+    if path.extension().is_none() {
+        path.with_extension(extension)
+    } else {
+        path.to_path_buf()
+    }
+}
+
+async fn data_url(assets: &AssetLibrary, key: &str) -> Result<String, StandaloneError> {
+    let (data, content_type) = assets.read(key).await?;
+    Ok(format!(
+        "data:{};base64,{}",
+        content_type,
+        BASE64.encode(data)
+    ))
+}
+
+fn render_html(spec: &PhaserGameSpec, runtime: &str) -> Result<String, serde_json::Error> {
+    // Neither inline script may contain "</script", which would end it early
+    let spec_json = serde_json::to_string(spec)?.replace('<', "\\u003c");
+    let runtime = runtime.replace("</script", "<\\/script");
+
+    Ok(render_page(
+        &spec.title,
+        &format!(
+            r#"<script>{runtime}</script>
+<script>pueoStart({spec_json}, "game");</script>"#
+        ),
+    ))
+}
+
+/// Page of a zip bundle, loading the runtime and spec from next to it
+fn render_bundle_index(spec: &PhaserGameSpec) -> String {
+    render_page(
+        &spec.title,
+        &format!(
+            r#"<script src="{BUNDLE_RUNTIME}"></script>
+<script>
+fetch("{BUNDLE_SPEC}")
+  .then((response) => response.json())
+  .then((spec) => pueoStart(spec, "game"));
+</script>"#
+        ),
+    )
+}
+
+fn render_page(title: &str, scripts: &str) -> String {
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>{title}</title>
+<style>
+body {{ margin: 0; min-height: 100vh; display: flex; align-items: center; justify-content: center; background: #111; }}
+</style>
+</head>
+<body>
+<div id="game"></div>
+{scripts}
+</body>
+</html>
+"#,
+        title = escape_html(title),
+    )
+}
+
+pub fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,345 @@
+/// Optional sync of the game library with a user-configured remote store
+///
+/// Each game is stored on the remote as a game file (history and assets
+/// included), next to a manifest recording every game's version and last
+/// update. A sync compares local games with the manifest and copies whichever
+/// side is newer by `(version, updated_at)`; the losing side's changes are
+/// overwritten. Deletions are not propagated, so a game deleted on one device
+/// comes back on the next pull.
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use crate::assets::AssetLibrary;
+use crate::db::{Database, DbError};
+use crate::game_file::{self, GameFile, GameFileError};
+
+/// Object holding the manifest of every synced game
+const MANIFEST_NAME: &str = "pueo-manifest.json";
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Debug, thiserror::Error)]
+pub enum SyncError {
+    #[error("HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("Remote returned {0} for '{1}'")]
+    Status(u16, String),
+    #[error("Invalid remote URL: {0}")]
+    InvalidUrl(String),
+    #[error("Database error: {0}")]
+    Db(#[from] DbError),
+    #[error("Game file error: {0}")]
+    GameFile(#[from] GameFileError),
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+/// Remote store the library syncs with. The password or secret access key
+/// is kept in the keyring, not here.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum SyncBackend {
+    /// Folder on a WebDAV server (Nextcloud, ownCloud, ...), using basic auth
+    WebDav { url: String, username: String },
+    /// Bucket on S3 or an S3-compatible service, addressed path-style
+    S3 {
+        endpoint: String,
+        bucket: String,
+        region: String,
+        access_key_id: String,
+        #[serde(default)]
+        prefix: String,
+    },
+}
+
+/// Outcome of a sync
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SyncReport {
+    /// Games uploaded because the local copy was newer or missing remotely
+    pub pushed: Vec<String>,
+    /// Games downloaded because the remote copy was newer or missing locally
+    pub pulled: Vec<String>,
+    pub unchanged: usize,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Manifest {
+    games: BTreeMap<String, ManifestEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ManifestEntry {
+    version: i64,
+    updated_at: DateTime<Utc>,
+}
+
+/// A game as stored on the remote
+#[derive(Debug, Serialize, Deserialize)]
+struct RemoteGame {
+    id: String,
+    updated_at: DateTime<Utc>,
+    file: GameFile,
+}
+
+/// Sync every game in the library with the remote
+pub async fn sync_library(
+    db: &Database,
+    assets: &AssetLibrary,
+    backend: &SyncBackend,
+    secret: &str,
+) -> Result<SyncReport, SyncError> {
+    let remote = Remote::new(backend, secret)?;
+    let mut manifest: Manifest = match remote.get(MANIFEST_NAME).await? {
+        Some(data) => serde_json::from_slice(&data)?,
+        None => Manifest::default(),
+    };
+    let mut report = SyncReport::default();
+
+    let stamps = db.list_game_stamps().await?;
+    for stamp in &stamps {
+        let local = (stamp.version, stamp.updated_at);
+        match manifest.games.get(&stamp.id) {
+            Some(entry) if (entry.version, entry.updated_at) > local => {
+                pull_game(db, assets, &remote, &stamp.id).await?;
+                report.pulled.push(stamp.id.clone());
+            }
+            Some(entry) if (entry.version, entry.updated_at) == local => {
+                report.unchanged += 1;
+            }
+            _ => {
+                push_game(db, assets, &remote, &stamp.id).await?;
+                manifest.games.insert(
+                    stamp.id.clone(),
+                    ManifestEntry {
+                        version: stamp.version,
+                        updated_at: stamp.updated_at,
+                    },
+                );
+                report.pushed.push(stamp.id.clone());
+            }
+        }
+    }
+
+    for id in manifest.games.keys() {
+        if !stamps.iter().any(|stamp| &stamp.id == id) {
+            pull_game(db, assets, &remote, id).await?;
+            report.pulled.push(id.clone());
+        }
+    }
+
+    if !report.pushed.is_empty() {
+        remote
+            .put(MANIFEST_NAME, serde_json::to_vec(&manifest)?)
+            .await?;
+    }
+    Ok(report)
+}
+
+async fn push_game(
+    db: &Database,
+    assets: &AssetLibrary,
+    remote: &Remote,
+    id: &str,
+) -> Result<(), SyncError> {
+    let game = db.get_game(id).await?;
+    let file = game_file::build_game_file(db, assets, id).await?;
+    let remote_game = RemoteGame {
+        id: id.to_string(),
+        updated_at: game.updated_at,
+        file,
+    };
+    remote
+        .put(&game_object_name(id), serde_json::to_vec(&remote_game)?)
+        .await
+}
+
+async fn pull_game(
+    db: &Database,
+    assets: &AssetLibrary,
+    remote: &Remote,
+    id: &str,
+) -> Result<(), SyncError> {
+    let name = game_object_name(id);
+    let data = remote
+        .get(&name)
+        .await?
+        .ok_or(SyncError::Status(404, name))?;
+    let remote_game: RemoteGame = serde_json::from_slice(&data)?;
+    game_file::restore_game_file(
+        db,
+        assets,
+        &remote_game.id,
+        remote_game.updated_at,
+        remote_game.file,
+    )
+    .await?;
+    Ok(())
+}
+
+fn game_object_name(id: &str) -> String {
+    format!("pueo-game-{}.json", id)
+}
+
+/// HTTP client for the configured backend
+struct Remote {
+    client: reqwest::Client,
+    backend: SyncBackend,
+    secret: String,
+}
+
+impl Remote {
+    fn new(backend: &SyncBackend, secret: &str) -> Result<Self, SyncError> {
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()?;
+        Ok(Self {
+            client,
+            backend: backend.clone(),
+            secret: secret.to_string(),
+        })
+    }
+
+    /// Download an object, or `None` if it doesn't exist yet
+    async fn get(&self, name: &str) -> Result<Option<Vec<u8>>, SyncError> {
+        let response = self
+            .request(reqwest::Method::GET, name, Vec::new())?
+            .send()
+            .await?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !response.status().is_success() {
+            return Err(SyncError::Status(
+                response.status().as_u16(),
+                name.to_string(),
+            ));
+        }
+        Ok(Some(response.bytes().await?.to_vec()))
+    }
+
+    /// Upload an object, replacing any existing one
+    async fn put(&self, name: &str, body: Vec<u8>) -> Result<(), SyncError> {
+        let response = self
+            .request(reqwest::Method::PUT, name, body)?
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(SyncError::Status(
+                response.status().as_u16(),
+                name.to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn request(
+        &self,
+        method: reqwest::Method,
+        name: &str,
+        body: Vec<u8>,
+    ) -> Result<reqwest::RequestBuilder, SyncError> {
+        match &self.backend {
+            SyncBackend::WebDav { url, username } => {
+                let url = format!("{}/{}", url.trim_end_matches('/'), uri_encode(name));
+                Ok(self
+                    .client
+                    .request(method, url)
+                    .basic_auth(username, Some(&self.secret))
+                    .body(body))
+            }
+            SyncBackend::S3 {
+                endpoint,
+                bucket,
+                region,
+                access_key_id,
+                prefix,
+            } => {
+                let key = match prefix.trim_matches('/') {
+                    "" => name.to_string(),
+                    prefix => format!("{}/{}", prefix, name),
+                };
+                let url = reqwest::Url::parse(&format!(
+                    "{}/{}/{}",
+                    endpoint.trim_end_matches('/'),
+                    uri_encode(bucket),
+                    uri_encode(&key)
+                ))
+                .map_err(|_| SyncError::InvalidUrl(endpoint.clone()))?;
+                let host = match (url.host_str(), url.port()) {
+                    (Some(host), Some(port)) => format!("{}:{}", host, port),
+                    (Some(host), None) => host.to_string(),
+                    (None, _) => return Err(SyncError::InvalidUrl(endpoint.clone())),
+                };
+
+                let now = Utc::now();
+                let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+                let date = now.format("%Y%m%d").to_string();
+                let payload_hash = format!("{:x}", Sha256::digest(&body));
+
+                // AWS Signature Version 4, signing only the headers we send
+                let signed_headers = "host;x-amz-content-sha256;x-amz-date";
+                let canonical_request = format!(
+                    "{}\n{}\n\nhost:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n\n{}\n{}",
+                    method,
+                    url.path(),
+                    host,
+                    payload_hash,
+                    amz_date,
+                    signed_headers,
+                    payload_hash
+                );
+                let scope = format!("{}/{}/s3/aws4_request", date, region);
+                let string_to_sign = format!(
+                    "AWS4-HMAC-SHA256\n{}\n{}\n{:x}",
+                    amz_date,
+                    scope,
+                    Sha256::digest(canonical_request.as_bytes())
+                );
+                let signing_key = [region.as_str(), "s3", "aws4_request"].iter().fold(
+                    hmac_sha256(format!("AWS4{}", self.secret).as_bytes(), date.as_bytes()),
+                    |key, part| hmac_sha256(&key, part.as_bytes()),
+                );
+                let signature: String = hmac_sha256(&signing_key, string_to_sign.as_bytes())
+                    .iter()
+                    .map(|byte| format!("{:02x}", byte))
+                    .collect();
+                let authorization = format!(
+                    "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+                    access_key_id, scope, signed_headers, signature
+                );
+
+                Ok(self
+                    .client
+                    .request(method, url)
+                    .header("x-amz-date", amz_date)
+                    .header("x-amz-content-sha256", payload_hash)
+                    .header("authorization", authorization)
+                    .body(body))
+            }
+        }
+    }
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// Percent-encode a path, leaving unreserved characters and `/` as they are
+fn uri_encode(path: &str) -> String {
+    let mut encoded = String::new();
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
@@ -0,0 +1,34 @@
+/// Curated starter game embedded in the binary and seeded into the templates table
+pub struct StarterTemplate {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    pub spec_json: &'static str,
+}
+
+pub const STARTER_TEMPLATES: [StarterTemplate; 4] = [
+    StarterTemplate {
+        id: "platformer",
+        name: "Platformer",
+        description: "Jump across platforms collecting coins on the way to a flag",
+        spec_json: include_str!("../templates/platformer.json"),
+    },
+    StarterTemplate {
+        id: "shooter",
+        name: "Shooter",
+        description: "Blast waves of invaders that speed up as your score climbs",
+        spec_json: include_str!("../templates/shooter.json"),
+    },
+    StarterTemplate {
+        id: "collector",
+        name: "Collector",
+        description: "Grab stars and dodge bouncing bombs before the timer runs out",
+        spec_json: include_str!("../templates/collector.json"),
+    },
+    StarterTemplate {
+        id: "maze",
+        name: "Maze",
+        description: "Collect gems and find the exit while a ghost chases you",
+        spec_json: include_str!("../templates/maze.json"),
+    },
+];
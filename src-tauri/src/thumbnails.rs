@@ -0,0 +1,28 @@
+/// Downscaling of captured game canvases into stored thumbnails
+use image::imageops::FilterType;
+use image::{ImageFormat, ImageReader, ImageResult, Limits};
+use std::io::Cursor;
+
+/// Thumbnails are scaled down to fit within this size, keeping their aspect ratio
+const MAX_WIDTH: u32 = 320;
+const MAX_HEIGHT: u32 = 240;
+
+/// Largest capture accepted on either side, so a huge image can't exhaust memory
+const MAX_CAPTURE_DIMENSION: u32 = 8192;
+
+/// Downscale a PNG capture to thumbnail size and re-encode it as PNG
+pub fn encode_thumbnail(png: &[u8]) -> ImageResult<Vec<u8>> {
+    let mut reader = ImageReader::with_format(Cursor::new(png), ImageFormat::Png);
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_CAPTURE_DIMENSION);
+    limits.max_image_height = Some(MAX_CAPTURE_DIMENSION);
+    reader.limits(limits);
+
+    let mut image = reader.decode()?;
+    if image.width() > MAX_WIDTH || image.height() > MAX_HEIGHT {
+        image = image.resize(MAX_WIDTH, MAX_HEIGHT, FilterType::Triangle);
+    }
+    let mut encoded = Cursor::new(Vec::new());
+    image.write_to(&mut encoded, ImageFormat::Png)?;
+    Ok(encoded.into_inner())
+}
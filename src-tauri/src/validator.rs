@@ -0,0 +1,968 @@
+/// Deep checks of a game spec, reported as a list of diagnostics instead of
+/// stopping at the first problem
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::game_builder::{
+    condition_variables, template_placeholders, ActionEffect, AssetType, CollisionBox,
+    CollisionBoxShape, CollisionMode, Controls, GameObject, ObjectType, PhaserGameSpec, Scene,
+    Tilemap, BUILTIN_VARIABLES,
+};
+
+/// How serious a diagnostic is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// The game cannot run as specified
+    Error,
+    /// The game runs but probably not as intended
+    Warning,
+}
+
+/// Single problem found in a spec
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// Location in the spec, e.g. `scenes[main].objects[player]`
+    pub path: String,
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+/// Interaction callbacks the renderer handles without an action definition
+const BUILTIN_CALLBACKS: [&str; 3] = ["null", "gameOver", "destroy"];
+
+/// Spawn intervals below this (in ms) should be bounded by a pool or max_count
+const RAPID_SPAWN_INTERVAL: u32 = 500;
+
+/// Setting holding the user's `PerformanceBudget`
+pub const BUDGET_SETTING: &str = "performance_budget";
+
+/// Key names the renderer maps to the cursor keys
+const CURSOR_KEYS: [&str; 9] = [
+    "arrowleft",
+    "left",
+    "arrowright",
+    "right",
+    "arrowup",
+    "up",
+    "arrowdown",
+    "down",
+    "space",
+];
+
+/// Multi-letter Phaser key code names accepted by `addKey`
+const NAMED_KEYS: [&str; 30] = [
+    "ENTER",
+    "SHIFT",
+    "CTRL",
+    "ALT",
+    "ESC",
+    "TAB",
+    "BACKSPACE",
+    "DELETE",
+    "INSERT",
+    "HOME",
+    "END",
+    "PAGE_UP",
+    "PAGE_DOWN",
+    "CAPS_LOCK",
+    "ZERO",
+    "ONE",
+    "TWO",
+    "THREE",
+    "FOUR",
+    "FIVE",
+    "SIX",
+    "SEVEN",
+    "EIGHT",
+    "NINE",
+    "COMMA",
+    "PERIOD",
+    "MINUS",
+    "PLUS",
+    "FORWARD_SLASH",
+    "BACK_SLASH",
+];
+
+/// Whether the renderer can bind a key by this name
+pub fn is_valid_key(name: &str) -> bool {
+    let upper = name.to_ascii_uppercase();
+    let is_letter = upper.len() == 1 && upper.chars().all(|c| c.is_ascii_uppercase());
+    let is_function_key = upper
+        .strip_prefix('F')
+        .and_then(|n| n.parse::<u8>().ok())
+        .is_some_and(|n| (1..=12).contains(&n));
+    is_letter
+        || is_function_key
+        || CURSOR_KEYS.contains(&name.to_ascii_lowercase().as_str())
+        || NAMED_KEYS.contains(&upper.as_str())
+}
+
+/// Collects diagnostics while walking the spec
+#[derive(Default)]
+struct Report {
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Report {
+    fn error(&mut self, path: impl Into<String>, message: impl Into<String>) {
+        self.push(Severity::Error, path.into(), message.into());
+    }
+
+    fn warning(&mut self, path: impl Into<String>, message: impl Into<String>) {
+        self.push(Severity::Warning, path.into(), message.into());
+    }
+
+    fn push(&mut self, severity: Severity, path: String, message: String) {
+        self.diagnostics.push(Diagnostic {
+            severity,
+            path,
+            message,
+        });
+    }
+}
+
+/// Limits that keep games playable on low-end machines like school laptops.
+/// Going over one is a warning: the game still runs, just slowly.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PerformanceBudget {
+    /// Objects a scene creates when it starts, tilemap tiles included
+    pub max_objects_per_scene: u32,
+    /// Objects from spawners and projectiles a scene can have alive at once
+    pub max_spawned: u32,
+}
+
+impl Default for PerformanceBudget {
+    fn default() -> Self {
+        Self {
+            max_objects_per_scene: 200,
+            max_spawned: 100,
+        }
+    }
+}
+
+impl PerformanceBudget {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.max_objects_per_scene == 0 || self.max_spawned == 0 {
+            return Err("Budget limits must be greater than 0".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Run every check against the spec
+pub fn validate_spec(spec: &PhaserGameSpec) -> Vec<Diagnostic> {
+    let mut report = Report::default();
+
+    if spec.scenes.is_empty() {
+        report.error("scenes", "Game must have at least one scene");
+    }
+    if spec.game.width == 0 || spec.game.height == 0 {
+        report.error("game", "Game dimensions must be greater than 0");
+    }
+    check_game_keys(spec, &mut report);
+    if let Some(audio) = &spec.game.audio {
+        if let Err(e) = audio.validate(&spec.assets) {
+            report.error("game.audio", e);
+        }
+    }
+
+    check_assets(spec, &mut report);
+    check_achievements(spec, &mut report);
+
+    let mut scene_names = HashSet::new();
+    for scene in &spec.scenes {
+        if !scene_names.insert(scene.name.as_str()) {
+            report.error(
+                format!("scenes[{}]", scene.name),
+                "Scene name is used more than once",
+            );
+        }
+        check_scene(spec, scene, &mut report);
+    }
+
+    report.diagnostics
+}
+
+/// Warnings for scenes that can go over the budget
+pub fn check_budget(spec: &PhaserGameSpec, budget: &PerformanceBudget) -> Vec<Diagnostic> {
+    let mut report = Report::default();
+    for scene in &spec.scenes {
+        let scene_path = format!("scenes[{}]", scene.name);
+
+        let tiles = scene.tilemap.as_ref().map_or(0, tile_count);
+        let objects = scene.objects.len() + tiles;
+        if objects > budget.max_objects_per_scene as usize {
+            report.warning(
+                &scene_path,
+                format!(
+                    "Starts with {} objects, over the budget of {}; use fewer objects or a smaller tilemap",
+                    objects, budget.max_objects_per_scene
+                ),
+            );
+        }
+
+        // Live spawned objects, as far as pools and max_count bound them
+        let mut spawned = 0;
+        let spawners = scene
+            .custom_logic
+            .iter()
+            .flat_map(|logic| logic.spawners.iter().flatten());
+        for spawner in spawners {
+            match spawner.max_live() {
+                Some(cap) => spawned += cap,
+                // Rapid unbounded spawners are already reported by validate_spec
+                None if spawner.interval >= RAPID_SPAWN_INTERVAL => report.warning(
+                    format!("{}.spawners[{}]", scene_path, spawner.id),
+                    format!(
+                        "Has no pool or max_count, so live objects can exceed the budget of {}; add a pool",
+                        budget.max_spawned
+                    ),
+                ),
+                None => (),
+            }
+        }
+        let projectile_pools = scene
+            .objects
+            .iter()
+            .filter_map(|object| object.controls.as_ref())
+            .filter(|controls| controls.projectile.is_some())
+            .filter_map(|controls| controls.projectile_pool.as_ref());
+        for pool in projectile_pools {
+            spawned += pool.max_live;
+        }
+        if spawned > budget.max_spawned {
+            report.warning(
+                &scene_path,
+                format!(
+                    "Up to {} spawned objects can be alive at once, over the budget of {}; lower pool max_live or max_count",
+                    spawned, budget.max_spawned
+                ),
+            );
+        }
+    }
+    report.diagnostics
+}
+
+/// Tiles a tilemap places; characters without a legend entry place nothing
+fn tile_count(tilemap: &Tilemap) -> usize {
+    tilemap
+        .rows
+        .iter()
+        .flat_map(|row| row.chars())
+        .filter(|c| !Tilemap::EMPTY_TILES.contains(c))
+        .filter(|c| tilemap.legend.iter().any(|entry| entry.symbol == *c))
+        .count()
+}
+
+/// Messages for every error-level diagnostic, ready to show or join
+pub fn error_messages(spec: &PhaserGameSpec) -> Vec<String> {
+    validate_spec(spec)
+        .into_iter()
+        .filter(|diagnostic| diagnostic.severity == Severity::Error)
+        .map(|diagnostic| diagnostic.to_string())
+        .collect()
+}
+
+fn check_game_keys(spec: &PhaserGameSpec, report: &mut Report) {
+    let keys = [
+        ("game.pause.key", spec.game.pause.as_ref().map(|p| &p.key)),
+        ("game.restart_key", spec.game.restart_key.as_ref()),
+        (
+            "game.audio.mute_key",
+            spec.game.audio.as_ref().and_then(|a| a.mute_key.as_ref()),
+        ),
+    ];
+    for (path, key) in keys {
+        if let Some(key) = key.filter(|key| !is_valid_key(key)) {
+            report.error(path, format!("Unknown key name '{}'", key));
+        }
+    }
+}
+
+fn check_assets(spec: &PhaserGameSpec, report: &mut Report) {
+    let mut keys = HashSet::new();
+    for asset in &spec.assets {
+        let path = format!("assets[{}]", asset.key);
+        if !keys.insert(asset.key.as_str()) {
+            report.error(&path, "Asset key is used more than once");
+        }
+        if matches!(asset.asset_type, AssetType::Spritesheet) && asset.frame_config.is_none() {
+            report.error(&path, "Spritesheet assets must define frame_config");
+        }
+        for animation in asset.animations.iter().flatten() {
+            if animation.end_frame < animation.start_frame {
+                report.error(
+                    &path,
+                    format!("Animation '{}' ends before it starts", animation.key),
+                );
+            }
+        }
+    }
+}
+
+fn check_achievements(spec: &PhaserGameSpec, report: &mut Report) {
+    let variables: HashSet<&str> = spec
+        .scenes
+        .iter()
+        .flat_map(|scene| scene.custom_logic.iter())
+        .flat_map(|logic| logic.variables.iter().flatten())
+        .map(|variable| variable.name.as_str())
+        .chain(BUILTIN_VARIABLES)
+        .collect();
+    let mut ids = HashSet::new();
+    for achievement in spec.achievements.iter().flatten() {
+        let path = format!("achievements[{}]", achievement.id);
+        if !ids.insert(achievement.id.as_str()) {
+            report.error(&path, "Achievement id is used more than once");
+        }
+        match condition_variables(&achievement.condition) {
+            Ok(names) => {
+                for name in names.into_iter().filter(|name| !variables.contains(name)) {
+                    report.error(
+                        &path,
+                        format!("Condition uses undeclared variable '{}'", name),
+                    );
+                }
+            }
+            Err(e) => report.error(&path, format!("Invalid condition: {}", e)),
+        }
+    }
+}
+
+fn check_scene(spec: &PhaserGameSpec, scene: &Scene, report: &mut Report) {
+    let scene_path = format!("scenes[{}]", scene.name);
+    let logic = scene.custom_logic.as_ref();
+    let actions: Vec<_> = logic
+        .iter()
+        .flat_map(|logic| logic.actions.iter().flatten())
+        .collect();
+    let action_names: Vec<&str> = actions.iter().map(|action| action.name.as_str()).collect();
+    let variables: Vec<&str> = logic
+        .iter()
+        .flat_map(|logic| logic.variables.iter().flatten())
+        .map(|variable| variable.name.as_str())
+        .chain(BUILTIN_VARIABLES)
+        .collect();
+
+    // Object ids must be unique, and must not shadow spawned groups
+    let mut object_ids = HashSet::new();
+    for object in &scene.objects {
+        if !object_ids.insert(object.id.as_str()) {
+            report.error(
+                format!("{}.objects[{}]", scene_path, object.id),
+                "Object id is used more than once",
+            );
+        }
+    }
+    let groups = group_names(scene);
+    for group in &groups {
+        if object_ids.contains(group) {
+            report.warning(
+                format!("{}.objects[{}]", scene_path, group),
+                "Object id is also used as a spawned template id; handlers will target the object",
+            );
+        }
+    }
+
+    let mut spawner_ids = HashSet::new();
+    for spawner in logic
+        .iter()
+        .flat_map(|logic| logic.spawners.iter().flatten())
+    {
+        let path = format!("{}.spawners[{}]", scene_path, spawner.id);
+        if !spawner_ids.insert(spawner.id.as_str()) {
+            report.error(&path, "Spawner id is used more than once");
+        }
+        if spawner.interval == 0 {
+            report.error(&path, "Spawn interval must be greater than 0");
+        }
+        match &spawner.pool {
+            Some(pool) if pool.max_live == 0 => {
+                report.error(&path, "Pool max_live must be greater than 0");
+            }
+            None if spawner.interval < RAPID_SPAWN_INTERVAL && spawner.max_count.is_none() => {
+                report.warning(
+                    &path,
+                    format!(
+                        "Spawns every {}ms without a pool or max_count; live objects can pile up",
+                        spawner.interval
+                    ),
+                );
+            }
+            _ => (),
+        }
+    }
+
+    let (world_width, world_height) = scene
+        .camera
+        .as_ref()
+        .map(|camera| {
+            (
+                camera.world_width.unwrap_or(spec.game.width),
+                camera.world_height.unwrap_or(spec.game.height),
+            )
+        })
+        .unwrap_or((spec.game.width, spec.game.height));
+    for object in &scene.objects {
+        let off_world = object.x < 0.0
+            || object.y < 0.0
+            || object.x > world_width as f32
+            || object.y > world_height as f32;
+        if off_world && !matches!(object.object_type, ObjectType::Group) {
+            report.warning(
+                format!("{}.objects[{}]", scene_path, object.id),
+                format!(
+                    "Position ({}, {}) is outside the {}x{} world",
+                    object.x, object.y, world_width, world_height
+                ),
+            );
+        }
+    }
+
+    for object in scene.all_objects() {
+        let path = format!("{}.objects[{}]", scene_path, object.id);
+        check_object(object, &path, &action_names, scene, report);
+    }
+
+    let texts = scene
+        .all_objects()
+        .into_iter()
+        .filter_map(|object| object.text.as_ref().map(|text| &text.text))
+        .chain(
+            scene
+                .hud
+                .iter()
+                .flat_map(|hud| hud.labels.iter().flatten())
+                .map(|label| &label.text),
+        )
+        .chain(actions.iter().filter_map(|action| match &action.effect {
+            ActionEffect::UpdateText { text, .. } => Some(text),
+            _ => None,
+        }));
+    for text in texts {
+        for placeholder in template_placeholders(text) {
+            if !variables.contains(&placeholder) {
+                report.error(
+                    &scene_path,
+                    format!("Text '{}' uses undeclared variable '{}'", text, placeholder),
+                );
+            }
+        }
+    }
+
+    let mut seen_actions = HashSet::new();
+    for action in &actions {
+        let path = format!("{}.actions[{}]", scene_path, action.name);
+        if !seen_actions.insert(action.name.as_str()) {
+            report.error(&path, "Action name is used more than once");
+        }
+        if let Err(e) = action.effect.validate() {
+            report.error(&path, e);
+        }
+        match &action.effect {
+            ActionEffect::UpdateVariable { name, .. }
+                if !variables.contains(&name.as_str())
+                    || BUILTIN_VARIABLES.contains(&name.as_str()) =>
+            {
+                report.error(&path, format!("Updates undeclared variable '{}'", name));
+            }
+            ActionEffect::Win {
+                next_scene: Some(next_scene),
+                ..
+            } if !spec.scenes.iter().any(|scene| &scene.name == next_scene) => {
+                report.error(&path, format!("Leads to unknown scene '{}'", next_scene));
+            }
+            ActionEffect::StartSpawner { spawner_id }
+            | ActionEffect::StopSpawner { spawner_id }
+            | ActionEffect::SetSpawnerInterval { spawner_id, .. }
+                if !spawner_ids.contains(spawner_id.as_str()) =>
+            {
+                report.error(&path, format!("Controls unknown spawner '{}'", spawner_id));
+            }
+            _ => (),
+        }
+    }
+
+    let handlers = logic
+        .iter()
+        .flat_map(|logic| {
+            let collisions = logic.on_collision.iter().flatten();
+            let overlaps = logic.on_overlap.iter().flatten();
+            collisions
+                .map(|handler| ("on_collision", handler))
+                .chain(overlaps.map(|handler| ("on_overlap", handler)))
+        })
+        .collect::<Vec<_>>();
+    for (kind, handler) in handlers {
+        let path = format!("{}.{}", scene_path, kind);
+        let Some((first, second, callback)) = parse_handler(handler) else {
+            report.error(
+                &path,
+                format!("Handler '{}' must look like 'a,b -> action'", handler),
+            );
+            continue;
+        };
+        for name in [first, second] {
+            if !object_ids.contains(name) && !groups.contains(name) {
+                report.error(
+                    &path,
+                    format!(
+                        "Handler '{}' references unknown object or group '{}'",
+                        handler, name
+                    ),
+                );
+            }
+        }
+        if !action_names.contains(&callback) && !BUILTIN_CALLBACKS.contains(&callback) {
+            report.error(
+                &path,
+                format!("Handler '{}' uses unknown action '{}'", handler, callback),
+            );
+        }
+    }
+
+    for timer in logic.iter().flat_map(|logic| logic.timers.iter().flatten()) {
+        let path = format!("{}.timers", scene_path);
+        match parse_timer(timer) {
+            None => report.error(
+                &path,
+                format!(
+                    "Timer '{}' must look like 'every 1000ms -> action' or 'after 1000ms -> action'",
+                    timer
+                ),
+            ),
+            Some((0, _)) => report.error(&path, format!("Timer '{}' has a zero delay", timer)),
+            Some((_, callback)) if !action_names.contains(&callback) => report.warning(
+                &path,
+                format!("Timer '{}' uses unknown action '{}'", timer, callback),
+            ),
+            Some(_) => (),
+        }
+    }
+
+    for action_name in logic
+        .iter()
+        .flat_map(|logic| logic.on_start.iter().flatten())
+    {
+        if !action_names.contains(&action_name.as_str()) {
+            report.error(
+                format!("{}.on_start", scene_path),
+                format!("Runs unknown action '{}'", action_name),
+            );
+        }
+    }
+
+    let destroy_triggers = logic
+        .iter()
+        .flat_map(|logic| logic.on_destroyed.iter().flatten());
+    for trigger in destroy_triggers {
+        let path = format!("{}.on_destroyed", scene_path);
+        let target = trigger.target.as_str();
+        if !object_ids.contains(target) && !groups.contains(target) {
+            report.error(
+                &path,
+                format!("Watches unknown object or group '{}'", target),
+            );
+        }
+        if !action_names.contains(&trigger.action.as_str()) {
+            report.error(&path, format!("Runs unknown action '{}'", trigger.action));
+        }
+    }
+
+    let score_triggers = logic
+        .iter()
+        .flat_map(|logic| logic.on_score_reached.iter().flatten());
+    for trigger in score_triggers {
+        let path = format!("{}.on_score_reached", scene_path);
+        if trigger.at == 0 {
+            report.error(&path, "Score thresholds must be greater than 0");
+        }
+        if !action_names.contains(&trigger.action.as_str()) {
+            report.error(&path, format!("Runs unknown action '{}'", trigger.action));
+        }
+    }
+
+    let collision_groups: HashSet<&str> = scene
+        .all_objects()
+        .into_iter()
+        .filter_map(|object| object.collision_group.as_deref())
+        .collect();
+    let mut rule_pairs = HashSet::new();
+    let rules = logic
+        .iter()
+        .flat_map(|logic| logic.collision_matrix.iter().flatten());
+    for rule in rules {
+        let path = format!("{}.collision_matrix", scene_path);
+        for group in [&rule.a, &rule.b] {
+            if !collision_groups.contains(group.as_str()) {
+                report.error(
+                    &path,
+                    format!("No object is in collision group '{}'", group),
+                );
+            }
+        }
+        let pair = if rule.a <= rule.b {
+            (&rule.a, &rule.b)
+        } else {
+            (&rule.b, &rule.a)
+        };
+        if !rule_pairs.insert(pair) {
+            report.warning(
+                &path,
+                format!(
+                    "Groups '{}' and '{}' have more than one rule",
+                    rule.a, rule.b
+                ),
+            );
+        }
+        match &rule.action {
+            Some(action) if rule.mode == CollisionMode::Ignore => report.warning(
+                &path,
+                format!(
+                    "Action '{}' never runs because '{}' and '{}' ignore each other",
+                    action, rule.a, rule.b
+                ),
+            ),
+            Some(action)
+                if !action_names.contains(&action.as_str())
+                    && !BUILTIN_CALLBACKS.contains(&action.as_str()) =>
+            {
+                report.error(&path, format!("Runs unknown action '{}'", action))
+            }
+            _ => (),
+        }
+    }
+
+    let key_triggers = logic
+        .iter()
+        .flat_map(|logic| logic.on_key_press.iter().flatten());
+    for trigger in key_triggers {
+        let path = format!("{}.on_key_press", scene_path);
+        if !is_valid_key(&trigger.key) {
+            report.error(&path, format!("Unknown key name '{}'", trigger.key));
+        }
+        if !action_names.contains(&trigger.action.as_str()) {
+            report.error(
+                &path,
+                format!(
+                    "Key '{}' triggers unknown action '{}'",
+                    trigger.key, trigger.action
+                ),
+            );
+        }
+    }
+
+    let portals = logic
+        .iter()
+        .flat_map(|logic| logic.portals.iter().flatten());
+    for portal in portals {
+        let path = format!("{}.portals", scene_path);
+        if portal.a == portal.b {
+            report.error(&path, format!("Portal '{}' is linked to itself", portal.a));
+        }
+        for endpoint in [&portal.a, &portal.b] {
+            if !object_ids.contains(endpoint.as_str()) {
+                report.error(
+                    &path,
+                    format!("Portal endpoint '{}' does not exist", endpoint),
+                );
+            }
+        }
+    }
+
+    if let Some(health_bar) = scene.hud.as_ref().and_then(|hud| hud.health_bar.as_ref()) {
+        if !has_health(scene, &health_bar.object_id) {
+            report.error(
+                format!("{}.hud.health_bar", scene_path),
+                format!(
+                    "Health bar must reference an object with health, got '{}'",
+                    health_bar.object_id
+                ),
+            );
+        }
+    }
+
+    for zone in scene.gravity_zones.iter().flatten() {
+        if zone.width <= 0.0 || zone.height <= 0.0 {
+            report.error(
+                format!("{}.gravity_zones[{}]", scene_path, zone.id),
+                "Gravity zones must have a positive size",
+            );
+        }
+    }
+
+    if let Some(procedural) = &scene.procedural {
+        if let Err(e) = procedural.validate() {
+            report.error(format!("{}.procedural", scene_path), e);
+        }
+    }
+
+    if let Some(countdown) = &scene.countdown {
+        let path = format!("{}.countdown", scene_path);
+        if countdown.seconds == 0 {
+            report.error(&path, "Countdown must last at least one second");
+        }
+        if !action_names.contains(&countdown.on_expire.as_str()) {
+            report.error(
+                &path,
+                format!("Expires into unknown action '{}'", countdown.on_expire),
+            );
+        }
+    }
+
+    if let Some(camera) = &scene.camera {
+        let path = format!("{}.camera", scene_path);
+        if let Some(target) = &camera.follow {
+            if !object_ids.contains(target.as_str()) {
+                report.error(&path, format!("Follows unknown object '{}'", target));
+            }
+        }
+        if camera
+            .world_width
+            .is_some_and(|width| width < spec.game.width)
+            || camera
+                .world_height
+                .is_some_and(|height| height < spec.game.height)
+        {
+            report.error(&path, "World bounds must be at least the canvas size");
+        }
+        if camera.lerp.is_some_and(|lerp| !(0.0..=1.0).contains(&lerp)) {
+            report.error(&path, "Camera lerp must be between 0 and 1");
+        }
+    }
+
+    if let Some(tilemap) = &scene.tilemap {
+        let path = format!("{}.tilemap", scene_path);
+        if tilemap.tile_size == 0 {
+            report.error(&path, "Tilemap must have a tile_size greater than 0");
+        }
+        let mut missing = HashSet::new();
+        for symbol in tilemap.rows.iter().flat_map(|row| row.chars()) {
+            if !Tilemap::EMPTY_TILES.contains(&symbol)
+                && !tilemap.legend.iter().any(|entry| entry.symbol == symbol)
+                && missing.insert(symbol)
+            {
+                report.error(
+                    &path,
+                    format!("Uses '{}' which is missing from the legend", symbol),
+                );
+            }
+        }
+    }
+}
+
+fn check_object(
+    object: &GameObject,
+    path: &str,
+    action_names: &[&str],
+    scene: &Scene,
+    report: &mut Report,
+) {
+    if let Err(e) = object.validate() {
+        report.error(path, e);
+    }
+
+    match object.object_type {
+        ObjectType::Sprite if object.texture.is_none() => {
+            report.error(path, "Sprite objects must define a texture");
+        }
+        ObjectType::Rectangle => match &object.shape {
+            None => report.error(path, "Rectangle objects must define shape properties"),
+            Some(shape)
+                if shape.width.is_some_and(|w| w <= 0.0)
+                    || shape.height.is_some_and(|h| h <= 0.0) =>
+            {
+                report.error(path, "Rectangle width and height must be positive");
+            }
+            Some(_) => (),
+        },
+        ObjectType::Circle => match &object.shape {
+            None => report.error(path, "Circle objects must define shape properties"),
+            Some(shape) if shape.radius.is_some_and(|r| r <= 0.0) => {
+                report.error(path, "Circle radius must be positive");
+            }
+            Some(_) => (),
+        },
+        ObjectType::Text if object.text.is_none() => {
+            report.error(path, "Text objects must define text properties");
+        }
+        ObjectType::Emoji if object.emoji.is_none() => {
+            report.error(path, "Emoji objects must define emoji properties");
+        }
+        _ => (),
+    }
+
+    if let Some(emoji) = &object.emoji {
+        check_collision_box(
+            &emoji.collision_box,
+            emoji.size.unwrap_or(32.0),
+            path,
+            report,
+        );
+    }
+
+    if let Some(controls) = &object.controls {
+        check_controls(controls, path, report);
+    }
+
+    if let Some(action_name) = &object.on_click {
+        if !action_names.contains(&action_name.as_str()) {
+            report.error(path, format!("Click uses unknown action '{}'", action_name));
+        }
+    }
+
+    let zone_actions = object
+        .zone
+        .iter()
+        .flat_map(|zone| [&zone.on_enter, &zone.on_exit])
+        .flatten();
+    for action_name in zone_actions {
+        if !action_names.contains(&action_name.as_str()) {
+            report.error(path, format!("Zone uses unknown action '{}'", action_name));
+        }
+    }
+
+    if let Some(hazard) = object.hazard.as_ref().filter(|h| h.damage.is_some()) {
+        let target = hazard.target.as_deref().unwrap_or("player");
+        if !has_health(scene, target) {
+            report.error(
+                path,
+                format!("Hazard damages '{}', which has no health", target),
+            );
+        }
+    }
+}
+
+fn check_collision_box(collision_box: &CollisionBox, size: f32, path: &str, report: &mut Report) {
+    let extent = match collision_box.shape {
+        CollisionBoxShape::Rectangle => match (collision_box.width, collision_box.height) {
+            (Some(width), Some(height)) if width > 0.0 && height > 0.0 => width.max(height),
+            _ => {
+                report.error(
+                    path,
+                    "Rectangle collision boxes need a positive width and height",
+                );
+                return;
+            }
+        },
+        CollisionBoxShape::Circle => match collision_box.radius {
+            Some(radius) if radius > 0.0 => radius * 2.0,
+            _ => {
+                report.error(path, "Circle collision boxes need a positive radius");
+                return;
+            }
+        },
+    };
+    if extent > size * 4.0 {
+        report.warning(
+            path,
+            format!(
+                "Collision box ({}px) is much larger than the {}px emoji",
+                extent, size
+            ),
+        );
+    }
+}
+
+fn check_controls(controls: &Controls, path: &str, report: &mut Report) {
+    let keys = [
+        &controls.left,
+        &controls.right,
+        &controls.jump,
+        &controls.up,
+        &controls.down,
+        &controls.shoot,
+    ];
+    for key in keys.into_iter().flatten() {
+        if !is_valid_key(key) {
+            report.error(path, format!("Unknown key name '{}'", key));
+        }
+    }
+    if controls
+        .projectile_pool
+        .as_ref()
+        .is_some_and(|pool| pool.max_live == 0)
+    {
+        report.error(path, "Projectile pool max_live must be greater than 0");
+    }
+    if controls.shoot.is_some() && controls.projectile.is_none() {
+        report.warning(
+            path,
+            "Shoot key is set but no projectile template is defined",
+        );
+    }
+}
+
+/// Group names handlers can target: spawned template ids and the projectile group
+pub fn group_names(scene: &Scene) -> HashSet<&str> {
+    let mut groups: HashSet<&str> = scene
+        .custom_logic
+        .iter()
+        .flat_map(|logic| logic.spawners.iter().flatten())
+        .map(|spawner| spawner.template.id.as_str())
+        .collect();
+    for entry in scene.tilemap.iter().flat_map(|tilemap| &tilemap.legend) {
+        groups.insert(&entry.template.id);
+    }
+    if let Some(procedural) = &scene.procedural {
+        groups.insert(&procedural.platform.id);
+        for template in [&procedural.obstacle, &procedural.collectible]
+            .into_iter()
+            .flatten()
+        {
+            groups.insert(&template.id);
+        }
+    }
+    let shoots = scene.objects.iter().any(|object| {
+        object
+            .controls
+            .as_ref()
+            .is_some_and(|controls| controls.projectile.is_some())
+    });
+    if shoots {
+        groups.insert("projectiles");
+    }
+    groups
+}
+
+fn has_health(scene: &Scene, object_id: &str) -> bool {
+    scene
+        .objects
+        .iter()
+        .any(|object| object.id == object_id && object.health.is_some())
+}
+
+/// Split `"a,b -> action"` into its parts
+pub fn parse_handler(handler: &str) -> Option<(&str, &str, &str)> {
+    let (pair, callback) = handler.split_once("->")?;
+    let (first, second) = pair.split_once(',')?;
+    let (first, second, callback) = (first.trim(), second.trim(), callback.trim());
+    if first.is_empty() || second.is_empty() || callback.is_empty() {
+        return None;
+    }
+    Some((first, second, callback))
+}
+
+/// Split `"every 1000ms -> action"` or `"after 1000ms -> action"` into delay and action
+pub fn parse_timer(timer: &str) -> Option<(u32, &str)> {
+    let (schedule, callback) = timer.split_once("->")?;
+    let schedule = schedule.trim();
+    let delay = schedule
+        .strip_prefix("every")
+        .or_else(|| schedule.strip_prefix("after"))?
+        .trim()
+        .strip_suffix("ms")?
+        .parse()
+        .ok()?;
+    let callback = callback.trim();
+    (!callback.is_empty()).then_some((delay, callback))
+}
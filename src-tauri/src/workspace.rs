@@ -0,0 +1,257 @@
+/// Workspace directory mode: the library kept as plain files in a
+/// user-chosen directory (e.g. inside Dropbox or OneDrive), one folder per game:
+///
+/// ```text
+/// <workspace>/<game id>/game.json   title, notes, tags, spec, and history
+/// <workspace>/<game id>/assets/     library assets the game uses
+/// ```
+///
+/// The files are the source of truth and the database is an index of them for
+/// listing and search. Changes made in the app are written through to the
+/// game's folder; a scan folds changes made outside the app back into the
+/// index. Favorites, archiving, thumbnails, and scores stay in the database.
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use crate::assets::AssetLibrary;
+use crate::db::{Database, DbError, GameStamp};
+use crate::game_file::{self, GameFile, GameFileError};
+
+/// Setting holding the workspace directory; unset keeps games in the database only
+pub const WORKSPACE_SETTING: &str = "workspace_dir";
+
+/// Setting holding the games found by the last scan, so a folder deleted
+/// outside the app can be told apart from a game that was never written
+const WORKSPACE_INDEX_SETTING: &str = "workspace_index";
+
+const GAME_FILE_NAME: &str = "game.json";
+const ASSETS_DIR_NAME: &str = "assets";
+
+#[derive(Debug, thiserror::Error)]
+pub enum WorkspaceError {
+    #[error("Workspace directory not found: {0}")]
+    NotFound(String),
+    #[error("Database error: {0}")]
+    Db(#[from] DbError),
+    #[error("Game file error: {0}")]
+    GameFile(#[from] GameFileError),
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+/// Outcome of a scan
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct ScanReport {
+    /// Games read into the index because their files were new or changed
+    pub loaded: Vec<String>,
+    /// Games written because the index was newer or they had no folder yet
+    pub written: Vec<String>,
+    /// Games dropped from the index because their folder was deleted
+    pub removed: Vec<String>,
+    /// Folders that couldn't be read, with the reason; they are left untouched
+    pub invalid: Vec<String>,
+    pub unchanged: usize,
+}
+
+/// A game's `game.json`. Asset data lives in the assets folder instead of
+/// being embedded.
+#[derive(Debug, Serialize, Deserialize)]
+struct WorkspaceGame {
+    version: i64,
+    updated_at: DateTime<Utc>,
+    #[serde(flatten)]
+    file: GameFile,
+}
+
+/// Check that `dir` is an existing directory, returning its canonical path
+pub async fn resolve_dir(dir: &str) -> Result<PathBuf, WorkspaceError> {
+    match tokio::fs::canonicalize(dir).await {
+        Ok(path) if path.is_dir() => Ok(path),
+        _ => Err(WorkspaceError::NotFound(dir.to_string())),
+    }
+}
+
+/// Write a game to its folder, replacing what was there
+pub async fn write_game(
+    db: &Database,
+    assets: &AssetLibrary,
+    dir: &Path,
+    id: &str,
+) -> Result<(), WorkspaceError> {
+    // Writing into a fresh directory would make the real one's games look deleted
+    if !tokio::fs::try_exists(dir).await? {
+        return Err(WorkspaceError::NotFound(dir.display().to_string()));
+    }
+    let game = db.get_game(id).await?;
+    let mut file = game_file::build_game_file(db, assets, id).await?;
+
+    let game_dir = dir.join(id);
+    let assets_dir = game_dir.join(ASSETS_DIR_NAME);
+    // Start the assets folder over so assets the game dropped don't linger
+    match tokio::fs::remove_dir_all(&assets_dir).await {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+        _ => {}
+    }
+    tokio::fs::create_dir_all(&assets_dir).await?;
+    for asset in &mut file.assets {
+        let data = BASE64
+            .decode(&asset.data)
+            .map_err(|_| GameFileError::AssetData(asset.key.clone()))?;
+        tokio::fs::write(assets_dir.join(&asset.file_name), data).await?;
+        asset.data.clear();
+    }
+
+    let workspace_game = WorkspaceGame {
+        version: game.version,
+        updated_at: game.updated_at,
+        file,
+    };
+    // Write then rename, so sync clients never pick up a half-written file
+    let path = game_dir.join(GAME_FILE_NAME);
+    let temp = game_dir.join(format!("{}.tmp", GAME_FILE_NAME));
+    tokio::fs::write(&temp, serde_json::to_vec_pretty(&workspace_game)?).await?;
+    tokio::fs::rename(&temp, &path).await?;
+    Ok(())
+}
+
+/// Delete a game's folder
+pub async fn remove_game(dir: &Path, id: &str) -> Result<(), WorkspaceError> {
+    match tokio::fs::remove_dir_all(dir.join(id)).await {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Bring the index and the workspace in line. A folder whose `game.json` is
+/// newer by `(version, updated_at)` replaces the indexed game; one with the
+/// same stamp but a hand-edited spec is saved as a new version. Games without
+/// a folder are written, unless the last scan saw their folder, in which case
+/// it was deleted outside the app and the game is dropped from the index.
+pub async fn scan(
+    db: &Database,
+    assets: &AssetLibrary,
+    dir: &Path,
+) -> Result<ScanReport, WorkspaceError> {
+    // An unmounted or unsynced directory must not look like an empty library
+    if !tokio::fs::try_exists(dir).await? {
+        return Err(WorkspaceError::NotFound(dir.display().to_string()));
+    }
+
+    let previous: BTreeSet<String> = db
+        .get_setting(WORKSPACE_INDEX_SETTING)
+        .await?
+        .unwrap_or_default();
+    let stamps = db.list_game_stamps().await?;
+    let mut report = ScanReport::default();
+    let mut found = BTreeSet::new();
+
+    let mut entries = tokio::fs::read_dir(dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path().join(GAME_FILE_NAME);
+        let Some(id) = entry.file_name().to_str().map(str::to_string) else {
+            continue;
+        };
+        if !tokio::fs::try_exists(&path).await? {
+            continue;
+        }
+        found.insert(id.clone());
+
+        let stamp = stamps.iter().find(|stamp| stamp.id == id);
+        match load_game(db, assets, &entry.path(), &id, stamp).await {
+            Ok(Load::Loaded) => report.loaded.push(id),
+            Ok(Load::Stale) => {
+                write_game(db, assets, dir, &id).await?;
+                report.written.push(id);
+            }
+            Ok(Load::Unchanged) => report.unchanged += 1,
+            Err(e) => report.invalid.push(format!("{}: {}", id, e)),
+        }
+    }
+
+    for stamp in &stamps {
+        if found.contains(&stamp.id) {
+            continue;
+        }
+        if previous.contains(&stamp.id) {
+            db.delete_game(&stamp.id).await?;
+            report.removed.push(stamp.id.clone());
+        } else {
+            write_game(db, assets, dir, &stamp.id).await?;
+            found.insert(stamp.id.clone());
+            report.written.push(stamp.id.clone());
+        }
+    }
+
+    db.set_setting(WORKSPACE_INDEX_SETTING, &found).await?;
+    Ok(report)
+}
+
+/// Forget which games the last scan saw, e.g. when switching workspaces
+pub async fn clear_index(db: &Database) -> Result<(), WorkspaceError> {
+    db.delete_setting(WORKSPACE_INDEX_SETTING).await?;
+    Ok(())
+}
+
+enum Load {
+    /// The folder's copy went into the index
+    Loaded,
+    /// The index is newer than the folder
+    Stale,
+    Unchanged,
+}
+
+/// Read a game's folder into the index if it's newer than the indexed copy
+async fn load_game(
+    db: &Database,
+    assets: &AssetLibrary,
+    game_dir: &Path,
+    id: &str,
+    stamp: Option<&GameStamp>,
+) -> Result<Load, WorkspaceError> {
+    let data = tokio::fs::read(game_dir.join(GAME_FILE_NAME)).await?;
+    let mut workspace_game: WorkspaceGame = serde_json::from_slice(&data)?;
+
+    let on_disk = (workspace_game.version, workspace_game.updated_at);
+    match stamp.map(|stamp| (stamp.version, stamp.updated_at)) {
+        Some(indexed) if indexed > on_disk => return Ok(Load::Stale),
+        Some(indexed) if indexed == on_disk => {
+            // Same stamp: only a hand edit of the spec needs saving
+            let game = db.get_game(id).await?;
+            if serde_json::to_value(&game.spec)? == workspace_game.file.spec {
+                return Ok(Load::Unchanged);
+            }
+            let spec = game_file::load_valid_spec(workspace_game.file.spec)?;
+            db.update_game(id, spec, Some("Edited in the workspace".to_string()))
+                .await?;
+            return Ok(Load::Stale);
+        }
+        _ => {}
+    }
+
+    let assets_dir = game_dir.join(ASSETS_DIR_NAME);
+    for asset in &mut workspace_game.file.assets {
+        // The file is user-editable, so never follow a name out of the folder
+        let file_name = Path::new(&asset.file_name)
+            .file_name()
+            .ok_or_else(|| GameFileError::AssetData(asset.key.clone()))?;
+        let data = tokio::fs::read(assets_dir.join(file_name)).await?;
+        asset.data = BASE64.encode(data);
+    }
+    game_file::restore_game_file(
+        db,
+        assets,
+        id,
+        workspace_game.updated_at,
+        workspace_game.file,
+    )
+    .await?;
+    Ok(Load::Loaded)
+}
@@ -1,16 +1,23 @@
 import { useEffect, useRef, useState } from 'react'
-import { listen, UnlistenFn } from '@tauri-apps/api/event'
-import { Sun, Moon } from 'lucide-react'
+import type { UnlistenFn } from '@tauri-apps/api/event'
+import { invoke } from '@tauri-apps/api/core'
+import { getCurrentWebviewWindow } from '@tauri-apps/api/webviewWindow'
+import { Sun, Moon, AppWindow } from 'lucide-react'
 import { useChatStore } from '@/store/useChatStore'
 import { useSettingsStore } from '@/store/useSettingsStore'
 import { GameBuilder } from '@/components/GameBuilder'
 import { GameLibrary } from '@/components/GameLibrary'
 import { GameRendererTest } from '@/components/GameRendererTest'
 import { Logo } from '@/components/Logo'
-import { PhaserGameSpecSchema } from '@/schemas/gameSpec'
+import { PhaserGameSpecSchema, type PhaserGameSpec } from '@/schemas/gameSpec'
 
 type View = 'chat' | 'library' | 'test'
 
+interface LocaleInfo {
+  locale: string
+  name: string
+}
+
 function App() {
   const [currentView, setCurrentView] = useState<View>('chat')
   const {
@@ -31,6 +38,21 @@ function App() {
 
   const { theme, toggleTheme } = useSettingsStore()
 
+  // Language the game builder answers in and errors are shown in
+  const [locales, setLocales] = useState<LocaleInfo[]>([])
+  const [locale, setLocale] = useState('en')
+
+  useEffect(() => {
+    invoke<LocaleInfo[]>('list_locales').then(setLocales)
+    invoke<string>('get_locale').then(setLocale)
+  }, [])
+
+  const changeLocale = (next: string) => {
+    invoke('set_locale', { locale: next })
+      .then(() => setLocale(next))
+      .catch((err) => setError(String(err)))
+  }
+
   useEffect(() => {
     if (theme === 'dark') {
       document.documentElement.classList.add('dark')
@@ -43,6 +65,36 @@ function App() {
     checkInitialization()
   }, [checkInitialization])
 
+  // Ask before importing the game in a pueo://import link. Links that launched
+  // the app wait in the backend until this window picks them up.
+  useEffect(() => {
+    const appWindow = getCurrentWebviewWindow()
+    if (appWindow.label !== 'main') {
+      return
+    }
+    const importLinks = async () => {
+      const links = await invoke<string[]>('take_deep_links')
+      for (const link of links) {
+        try {
+          const spec = await invoke<PhaserGameSpec>('read_share_link', { link })
+          if (!confirm(`Add "${spec.title}" to your library?`)) {
+            continue
+          }
+          await invoke('import_share_link', { link })
+          setCurrentView('library')
+        } catch (err) {
+          alert(String(err))
+          console.error('Failed to import link:', err)
+        }
+      }
+    }
+    importLinks()
+    const unlisten = appWindow.listen('deep-link-received', () => importLinks())
+    return () => {
+      unlisten.then((fn) => fn())
+    }
+  }, [])
+
   // https://stackoverflow.com/a/72238236 - fixes double initialization in strict mode
   const listenersInitialized = useRef(false)
 
@@ -53,31 +105,37 @@ function App() {
     }
     let unlisten: UnlistenFn[] = []
 
+    // Chat events are sent only to the window whose conversation they belong to
+    const appWindow = getCurrentWebviewWindow()
+
     const setupListeners = async () => {
-      const unlistenToken = await listen<string>('chat-token', (event) => {
+      const unlistenToken = await appWindow.listen<string>('chat-token', (event) => {
         appendStreamingResponse(event.payload)
       })
 
       // Listen for reasoning (Claude extended thinking)
-      const unlistenReasoning = await listen<string>('reasoning', () => {
+      const unlistenReasoning = await appWindow.listen<string>('reasoning', () => {
         // Show thinking indicator
         setActiveToolCall({ name: 'thinking', timestamp: Date.now() })
       })
 
       // Listen for thinking (when LLM is planning which tool to call)
-      const unlistenThinking = await listen('thinking', () => {
+      const unlistenThinking = await appWindow.listen('thinking', () => {
         // Show thinking indicator during tool planning phase
         setActiveToolCall({ name: 'thinking', timestamp: Date.now() })
       })
 
       // Listen for tool calls (when LLM decides to call a tool)
       // With multi-turn enabled, the tool is automatically executed by rig
-      const unlistenToolCall = await listen<{ function: { name: string; arguments: unknown } }>(
+      const unlistenToolCall = await appWindow.listen<{ function: { name: string; arguments: unknown } }>(
         'tool-call',
         (event) => {
           const toolCall = event.payload
 
-          if (toolCall.function.name === 'generate_phaser_game') {
+          if (
+            toolCall.function.name === 'generate_phaser_game' ||
+            toolCall.function.name === 'patch_phaser_game'
+          ) {
             // Show "Generating Game..." indicator while tool executes
             setActiveToolCall({ name: toolCall.function.name, timestamp: Date.now() })
           }
@@ -86,7 +144,7 @@ function App() {
 
       // Listen for tool results (after tool execution completes)
       // This contains the processed output with defaults applied
-      const unlistenToolResult = await listen<string>('tool-result', (event) => {
+      const unlistenToolResult = await appWindow.listen<string>('tool-result', (event) => {
         // Tool execution completed, clear the indicator
         setActiveToolCall(null)
 
@@ -110,7 +168,7 @@ function App() {
       })
 
       // Listen for new turn (when agent responds again after tool use)
-      const unlistenNewTurn = await listen('chat-new-turn', () => {
+      const unlistenNewTurn = await appWindow.listen('chat-new-turn', () => {
         // Get the current streaming response from the store
         const store = useChatStore.getState()
         const currentStreaming = store.streamingResponse
@@ -126,7 +184,7 @@ function App() {
         store.setStreamingResponse('')
       })
 
-      const unlistenFinalResponse = await listen<string>(
+      const unlistenFinalResponse = await appWindow.listen<string>(
         'chat-final-response',
         (event) => {
           // When we receive the final response, add it to messages
@@ -143,8 +201,26 @@ function App() {
         },
       )
 
+      // Stopped early: keep what was streamed so far as the assistant's reply
+      const unlistenStopped = await appWindow.listen('chat-stopped', () => {
+        const store = useChatStore.getState()
+        if (store.streamingResponse) {
+          store.addMessage({
+            id: crypto.randomUUID(),
+            role: 'assistant',
+            content: store.streamingResponse,
+          })
+        }
+        store.setStreamingResponse('')
+      })
+
+      // Auto-save stored the generated game; later turns update the same game
+      const unlistenSaved = await appWindow.listen<{ id: string }>('game-saved', (event) => {
+        useChatStore.getState().setGameId(event.payload.id)
+      })
+
       // Listen for stream completion (just cleanup, message already added by final-response)
-      const unlistenComplete = await listen('chat-complete', () => {
+      const unlistenComplete = await appWindow.listen('chat-complete', () => {
         // Just ensure streaming state is cleaned up
         setIsStreaming(false)
         setStreamingResponse('')
@@ -152,7 +228,7 @@ function App() {
       })
 
       // Listen for errors
-      const unlistenError = await listen<string>('chat-error', (event) => {
+      const unlistenError = await appWindow.listen<string>('chat-error', (event) => {
         setError(event.payload)
         setIsStreaming(false)
         setStreamingResponse('')
@@ -167,6 +243,8 @@ function App() {
         unlistenToolResult,
         unlistenNewTurn,
         unlistenFinalResponse,
+        unlistenStopped,
+        unlistenSaved,
         unlistenComplete,
         unlistenError,
       ]
@@ -200,6 +278,25 @@ function App() {
           >
             {theme === 'light' ? <Sun size={18} /> : <Moon size={18} />}
           </button>
+          <button
+            onClick={() => invoke('open_chat_window').catch((err) => setError(String(err)))}
+            className="p-2 btn-sm"
+            title="Open a new window with its own conversation"
+          >
+            <AppWindow size={18} />
+          </button>
+          <select
+            value={locale}
+            onChange={(e) => changeLocale(e.target.value)}
+            className="input py-1 w-auto"
+            title="Language"
+          >
+            {locales.map((info) => (
+              <option key={info.locale} value={info.locale}>
+                {info.name}
+              </option>
+            ))}
+          </select>
         </div>
 
         {/* Navigation tabs */}
@@ -5,10 +5,27 @@ import { useChatStore, ChatMessage } from '@/store/useChatStore'
 import { Markdown } from '@/components/Markdown'
 import { PhaserGameRenderer } from '@/components/PhaserGameRenderer'
 
+interface SimilarGame {
+  game: { id: string; title: string }
+  kind: 'same_title' | 'similar_title' | 'similar_description'
+}
+
+type SaveResolution =
+  | { action: 'overwrite'; game_id: string }
+  | { action: 'save_as_new' }
+  | { action: 'merge'; game_id: string }
+
+type SaveOutcome =
+  | { status: 'saved'; game: { id: string } }
+  | { status: 'conflict'; similar: SimilarGame[] }
+
 export function GameBuilder() {
   const [showGameRenderer, setShowGameRenderer] = useState(false)
   const [showNewGameNotification, setShowNewGameNotification] = useState(false)
   const [showIdleThinking, setShowIdleThinking] = useState(false)
+  const [autoSave, setAutoSave] = useState(false)
+  // Existing games the last save looked like, until the user picks what to do
+  const [saveConflict, setSaveConflict] = useState<SimilarGame[] | null>(null)
   const previousGameSpecRef = useRef<string | null>(null)
   const streamingResponseRef = useRef<string>('')
   const {
@@ -18,15 +35,27 @@ export function GameBuilder() {
     streamingResponse,
     isStreaming,
     error,
+    setError,
     sendMessage,
     clearChat,
     setMessages,
-    systemPrompt,
     setSystemPrompt,
     generatedGameSpec,
     activeToolCall,
+    gameId,
+    setGameId,
   } = useChatStore()
 
+  useEffect(() => {
+    invoke<boolean>('get_auto_save').then(setAutoSave)
+  }, [])
+
+  const toggleAutoSave = (enabled: boolean) => {
+    invoke('set_auto_save', { enabled })
+      .then(() => setAutoSave(enabled))
+      .catch((err) => setError(String(err)))
+  }
+
   // Track when streaming response stops growing (indicates planning phase)
   useEffect(() => {
     if (!isStreaming || activeToolCall) {
@@ -65,40 +94,44 @@ export function GameBuilder() {
   }, [generatedGameSpec])
 
   const handleSendMessage = async () => {
-    let systemMessage: ChatMessage
-    if (systemPrompt) {
-      systemMessage = {
-        id: crypto.randomUUID(),
-        role: 'system',
-        content: systemPrompt,
-      }
-    } else {
+    // Temporarily inject system message
+    const currentMessages = [...messages]
+    if (currentMessages.length === 0 || currentMessages[0].role !== 'system') {
+      // Fetched for each new conversation so edits to a developer prompt file apply
       const newSystemPrompt = await invoke<string>('get_game_builder_prompt')
       setSystemPrompt(newSystemPrompt)
-      systemMessage = {
+      const systemMessage: ChatMessage = {
         id: crypto.randomUUID(),
         role: 'system',
         content: newSystemPrompt,
       }
-    }
-
-    // Temporarily inject system message
-    const currentMessages = [...messages]
-    if (currentMessages.length === 0 || currentMessages[0].role !== 'system') {
       setMessages([systemMessage, ...currentMessages])
     }
 
     await sendMessage()
   }
 
-  const saveGame = async () => {
+  const saveGame = async (resolution?: SaveResolution) => {
     if (!generatedGameSpec) return
 
     try {
-      const gameId = await invoke<string>('save_game', {
-        spec: generatedGameSpec,
-      })
-      alert(`Game saved successfully! ID: ${gameId}`)
+      let game: { id: string }
+      if (gameId && !resolution) {
+        game = await invoke<{ id: string }>('update_game', { id: gameId, spec: generatedGameSpec })
+      } else {
+        const outcome = await invoke<SaveOutcome>('save_game', {
+          spec: generatedGameSpec,
+          resolution,
+        })
+        if (outcome.status === 'conflict') {
+          setSaveConflict(outcome.similar)
+          return
+        }
+        game = outcome.game
+      }
+      setSaveConflict(null)
+      setGameId(game.id)
+      alert(`Game saved successfully! ID: ${game.id}`)
     } catch (err) {
       alert(`Failed to save game: ${err}`)
       console.error('Failed to save game:', err)
@@ -226,9 +259,19 @@ export function GameBuilder() {
             disabled={isStreaming}
             className="flex-1 input"
           />
-          <button type="submit" disabled={isStreaming} className="btn">
-            Send
-          </button>
+          {isStreaming ? (
+            <button
+              type="button"
+              onClick={() => invoke('stop_generation').catch((err) => setError(String(err)))}
+              className="btn"
+            >
+              Stop
+            </button>
+          ) : (
+            <button type="submit" className="btn">
+              Send
+            </button>
+          )}
           {generatedGameSpec && (
             <>
               <button
@@ -238,7 +281,7 @@ export function GameBuilder() {
               >
                 Play Game
               </button>
-              <button type="button" onClick={saveGame} className="btn-primary">
+              <button type="button" onClick={() => saveGame()} className="btn-primary">
                 Save Game
               </button>
             </>
@@ -246,7 +289,62 @@ export function GameBuilder() {
           <button type="button" onClick={clearChat} className="btn">
             Clear
           </button>
+          <label
+            className="flex items-center gap-1 text-sm"
+            title="Save every game the builder makes to the library"
+          >
+            <input
+              type="checkbox"
+              checked={autoSave}
+              onChange={(e) => toggleAutoSave(e.target.checked)}
+            />
+            Auto-save
+          </label>
         </form>
+
+        {saveConflict && (
+          <div className="mt-4 p-4 rounded-lg border border-yellow-400 text-left">
+            <p className="font-semibold mb-2">This looks like a game you already saved:</p>
+            {saveConflict.map(({ game, kind }) => (
+              <div key={game.id} className="flex items-center gap-2 mb-2">
+                <span className="flex-1">
+                  {game.title}
+                  <span className="text-muted text-sm ml-2">
+                    {kind === 'similar_description' ? '(similar description)' : '(similar title)'}
+                  </span>
+                </span>
+                <button
+                  type="button"
+                  onClick={() => saveGame({ action: 'overwrite', game_id: game.id })}
+                  className="btn-sm"
+                  title="Save this as the newest version of that game"
+                >
+                  Overwrite
+                </button>
+                <button
+                  type="button"
+                  onClick={() => saveGame({ action: 'merge', game_id: game.id })}
+                  className="btn-sm"
+                  title="Add this game's scenes to that game"
+                >
+                  Merge
+                </button>
+              </div>
+            ))}
+            <div className="flex gap-2 mt-3">
+              <button
+                type="button"
+                onClick={() => saveGame({ action: 'save_as_new' })}
+                className="btn-primary"
+              >
+                Save as New
+              </button>
+              <button type="button" onClick={() => setSaveConflict(null)} className="btn">
+                Cancel
+              </button>
+            </div>
+          </div>
+        )}
       </div>
 
       {showGameRenderer && generatedGameSpec && (
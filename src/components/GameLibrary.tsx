@@ -1,5 +1,7 @@
 import { useEffect, useState } from 'react'
 import { invoke } from '@tauri-apps/api/core'
+import { listen } from '@tauri-apps/api/event'
+import { AppWindow } from 'lucide-react'
 import { PhaserGameRenderer } from '@/components/PhaserGameRenderer'
 import type { GameSummary, PhaserGameSpec } from '@/schemas/gameSpec'
 
@@ -8,13 +10,23 @@ export function GameLibrary() {
   const [filteredGames, setFilteredGames] = useState<GameSummary[]>([])
   const [searchQuery, setSearchQuery] = useState('')
   const [selectedGame, setSelectedGame] = useState<PhaserGameSpec | null>(null)
+  const [selectedGameId, setSelectedGameId] = useState<string | null>(null)
+  const [thumbnails, setThumbnails] = useState<Record<string, string>>({})
   const [showRenderer, setShowRenderer] = useState(false)
   const [loading, setLoading] = useState(true)
   const [error, setError] = useState('')
 
-  // Load games on mount
+  // Load games on mount, and again once the startup workspace scan finishes
+  // or a game comes in through a share link
   useEffect(() => {
     loadGames()
+    const unlisten = [
+      listen('workspace-scanned', () => loadGames()),
+      listen('game-imported', () => loadGames()),
+    ]
+    return () => {
+      unlisten.forEach((promise) => promise.then((fn) => fn()))
+    }
   }, [])
 
   // Filter games when search query changes
@@ -39,6 +51,7 @@ export function GameLibrary() {
       const gameList = await invoke<GameSummary[]>('list_games')
       setGames(gameList)
       setFilteredGames(gameList)
+      await Promise.all(gameList.map((game) => loadThumbnail(game.id)))
     } catch (err) {
       setError(String(err))
       console.error('Failed to load games:', err)
@@ -47,10 +60,32 @@ export function GameLibrary() {
     }
   }
 
+  // Pick up games edited in the workspace directory outside the app first
+  const refreshGames = async () => {
+    try {
+      await invoke('scan_workspace')
+    } catch (err) {
+      console.error('Failed to scan workspace:', err)
+    }
+    await loadGames()
+  }
+
+  const loadThumbnail = async (gameId: string) => {
+    try {
+      const thumbnail = await invoke<string | null>('get_game_thumbnail', { gameId })
+      if (thumbnail) {
+        setThumbnails((current) => ({ ...current, [gameId]: thumbnail }))
+      }
+    } catch (err) {
+      console.error('Failed to load thumbnail:', err)
+    }
+  }
+
   const playGame = async (gameId: string) => {
     try {
       const game = await invoke<{ spec: PhaserGameSpec }>('get_game', { id: gameId })
       setSelectedGame(game.spec)
+      setSelectedGameId(gameId)
       setShowRenderer(true)
     } catch (err) {
       alert(`Failed to load game: ${err}`)
@@ -58,6 +93,16 @@ export function GameLibrary() {
     }
   }
 
+  // Play in a separate window, so the library stays usable
+  const openGameWindow = async (gameId: string) => {
+    try {
+      await invoke('open_game_window', { gameId })
+    } catch (err) {
+      alert(`Failed to open game window: ${err}`)
+      console.error('Failed to open game window:', err)
+    }
+  }
+
   const deleteGame = async (gameId: string, title: string) => {
     if (!confirm(`Are you sure you want to delete "${title}"?`)) {
       return
@@ -88,7 +133,7 @@ export function GameLibrary() {
       <div className="flex justify-between items-center mb-6">
         <h2 className="text-2xl font-bold">Game Library</h2>
         <button
-          onClick={loadGames}
+          onClick={refreshGames}
           disabled={loading}
           className="btn-refresh"
         >
@@ -128,6 +173,13 @@ export function GameLibrary() {
             key={game.id}
             className="card"
           >
+            {thumbnails[game.id] && (
+              <img
+                src={thumbnails[game.id]}
+                alt={`${game.title} preview`}
+                className="w-full aspect-[4/3] object-contain bg-black rounded mb-3"
+              />
+            )}
             <h3 className="text-lg font-semibold mb-2">{game.title}</h3>
             <p className="text-sm text-muted mb-3 line-clamp-2">
               {game.description}
@@ -142,6 +194,13 @@ export function GameLibrary() {
               >
                 Play
               </button>
+              <button
+                onClick={() => openGameWindow(game.id)}
+                className="btn-sm"
+                title="Play in a new window"
+              >
+                <AppWindow size={16} />
+              </button>
               <button
                 onClick={() => deleteGame(game.id, game.title)}
                 className="btn-danger-sm"
@@ -157,9 +216,12 @@ export function GameLibrary() {
       {showRenderer && selectedGame && (
         <PhaserGameRenderer
           spec={selectedGame}
+          gameId={selectedGameId ?? undefined}
+          onThumbnailCaptured={selectedGameId ? () => loadThumbnail(selectedGameId) : undefined}
           onClose={() => {
             setShowRenderer(false)
             setSelectedGame(null)
+            setSelectedGameId(null)
           }}
         />
       )}
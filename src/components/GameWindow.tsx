@@ -0,0 +1,35 @@
+import { useEffect, useState } from 'react'
+import { invoke } from '@tauri-apps/api/core'
+import { getCurrentWindow } from '@tauri-apps/api/window'
+import { PhaserGameRenderer } from '@/components/PhaserGameRenderer'
+import type { PhaserGameSpec } from '@/schemas/gameSpec'
+
+interface GameWindowProps {
+  gameId: string
+}
+
+// Contents of a window opened to play a single saved game
+export function GameWindow({ gameId }: GameWindowProps) {
+  const [spec, setSpec] = useState<PhaserGameSpec | null>(null)
+  const [error, setError] = useState('')
+
+  useEffect(() => {
+    invoke<{ spec: PhaserGameSpec }>('get_game', { id: gameId })
+      .then((game) => setSpec(game.spec))
+      .catch((err) => setError(String(err)))
+  }, [gameId])
+
+  if (error) {
+    return <div className="p-10 text-center text-error">{error}</div>
+  }
+  if (!spec) {
+    return <div className="p-10 text-center text-muted">Loading...</div>
+  }
+  return (
+    <PhaserGameRenderer
+      spec={spec}
+      gameId={gameId}
+      onClose={() => getCurrentWindow().close()}
+    />
+  )
+}
@@ -1,35 +1,64 @@
 import { useEffect, useRef } from 'react'
 import type Phaser from 'phaser'
+import { invoke } from '@tauri-apps/api/core'
 import { createPhaserGame } from '@/utils/phaserRenderer'
 import type { PhaserGameSpec } from '@/schemas/gameSpec'
 
+// How long a saved game runs before its canvas is captured as the thumbnail
+const THUMBNAIL_DELAY_MS = 1500
+
 interface PhaserGameRendererProps {
   spec: PhaserGameSpec
   onClose: () => void
+  // Saved games get a thumbnail captured from the running canvas, and keep
+  // their high scores
+  gameId?: string
+  onThumbnailCaptured?: () => void
 }
 
-export function PhaserGameRenderer({ spec, onClose }: PhaserGameRendererProps) {
+export function PhaserGameRenderer({ spec, onClose, gameId, onThumbnailCaptured }: PhaserGameRendererProps) {
   const gameContainerRef = useRef<HTMLDivElement>(null)
   const gameInstanceRef = useRef<Phaser.Game | null>(null)
+  // Kept in a ref so a new callback doesn't restart the game
+  const onThumbnailCapturedRef = useRef(onThumbnailCaptured)
+  onThumbnailCapturedRef.current = onThumbnailCaptured
 
   useEffect(() => {
     if (!gameContainerRef.current) return
 
     // Create the game
     try {
-      gameInstanceRef.current = createPhaserGame(spec, gameContainerRef.current)
+      gameInstanceRef.current = createPhaserGame(spec, gameContainerRef.current, gameId)
     } catch (error) {
       console.error('Failed to create Phaser game:', error)
     }
 
+    // Capture a thumbnail once the game has had time to draw something
+    const thumbnailTimer = gameId
+      ? setTimeout(() => {
+          gameInstanceRef.current?.renderer.snapshot(async (snapshot) => {
+            if (!(snapshot instanceof HTMLImageElement)) return
+            try {
+              const response = await fetch(snapshot.src)
+              const pngBytes = Array.from(new Uint8Array(await response.arrayBuffer()))
+              await invoke('capture_game_thumbnail', { gameId, pngBytes })
+              onThumbnailCapturedRef.current?.()
+            } catch (error) {
+              console.error('Failed to capture thumbnail:', error)
+            }
+          })
+        }, THUMBNAIL_DELAY_MS)
+      : undefined
+
     // Cleanup on unmount
     return () => {
+      clearTimeout(thumbnailTimer)
       if (gameInstanceRef.current) {
         gameInstanceRef.current.destroy(true)
         gameInstanceRef.current = null
       }
     }
-  }, [spec])
+  }, [spec, gameId])
 
   return (
     <div className="fixed inset-0 bg-black bg-opacity-50 flex items-center justify-center z-50">
@@ -1,10 +1,14 @@
 import React from 'react'
 import ReactDOM from 'react-dom/client'
 import App from '@/App'
+import { GameWindow } from '@/components/GameWindow'
 import '@/globals.css'
 
+// Game windows are opened with the id of the game to play
+const playGameId = new URLSearchParams(window.location.search).get('play')
+
 ReactDOM.createRoot(document.getElementById('root') as HTMLElement).render(
   <React.StrictMode>
-    <App />
+    {playGameId ? <GameWindow gameId={playGameId} /> : <App />}
   </React.StrictMode>,
 )
@@ -0,0 +1,17 @@
+/**
+ * Entry point of the standalone game runtime: Phaser and the spec interpreter
+ * bundled into one script, which exported HTML files inline to play a game
+ * without the app
+ */
+import { createPhaserGame } from '@/utils/phaserRenderer'
+import type { PhaserGameSpec } from '@/schemas/gameSpec'
+
+declare global {
+  interface Window {
+    pueoStart: (spec: PhaserGameSpec, parent: string) => void
+  }
+}
+
+window.pueoStart = (spec, parent) => {
+  createPhaserGame(spec, parent)
+}
@@ -1,9 +1,13 @@
 // Zod schemas for game specification with runtime validation
+//
+// These mirror the Rust spec types; pueo.schema.json next to this file is the
+// JSON Schema exported from them (`npm run schema`). Keep the two in step:
+// fields missing here are stripped from generated games before they render.
 import { z } from 'zod'
 
 // Gravity configuration
 export const GravityConfigSchema = z.object({
-  x: z.number(),
+  x: z.number().default(0),
   y: z.number(),
 })
 
@@ -11,24 +15,78 @@ export const GravityConfigSchema = z.object({
 export const PhysicsConfigSchema = z.object({
   enabled: z.boolean(),
   gravity: GravityConfigSchema,
-  debug: z.boolean(),
+  debug: z.boolean().default(false),
+  wrap_world: z.boolean().default(false),
+})
+
+// Audio
+export const MusicStyleSchema = z.enum(['chiptune', 'upbeat', 'calm', 'spooky'])
+
+export const BackgroundMusicSchema = z.discriminatedUnion('source', [
+  z.object({
+    source: z.literal('asset'),
+    key: z.string(),
+    volume: z.number().optional(),
+  }),
+  z.object({
+    source: z.literal('generated'),
+    style: MusicStyleSchema,
+    tempo: z.number().optional(),
+    volume: z.number().optional(),
+  }),
+])
+
+export const AudioConfigSchema = z.object({
+  background_music: BackgroundMusicSchema.optional(),
+  master_volume: z.number().optional(),
+  mute_key: z.string().optional(),
+})
+
+// Pause configuration
+export const PauseConfigSchema = z.object({
+  key: z.string().default('P'),
+  overlay_text: z.string().optional(),
+  halt_timers: z.boolean().default(true),
+  halt_spawners: z.boolean().default(true),
 })
 
 // Game configuration
 export const GameConfigSchema = z.object({
   width: z.number().positive(),
   height: z.number().positive(),
-  background_color: z.string(),
+  background_color: z.string().default('#87CEEB'),
   physics: PhysicsConfigSchema,
+  audio: AudioConfigSchema.optional(),
+  pause: PauseConfigSchema.optional(),
+  restart_key: z.string().optional(),
 })
 
 // Asset types
-export const AssetTypeSchema = z.enum(['sprite', 'audio', 'image'])
+export const AssetTypeSchema = z.enum(['sprite', 'spritesheet', 'audio', 'image'])
+
+export const FrameConfigSchema = z.object({
+  frame_width: z.number(),
+  frame_height: z.number(),
+  margin: z.number().optional(),
+  spacing: z.number().optional(),
+})
+
+export const SpriteAnimationSchema = z.object({
+  key: z.string(),
+  start_frame: z.number(),
+  end_frame: z.number(),
+  frame_rate: z.number().optional(),
+  repeat: z.number().optional(),
+})
 
 export const AssetSchema = z.object({
   key: z.string(),
   type: AssetTypeSchema,
-  url: z.string().url(),
+  // Empty for library assets, which are loaded by library_key
+  url: z.string().default(''),
+  library_key: z.string().optional(),
+  frame_config: FrameConfigSchema.optional(),
+  animations: z.array(SpriteAnimationSchema).optional(),
 })
 
 // Physics body types
@@ -36,16 +94,41 @@ export const PhysicsBodySchema = z.enum(['dynamic', 'static', 'none'])
 
 // Velocity configuration
 export const VelocityConfigSchema = z.object({
-  x: z.number(),
-  y: z.number(),
+  x: z.number().default(0),
+  y: z.number().default(0),
 })
 
+// Platform surfaces
+export const SurfaceSchema = z.discriminatedUnion('type', [
+  z.object({
+    type: z.literal('ice'),
+    friction: z.number().optional(),
+  }),
+  z.object({
+    type: z.literal('conveyor'),
+    speed: z.number(),
+  }),
+  z.object({
+    type: z.literal('bounce'),
+    impulse: z.number(),
+  }),
+])
+
 // Object physics
 export const ObjectPhysicsSchema = z.object({
   body: PhysicsBodySchema,
   bounce: z.number().optional(),
   collide_world_bounds: z.boolean().optional(),
   velocity: VelocityConfigSchema.optional(),
+  gravity: GravityConfigSchema.optional(),
+  drag: z.number().optional(),
+  max_velocity: z.number().optional(),
+  mass: z.number().optional(),
+  immovable: z.boolean().optional(),
+  angular_velocity: z.number().optional(),
+  wrap_world: z.boolean().optional(),
+  one_way: z.boolean().optional(),
+  surface: SurfaceSchema.optional(),
 })
 
 // Shape properties
@@ -57,10 +140,27 @@ export const ShapePropertiesSchema = z.object({
 })
 
 // Text properties
+export const TextAlignSchema = z.enum(['left', 'center', 'right'])
+
+export const TextShadowSchema = z.object({
+  offset_x: z.number().optional(),
+  offset_y: z.number().optional(),
+  color: z.string().optional(),
+  blur: z.number().optional(),
+})
+
 export const TextPropertiesSchema = z.object({
   text: z.string(),
   font_size: z.string().optional(),
   fill: z.string().optional(),
+  font_family: z.string().optional(),
+  stroke: z.string().optional(),
+  stroke_thickness: z.number().optional(),
+  shadow: TextShadowSchema.optional(),
+  align: TextAlignSchema.optional(),
+  word_wrap_width: z.number().optional(),
+  background_color: z.string().optional(),
+  padding: z.number().optional(),
 })
 
 // Collision box
@@ -74,19 +174,74 @@ export const CollisionBoxSchema = z.object({
 })
 
 // Emoji properties
+export const EmojiLayerSchema = z.object({
+  emoji: z.string(),
+  offset_x: z.number().default(0),
+  offset_y: z.number().default(0),
+  scale: z.number().optional(),
+  behind: z.boolean().default(false),
+})
+
 export const EmojiPropertiesSchema = z.object({
   emoji: z.string(),
   size: z.number().optional(),
   collision_box: CollisionBoxSchema,
+  layers: z.array(EmojiLayerSchema).optional(),
+})
+
+// Trigger zone properties
+export const ZonePropertiesSchema = z.object({
+  width: z.number(),
+  height: z.number(),
+  triggered_by: z.string().optional(),
+  on_enter: z.string().optional(),
+  on_exit: z.string().optional(),
+  once: z.boolean().optional(),
+})
+
+// Hazard presets
+export const HazardKindSchema = z.enum(['spikes', 'saw', 'lava'])
+
+export const HazardPropertiesSchema = z.object({
+  kind: HazardKindSchema,
+  width: z.number().optional(),
+  height: z.number().optional(),
+  damage: z.number().optional(),
+  target: z.string().optional(),
+})
+
+// Animations played for movement states
+export const ObjectAnimationsSchema = z.object({
+  idle: z.string().optional(),
+  walk: z.string().optional(),
+  jump: z.string().optional(),
 })
 
 // Behavior types
 export const BehaviorTypeSchema = z.enum(['patrol', 'follow', 'static', 'random'])
 
 // Object types
-export const ObjectTypeSchema = z.enum(['sprite', 'rectangle', 'circle', 'text', 'emoji', 'group'])
+export const ObjectTypeSchema = z.enum([
+  'sprite',
+  'rectangle',
+  'circle',
+  'text',
+  'emoji',
+  'group',
+  'hazard',
+  'zone',
+])
+
+// Object pools
+export const RecyclePolicySchema = z.enum(['skip', 'oldest'])
 
-// Controls
+export const PoolConfigSchema = z.object({
+  max_live: z.number(),
+  recycle: RecyclePolicySchema.default('skip'),
+})
+
+// Controls. Projectiles are game objects themselves, so the schema refers
+// back to GameObjectSchema lazily.
 export const ControlsSchema = z.object({
   left: z.string().optional(),
   right: z.string().optional(),
@@ -94,23 +249,64 @@ export const ControlsSchema = z.object({
   up: z.string().optional(),
   down: z.string().optional(),
   shoot: z.string().optional(),
-  projectile: z.lazy(() => GameObjectSchema).optional(),
+  projectile: z.lazy((): z.ZodType<GameObject> => GameObjectSchema).optional(),
+  projectile_pool: PoolConfigSchema.optional(),
 })
 
-// Game object
-export const GameObjectSchema: z.ZodType<any> = z.object({
+// Game object, written out as an interface because it contains itself
+// through controls.projectile
+export interface GameObject {
+  id: string
+  type: ObjectType
+  x: number
+  y: number
+  texture?: string
+  animations?: ObjectAnimations
+  shape?: ShapeProperties
+  text?: TextProperties
+  emoji?: EmojiProperties
+  zone?: ZoneProperties
+  hazard?: HazardProperties
+  depth?: number
+  alpha?: number
+  tint?: string
+  rotation?: number
+  scale?: number
+  physics?: ObjectPhysics
+  controls?: Controls
+  behavior?: BehaviorType
+  behavior_params?: any
+  health?: number
+  checkpoint?: boolean
+  on_click?: string
+  collision_group?: string
+}
+
+export const GameObjectSchema: z.ZodType<GameObject> = z.object({
   id: z.string(),
   type: ObjectTypeSchema,
   x: z.number(),
   y: z.number(),
   texture: z.string().optional(),
+  animations: ObjectAnimationsSchema.optional(),
   shape: ShapePropertiesSchema.optional(),
   text: TextPropertiesSchema.optional(),
   emoji: EmojiPropertiesSchema.optional(),
+  zone: ZonePropertiesSchema.optional(),
+  hazard: HazardPropertiesSchema.optional(),
+  depth: z.number().optional(),
+  alpha: z.number().optional(),
+  tint: z.string().optional(),
+  rotation: z.number().optional(),
+  scale: z.number().optional(),
   physics: ObjectPhysicsSchema.optional(),
   controls: ControlsSchema.optional(),
   behavior: BehaviorTypeSchema.optional(),
   behavior_params: z.any().optional(),
+  health: z.number().optional(),
+  checkpoint: z.boolean().optional(),
+  on_click: z.string().optional(),
+  collision_group: z.string().optional(),
 })
 
 // Position variance
@@ -129,25 +325,145 @@ export const SpawnerSchema = z.object({
   max_count: z.number().optional(),
   spawn_area: z.string(),
   position_variance: PositionVarianceSchema.optional(),
+  pool: PoolConfigSchema.optional(),
 })
 
+// End screens
+export const ScreenStyleSchema = z.object({
+  background_color: z.string().optional(),
+  background_alpha: z.number().optional(),
+  fill: z.string().optional(),
+  font_size: z.string().optional(),
+})
+
+export const ScreenStatSchema = z.enum(['score', 'time', 'lives'])
+
 // Action effects
-export const ActionEffectSchema = z.union([
+export const ActionEffectSchema = z.discriminatedUnion('type', [
   z.object({
     type: z.literal('updateScore'),
     points: z.number(),
   }),
   z.object({
     type: z.literal('gameOver'),
+    message: z.string().optional(),
+    show_score: z.boolean().optional(),
+    restart_prompt: z.string().optional(),
+    style: ScreenStyleSchema.optional(),
+  }),
+  z.object({
+    type: z.literal('win'),
+    message: z.string().optional(),
+    stats: z.array(ScreenStatSchema).optional(),
+    next_scene: z.string().optional(),
+    next_button_text: z.string().optional(),
+    style: ScreenStyleSchema.optional(),
   }),
   z.object({
     type: z.literal('destroy'),
   }),
+  z.object({
+    type: z.literal('restartScene'),
+  }),
+  z.object({
+    type: z.literal('saveHighScore'),
+    ask_name: z.boolean().optional(),
+  }),
   z.object({
     type: z.literal('updateText'),
     object_id: z.string(),
     text: z.string(),
   }),
+  z.object({
+    type: z.literal('updateVariable'),
+    name: z.string(),
+    change: z.number().optional(),
+    value: z.number().optional(),
+  }),
+  z.object({
+    type: z.literal('damage'),
+    amount: z.number(),
+    object_id: z.string().optional(),
+  }),
+  z.object({
+    type: z.literal('loseLife'),
+  }),
+  z.object({
+    type: z.literal('setAlpha'),
+    alpha: z.number(),
+    object_id: z.string().optional(),
+    duration: z.number().optional(),
+  }),
+  z.object({
+    type: z.literal('setTint'),
+    color: z.string().optional(),
+    object_id: z.string().optional(),
+    duration: z.number().optional(),
+  }),
+  z.object({
+    type: z.literal('rotate'),
+    degrees: z.number(),
+    object_id: z.string().optional(),
+    duration: z.number().optional(),
+  }),
+  z.object({
+    type: z.literal('scale'),
+    scale: z.number(),
+    object_id: z.string().optional(),
+    duration: z.number().optional(),
+  }),
+  z.object({
+    type: z.literal('setCheckpoint'),
+    object_id: z.string().optional(),
+  }),
+  z.object({
+    type: z.literal('respawnAtCheckpoint'),
+    object_id: z.string().optional(),
+  }),
+  z.object({
+    type: z.literal('cameraShake'),
+    duration: z.number(),
+    intensity: z.number().optional(),
+  }),
+  z.object({
+    type: z.literal('cameraFlash'),
+    duration: z.number(),
+    color: z.string().optional(),
+  }),
+  z.object({
+    type: z.literal('cameraZoom'),
+    zoom: z.number(),
+    duration: z.number().optional(),
+  }),
+  z.object({
+    type: z.literal('startSpawner'),
+    spawner_id: z.string(),
+  }),
+  z.object({
+    type: z.literal('stopSpawner'),
+    spawner_id: z.string(),
+  }),
+  z.object({
+    type: z.literal('setSpawnerInterval'),
+    spawner_id: z.string(),
+    interval: z.number(),
+  }),
+  z.object({
+    type: z.literal('flashScreen'),
+    color: z.string(),
+    duration: z.number(),
+    alpha: z.number().optional(),
+  }),
+  z.object({
+    type: z.literal('setBackgroundColor'),
+    color: z.string(),
+    duration: z.number().optional(),
+  }),
+  z.object({
+    type: z.literal('setTimeScale'),
+    scale: z.number(),
+    duration: z.number().optional(),
+  }),
 ])
 
 // Action definition
@@ -156,6 +472,48 @@ export const ActionDefinitionSchema = z.object({
   effect: ActionEffectSchema,
 })
 
+// Triggers
+export const KeyTriggerSchema = z.object({
+  key: z.string(),
+  action: z.string(),
+})
+
+export const ScoreTriggerSchema = z.object({
+  at: z.number(),
+  action: z.string(),
+  repeat: z.boolean().default(false),
+})
+
+export const DestroyTriggerSchema = z.object({
+  target: z.string(),
+  action: z.string(),
+  all: z.boolean().default(false),
+})
+
+// Collision matrix
+export const CollisionModeSchema = z.enum(['collide', 'overlap', 'ignore'])
+
+export const CollisionRuleSchema = z.object({
+  a: z.string(),
+  b: z.string(),
+  mode: CollisionModeSchema.default('collide'),
+  action: z.string().optional(),
+})
+
+// Portals
+export const PortalPairSchema = z.object({
+  a: z.string(),
+  b: z.string(),
+  cooldown: z.number().optional(),
+  preserve_velocity: z.boolean().optional(),
+})
+
+// Variables
+export const VariableSchema = z.object({
+  name: z.string(),
+  initial: z.number().default(0),
+})
+
 // Custom logic
 export const CustomLogicSchema = z.object({
   on_collision: z.array(z.string()).optional(),
@@ -163,6 +521,130 @@ export const CustomLogicSchema = z.object({
   timers: z.array(z.string()).optional(),
   spawners: z.array(SpawnerSchema).optional(),
   actions: z.array(ActionDefinitionSchema).optional(),
+  variables: z.array(VariableSchema).optional(),
+  on_key_press: z.array(KeyTriggerSchema).optional(),
+  on_start: z.array(z.string()).optional(),
+  on_destroyed: z.array(DestroyTriggerSchema).optional(),
+  on_score_reached: z.array(ScoreTriggerSchema).optional(),
+  collision_matrix: z.array(CollisionRuleSchema).optional(),
+  portals: z.array(PortalPairSchema).optional(),
+})
+
+// Camera
+export const CameraDeadzoneSchema = z.object({
+  width: z.number(),
+  height: z.number(),
+})
+
+export const CameraConfigSchema = z.object({
+  follow: z.string().optional(),
+  world_width: z.number().optional(),
+  world_height: z.number().optional(),
+  lerp: z.number().optional(),
+  deadzone: CameraDeadzoneSchema.optional(),
+})
+
+// Heads-up display
+export const HudScoreSchema = z.object({
+  x: z.number(),
+  y: z.number(),
+  label: z.string().optional(),
+  font_size: z.string().optional(),
+  fill: z.string().optional(),
+})
+
+export const HudHealthBarSchema = z.object({
+  object_id: z.string(),
+  x: z.number(),
+  y: z.number(),
+  width: z.number().optional(),
+  height: z.number().optional(),
+  color: z.string().optional(),
+})
+
+export const HudLivesSchema = z.object({
+  count: z.number(),
+  x: z.number(),
+  y: z.number(),
+  icon: z.string().optional(),
+})
+
+export const HudTimerSchema = z.object({
+  x: z.number(),
+  y: z.number(),
+  label: z.string().optional(),
+})
+
+export const HudLabelSchema = z.object({
+  x: z.number(),
+  y: z.number(),
+  text: z.string(),
+  font_size: z.string().optional(),
+  fill: z.string().optional(),
+})
+
+export const HudMuteButtonSchema = z.object({
+  x: z.number(),
+  y: z.number(),
+})
+
+export const HudConfigSchema = z.object({
+  score: HudScoreSchema.optional(),
+  best_score: HudScoreSchema.optional(),
+  health_bar: HudHealthBarSchema.optional(),
+  lives: HudLivesSchema.optional(),
+  timer: HudTimerSchema.optional(),
+  labels: z.array(HudLabelSchema).optional(),
+  mute_button: HudMuteButtonSchema.optional(),
+})
+
+// Countdown
+export const CountdownSchema = z.object({
+  seconds: z.number(),
+  on_expire: z.string(),
+  display: z.boolean().default(true),
+})
+
+// Tilemap
+export const TileLegendEntrySchema = z.object({
+  symbol: z.string(),
+  template: GameObjectSchema,
+})
+
+export const TilemapSchema = z.object({
+  tile_size: z.number(),
+  rows: z.array(z.string()),
+  legend: z.array(TileLegendEntrySchema),
+  offset_x: z.number().optional(),
+  offset_y: z.number().optional(),
+})
+
+// Procedural level
+export const GapRangeSchema = z.object({
+  min: z.number(),
+  max: z.number(),
+})
+
+export const ProceduralLevelSchema = z.object({
+  seed: z.number().optional(),
+  length: z.number(),
+  platform: GameObjectSchema,
+  gap_width: GapRangeSchema.optional(),
+  obstacle: GameObjectSchema.optional(),
+  obstacle_density: z.number().optional(),
+  collectible: GameObjectSchema.optional(),
+  collectible_frequency: z.number().optional(),
+})
+
+// Gravity zones
+export const GravityZoneSchema = z.object({
+  id: z.string(),
+  x: z.number(),
+  y: z.number(),
+  width: z.number(),
+  height: z.number(),
+  gravity: GravityConfigSchema,
+  color: z.string().optional(),
 })
 
 // Scene
@@ -170,15 +652,32 @@ export const SceneSchema = z.object({
   name: z.string(),
   objects: z.array(GameObjectSchema),
   custom_logic: CustomLogicSchema.optional(),
+  camera: CameraConfigSchema.optional(),
+  hud: HudConfigSchema.optional(),
+  countdown: CountdownSchema.optional(),
+  tilemap: TilemapSchema.optional(),
+  procedural: ProceduralLevelSchema.optional(),
+  gravity_zones: z.array(GravityZoneSchema).optional(),
+})
+
+// Achievements
+export const AchievementSchema = z.object({
+  id: z.string(),
+  name: z.string(),
+  description: z.string().optional(),
+  icon: z.string().optional(),
+  condition: z.string(),
 })
 
 // Complete Phaser game specification
 export const PhaserGameSpecSchema = z.object({
+  schema_version: z.number().optional(),
   title: z.string(),
   description: z.string(),
   game: GameConfigSchema,
   assets: z.array(AssetSchema).default([]), // Default to empty array
   scenes: z.array(SceneSchema).min(1, 'Game must have at least one scene'),
+  achievements: z.array(AchievementSchema).optional(),
   controls_description: z.array(z.string()),
   key_concepts: z.array(z.string()),
 })
@@ -194,6 +693,8 @@ export const GameRecordSchema = z.object({
   version: z.number(),
 })
 
+export const GameStatusSchema = z.enum(['draft', 'published'])
+
 export const GameSummarySchema = z.object({
   id: z.string(),
   title: z.string(),
@@ -201,6 +702,10 @@ export const GameSummarySchema = z.object({
   created_at: z.string(),
   updated_at: z.string(),
   version: z.number(),
+  favorite: z.boolean(),
+  status: GameStatusSchema,
+  archived: z.boolean(),
+  tags: z.array(z.string()),
 })
 
 export const GameVersionSchema = z.object({
@@ -210,6 +715,7 @@ export const GameVersionSchema = z.object({
   spec: PhaserGameSpecSchema,
   created_at: z.string(),
   notes: z.string().nullable(),
+  label: z.string().nullable(),
 })
 
 // Export inferred TypeScript types from Zod schemas
@@ -217,26 +723,67 @@ export type PhaserGameSpec = z.infer<typeof PhaserGameSpecSchema>
 export type GameConfig = z.infer<typeof GameConfigSchema>
 export type PhysicsConfig = z.infer<typeof PhysicsConfigSchema>
 export type GravityConfig = z.infer<typeof GravityConfigSchema>
+export type AudioConfig = z.infer<typeof AudioConfigSchema>
+export type BackgroundMusic = z.infer<typeof BackgroundMusicSchema>
+export type MusicStyle = z.infer<typeof MusicStyleSchema>
+export type PauseConfig = z.infer<typeof PauseConfigSchema>
 export type AssetType = z.infer<typeof AssetTypeSchema>
 export type Asset = z.infer<typeof AssetSchema>
+export type FrameConfig = z.infer<typeof FrameConfigSchema>
+export type SpriteAnimation = z.infer<typeof SpriteAnimationSchema>
 export type PhysicsBody = z.infer<typeof PhysicsBodySchema>
 export type ObjectPhysics = z.infer<typeof ObjectPhysicsSchema>
+export type Surface = z.infer<typeof SurfaceSchema>
 export type VelocityConfig = z.infer<typeof VelocityConfigSchema>
 export type Controls = z.infer<typeof ControlsSchema>
 export type ObjectType = z.infer<typeof ObjectTypeSchema>
 export type ShapeProperties = z.infer<typeof ShapePropertiesSchema>
+export type TextAlign = z.infer<typeof TextAlignSchema>
+export type TextShadow = z.infer<typeof TextShadowSchema>
 export type TextProperties = z.infer<typeof TextPropertiesSchema>
+export type EmojiLayer = z.infer<typeof EmojiLayerSchema>
 export type EmojiProperties = z.infer<typeof EmojiPropertiesSchema>
 export type CollisionBox = z.infer<typeof CollisionBoxSchema>
 export type CollisionBoxShape = z.infer<typeof CollisionBoxShapeSchema>
+export type ZoneProperties = z.infer<typeof ZonePropertiesSchema>
+export type HazardKind = z.infer<typeof HazardKindSchema>
+export type HazardProperties = z.infer<typeof HazardPropertiesSchema>
+export type ObjectAnimations = z.infer<typeof ObjectAnimationsSchema>
 export type BehaviorType = z.infer<typeof BehaviorTypeSchema>
-export type GameObject = z.infer<typeof GameObjectSchema>
+export type RecyclePolicy = z.infer<typeof RecyclePolicySchema>
+export type PoolConfig = z.infer<typeof PoolConfigSchema>
 export type Spawner = z.infer<typeof SpawnerSchema>
 export type PositionVariance = z.infer<typeof PositionVarianceSchema>
+export type ScreenStyle = z.infer<typeof ScreenStyleSchema>
+export type ScreenStat = z.infer<typeof ScreenStatSchema>
 export type CustomLogic = z.infer<typeof CustomLogicSchema>
 export type ActionDefinition = z.infer<typeof ActionDefinitionSchema>
 export type ActionEffect = z.infer<typeof ActionEffectSchema>
+export type KeyTrigger = z.infer<typeof KeyTriggerSchema>
+export type ScoreTrigger = z.infer<typeof ScoreTriggerSchema>
+export type DestroyTrigger = z.infer<typeof DestroyTriggerSchema>
+export type CollisionMode = z.infer<typeof CollisionModeSchema>
+export type CollisionRule = z.infer<typeof CollisionRuleSchema>
+export type PortalPair = z.infer<typeof PortalPairSchema>
+export type Variable = z.infer<typeof VariableSchema>
+export type CameraDeadzone = z.infer<typeof CameraDeadzoneSchema>
+export type CameraConfig = z.infer<typeof CameraConfigSchema>
+export type HudScore = z.infer<typeof HudScoreSchema>
+export type HudHealthBar = z.infer<typeof HudHealthBarSchema>
+export type HudLives = z.infer<typeof HudLivesSchema>
+export type HudTimer = z.infer<typeof HudTimerSchema>
+export type HudLabel = z.infer<typeof HudLabelSchema>
+export type HudMuteButton = z.infer<typeof HudMuteButtonSchema>
+export type HudConfig = z.infer<typeof HudConfigSchema>
+export type Countdown = z.infer<typeof CountdownSchema>
+export type TileLegendEntry = z.infer<typeof TileLegendEntrySchema>
+export type Tilemap = z.infer<typeof TilemapSchema>
+export type GapRange = z.infer<typeof GapRangeSchema>
+export type ProceduralLevel = z.infer<typeof ProceduralLevelSchema>
+export type GravityZone = z.infer<typeof GravityZoneSchema>
 export type Scene = z.infer<typeof SceneSchema>
+export type Achievement = z.infer<typeof AchievementSchema>
 export type GameRecord = z.infer<typeof GameRecordSchema>
+export type GameStatus = z.infer<typeof GameStatusSchema>
 export type GameSummary = z.infer<typeof GameSummarySchema>
 export type GameVersion = z.infer<typeof GameVersionSchema>
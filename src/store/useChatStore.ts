@@ -26,6 +26,8 @@ interface ChatStore {
   // Game Builder state
   systemPrompt: string
   generatedGameSpec: PhaserGameSpec | null
+  // Saved game this conversation is iterating on, if any
+  gameId: string | null
 
   // AI Initialization actions
   setApiKey: (key: string) => void
@@ -51,6 +53,7 @@ interface ChatStore {
   // Game Builder actions
   setSystemPrompt: (prompt: string) => void
   setGeneratedGameSpec: (spec: PhaserGameSpec | null) => void
+  setGameId: (id: string | null) => void
 }
 
 export const useChatStore = create<ChatStore>((set, get) => ({
@@ -69,6 +72,7 @@ export const useChatStore = create<ChatStore>((set, get) => ({
   // Game Builder initial state
   systemPrompt: '',
   generatedGameSpec: null,
+  gameId: null,
 
   // AI Initialization actions
   setApiKey: (key) => set({ apiKey: key }),
@@ -107,7 +111,7 @@ export const useChatStore = create<ChatStore>((set, get) => ({
   addMessage: (message) =>
     set((state) => ({ messages: [...state.messages, message] })),
   sendMessage: async () => {
-    const { input, messages, model } = get()
+    const { input, messages, model, gameId, generatedGameSpec } = get()
     if (!input.trim()) return
 
     set({ error: '' })
@@ -128,6 +132,8 @@ export const useChatStore = create<ChatStore>((set, get) => ({
       await invoke('stream_chat', {
         messages: updatedMessages,
         model,
+        gameId,
+        spec: generatedGameSpec,
       })
     } catch (err) {
       set({ error: String(err), isStreaming: false })
@@ -139,9 +145,11 @@ export const useChatStore = create<ChatStore>((set, get) => ({
       messages: [],
       streamingResponse: '',
       error: '',
+      gameId: null,
     }),
 
   // Game Builder actions
   setSystemPrompt: (prompt) => set({ systemPrompt: prompt }),
   setGeneratedGameSpec: (spec) => set({ generatedGameSpec: spec }),
+  setGameId: (id) => set({ gameId: id }),
 }))
@@ -1,4 +1,5 @@
 import Phaser from 'phaser'
+import { convertFileSrc, invoke, isTauri } from '@tauri-apps/api/core'
 import type {
   PhaserGameSpec,
   Scene as SceneSpec,
@@ -8,8 +9,36 @@ import type {
   ActionDefinition,
   ActionEffect,
   BehaviorType,
+  Tilemap,
+  CameraConfig,
+  HudConfig,
+  ScreenStyle,
+  AudioConfig,
+  MusicStyle,
+  EmojiProperties,
+  TextProperties,
+  Surface,
+  PhysicsBody,
+  HazardProperties,
+  PortalPair,
+  ProceduralLevel,
+  GravityZone,
+  ZoneProperties,
+  CollisionRule,
+  PoolConfig,
+  Achievement,
 } from '@/schemas/gameSpec'
 
+/**
+ * End screen options of the gameOver effect
+ */
+type GameOverEffect = Extract<ActionEffect, { type: 'gameOver' }>
+
+/**
+ * Victory screen options of the win effect
+ */
+type WinEffect = Extract<ActionEffect, { type: 'win' }>
+
 /**
  * Behavior state storage for patrol, follow, random behaviors
  */
@@ -39,6 +68,82 @@ function hasArcadeBody(obj: Phaser.GameObjects.GameObject): obj is GameObjectWit
   return hasBody(obj) && obj.body instanceof Phaser.Physics.Arcade.Body
 }
 
+/**
+ * Object drawn on screen, which has a depth, alpha, and transform
+ */
+type DisplayObject = Phaser.GameObjects.GameObject &
+  Phaser.GameObjects.Components.Depth &
+  Phaser.GameObjects.Components.AlphaSingle &
+  Phaser.GameObjects.Components.Transform
+
+/**
+ * Type guard to check if object is drawn on screen (not a group or zone)
+ */
+function isDisplayObject(obj: Phaser.GameObjects.GameObject): obj is DisplayObject {
+  return 'setDepth' in obj && 'setAlpha' in obj && 'setAngle' in obj
+}
+
+/**
+ * Type guard to check if object can be tinted (sprites and text, not shapes)
+ */
+function hasTint(obj: Phaser.GameObjects.GameObject): obj is Phaser.GameObjects.GameObject & Phaser.GameObjects.Components.Tint {
+  return 'setTint' in obj
+}
+
+/**
+ * Object passed to a collider or overlap callback
+ */
+type ArcadeCollidable =
+  | Phaser.Physics.Arcade.Body
+  | Phaser.Physics.Arcade.StaticBody
+  | Phaser.Types.Physics.Arcade.GameObjectWithBody
+  | Phaser.Tilemaps.Tile
+
+/**
+ * Game object of a collider or overlap callback argument
+ */
+function toGameObject(obj: ArcadeCollidable): Phaser.GameObjects.GameObject | undefined {
+  if ('gameObject' in obj) {
+    return obj.gameObject as Phaser.GameObjects.GameObject
+  }
+  if (obj instanceof Phaser.GameObjects.GameObject) {
+    return obj
+  }
+  return undefined
+}
+
+/**
+ * Phaser key code name for a key from a spec (e.g. 'ArrowLeft' -> 'LEFT')
+ */
+function phaserKey(key: string): string {
+  switch (key.toLowerCase()) {
+    case 'arrowleft':
+    case 'left':
+      return 'LEFT'
+    case 'arrowright':
+    case 'right':
+      return 'RIGHT'
+    case 'arrowup':
+    case 'up':
+      return 'UP'
+    case 'arrowdown':
+    case 'down':
+      return 'DOWN'
+    case ' ':
+    case 'space':
+    case 'spacebar':
+      return 'SPACE'
+    case 'enter':
+    case 'return':
+      return 'ENTER'
+    case 'escape':
+    case 'esc':
+      return 'ESC'
+    default:
+      return key.toUpperCase()
+  }
+}
+
 /**
  * Parse hex color string to number for Phaser
  * Supports both '#RRGGBB' and '0xRRGGBB' formats
@@ -53,6 +158,240 @@ function parseColor(color: string): number {
   return parseInt(color, 16)
 }
 
+/**
+ * Phaser style of a text object
+ */
+function textStyle(text: TextProperties): Phaser.Types.GameObjects.Text.TextStyle {
+  const style: Phaser.Types.GameObjects.Text.TextStyle = {
+    fontSize: text.font_size || '32px',
+    color: text.fill || '#ffffff',
+  }
+  if (text.font_family) style.fontFamily = text.font_family
+  if (text.stroke) {
+    style.stroke = text.stroke
+    style.strokeThickness = text.stroke_thickness ?? 0
+  }
+  if (text.shadow) {
+    style.shadow = {
+      offsetX: text.shadow.offset_x ?? 2,
+      offsetY: text.shadow.offset_y ?? 2,
+      color: text.shadow.color ?? '#000000',
+      blur: text.shadow.blur ?? 0,
+      fill: true,
+      stroke: !!text.stroke,
+    }
+  }
+  if (text.align) style.align = text.align
+  if (text.word_wrap_width) style.wordWrap = { width: text.word_wrap_width }
+  if (text.background_color) style.backgroundColor = text.background_color
+  if (text.padding !== undefined) style.padding = { x: text.padding, y: text.padding }
+  return style
+}
+
+/**
+ * Body of an object. Hazards and zones have one without physics: saws move,
+ * the rest stay put.
+ */
+function bodyType(objSpec: GameObject): PhysicsBody | undefined {
+  if (objSpec.physics) return objSpec.physics.body
+  if (objSpec.type === 'hazard') return objSpec.hazard?.kind === 'saw' ? 'dynamic' : 'static'
+  if (objSpec.type === 'zone') return 'static'
+  return undefined
+}
+
+/**
+ * Behavior of an object; saws patrol unless given another behavior
+ */
+function behaviorOf(objSpec: GameObject): BehaviorType | undefined {
+  if (objSpec.behavior) return objSpec.behavior
+  return objSpec.type === 'hazard' && objSpec.hazard?.kind === 'saw' ? 'patrol' : undefined
+}
+
+/**
+ * Random number generator returning numbers in [0, 1) from a seed, so a
+ * seeded level comes out the same every play (mulberry32)
+ */
+function seededRandom(seed: number): () => number {
+  let state = seed >>> 0
+  return () => {
+    state = (state + 0x6d2b79f5) >>> 0
+    let t = state
+    t = Math.imul(t ^ (t >>> 15), t | 1)
+    t ^= t + Math.imul(t ^ (t >>> 7), t | 61)
+    return ((t ^ (t >>> 14)) >>> 0) / 4294967296
+  }
+}
+
+/**
+ * Approximate size of an object from its spec, before it's created
+ */
+function specSize(objSpec: GameObject): { width: number; height: number } {
+  if (objSpec.shape?.radius) {
+    return { width: objSpec.shape.radius * 2, height: objSpec.shape.radius * 2 }
+  }
+  if (objSpec.shape) {
+    return { width: objSpec.shape.width || 100, height: objSpec.shape.height || 100 }
+  }
+  if (objSpec.hazard) {
+    return {
+      width: objSpec.hazard.width ?? 40,
+      height: objSpec.hazard.height ?? (objSpec.hazard.kind === 'lava' ? 20 : 40),
+    }
+  }
+  const size = objSpec.emoji?.size ?? 32
+  return { width: size, height: size }
+}
+
+/**
+ * Comparison operators of achievement conditions, longest first
+ */
+const CONDITION_OPERATORS = ['>=', '<=', '==', '!=', '>', '<'] as const
+
+function compare(value: number, operator: (typeof CONDITION_OPERATORS)[number], target: number): boolean {
+  switch (operator) {
+    case '>=':
+      return value >= target
+    case '<=':
+      return value <= target
+    case '==':
+      return value === target
+    case '!=':
+      return value !== target
+    case '>':
+      return value > target
+    case '<':
+      return value < target
+  }
+}
+
+/**
+ * Achievements unlocked so far, kept for the whole game across scenes.
+ * `loaded` is false until the ones unlocked in earlier sessions are known.
+ */
+interface AchievementProgress {
+  unlocked: Set<string>
+  loaded: boolean
+}
+
+/**
+ * Time after a hazard hit during which the same target can't be hurt again
+ */
+const HAZARD_COOLDOWN = 1000
+
+/**
+ * Draw order of the HUD, above every object of the scene
+ */
+const HUD_DEPTH = 1000
+
+/**
+ * URL of a library asset, served by the app's pueo-asset protocol
+ */
+function libraryAssetUrl(key: string): string {
+  return convertFileSrc(key, 'pueo-asset')
+}
+
+/**
+ * Waveform, melody (MIDI note numbers), and beats per note of each style of
+ * generated music
+ */
+const MUSIC_STYLES: Record<MusicStyle, { wave: OscillatorType; notes: number[]; beats: number }> = {
+  chiptune: { wave: 'square', notes: [60, 64, 67, 72, 67, 64, 62, 65, 69, 74, 69, 65], beats: 0.5 },
+  upbeat: { wave: 'triangle', notes: [60, 62, 64, 67, 69, 67, 64, 62, 65, 67, 69, 72], beats: 0.5 },
+  calm: { wave: 'sine', notes: [60, 64, 67, 64, 57, 60, 64, 60, 53, 57, 60, 57], beats: 1 },
+  spooky: { wave: 'sawtooth', notes: [45, 48, 51, 48, 44, 47, 50, 47], beats: 1 },
+}
+
+/**
+ * Loop a generated tune until the returned function is called
+ */
+function playGeneratedMusic(
+  context: AudioContext,
+  destination: AudioNode,
+  style: MusicStyle,
+  tempo: number,
+  volume: number
+): () => void {
+  const { wave, notes, beats } = MUSIC_STYLES[style]
+  const noteLength = (60 / tempo) * beats
+  const output = context.createGain()
+  output.gain.value = volume
+  output.connect(destination)
+
+  let next = context.currentTime
+  let index = 0
+  // Schedule notes slightly ahead of the audio clock, so timer jitter
+  // doesn't leave gaps
+  const schedule = () => {
+    next = Math.max(next, context.currentTime)
+    while (next < context.currentTime + 0.2) {
+      const oscillator = context.createOscillator()
+      oscillator.type = wave
+      oscillator.frequency.value = 440 * 2 ** ((notes[index % notes.length] - 69) / 12)
+      const envelope = context.createGain()
+      envelope.gain.setValueAtTime(0.3, next)
+      envelope.gain.exponentialRampToValueAtTime(0.001, next + noteLength)
+      oscillator.connect(envelope).connect(output)
+      oscillator.start(next)
+      oscillator.stop(next + noteLength)
+      next += noteLength
+      index++
+    }
+  }
+  const interval = window.setInterval(schedule, 50)
+  return () => {
+    window.clearInterval(interval)
+    output.disconnect()
+  }
+}
+
+/**
+ * Every object in a scene, including projectile, spawner, tilemap, and
+ * procedural templates
+ */
+function sceneObjects(sceneSpec: SceneSpec): GameObject[] {
+  const objects: GameObject[] = []
+  const collect = (objSpec: GameObject) => {
+    objects.push(objSpec)
+    if (objSpec.controls?.projectile) {
+      collect(objSpec.controls.projectile)
+    }
+  }
+  sceneSpec.objects.forEach(collect)
+  sceneSpec.custom_logic?.spawners?.forEach((spawner) => collect(spawner.template))
+  sceneSpec.tilemap?.legend.forEach((entry) => collect(entry.template))
+  if (sceneSpec.procedural) {
+    collect(sceneSpec.procedural.platform)
+    if (sceneSpec.procedural.obstacle) collect(sceneSpec.procedural.obstacle)
+    if (sceneSpec.procedural.collectible) collect(sceneSpec.procedural.collectible)
+  }
+  return objects
+}
+
+/**
+ * HUD elements that change while the game runs
+ */
+interface HudDisplay {
+  score?: Phaser.GameObjects.Text
+  bestScore?: Phaser.GameObjects.Text
+  healthBar?: { fill: Phaser.GameObjects.Rectangle; objectId: string; maxHealth: number }
+  lives?: Phaser.GameObjects.Text
+  timer?: Phaser.GameObjects.Text
+  countdown?: Phaser.GameObjects.Text
+  pauseOverlay?: Phaser.GameObjects.Text
+  muteButton?: Phaser.GameObjects.Text
+}
+
+/**
+ * Extra emoji drawn with an emoji object, following it around
+ */
+interface EmojiLayerDisplay {
+  text: Phaser.GameObjects.Text
+  owner: Phaser.GameObjects.Text
+  offsetX: number
+  offsetY: number
+  behind: boolean
+}
+
 /**
  * Game state to track created objects and their references
  */
@@ -62,6 +401,8 @@ interface GameState {
   cursors: Phaser.Types.Input.Keyboard.CursorKeys | null
   customKeys: Map<string, Phaser.Input.Keyboard.Key>
   timers: Phaser.Time.TimerEvent[]
+  spawners: Map<string, Phaser.Time.TimerEvent>  // Spawner timers, also listed in timers
+  stoppedSpawners: Set<string>  // Spawners turned off by stopSpawner, which stay off after a pause
   spawnCounters: Map<string, number>
   score: number
   actions: Map<string, ActionDefinition>
@@ -69,55 +410,187 @@ interface GameState {
   objectBehaviors: Map<string, { behavior: BehaviorType; params?: Record<string, unknown> }>  // Track which objects have behaviors
   projectileCounter: number  // Track projectile spawns
   lastShootTime: Map<string, number>  // Track last shoot time per object (for rate limiting)
+  lives: number
+  elapsed: number  // Milliseconds played, stopped when the game ends
+  ended: boolean  // Set by game over
+  countdownExpired: boolean
+  paused: boolean
+  hud: HudDisplay
+  emojiLayers: EmojiLayerDisplay[]
+  surfaces: { gameObject: GameObjectWithPhysics; surface: Surface }[]  // Platforms with a special surface
+  checkpoint: { x: number; y: number } | null  // Respawn point, or the start position if null
+  bestScore: number  // Best saved score of the game
+  zones: Map<Phaser.GameObjects.GameObject, { inside: Set<Phaser.GameObjects.GameObject>; entered: boolean; exited: boolean }>
+  variables: Map<string, number>
+  liveTexts: Map<Phaser.GameObjects.Text, string>  // Texts with placeholders, and their template
+  collisionGroups: Map<string, Phaser.GameObjects.GameObject[]>  // Live members of each collision group
+  pools: Map<string, Phaser.GameObjects.GameObject[]>  // Live objects of each spawner or shooter, oldest first
+}
+
+/**
+ * State of a scene that is starting, or starting over
+ */
+function createGameState(): GameState {
+  return {
+    objects: new Map(),
+    groups: new Map(),
+    cursors: null,
+    customKeys: new Map(),
+    timers: [],
+    spawners: new Map(),
+    stoppedSpawners: new Set(),
+    spawnCounters: new Map(),
+    score: 0,
+    actions: new Map(),
+    behaviorState: new Map(),
+    objectBehaviors: new Map(),
+    projectileCounter: 0,
+    lastShootTime: new Map(),
+    lives: 0,
+    elapsed: 0,
+    ended: false,
+    countdownExpired: false,
+    paused: false,
+    hud: {},
+    emojiLayers: [],
+    surfaces: [],
+    checkpoint: null,
+    bestScore: 0,
+    zones: new Map(),
+    variables: new Map(),
+    liveTexts: new Map(),
+    collisionGroups: new Map(),
+    pools: new Map(),
+  }
 }
 
 /**
- * Create a Phaser scene from a SceneSpec
+ * Create a Phaser scene from a SceneSpec. Games saved in the library have an
+ * id, which their high scores are saved under.
  */
-function createSceneClass(sceneSpec: SceneSpec, gameSpec: PhaserGameSpec) {
+function createSceneClass(sceneSpec: SceneSpec, gameSpec: PhaserGameSpec, gameId?: string) {
   return class extends Phaser.Scene {
-    private state: GameState = {
-      objects: new Map(),
-      groups: new Map(),
-      cursors: null,
-      customKeys: new Map(),
-      timers: [],
-      spawnCounters: new Map(),
-      score: 0,
-      actions: new Map(),
-      behaviorState: new Map(),
-      objectBehaviors: new Map(),
-      projectileCounter: 0,
-      lastShootTime: new Map(),
-    }
+    private state: GameState = createGameState()
 
     constructor() {
       super({ key: sceneSpec.name })
     }
 
+    init() {
+      // Animations and tweens are paused manager-wide, so restarting while
+      // paused has to resume them
+      if (this.state.paused) {
+        this.anims.resumeAll()
+        this.tweens.resumeAll()
+      }
+      // Restarting a scene reuses this instance, and its clock
+      this.state = createGameState()
+      this.setTimeScale(1)
+    }
+
     preload() {
-      // Load assets if any are provided
-      if (gameSpec.assets && gameSpec.assets.length > 0) {
-        for (const asset of gameSpec.assets) {
-          switch (asset.type) {
-            case 'sprite':
-            case 'image':
-              this.load.image(asset.key, asset.url)
-              break
-            case 'audio':
-              this.load.audio(asset.key, asset.url)
-              break
+      // Load assets if any are provided; library assets have no url of their own
+      for (const asset of gameSpec.assets) {
+        const url = asset.library_key ? libraryAssetUrl(asset.library_key) : asset.url
+        switch (asset.type) {
+          case 'sprite':
+          case 'image':
+            this.load.image(asset.key, url)
+            break
+          case 'spritesheet':
+            if (asset.frame_config) {
+              this.load.spritesheet(asset.key, url, {
+                frameWidth: asset.frame_config.frame_width,
+                frameHeight: asset.frame_config.frame_height,
+                margin: asset.frame_config.margin,
+                spacing: asset.frame_config.spacing,
+              })
+            } else {
+              this.load.image(asset.key, url)
+            }
+            break
+          case 'audio':
+            this.load.audio(asset.key, url)
+            break
+        }
+      }
+
+      // Textures can name a library asset without declaring it. Exported
+      // games declare every library asset they use, so this is app-only.
+      if (isTauri()) {
+        const declared = new Set(gameSpec.assets.map((asset) => asset.key))
+        for (const objSpec of sceneObjects(sceneSpec)) {
+          if (objSpec.texture && !declared.has(objSpec.texture)) {
+            declared.add(objSpec.texture)
+            this.load.image(objSpec.texture, libraryAssetUrl(objSpec.texture))
           }
         }
       }
     }
 
     create() {
+      // Animations are global, so a scene that runs twice must not redefine them
+      for (const asset of gameSpec.assets) {
+        for (const animation of asset.animations ?? []) {
+          if (!this.anims.exists(animation.key)) {
+            this.anims.create({
+              key: animation.key,
+              frames: this.anims.generateFrameNumbers(asset.key, {
+                start: animation.start_frame,
+                end: animation.end_frame,
+              }),
+              frameRate: animation.frame_rate ?? 10,
+              repeat: animation.repeat ?? -1,
+            })
+          }
+        }
+      }
+
+      // Variables come first so texts can show them
+      for (const variable of sceneSpec.custom_logic?.variables ?? []) {
+        this.state.variables.set(variable.name, variable.initial)
+      }
+
+      // Expand the tilemap first so the level is drawn behind the scene's objects
+      if (sceneSpec.tilemap) {
+        this.createTilemap(sceneSpec.tilemap)
+      }
+      if (sceneSpec.procedural) {
+        this.createProceduralLevel(sceneSpec.procedural)
+      }
+
+      // Gravity zones are drawn behind everything else
+      for (const zone of sceneSpec.gravity_zones ?? []) {
+        this.add
+          .rectangle(zone.x, zone.y, zone.width, zone.height, parseColor(zone.color ?? '#4488ff'), 0.2)
+          .setDepth(-1)
+      }
+
       // Create all objects
       for (const objSpec of sceneSpec.objects) {
         this.createObject(objSpec)
       }
 
+      // Size the world and follow the camera target
+      if (sceneSpec.camera) {
+        this.setupCamera(sceneSpec.camera)
+      }
+
+      // Fixed display of score, health, lives, and time
+      this.state.lives = sceneSpec.hud?.lives?.count ?? 0
+      if (sceneSpec.hud) {
+        this.createHud(sceneSpec.hud)
+      }
+
+      // Time left on the countdown, unless the HUD timer already shows it
+      if (sceneSpec.countdown?.display && !sceneSpec.hud?.timer) {
+        this.state.hud.countdown = this.add
+          .text(gameSpec.game.width / 2, 16, '', { fontSize: '32px', color: '#ffffff' })
+          .setOrigin(0.5, 0)
+          .setScrollFactor(0)
+          .setDepth(HUD_DEPTH)
+      }
+
       // Pre-create projectiles group if any object has shooting controls
       const hasShootControls = sceneSpec.objects.some((obj) => obj.controls?.shoot)
       if (hasShootControls) {
@@ -130,14 +603,83 @@ function createSceneClass(sceneSpec: SceneSpec, gameSpec: PhaserGameSpec) {
         this.setupCustomLogic(sceneSpec.custom_logic)
       }
 
+      this.setupHazards()
+      this.setupCheckpoints()
+
+      // Portals need a body to be touched
+      for (const pair of sceneSpec.custom_logic?.portals ?? []) {
+        for (const id of [pair.a, pair.b]) {
+          const portal = this.state.objects.get(id)
+          if (!portal) {
+            console.warn(`Portal not found: ${id}`)
+          } else if (!hasBody(portal)) {
+            this.physics.add.existing(portal, true)
+          }
+        }
+      }
+
       // Initialize cursor keys if any object has controls
       const hasControls = sceneSpec.objects.some((obj) => obj.controls)
       if (hasControls && this.input.keyboard) {
         this.state.cursors = this.input.keyboard.createCursorKeys()
       }
+
+      // Pause key
+      const pause = gameSpec.game.pause
+      if (pause) {
+        this.input.keyboard?.on(`keydown-${phaserKey(pause.key)}`, () => this.togglePause())
+      }
+
+      // Restart key, once the game is over
+      const restartKey = gameSpec.game.restart_key
+      if (restartKey) {
+        this.input.keyboard?.on(`keydown-${phaserKey(restartKey)}`, () => {
+          if (this.state.ended) {
+            this.scene.restart()
+          }
+        })
+      }
+
+      if (gameSpec.game.audio) {
+        this.setupAudio(gameSpec.game.audio)
+      }
+
+      // Emoji layers follow their emoji once physics has moved it. Scene
+      // events outlive a restart, so the listener goes with the scene.
+      this.events.on(Phaser.Scenes.Events.POST_UPDATE, this.updateEmojiLayers, this)
+      this.events.once(Phaser.Scenes.Events.SHUTDOWN, () => {
+        this.events.off(Phaser.Scenes.Events.POST_UPDATE, this.updateEmojiLayers, this)
+      })
+
+      // Start actions run once the scene is set up, with no objects involved
+      for (const action of sceneSpec.custom_logic?.on_start ?? []) {
+        this.runAction(action)
+      }
     }
 
-    update() {
+    update(_time: number, delta: number) {
+      // The clock keeps running while paused unless timers halt
+      const haltTime = this.state.paused && gameSpec.game.pause?.halt_timers
+      if (!this.state.ended && !haltTime) {
+        this.state.elapsed += delta
+      }
+
+      // Run the countdown's action once time is up
+      const countdown = sceneSpec.countdown
+      if (countdown && !this.state.countdownExpired && this.state.elapsed >= countdown.seconds * 1000) {
+        this.state.countdownExpired = true
+        this.runAction(countdown.on_expire)
+      }
+
+      if (gameSpec.achievements) {
+        this.checkAchievements(gameSpec.achievements)
+      }
+
+      if (this.state.paused) {
+        this.updateHud()
+        return
+      }
+
       // Handle controls for all objects
       for (const objSpec of sceneSpec.objects) {
         if (objSpec.controls) {
@@ -145,13 +687,33 @@ function createSceneClass(sceneSpec: SceneSpec, gameSpec: PhaserGameSpec) {
         }
       }
 
+      this.applySurfaces(delta)
+      this.updateZones()
+      this.wrapObjects()
+      if (sceneSpec.gravity_zones) {
+        this.applyGravityZones(sceneSpec.gravity_zones)
+      }
+
+      for (const pair of sceneSpec.custom_logic?.portals ?? []) {
+        this.updatePortal(pair)
+      }
+
       // Handle behaviors for scene objects
       for (const objSpec of sceneSpec.objects) {
-        if (objSpec.behavior) {
+        if (behaviorOf(objSpec)) {
           this.handleBehavior(objSpec)
         }
       }
 
+      // Play the animation for each object's movement state
+      for (const objSpec of sceneSpec.objects) {
+        if (objSpec.animations) {
+          this.updateAnimation(objSpec)
+        }
+      }
+
+      this.updateHud()
+
       // Handle behaviors for spawned objects
       for (const [id, behaviorInfo] of this.state.objectBehaviors) {
         const obj = this.state.objects.get(id)
@@ -172,14 +734,460 @@ function createSceneClass(sceneSpec: SceneSpec, gameSpec: PhaserGameSpec) {
     }
 
     private createObject(objSpec: GameObject) {
+      if (objSpec.type === 'group') {
+        // Create a group for managing multiple objects
+        const group = this.add.group()
+        this.state.groups.set(objSpec.id, group)
+        return // Groups don't need physics setup
+      }
+
+      const gameObject = this.buildObject(objSpec, objSpec.x, objSpec.y)
+      if (!gameObject) {
+        console.warn(`Failed to create object: ${objSpec.id}`)
+        return
+      }
+
+      // Store reference
+      this.state.objects.set(objSpec.id, gameObject)
+    }
+
+    /**
+     * Volume, mute key, and background music. The music plays across scenes,
+     * so only the first scene to start it does.
+     */
+    private setupAudio(audio: AudioConfig) {
+      if (audio.master_volume !== undefined) {
+        this.sound.volume = audio.master_volume
+      }
+      if (audio.mute_key) {
+        this.input.keyboard?.on(`keydown-${phaserKey(audio.mute_key)}`, () => {
+          this.sound.mute = !this.sound.mute
+        })
+      }
+
+      const music = audio.background_music
+      if (!music || this.game.registry.has('music')) {
+        return
+      }
+      switch (music.source) {
+        case 'asset':
+          if (this.cache.audio.exists(music.key)) {
+            this.sound.add(music.key, { loop: true, volume: music.volume ?? 0.5 }).play()
+            this.game.registry.set('music', music.key)
+          } else {
+            console.warn(`Music not loaded: ${music.key}`)
+          }
+          break
+        case 'generated':
+          // Routed through the sound manager so master volume and mute apply
+          if (this.sound instanceof Phaser.Sound.WebAudioSoundManager) {
+            const stop = playGeneratedMusic(
+              this.sound.context,
+              this.sound.destination,
+              music.style,
+              music.tempo ?? 120,
+              music.volume ?? 0.5
+            )
+            this.game.registry.set('music', music.style)
+            this.game.events.once(Phaser.Core.Events.DESTROY, stop)
+          }
+          break
+      }
+    }
+
+    /**
+     * Heads-up display fixed to the screen above the scene
+     */
+    private createHud(hud: HudConfig) {
+      const display = this.state.hud
+      if (hud.score) {
+        display.score = this.add
+          .text(hud.score.x, hud.score.y, '', {
+            fontSize: hud.score.font_size ?? '24px',
+            color: hud.score.fill ?? '#ffffff',
+          })
+          .setScrollFactor(0)
+          .setDepth(HUD_DEPTH)
+      }
+
+      if (hud.best_score) {
+        display.bestScore = this.add
+          .text(hud.best_score.x, hud.best_score.y, '', {
+            fontSize: hud.best_score.font_size ?? '24px',
+            color: hud.best_score.fill ?? '#ffffff',
+          })
+          .setScrollFactor(0)
+          .setDepth(HUD_DEPTH)
+        if (gameId) {
+          invoke<{ score: number }[]>('get_high_scores', { gameId, limit: 1 })
+            .then(([best]) => {
+              this.state.bestScore = Math.max(this.state.bestScore, best?.score ?? 0)
+            })
+            .catch((error) => console.error('Failed to load high scores:', error))
+        }
+      }
+
+      if (hud.health_bar) {
+        const bar = hud.health_bar
+        const width = bar.width ?? 200
+        const height = bar.height ?? 20
+        this.add
+          .rectangle(bar.x, bar.y, width, height, 0x333333)
+          .setOrigin(0)
+          .setScrollFactor(0)
+          .setDepth(HUD_DEPTH)
+        const fill = this.add
+          .rectangle(bar.x, bar.y, width, height, parseColor(bar.color ?? '#00ff00'))
+          .setOrigin(0)
+          .setScrollFactor(0)
+          .setDepth(HUD_DEPTH)
+        // The bar is full at the object's starting health
+        const maxHealth = sceneSpec.objects.find((obj) => obj.id === bar.object_id)?.health || 100
+        display.healthBar = { fill, objectId: bar.object_id, maxHealth }
+      }
+
+      if (hud.lives) {
+        display.lives = this.add
+          .text(hud.lives.x, hud.lives.y, '', { fontSize: '24px' })
+          .setScrollFactor(0)
+          .setDepth(HUD_DEPTH)
+      }
+
+      if (hud.timer) {
+        display.timer = this.add
+          .text(hud.timer.x, hud.timer.y, '', { fontSize: '24px', color: '#ffffff' })
+          .setScrollFactor(0)
+          .setDepth(HUD_DEPTH)
+      }
+
+      for (const label of hud.labels ?? []) {
+        const text = this.add
+          .text(label.x, label.y, '', {
+            fontSize: label.font_size ?? '24px',
+            color: label.fill ?? '#ffffff',
+          })
+          .setScrollFactor(0)
+          .setDepth(HUD_DEPTH)
+        this.setLiveText(text, label.text)
+      }
+
+      if (hud.mute_button) {
+        display.muteButton = this.add
+          .text(hud.mute_button.x, hud.mute_button.y, '', { fontSize: '24px' })
+          .setScrollFactor(0)
+          .setDepth(HUD_DEPTH)
+          .setInteractive({ useHandCursor: true })
+          .on('pointerdown', () => {
+            this.sound.mute = !this.sound.mute
+          })
+      }
+
+      this.updateHud()
+    }
+
+    private updateHud() {
+      const hud = sceneSpec.hud
+      const display = this.state.hud
+      display.score?.setText(`${hud?.score?.label ?? 'Score: '}${this.state.score}`)
+      display.bestScore?.setText(
+        `${hud?.best_score?.label ?? 'Best: '}${Math.max(this.state.bestScore, this.state.score)}`
+      )
+      if (display.healthBar) {
+        const { fill, objectId, maxHealth } = display.healthBar
+        // Destroyed objects have no health left
+        const obj = this.state.objects.get(objectId)
+        const health = obj?.active ? ((obj.getData('health') as number | undefined) ?? 0) : 0
+        fill.setScale(Phaser.Math.Clamp(health / maxHealth, 0, 1), 1)
+      }
+      display.lives?.setText((hud?.lives?.icon ?? '❤️').repeat(this.state.lives))
+      display.timer?.setText(`${hud?.timer?.label ?? 'Time: '}${this.currentTime()}`)
+      display.countdown?.setText(`${this.currentTime()}`)
+      display.muteButton?.setText(this.sound.mute ? '🔇' : '🔊')
+      for (const [text, template] of this.state.liveTexts) {
+        text.setText(this.interpolate(template))
+      }
+    }
+
+    /**
+     * Show a text, kept up to date while the game runs if it has placeholders
+     */
+    private setLiveText(text: Phaser.GameObjects.Text, template: string) {
+      if (template.includes('{')) {
+        if (!this.state.liveTexts.has(text)) {
+          text.once('destroy', () => this.state.liveTexts.delete(text))
+        }
+        this.state.liveTexts.set(text, template)
+      } else {
+        this.state.liveTexts.delete(text)
+      }
+      text.setText(this.interpolate(template))
+    }
+
+    /**
+     * Fill in a text's {name} placeholders with the score, lives, time, or a
+     * variable; unknown names are left as they are
+     */
+    private interpolate(template: string): string {
+      return template.replace(/\{([^}]*)\}/g, (placeholder, name: string) => {
+        const value = this.variableValue(name.trim())
+        return value === undefined ? placeholder : `${value}`
+      })
+    }
+
+    /**
+     * Value of the score, lives, time, or a declared variable
+     */
+    private variableValue(name: string): number | undefined {
+      switch (name) {
+        case 'score':
+          return this.state.score
+        case 'lives':
+          return this.state.lives
+        case 'time':
+          return this.currentTime()
+        default:
+          return this.state.variables.get(name)
+      }
+    }
+
+    /**
+     * Whether every `name op number` clause of an achievement condition holds
+     */
+    private conditionHolds(condition: string): boolean {
+      return condition.split('&&').every((clause) => {
+        const operator = CONDITION_OPERATORS.find((operator) => clause.includes(operator))
+        if (!operator) return false
+        const index = clause.indexOf(operator)
+        const value = this.variableValue(clause.slice(0, index).trim())
+        const target = Number(clause.slice(index + operator.length).trim())
+        return value !== undefined && !Number.isNaN(target) && compare(value, operator, target)
+      })
+    }
+
+    /**
+     * Progress shared by every scene of the game. Library games pick up the
+     * achievements unlocked in earlier sessions, so they aren't shown again.
+     */
+    private achievementProgress(): AchievementProgress {
+      let progress = this.game.registry.get('achievements') as AchievementProgress | undefined
+      if (!progress) {
+        const created: AchievementProgress = { unlocked: new Set(), loaded: !gameId }
+        progress = created
+        this.game.registry.set('achievements', created)
+        if (gameId) {
+          invoke<{ id: string; unlocked_at: string | null }[]>('get_achievements', { gameId })
+            .then((statuses) => {
+              for (const status of statuses) {
+                if (status.unlocked_at) created.unlocked.add(status.id)
+              }
+            })
+            .catch((error) => console.error('Failed to load achievements:', error))
+            .finally(() => {
+              created.loaded = true
+            })
+        }
+      }
+      return progress
+    }
+
+    /**
+     * Unlock the achievements whose condition holds for the first time
+     */
+    private checkAchievements(achievements: Achievement[]) {
+      const progress = this.achievementProgress()
+      if (!progress.loaded) return
+
+      for (const achievement of achievements) {
+        if (progress.unlocked.has(achievement.id) || !this.conditionHolds(achievement.condition)) continue
+
+        progress.unlocked.add(achievement.id)
+        this.showAchievement(achievement)
+        if (gameId) {
+          invoke('unlock_achievement', { gameId, achievementId: achievement.id }).catch((error) =>
+            console.error('Failed to unlock achievement:', error)
+          )
+        }
+      }
+    }
+
+    /**
+     * Announce an unlocked achievement in the corner of the screen
+     */
+    private showAchievement(achievement: Achievement) {
+      const toast = this.add
+        .text(this.scale.width - 16, 16, `${achievement.icon ?? '🏆'} ${achievement.name}`, {
+          fontSize: '20px',
+          color: '#ffffff',
+          backgroundColor: '#333333',
+          padding: { x: 12, y: 8 },
+        })
+        .setOrigin(1, 0)
+        .setScrollFactor(0)
+        .setDepth(HUD_DEPTH)
+      this.tweens.add({
+        targets: toast,
+        alpha: 0,
+        delay: 2500,
+        duration: 500,
+        onComplete: () => toast.destroy(),
+      })
+    }
+
+    /**
+     * Whole seconds left on the countdown, or played if the scene has none
+     */
+    private currentTime(): number {
+      const countdown = sceneSpec.countdown
+      if (countdown) {
+        return Math.max(0, Math.ceil(countdown.seconds - this.state.elapsed / 1000))
+      }
+      return Math.floor(this.state.elapsed / 1000)
+    }
+
+    /**
+     * World larger than the canvas, scrolled by a camera following an object
+     */
+    private setupCamera(camera: CameraConfig) {
+      const width = camera.world_width ?? gameSpec.game.width
+      const height = camera.world_height ?? gameSpec.game.height
+      this.cameras.main.setBounds(0, 0, width, height)
+      // Objects that collide with the world bounds are kept inside the world, not the canvas
+      if (gameSpec.game.physics.enabled) {
+        this.physics.world.setBounds(0, 0, width, height)
+      }
+
+      if (camera.follow) {
+        const target = this.state.objects.get(camera.follow)
+        if (target) {
+          const lerp = camera.lerp ?? 1
+          this.cameras.main.startFollow(target, true, lerp, lerp)
+        } else {
+          console.warn(`Camera target not found: ${camera.follow}`)
+        }
+      }
+      if (camera.deadzone) {
+        this.cameras.main.setDeadzone(camera.deadzone.width, camera.deadzone.height)
+      }
+    }
+
+    /**
+     * Place a copy of each tile's legend template at the tile's center
+     */
+    private createTilemap(tilemap: Tilemap) {
+      const legend = new Map(tilemap.legend.map((entry) => [entry.symbol, entry.template]))
+      const size = tilemap.tile_size
+      const offsetX = tilemap.offset_x ?? 0
+      const offsetY = tilemap.offset_y ?? 0
+
+      tilemap.rows.forEach((row, rowIndex) => {
+        Array.from(row).forEach((symbol, colIndex) => {
+          // '.' and ' ' are empty tiles and have no legend entry
+          const template = legend.get(symbol)
+          if (template) {
+            this.createTemplateObject(
+              template,
+              `${template.id}_${rowIndex}_${colIndex}`,
+              offsetX + colIndex * size + size / 2,
+              offsetY + rowIndex * size + size / 2
+            )
+          }
+        })
+      })
+    }
+
+    /**
+     * Lay out ground segments with random gaps between them, with obstacles
+     * on the ground and collectibles above it. The first segment is left
+     * clear for the player to start on.
+     */
+    private createProceduralLevel(level: ProceduralLevel) {
+      const random = level.seed !== undefined ? seededRandom(level.seed) : Math.random
+      const between = (min: number, max: number) => min + random() * (max - min)
+
+      const platform = specSize(level.platform)
+      const groundTop = level.platform.y - platform.height / 2
+      let count = 0
+      for (let x = 0; x < level.length; x += platform.width) {
+        const n = count++
+        const first = n === 0
+        this.createTemplateObject(level.platform, `${level.platform.id}_${n}`, x + platform.width / 2, level.platform.y)
+
+        if (!first && level.obstacle && random() < (level.obstacle_density ?? 0.2)) {
+          const obstacle = specSize(level.obstacle)
+          this.createTemplateObject(
+            level.obstacle,
+            `${level.obstacle.id}_${n}`,
+            x + between(obstacle.width / 2, platform.width - obstacle.width / 2),
+            groundTop - obstacle.height / 2
+          )
+        }
+        if (level.collectible && random() < (level.collectible_frequency ?? 0.3)) {
+          const collectible = specSize(level.collectible)
+          this.createTemplateObject(
+            level.collectible,
+            `${level.collectible.id}_${n}`,
+            x + platform.width / 2,
+            groundTop - collectible.height / 2 - between(40, 120)
+          )
+        }
+
+        if (level.gap_width) {
+          x += between(level.gap_width.min, level.gap_width.max)
+        }
+      }
+    }
+
+    /**
+     * Create a copy of a template under its own id, in a group named after the
+     * template so handlers can refer to every copy at once. Copies without a
+     * dynamic body get a static one, so they can still be touched.
+     */
+    private createTemplateObject(template: GameObject, id: string, x: number, y: number) {
+      let group = this.state.groups.get(template.id)
+      if (!group) {
+        group = bodyType(template) === 'dynamic' ? this.physics.add.group() : this.physics.add.staticGroup()
+        this.state.groups.set(template.id, group)
+      }
+
+      const gameObject = this.buildObject(template, x, y, group)
+      if (!gameObject) {
+        console.warn(`Failed to create object: ${id}`)
+        return
+      }
+
+      this.state.objects.set(id, gameObject)
+      const behavior = behaviorOf(template)
+      if (behavior) {
+        this.state.objectBehaviors.set(id, {
+          behavior,
+          params: template.behavior_params,
+        })
+      }
+      gameObject.once('destroy', () => {
+        this.state.objects.delete(id)
+        this.state.objectBehaviors.delete(id)
+        this.state.behaviorState.delete(id)
+      })
+    }
+
+    /**
+     * Create an object from a spec or template at (x, y). Objects added to a
+     * physics group get their body from the group.
+     */
+    private buildObject(
+      objSpec: GameObject,
+      x: number,
+      y: number,
+      group?: Phaser.GameObjects.Group
+    ): Phaser.GameObjects.GameObject | null {
       let gameObject: Phaser.GameObjects.GameObject | null = null
 
       switch (objSpec.type) {
         case 'rectangle':
           if (objSpec.shape) {
             const rect = this.add.rectangle(
-              objSpec.x,
-              objSpec.y,
+              x,
+              y,
               objSpec.shape.width || 100,
               objSpec.shape.height || 100,
               objSpec.shape.color ? parseColor(objSpec.shape.color) : 0xffffff
@@ -191,8 +1199,8 @@ function createSceneClass(sceneSpec: SceneSpec, gameSpec: PhaserGameSpec) {
         case 'circle':
           if (objSpec.shape) {
             const circle = this.add.circle(
-              objSpec.x,
-              objSpec.y,
+              x,
+              y,
               objSpec.shape.radius || 50,
               objSpec.shape.color ? parseColor(objSpec.shape.color) : 0xffffff
             )
@@ -202,12 +1210,10 @@ function createSceneClass(sceneSpec: SceneSpec, gameSpec: PhaserGameSpec) {
 
         case 'text':
           if (objSpec.text) {
-            const text = this.add.text(objSpec.x, objSpec.y, objSpec.text.text, {
-              fontSize: objSpec.text.font_size || '32px',
-              color: objSpec.text.fill || '#ffffff',
-            })
+            const text = this.add.text(x, y, '', textStyle(objSpec.text))
+            this.setLiveText(text, objSpec.text.text)
             // Set origin to center to match other game objects (except for UI text like scores)
-            if (objSpec.physics && objSpec.physics.body !== 'none') {
+            if (group || (objSpec.physics && objSpec.physics.body !== 'none')) {
               text.setOrigin(0.5, 0.5)
             }
             gameObject = text
@@ -216,60 +1222,342 @@ function createSceneClass(sceneSpec: SceneSpec, gameSpec: PhaserGameSpec) {
 
         case 'emoji':
           if (objSpec.emoji) {
-            const emoji = this.add.text(objSpec.x, objSpec.y, objSpec.emoji.emoji, {
+            const emoji = this.add.text(x, y, objSpec.emoji.emoji, {
               fontSize: `${objSpec.emoji.size || 32}px`,
               color: '#ffffff',
             })
             // Center the emoji
             emoji.setOrigin(0.5, 0.5)
+            this.addEmojiLayers(emoji, objSpec.emoji)
             gameObject = emoji
           }
           break
 
         case 'sprite':
           if (objSpec.texture) {
-            const sprite = this.add.sprite(objSpec.x, objSpec.y, objSpec.texture)
+            const sprite = this.add.sprite(x, y, objSpec.texture)
             gameObject = sprite
           }
           break
 
+        case 'hazard':
+          if (objSpec.hazard) {
+            const hazard = this.add.image(x, y, this.hazardTexture(objSpec.hazard))
+            if (objSpec.hazard.kind === 'saw') {
+              this.tweens.add({ targets: hazard, angle: 360, duration: 1000, repeat: -1 })
+            }
+            gameObject = hazard
+          }
+          break
+
+        case 'zone':
+          if (objSpec.zone) {
+            gameObject = this.add.zone(x, y, objSpec.zone.width, objSpec.zone.height)
+          }
+          break
+
         case 'group':
-          // Create a group for managing multiple objects
-          const group = this.add.group()
-          this.state.groups.set(objSpec.id, group)
-          return // Groups don't need physics setup
+          // Groups aren't display objects; createObject handles them
+          break
       }
 
       if (!gameObject) {
-        console.warn(`Failed to create object: ${objSpec.id}`)
-        return
+        return null
       }
 
-      // Store reference
-      this.state.objects.set(objSpec.id, gameObject)
+      if (isDisplayObject(gameObject)) {
+        if (objSpec.depth !== undefined) gameObject.setDepth(objSpec.depth)
+        if (objSpec.alpha !== undefined) gameObject.setAlpha(objSpec.alpha)
+        // Before the body is added, so the body takes the scaled size
+        if (objSpec.rotation !== undefined) gameObject.setAngle(objSpec.rotation)
+        if (objSpec.scale !== undefined) gameObject.setScale(objSpec.scale)
+      }
+      if (objSpec.tint && hasTint(gameObject)) {
+        gameObject.setTint(parseColor(objSpec.tint))
+      }
+
+      if (objSpec.health !== undefined) {
+        gameObject.setData('health', objSpec.health)
+      }
+
+      // Destroy triggers watch every object, since groups gain members as
+      // the game runs. Objects destroyed with the scene don't count.
+      if (sceneSpec.custom_logic?.on_destroyed) {
+        const destroyed = gameObject
+        destroyed.once(Phaser.GameObjects.Events.DESTROY, (_obj: unknown, fromScene?: boolean) => {
+          if (!fromScene) {
+            this.runDestroyTriggers(destroyed, objSpec.id, group)
+          }
+        })
+      }
+
+      // Clicking the object runs its action, with the object as the target
+      const onClick = objSpec.on_click
+      if (onClick) {
+        const clicked = gameObject
+        clicked.setInteractive({ useHandCursor: true }).on('pointerdown', () => {
+          if (!this.state.paused && !this.state.ended) {
+            this.runAction(onClick, clicked)
+          }
+        })
+      }
+
+      // Start the idle animation of animated sprites
+      if (objSpec.animations?.idle && gameObject instanceof Phaser.GameObjects.Sprite) {
+        gameObject.play(objSpec.animations.idle)
+      }
+
+      if (group instanceof Phaser.Physics.Arcade.Group || group instanceof Phaser.Physics.Arcade.StaticGroup) {
+        // Adding to a physics group gives the object a body and resets its
+        // settings, so the body is configured afterwards
+        group.add(gameObject)
+        this.configureBody(gameObject, objSpec)
+      } else {
+        // Group objects of the spec are plain groups, which add no body
+        group?.add(gameObject)
+        this.applyPhysics(gameObject, objSpec)
+      }
+
+      // Colliders of the collision matrix hold the member lists, so objects
+      // joining or leaving a group are picked up as the game runs
+      if (objSpec.collision_group && hasBody(gameObject)) {
+        const members = this.collisionGroup(objSpec.collision_group)
+        const member = gameObject
+        members.push(member)
+        member.setData('collisionGroup', objSpec.collision_group)
+        member.once(Phaser.GameObjects.Events.DESTROY, () => {
+          const index = members.indexOf(member)
+          if (index >= 0) members.splice(index, 1)
+        })
+      }
+
+      // Saws float along their patrol and can't be pushed around
+      if (objSpec.type === 'hazard' && hasArcadeBody(gameObject)) {
+        gameObject.body.setAllowGravity(false)
+        gameObject.body.setImmovable(true)
+      }
 
-      // Apply physics if specified
-      if (objSpec.physics && gameObject) {
-        this.applyPhysics(gameObject, objSpec.physics, objSpec)
+      return gameObject
+    }
+
+    private collisionGroup(name: string): Phaser.GameObjects.GameObject[] {
+      let members = this.state.collisionGroups.get(name)
+      if (!members) {
+        members = []
+        this.state.collisionGroups.set(name, members)
+      }
+      return members
+    }
+
+    /**
+     * Collide or overlap the collision groups paired in the matrix. Groups
+     * without a rule, or with an ignore rule, pass through each other.
+     */
+    private setupCollisionMatrix(rules: CollisionRule[]) {
+      for (const rule of rules) {
+        if (rule.mode === 'ignore') continue
+
+        const a = this.collisionGroup(rule.a)
+        const b = this.collisionGroup(rule.b)
+        const handler = rule.action ? this.createInteractionHandler(rule.action) : undefined
+        // A group paired with itself would otherwise pair each object with itself
+        const distinct = (obj1: ArcadeCollidable, obj2: ArcadeCollidable) => obj1 !== obj2
+        if (rule.mode === 'overlap') {
+          this.physics.add.overlap(a, b, handler, distinct, this)
+        } else {
+          this.physics.add.collider(a, b, handler, distinct, this)
+        }
       }
     }
 
-    private applyPhysics(
-      gameObject: Phaser.GameObjects.GameObject,
-      physics: GameObject['physics'],
-      objSpec?: GameObject
+    /**
+     * Whether the collision matrix says two objects ignore each other, which
+     * overrides any handler pairing them
+     */
+    private ignoresCollision(obj1: ArcadeCollidable, obj2: ArcadeCollidable): boolean {
+      const group1 = toGameObject(obj1)?.getData('collisionGroup') as string | undefined
+      const group2 = toGameObject(obj2)?.getData('collisionGroup') as string | undefined
+      if (!group1 || !group2) return false
+
+      return (sceneSpec.custom_logic?.collision_matrix ?? []).some(
+        (rule) =>
+          rule.mode === 'ignore' &&
+          ((rule.a === group1 && rule.b === group2) || (rule.a === group2 && rule.b === group1))
+      )
+    }
+
+    /**
+     * Run the actions watching a destroyed object, or its group. Triggers
+     * with `all` wait until the rest of the group is gone too.
+     */
+    private runDestroyTriggers(
+      obj: Phaser.GameObjects.GameObject,
+      id: string,
+      group: Phaser.GameObjects.Group | undefined
     ) {
-      if (!physics || physics.body === 'none') return
+      for (const trigger of sceneSpec.custom_logic?.on_destroyed ?? []) {
+        const watched = this.state.groups.get(trigger.target)
+        if (trigger.target !== id && (!group || watched !== group)) continue
+
+        if (trigger.all && watched) {
+          const remaining = watched.getChildren().filter((child) => child !== obj && child.active)
+          if (remaining.length > 0) continue
+        }
+        this.runAction(trigger.action, obj)
+      }
+    }
+
+    /**
+     * Texture drawn for a hazard of the given kind and size, shared by every
+     * hazard that looks the same
+     */
+    private hazardTexture(hazard: HazardProperties): string {
+      const width = hazard.width ?? 40
+      const height = hazard.height ?? (hazard.kind === 'lava' ? 20 : 40)
+      const key = `hazard_${hazard.kind}_${width}x${height}`
+      if (this.textures.exists(key)) return key
+
+      const graphics = this.make.graphics({}, false)
+      switch (hazard.kind) {
+        case 'spikes': {
+          // Row of spikes about as wide as they are tall
+          const count = Math.max(1, Math.round(width / Math.min(width, height)))
+          const spike = width / count
+          graphics.fillStyle(0x9e9e9e)
+          for (let i = 0; i < count; i++) {
+            graphics.fillTriangle(i * spike, height, i * spike + spike / 2, 0, (i + 1) * spike, height)
+          }
+          break
+        }
+        case 'saw': {
+          const radius = Math.min(width, height) / 2
+          const teeth = 8
+          graphics.fillStyle(0xb0b0b0)
+          for (let i = 0; i < teeth; i++) {
+            const angle = (i / teeth) * Math.PI * 2
+            const next = angle + Math.PI / teeth
+            graphics.fillTriangle(
+              width / 2 + Math.cos(angle) * radius * 0.7,
+              height / 2 + Math.sin(angle) * radius * 0.7,
+              width / 2 + Math.cos(next) * radius,
+              height / 2 + Math.sin(next) * radius,
+              width / 2 + Math.cos(angle + (2 * Math.PI) / teeth) * radius * 0.7,
+              height / 2 + Math.sin(angle + (2 * Math.PI) / teeth) * radius * 0.7
+            )
+          }
+          graphics.fillCircle(width / 2, height / 2, radius * 0.75)
+          graphics.fillStyle(0x666666)
+          graphics.fillCircle(width / 2, height / 2, radius * 0.2)
+          break
+        }
+        case 'lava':
+          graphics.fillStyle(0xff4500)
+          graphics.fillRect(0, 0, width, height)
+          graphics.fillStyle(0xffa500)
+          graphics.fillRect(0, 0, width, Math.min(4, height))
+          break
+      }
+      graphics.generateTexture(key, width, height)
+      graphics.destroy()
+      return key
+    }
+
+    /**
+     * Hurt each hazard's target when it touches the hazard: damage if the
+     * hazard has any, otherwise game over. A target that was just hurt can't
+     * be hurt again right away.
+     */
+    private setupHazards() {
+      for (const objSpec of sceneObjects(sceneSpec)) {
+        if (objSpec.type !== 'hazard' || !objSpec.hazard) continue
+        const hazard = objSpec.hazard
+
+        const hazards = this.state.objects.get(objSpec.id) || this.state.groups.get(objSpec.id)
+        const targetId = hazard.target ?? 'player'
+        const target = this.state.objects.get(targetId)
+        if (!hazards || !target) {
+          console.warn(`Objects not found for hazard: ${objSpec.id}, ${targetId}`)
+          continue
+        }
+
+        this.physics.add.overlap(target, hazards, () => {
+          if (hazard.damage === undefined) {
+            this.handleGameOver()
+            return
+          }
+          const lastHit = (target.getData('hazardHitAt') as number | undefined) ?? -HAZARD_COOLDOWN
+          if (this.time.now - lastHit < HAZARD_COOLDOWN) return
+          target.setData('hazardHitAt', this.time.now)
+          this.damage(target, hazard.damage)
+        })
+      }
+    }
+
+    /**
+     * Draw an emoji's layers, which follow it until it's destroyed
+     */
+    private addEmojiLayers(owner: Phaser.GameObjects.Text, emoji: EmojiProperties) {
+      const size = emoji.size || 32
+      for (const layer of emoji.layers ?? []) {
+        const text = this.add
+          .text(owner.x + layer.offset_x, owner.y + layer.offset_y, layer.emoji, {
+            fontSize: `${size * (layer.scale ?? 1)}px`,
+            color: '#ffffff',
+          })
+          .setOrigin(0.5, 0.5)
+        const display = { text, owner, offsetX: layer.offset_x, offsetY: layer.offset_y, behind: layer.behind }
+        this.state.emojiLayers.push(display)
+        owner.once('destroy', () => {
+          text.destroy()
+          this.state.emojiLayers = this.state.emojiLayers.filter((other) => other !== display)
+        })
+      }
+    }
+
+    private updateEmojiLayers() {
+      for (const { text, owner, offsetX, offsetY, behind } of this.state.emojiLayers) {
+        text.setPosition(owner.x + offsetX, owner.y + offsetY)
+        text.setDepth(owner.depth + (behind ? -0.5 : 0.5))
+      }
+    }
+
+    private applyPhysics(gameObject: Phaser.GameObjects.GameObject, objSpec: GameObject) {
+      const body = bodyType(objSpec)
+      if (!body || body === 'none') return
+
+      this.physics.add.existing(gameObject, body === 'static')
+      this.configureBody(gameObject, objSpec)
+    }
+
+    private configureBody(gameObject: Phaser.GameObjects.GameObject, objSpec: GameObject) {
+      const physics = objSpec.physics
+      if (!physics) return
+
+      // Jump-through platforms only stop objects landing on top of them
+      if (physics.one_way && hasBody(gameObject)) {
+        const { checkCollision } = gameObject.body
+        checkCollision.down = false
+        checkCollision.left = false
+        checkCollision.right = false
+      }
 
-      const isStatic = physics.body === 'static'
-      this.physics.add.existing(gameObject, isStatic)
+      // Surfaces act on the objects standing on them. Recycled pool objects
+      // are configured again, but only registered once.
+      const surface = physics.surface
+      if (surface && hasBody(gameObject) && !this.state.surfaces.some((entry) => entry.gameObject === gameObject)) {
+        const entry = { gameObject, surface }
+        this.state.surfaces.push(entry)
+        gameObject.once('destroy', () => {
+          this.state.surfaces = this.state.surfaces.filter((other) => other !== entry)
+        })
+      }
 
       // Apply physics properties (only works on dynamic bodies)
       if (hasArcadeBody(gameObject)) {
         const body = gameObject.body
 
         // For emoji objects, use the collision box to set physics body size
-        if (objSpec?.type === 'emoji' && objSpec.emoji?.collision_box) {
+        if (objSpec.type === 'emoji' && objSpec.emoji?.collision_box) {
           const collisionBox = objSpec.emoji.collision_box
           if (collisionBox.shape === 'rectangle' && collisionBox.width && collisionBox.height) {
             // For text objects with centered origin, we need to ensure the body is properly positioned
@@ -322,6 +1610,27 @@ function createSceneClass(sceneSpec: SceneSpec, gameSpec: PhaserGameSpec) {
         if (physics.velocity) {
           body.setVelocity(physics.velocity.x, physics.velocity.y)
         }
+        if (physics.drag !== undefined) {
+          body.setDrag(physics.drag, physics.drag)
+        }
+        if (physics.max_velocity !== undefined) {
+          body.setMaxVelocity(physics.max_velocity, physics.max_velocity)
+        }
+        if (physics.mass !== undefined) {
+          body.setMass(physics.mass)
+        }
+        if (physics.immovable !== undefined) {
+          body.setImmovable(physics.immovable)
+        }
+        if (physics.angular_velocity !== undefined) {
+          body.setAngularVelocity(physics.angular_velocity)
+        }
+        if (physics.gravity) {
+          // Body gravity adds to the world's, so subtract it to replace it
+          const world = gameSpec.game.physics.gravity
+          body.setGravity(physics.gravity.x - world.x, physics.gravity.y - world.y)
+          gameObject.setData('gravity', body.gravity.clone())
+        }
       }
     }
 
@@ -354,12 +1663,25 @@ function createSceneClass(sceneSpec: SceneSpec, gameSpec: PhaserGameSpec) {
         }
       }
 
+      if (logic.collision_matrix) {
+        this.setupCollisionMatrix(logic.collision_matrix)
+      }
+
       // Set up timers
       if (logic.timers) {
         for (const timerSpec of logic.timers) {
           this.parseAndSetupTimer(timerSpec)
         }
       }
+
+      // Set up key triggers, which rest while the game is paused or over
+      for (const trigger of logic.on_key_press ?? []) {
+        this.input.keyboard?.on(`keydown-${phaserKey(trigger.key)}`, () => {
+          if (!this.state.paused && !this.state.ended) {
+            this.runAction(trigger.action)
+          }
+        })
+      }
     }
 
     private parseAndSetupInteraction(
@@ -387,65 +1709,50 @@ function createSceneClass(sceneSpec: SceneSpec, gameSpec: PhaserGameSpec) {
       }
 
       const handler = this.createInteractionHandler(callback.trim())
+      const allowed = (o1: ArcadeCollidable, o2: ArcadeCollidable) => !this.ignoresCollision(o1, o2)
 
       if (type === 'collision') {
-        this.physics.add.collider(obj1, obj2, handler, undefined, this)
+        this.physics.add.collider(obj1, obj2, handler, allowed, this)
       } else {
-        this.physics.add.overlap(obj1, obj2, handler, undefined, this)
+        this.physics.add.overlap(obj1, obj2, handler, allowed, this)
       }
     }
 
     private createInteractionHandler(callback: string) {
-      return (
-        obj1: Phaser.Physics.Arcade.Body | Phaser.Physics.Arcade.StaticBody | Phaser.Types.Physics.Arcade.GameObjectWithBody | Phaser.Tilemaps.Tile,
-        obj2: Phaser.Physics.Arcade.Body | Phaser.Physics.Arcade.StaticBody | Phaser.Types.Physics.Arcade.GameObjectWithBody | Phaser.Tilemaps.Tile
-      ) => {
-        // First check if it's a registered action
-        const action = this.state.actions.get(callback)
-        if (action) {
-          // Extract GameObjects from both collision objects
-          let gameObj1: Phaser.GameObjects.GameObject | undefined
-          let gameObj2: Phaser.GameObjects.GameObject | undefined
-
-          if ('gameObject' in obj1) {
-            gameObj1 = obj1.gameObject as Phaser.GameObjects.GameObject
-          } else if (obj1 instanceof Phaser.GameObjects.GameObject) {
-            gameObj1 = obj1
-          }
-
-          if ('gameObject' in obj2) {
-            gameObj2 = obj2.gameObject as Phaser.GameObjects.GameObject
-          } else if (obj2 instanceof Phaser.GameObjects.GameObject) {
-            gameObj2 = obj2
-          }
+      return (obj1: ArcadeCollidable, obj2: ArcadeCollidable) => {
+        this.runAction(callback, toGameObject(obj2), toGameObject(obj1))
+      }
+    }
 
-          this.executeAction(action.effect, gameObj2, gameObj1)
-          return
-        }
+    /**
+     * Run a registered action by name. `target` is the second object of a
+     * handler pair and `source` the first.
+     */
+    private runAction(
+      name: string,
+      targetObj?: Phaser.GameObjects.GameObject,
+      sourceObj?: Phaser.GameObjects.GameObject
+    ) {
+      // First check if it's a registered action
+      const action = this.state.actions.get(name)
+      if (action) {
+        this.executeAction(action.effect, targetObj, sourceObj)
+        return
+      }
 
-        // Fall back to legacy hard-coded callbacks for backward compatibility
-        switch (callback) {
-          case 'null':
-            // Do nothing - just physics response
-            break
-          case 'gameOver':
-            this.handleGameOver()
-            break
-          case 'destroy':
-            // Extract GameObject and destroy it
-            let gameObj: Phaser.GameObjects.GameObject | undefined
-            if ('gameObject' in obj2) {
-              gameObj = obj2.gameObject as Phaser.GameObjects.GameObject
-            } else if (obj2 instanceof Phaser.GameObjects.GameObject) {
-              gameObj = obj2
-            }
-            if (gameObj) {
-              gameObj.destroy()
-            }
-            break
-          default:
-            console.warn(`Unknown callback: ${callback}`)
-        }
+      // Fall back to legacy hard-coded callbacks for backward compatibility
+      switch (name) {
+        case 'null':
+          // Do nothing - just physics response
+          break
+        case 'gameOver':
+          this.handleGameOver()
+          break
+        case 'destroy':
+          targetObj?.destroy()
+          break
+        default:
+          console.warn(`Unknown callback: ${name}`)
       }
     }
 
@@ -455,7 +1762,8 @@ function createSceneClass(sceneSpec: SceneSpec, gameSpec: PhaserGameSpec) {
       sourceObj?: Phaser.GameObjects.GameObject
     ) {
       switch (effect.type) {
-        case 'updateScore':
+        case 'updateScore': {
+          const previous = this.state.score
           this.state.score += effect.points
           this.updateScoreDisplay()
           // Always destroy the target object
@@ -469,21 +1777,264 @@ function createSceneClass(sceneSpec: SceneSpec, gameSpec: PhaserGameSpec) {
               sourceObj.destroy()
             }
           }
+          this.runScoreTriggers(previous)
           break
+        }
         case 'gameOver':
-          this.handleGameOver()
+          this.handleGameOver(effect)
+          break
+        case 'win':
+          this.handleWin(effect)
           break
         case 'destroy':
           if (targetObj) {
             targetObj.destroy()
           }
           break
-        case 'updateText':
+        case 'restartScene':
+          this.scene.restart()
+          break
+        case 'updateText': {
           const textObj = this.state.objects.get(effect.object_id)
-          if (textObj && (textObj as Phaser.GameObjects.Text).setText) {
-            ;(textObj as Phaser.GameObjects.Text).setText(effect.text)
+          if (textObj instanceof Phaser.GameObjects.Text) {
+            this.setLiveText(textObj, effect.text)
+          }
+          break
+        }
+        case 'updateVariable': {
+          const current = this.state.variables.get(effect.name) ?? 0
+          if (effect.value !== undefined) {
+            this.state.variables.set(effect.name, effect.value)
+          } else if (effect.change !== undefined) {
+            this.state.variables.set(effect.name, current + effect.change)
+          }
+          break
+        }
+        case 'damage':
+          this.damage(this.effectObject(effect.object_id, sourceObj), effect.amount)
+          break
+        case 'loseLife':
+          this.state.lives = Math.max(0, this.state.lives - 1)
+          if (this.state.lives === 0) {
+            this.handleGameOver()
+          }
+          break
+        case 'cameraShake':
+          this.cameras.main.shake(effect.duration, effect.intensity ?? 0.01)
+          break
+        case 'cameraFlash': {
+          const color = parseColor(effect.color ?? '#ffffff')
+          this.cameras.main.flash(effect.duration, (color >> 16) & 0xff, (color >> 8) & 0xff, color & 0xff)
+          break
+        }
+        case 'cameraZoom':
+          this.cameras.main.zoomTo(effect.zoom, effect.duration ?? 500)
+          break
+        case 'saveHighScore':
+          this.saveHighScore(effect.ask_name ?? false)
+          break
+        case 'startSpawner':
+          this.state.stoppedSpawners.delete(effect.spawner_id)
+          this.updateSpawnerPaused(effect.spawner_id)
+          break
+        case 'stopSpawner':
+          this.state.stoppedSpawners.add(effect.spawner_id)
+          this.updateSpawnerPaused(effect.spawner_id)
+          break
+        case 'setSpawnerInterval': {
+          const timer = this.state.spawners.get(effect.spawner_id)
+          const spawner = sceneSpec.custom_logic?.spawners?.find((spawner) => spawner.id === effect.spawner_id)
+          if (timer && spawner) {
+            timer.reset({
+              delay: effect.interval,
+              callback: () => this.spawnObject(spawner),
+              callbackScope: this,
+              loop: true,
+            })
+            this.updateSpawnerPaused(spawner.id)
+          } else {
+            console.warn(`Spawner not found: ${effect.spawner_id}`)
+          }
+          break
+        }
+        case 'flashScreen': {
+          const { width, height } = this.scale
+          const overlay = this.add
+            .rectangle(0, 0, width, height, parseColor(effect.color), effect.alpha ?? 0.5)
+            .setOrigin(0)
+            .setScrollFactor(0)
+            .setDepth(HUD_DEPTH)
+          this.tweens.add({
+            targets: overlay,
+            alpha: 0,
+            duration: effect.duration,
+            onComplete: () => overlay.destroy(),
+          })
+          break
+        }
+        case 'setBackgroundColor':
+          this.setBackgroundColor(effect.color, effect.duration)
+          break
+        case 'setTimeScale': {
+          const scale = effect.scale
+          this.setTimeScale(scale)
+          // Real time, since the scene clock is scaled. Only undo this
+          // change, in case the scene restarted or the scale changed again.
+          if (effect.duration) {
+            window.setTimeout(() => {
+              if (this.sys.isActive() && this.time.timeScale === scale) {
+                this.setTimeScale(1)
+              }
+            }, effect.duration)
+          }
+          break
+        }
+        case 'setCheckpoint':
+          this.setCheckpoint(this.effectObject(effect.object_id, targetObj))
+          break
+        case 'respawnAtCheckpoint': {
+          const objectId = effect.object_id ?? 'player'
+          this.respawn(this.state.objects.get(objectId), objectId)
+          break
+        }
+        case 'setAlpha': {
+          const obj = this.effectObject(effect.object_id, sourceObj)
+          if (!obj || !isDisplayObject(obj)) break
+          if (effect.duration) {
+            this.tweens.add({ targets: obj, alpha: effect.alpha, duration: effect.duration })
+          } else {
+            obj.setAlpha(effect.alpha)
+          }
+          break
+        }
+        case 'setTint': {
+          const obj = this.effectObject(effect.object_id, sourceObj)
+          if (!obj || !hasTint(obj)) break
+          if (effect.color) {
+            obj.setTint(parseColor(effect.color))
+            // A tint with a duration wears off
+            if (effect.duration) {
+              this.time.delayedCall(effect.duration, () => obj.clearTint())
+            }
+          } else {
+            obj.clearTint()
           }
           break
+        }
+        case 'rotate': {
+          const obj = this.effectObject(effect.object_id, sourceObj)
+          if (!obj || !isDisplayObject(obj)) break
+          if (effect.duration) {
+            this.tweens.add({ targets: obj, angle: obj.angle + effect.degrees, duration: effect.duration })
+          } else {
+            obj.angle += effect.degrees
+          }
+          break
+        }
+        case 'scale': {
+          const obj = this.effectObject(effect.object_id, sourceObj)
+          if (!obj || !isDisplayObject(obj)) break
+          if (effect.duration) {
+            this.tweens.add({ targets: obj, scale: effect.scale, duration: effect.duration })
+          } else {
+            obj.setScale(effect.scale)
+            // Static bodies don't follow their object on their own
+            if (hasBody(obj) && obj.body instanceof Phaser.Physics.Arcade.StaticBody) {
+              obj.body.updateFromGameObject()
+            }
+          }
+          break
+        }
+      }
+    }
+
+    /**
+     * Speed up (above 1) or slow down (below 1) the scene's timers, tweens,
+     * and physics
+     */
+    private setTimeScale(scale: number) {
+      this.time.timeScale = scale
+      this.tweens.timeScale = scale
+      // Arcade physics time scale is inverted: 2 runs at half speed
+      if (gameSpec.game.physics.enabled) {
+        this.physics.world.timeScale = 1 / scale
+      }
+    }
+
+    /**
+     * Change the background color, blending into it over `duration` ms if given
+     */
+    private setBackgroundColor(color: string, duration?: number) {
+      const camera = this.cameras.main
+      if (!duration) {
+        camera.setBackgroundColor(color)
+        return
+      }
+
+      const from = camera.backgroundColor.clone()
+      const to = Phaser.Display.Color.ValueToColor(color)
+      this.tweens.addCounter({
+        from: 0,
+        to: 100,
+        duration,
+        onUpdate: (tween) => {
+          const blended = Phaser.Display.Color.Interpolate.ColorWithColor(from, to, 100, tween.getValue() ?? 100)
+          camera.setBackgroundColor(Phaser.Display.Color.GetColor(blended.r, blended.g, blended.b))
+        },
+      })
+    }
+
+    /**
+     * Add the score to the game's high-score table. Games played outside the
+     * library have no table.
+     */
+    private saveHighScore(askName: boolean) {
+      const score = this.state.score
+      this.state.bestScore = Math.max(this.state.bestScore, score)
+      if (!gameId) return
+
+      const playerName = askName ? window.prompt('Enter your name for the high scores') ?? undefined : undefined
+      invoke('submit_high_score', { gameId, score, playerName }).catch((error) =>
+        console.error('Failed to save high score:', error)
+      )
+    }
+
+    /**
+     * Object named by an effect's object_id, or the handler object it defaults to
+     */
+    private effectObject(
+      objectId: string | undefined,
+      fallback?: Phaser.GameObjects.GameObject
+    ): Phaser.GameObjects.GameObject | undefined {
+      return objectId ? this.state.objects.get(objectId) : fallback
+    }
+
+    /**
+     * Remove health from an object, destroying it when its health runs out
+     */
+    private damage(obj: Phaser.GameObjects.GameObject | undefined, amount: number) {
+      if (!obj || !obj.active) return
+
+      const health = ((obj.getData('health') as number | undefined) ?? 0) - amount
+      obj.setData('health', health)
+      if (health <= 0) {
+        obj.destroy()
+      }
+    }
+
+    /**
+     * Run the actions of the score milestones passed since the score was
+     * `previous`. Repeating milestones fire at every multiple.
+     */
+    private runScoreTriggers(previous: number) {
+      const score = this.state.score
+      for (const trigger of sceneSpec.custom_logic?.on_score_reached ?? []) {
+        const reached = trigger.repeat
+          ? Math.floor(score / Math.max(trigger.at, 1)) > Math.floor(previous / Math.max(trigger.at, 1))
+          : previous < trigger.at && score >= trigger.at
+        if (reached) {
+          this.runAction(trigger.action)
+        }
       }
     }
 
@@ -495,17 +2046,58 @@ function createSceneClass(sceneSpec: SceneSpec, gameSpec: PhaserGameSpec) {
       }
     }
 
-    private handleGameOver() {
-      const gameOverText = this.add.text(
-        this.cameras.main.centerX,
-        this.cameras.main.centerY,
-        'GAME OVER',
-        {
-          fontSize: '64px',
-          color: '#ff0000',
+    private handleGameOver(effect?: GameOverEffect) {
+      if (!this.endGame()) return
+
+      const lines: string[] = []
+      if (effect?.show_score ?? true) {
+        lines.push(`Score: ${this.state.score}`)
+      }
+      if (effect?.restart_prompt) {
+        lines.push(effect.restart_prompt)
+      }
+      this.showEndScreen(effect?.message ?? 'GAME OVER', '#ff0000', lines, effect?.style)
+    }
+
+    private handleWin(effect: WinEffect) {
+      if (!this.endGame()) return
+
+      const lines = (effect.stats ?? ['score']).map((stat) => {
+        switch (stat) {
+          case 'score':
+            return `Score: ${this.state.score}`
+          case 'time':
+            return `Time: ${Math.floor(this.state.elapsed / 1000)}s`
+          case 'lives':
+            return `Lives: ${this.state.lives}`
         }
-      )
-      gameOverText.setOrigin(0.5)
+      })
+      const bottom = this.showEndScreen(effect.message ?? 'YOU WIN!', '#00ff00', lines, effect.style)
+
+      // Button starting the next level
+      const nextScene = effect.next_scene
+      if (nextScene) {
+        this.add
+          .text(this.cameras.main.centerX, bottom + 24, effect.next_button_text ?? 'Next Level', {
+            fontSize: '32px',
+            color: '#ffffff',
+            backgroundColor: '#333333',
+            padding: { x: 16, y: 8 },
+          })
+          .setOrigin(0.5, 0)
+          .setScrollFactor(0)
+          .setDepth(HUD_DEPTH)
+          .setInteractive({ useHandCursor: true })
+          .once('pointerdown', () => this.scene.start(nextScene))
+      }
+    }
+
+    /**
+     * Freeze the game, returning false if it had already ended
+     */
+    private endGame(): boolean {
+      if (this.state.ended) return false
+      this.state.ended = true
 
       // Pause physics
       this.physics.pause()
@@ -515,6 +2107,87 @@ function createSceneClass(sceneSpec: SceneSpec, gameSpec: PhaserGameSpec) {
         timer.remove()
       }
       this.state.timers = []
+      return true
+    }
+
+    /**
+     * Overlay with a message and lines of detail under it, fixed to the
+     * screen. Returns the y position below the last line.
+     */
+    private showEndScreen(message: string, color: string, lines: string[], style?: ScreenStyle): number {
+      const { centerX, centerY } = this.cameras.main
+      if (style?.background_color) {
+        this.add
+          .rectangle(0, 0, this.scale.width, this.scale.height, parseColor(style.background_color), style.background_alpha ?? 0.7)
+          .setOrigin(0)
+          .setScrollFactor(0)
+          .setDepth(HUD_DEPTH)
+      }
+
+      const title = this.add
+        .text(centerX, centerY, message, {
+          fontSize: style?.font_size ?? '64px',
+          color: style?.fill ?? color,
+        })
+        .setOrigin(0.5)
+        .setScrollFactor(0)
+        .setDepth(HUD_DEPTH)
+
+      let y = centerY + title.height / 2 + 16
+      for (const line of lines) {
+        const text = this.add
+          .text(centerX, y, line, { fontSize: '24px', color: '#ffffff' })
+          .setOrigin(0.5, 0)
+          .setScrollFactor(0)
+          .setDepth(HUD_DEPTH)
+        y += text.height + 8
+      }
+      return y
+    }
+
+    /**
+     * Pause or resume the game. Physics, tweens, and animations freeze; timers
+     * and spawners only halt if the pause config says so.
+     */
+    private togglePause() {
+      const pause = gameSpec.game.pause
+      if (!pause || this.state.ended) return
+
+      const paused = !this.state.paused
+      this.state.paused = paused
+      if (paused) {
+        this.physics.pause()
+        this.tweens.pauseAll()
+        this.anims.pauseAll()
+      } else {
+        this.physics.resume()
+        this.tweens.resumeAll()
+        this.anims.resumeAll()
+      }
+
+      const spawnerTimers = new Set(this.state.spawners.values())
+      for (const timer of this.state.timers) {
+        if (!spawnerTimers.has(timer)) {
+          timer.paused = paused && pause.halt_timers
+        }
+      }
+      for (const id of this.state.spawners.keys()) {
+        this.updateSpawnerPaused(id)
+      }
+
+      if (paused) {
+        this.state.hud.pauseOverlay = this.add
+          .text(this.cameras.main.centerX, this.cameras.main.centerY, pause.overlay_text ?? 'PAUSED', {
+            fontSize: '64px',
+            color: '#ffffff',
+          })
+          .setOrigin(0.5)
+          .setScrollFactor(0)
+          .setDepth(HUD_DEPTH)
+      } else {
+        this.state.hud.pauseOverlay?.destroy()
+        this.state.hud.pauseOverlay = undefined
+      }
     }
 
     private parseAndSetupTimer(spec: string) {
@@ -526,7 +2199,7 @@ function createSceneClass(sceneSpec: SceneSpec, gameSpec: PhaserGameSpec) {
         const [, delay, callback] = everyMatch
         const timer = this.time.addEvent({
           delay: parseInt(delay),
-          callback: () => this.handleTimerCallback(callback.trim()),
+          callback: () => this.runAction(callback.trim()),
           callbackScope: this,
           loop: true,
         })
@@ -535,7 +2208,7 @@ function createSceneClass(sceneSpec: SceneSpec, gameSpec: PhaserGameSpec) {
         const [, delay, callback] = afterMatch
         const timer = this.time.addEvent({
           delay: parseInt(delay),
-          callback: () => this.handleTimerCallback(callback.trim()),
+          callback: () => this.runAction(callback.trim()),
           callbackScope: this,
           loop: false,
         })
@@ -543,15 +2216,17 @@ function createSceneClass(sceneSpec: SceneSpec, gameSpec: PhaserGameSpec) {
       }
     }
 
-    private handleTimerCallback(callback: string) {
-      switch (callback) {
-        case 'spawnEnemy':
-          // Implement enemy spawning logic
-          console.log('Spawn enemy triggered')
-          break
-        default:
-          console.log(`Unhandled timer callback: ${callback}`)
+    /**
+     * Run a spawner unless it was stopped or the pause halts spawners
+     */
+    private updateSpawnerPaused(id: string) {
+      const timer = this.state.spawners.get(id)
+      if (!timer) {
+        console.warn(`Spawner not found: ${id}`)
+        return
       }
+      const halted = this.state.paused && (gameSpec.game.pause?.halt_spawners ?? true)
+      timer.paused = this.state.stoppedSpawners.has(id) || halted
     }
 
     private setupSpawner(spawner: Spawner) {
@@ -572,6 +2247,7 @@ function createSceneClass(sceneSpec: SceneSpec, gameSpec: PhaserGameSpec) {
       })
 
       this.state.timers.push(timer)
+      this.state.spawners.set(spawner.id, timer)
     }
 
     private spawnObject(spawner: Spawner) {
@@ -584,8 +2260,14 @@ function createSceneClass(sceneSpec: SceneSpec, gameSpec: PhaserGameSpec) {
       // Calculate spawn position
       const position = this.calculateSpawnPosition(spawner)
 
-      // Create object from template
-      const spawnedObj = this.createSpawnedObject(spawner.template, position)
+      // A full pool skips this spawn or moves its oldest object here
+      if (this.poolIsFull(spawner.id, spawner.pool, spawner.template, position.x, position.y)) {
+        return
+      }
+
+      // Create object from template, in the group for this template
+      const group = this.state.groups.get(spawner.template.id)
+      const spawnedObj = this.buildObject(spawner.template, position.x, position.y, group)
 
       if (spawnedObj) {
         // Generate unique ID for spawned object
@@ -593,54 +2275,17 @@ function createSceneClass(sceneSpec: SceneSpec, gameSpec: PhaserGameSpec) {
         this.state.objects.set(uniqueId, spawnedObj)
 
         // Register behavior if template has one
-        if (spawner.template.behavior) {
+        const behavior = behaviorOf(spawner.template)
+        if (behavior) {
           this.state.objectBehaviors.set(uniqueId, {
-            behavior: spawner.template.behavior,
+            behavior,
             params: spawner.template.behavior_params,
           })
         }
 
-        // Add to the group for this template
-        const templateId = spawner.template.id
-        const group = this.state.groups.get(templateId)
-        if (group) {
-          group.add(spawnedObj)
-
-          // IMPORTANT: Re-apply physics settings after adding to group
-          // Adding to group can reset body properties
-          if (spawner.template.physics && hasArcadeBody(spawnedObj)) {
-            const body = spawnedObj.body
-
-            // Re-apply collision box for emoji objects
-            if (spawner.template.type === 'emoji' && spawner.template.emoji?.collision_box) {
-              const collisionBox = spawner.template.emoji.collision_box
-              if (collisionBox.shape === 'rectangle' && collisionBox.width && collisionBox.height) {
-                body.setSize(collisionBox.width, collisionBox.height)
-                if (spawnedObj instanceof Phaser.GameObjects.Text) {
-                  const offsetX = (spawnedObj.width - collisionBox.width) / 2
-                  const offsetY = (spawnedObj.height - collisionBox.height) / 2
-                  body.setOffset(offsetX, offsetY)
-                }
-              } else if (collisionBox.shape === 'circle' && collisionBox.radius) {
-                body.setCircle(collisionBox.radius)
-                if (spawnedObj instanceof Phaser.GameObjects.Text) {
-                  const diameter = collisionBox.radius * 2
-                  const offsetX = (spawnedObj.width - diameter) / 2
-                  const offsetY = (spawnedObj.height - diameter) / 2
-                  body.setOffset(offsetX, offsetY)
-                }
-              }
-            }
-
-            // Re-apply velocity if it was reset
-            if (spawner.template.physics.velocity) {
-              body.setVelocity(spawner.template.physics.velocity.x, spawner.template.physics.velocity.y)
-            }
-          }
-        }
-
         // Increment counter
         this.state.spawnCounters.set(spawner.id, currentCount + 1)
+        this.addToPool(spawner.id, spawnedObj)
 
         // Clean up when object is destroyed
         spawnedObj.once('destroy', () => {
@@ -704,84 +2349,202 @@ function createSceneClass(sceneSpec: SceneSpec, gameSpec: PhaserGameSpec) {
       return { x, y }
     }
 
-    private createSpawnedObject(
-      template: GameObject,
-      position: { x: number; y: number }
-    ): Phaser.GameObjects.GameObject | null {
-      let gameObject: Phaser.GameObjects.GameObject | null = null
+    /**
+     * Surface of the platform an object is standing on
+     */
+    private surfaceUnder(body: Phaser.Physics.Arcade.Body): Surface | undefined {
+      if (!body.touching.down && !body.blocked.down) return undefined
 
-      // Create object based on template type
-      switch (template.type) {
-        case 'rectangle':
-          if (template.shape) {
-            const rect = this.add.rectangle(
-              position.x,
-              position.y,
-              template.shape.width || 100,
-              template.shape.height || 100,
-              template.shape.color ? parseColor(template.shape.color) : 0xffffff
-            )
-            gameObject = rect
-          }
-          break
+      for (const { gameObject, surface } of this.state.surfaces) {
+        const platform = gameObject.body
+        if (Math.abs(body.bottom - platform.top) <= 2 && body.right > platform.left && body.left < platform.right) {
+          return surface
+        }
+      }
+      return undefined
+    }
 
-        case 'circle':
-          if (template.shape) {
-            const circle = this.add.circle(
-              position.x,
-              position.y,
-              template.shape.radius || 50,
-              template.shape.color ? parseColor(template.shape.color) : 0xffffff
-            )
-            gameObject = circle
-          }
-          break
+    /**
+     * Make each checkpoint the respawn point once the player touches it
+     */
+    private setupCheckpoints() {
+      const player = this.state.objects.get('player')
+      if (!player) return
+
+      for (const objSpec of sceneObjects(sceneSpec)) {
+        if (!objSpec.checkpoint) continue
+        const checkpoints = this.state.objects.get(objSpec.id) || this.state.groups.get(objSpec.id)
+        if (!checkpoints) continue
+        // Checkpoints only need a body to be touched
+        if (checkpoints instanceof Phaser.GameObjects.GameObject && !hasBody(checkpoints)) {
+          this.physics.add.existing(checkpoints, true)
+        }
 
-        case 'text':
-          if (template.text) {
-            const text = this.add.text(position.x, position.y, template.text.text, {
-              fontSize: template.text.font_size || '32px',
-              color: template.text.fill || '#ffffff',
-            })
-            // Set origin to center to match other game objects
-            text.setOrigin(0.5, 0.5)
-            gameObject = text
-          }
-          break
+        this.physics.add.overlap(player, checkpoints, (_player, checkpoint) => {
+          this.setCheckpoint(toGameObject(checkpoint))
+        })
+      }
+    }
 
-        case 'emoji':
-          if (template.emoji) {
-            const emoji = this.add.text(position.x, position.y, template.emoji.emoji, {
-              fontSize: `${template.emoji.size || 32}px`,
-              color: '#ffffff',
-            })
-            // Center the emoji
-            emoji.setOrigin(0.5, 0.5)
-            gameObject = emoji
-          }
-          break
+    private setCheckpoint(obj: Phaser.GameObjects.GameObject | undefined) {
+      if (obj && isDisplayObject(obj)) {
+        this.state.checkpoint = { x: obj.x, y: obj.y }
+      }
+    }
 
-        case 'sprite':
-          if (template.texture) {
-            const sprite = this.add.sprite(position.x, position.y, template.texture)
-            gameObject = sprite
+    /**
+     * Move an object back to the last checkpoint, or where it started
+     */
+    private respawn(obj: Phaser.GameObjects.GameObject | undefined, objectId: string) {
+      if (!obj?.active || !isDisplayObject(obj)) return
+
+      const start = sceneSpec.objects.find((objSpec) => objSpec.id === objectId)
+      const point = this.state.checkpoint ?? start
+      if (!point) return
+      if (hasArcadeBody(obj)) {
+        obj.body.reset(point.x, point.y)
+      } else {
+        obj.setPosition(point.x, point.y)
+      }
+    }
+
+    /**
+     * Send dynamic objects touching one portal of a pair out of the other.
+     * Objects that just came through can't go back until the cooldown is over.
+     */
+    private updatePortal(pair: PortalPair) {
+      const a = this.state.objects.get(pair.a)
+      const b = this.state.objects.get(pair.b)
+      if (!a?.active || !b?.active) return
+
+      const cooldown = pair.cooldown ?? 500
+      for (const obj of this.state.objects.values()) {
+        if (obj === a || obj === b || !hasArcadeBody(obj)) continue
+        const lastTeleport = (obj.getData('portalAt') as number | undefined) ?? -cooldown
+        if (this.time.now - lastTeleport < cooldown) continue
+
+        const exit = this.physics.overlap(obj, a) ? b : this.physics.overlap(obj, b) ? a : undefined
+        if (!exit || !isDisplayObject(exit)) continue
+
+        const velocity = obj.body.velocity.clone()
+        obj.body.reset(exit.x, exit.y)
+        if (pair.preserve_velocity ?? true) {
+          obj.body.setVelocity(velocity.x, velocity.y)
+        }
+        obj.setData('portalAt', this.time.now)
+      }
+    }
+
+    /**
+     * Run each zone's on_enter action when an object that triggers it comes
+     * in, and on_exit when it leaves. The object is the first of the pair and
+     * the zone the second, as if they'd overlapped.
+     */
+    private updateZones() {
+      for (const objSpec of sceneObjects(sceneSpec)) {
+        if (objSpec.type !== 'zone' || !objSpec.zone) continue
+        const zones = this.state.groups.get(objSpec.id)?.getChildren() ?? [this.state.objects.get(objSpec.id)]
+        for (const zone of zones) {
+          if (zone?.active) {
+            this.updateZone(zone, objSpec.zone)
           }
-          break
+        }
       }
+    }
 
-      if (!gameObject) {
-        return null
+    private updateZone(zone: Phaser.GameObjects.GameObject, props: ZoneProperties) {
+      let tracked = this.state.zones.get(zone)
+      if (!tracked) {
+        tracked = { inside: new Set(), entered: false, exited: false }
+        this.state.zones.set(zone, tracked)
+      }
+
+      const triggeredBy = props.triggered_by ?? 'player'
+      const candidates = this.state.groups.get(triggeredBy)?.getChildren() ?? [this.state.objects.get(triggeredBy)]
+      for (const obj of candidates) {
+        if (!obj) continue
+        // Destroyed objects don't leave, they're just gone
+        if (!obj.active) {
+          tracked.inside.delete(obj)
+          continue
+        }
+        const inside = hasBody(obj) && this.physics.overlap(obj, zone)
+        if (inside && !tracked.inside.has(obj)) {
+          tracked.inside.add(obj)
+          if (props.on_enter && !(props.once && tracked.entered)) {
+            tracked.entered = true
+            this.runAction(props.on_enter, zone, obj)
+          }
+        } else if (!inside && tracked.inside.has(obj)) {
+          tracked.inside.delete(obj)
+          if (props.on_exit && !(props.once && tracked.exited)) {
+            tracked.exited = true
+            this.runAction(props.on_exit, zone, obj)
+          }
+        }
       }
+    }
 
-      // Apply physics from template
-      if (template.physics) {
-        this.applyPhysics(gameObject, template.physics, template)
+    /**
+     * Give dynamic objects inside a gravity zone the zone's gravity, and
+     * objects outside every zone their own
+     */
+    private applyGravityZones(zones: GravityZone[]) {
+      const world = gameSpec.game.physics.gravity
+      for (const obj of this.state.objects.values()) {
+        if (!hasArcadeBody(obj) || !obj.body.allowGravity) continue
+        const { x, y } = obj.body.center
+        const zone = zones.find(
+          (zone) => Math.abs(x - zone.x) <= zone.width / 2 && Math.abs(y - zone.y) <= zone.height / 2
+        )
+        if (zone) {
+          obj.body.setGravity(zone.gravity.x - world.x, zone.gravity.y - world.y)
+        } else {
+          const own = obj.getData('gravity') as Phaser.Math.Vector2 | undefined
+          obj.body.setGravity(own?.x ?? 0, own?.y ?? 0)
+        }
       }
+    }
 
-      // Note: Behavior state is managed separately in this.state.behaviorState
-      // and initialized when the behavior handler is first called
+    /**
+     * Bring objects that left one edge of the world back on the opposite
+     * edge: those that wrap, or every dynamic object if the whole world wraps
+     */
+    private wrapObjects() {
+      const wrapWorld = gameSpec.game.physics.wrap_world
+      for (const objSpec of sceneObjects(sceneSpec)) {
+        if (!(objSpec.physics?.wrap_world ?? wrapWorld)) continue
+        const objects = this.state.groups.get(objSpec.id)?.getChildren() ?? [this.state.objects.get(objSpec.id)]
+        for (const obj of objects) {
+          if (obj && hasArcadeBody(obj)) {
+            this.physics.world.wrap(obj)
+          }
+        }
+      }
+    }
 
-      return gameObject
+    /**
+     * Carry objects along conveyors and launch them off bounce pads
+     */
+    private applySurfaces(delta: number) {
+      if (this.state.surfaces.length === 0) return
+
+      for (const obj of this.state.objects.values()) {
+        if (!hasArcadeBody(obj)) continue
+        const surface = this.surfaceUnder(obj.body)
+        if (!surface) continue
+        switch (surface.type) {
+          case 'conveyor':
+            // Moves the object rather than its body, which follows the object
+            if (isDisplayObject(obj)) {
+              obj.x += (surface.speed * delta) / 1000
+            }
+            break
+          case 'bounce':
+            obj.body.setVelocityY(-surface.impulse)
+            break
+        }
+      }
     }
 
     private handleControls(objSpec: GameObject) {
@@ -798,15 +2561,20 @@ function createSceneClass(sceneSpec: SceneSpec, gameSpec: PhaserGameSpec) {
         const leftKey = this.getKey(controls.left)
         const rightKey = this.getKey(controls.right)
 
-        // Reset horizontal velocity first
-        body.setVelocityX(0)
-
+        let velocityX = 0
         if (leftKey?.isDown) {
-          body.setVelocityX(-160)
+          velocityX = -160
         }
         if (rightKey?.isDown) {
-          body.setVelocityX(160)
+          velocityX = 160
         }
+
+        // On ice, objects only gradually pick up the speed of the controls
+        const surface = this.surfaceUnder(body)
+        if (surface?.type === 'ice') {
+          velocityX = body.velocity.x + (velocityX - body.velocity.x) * (surface.friction ?? 0.1)
+        }
+        body.setVelocityX(velocityX)
       }
 
       // Handle up/down (continuous movement, not gravity-based)
@@ -880,6 +2648,53 @@ function createSceneClass(sceneSpec: SceneSpec, gameSpec: PhaserGameSpec) {
       return this.state.customKeys.get(keyName)
     }
 
+    /**
+     * Track an object in its pool until it's destroyed
+     */
+    private addToPool(poolId: string, obj: Phaser.GameObjects.GameObject) {
+      let live = this.state.pools.get(poolId)
+      if (!live) {
+        live = []
+        this.state.pools.set(poolId, live)
+      }
+      const members = live
+      members.push(obj)
+      obj.once(Phaser.GameObjects.Events.DESTROY, () => {
+        const index = members.indexOf(obj)
+        if (index >= 0) members.splice(index, 1)
+      })
+    }
+
+    /**
+     * Whether a pool has no room for a new object. With the oldest recycle
+     * policy, the pool's oldest object starts over at (x, y) instead.
+     */
+    private poolIsFull(
+      poolId: string,
+      pool: PoolConfig | undefined,
+      objSpec: GameObject,
+      x: number,
+      y: number
+    ): boolean {
+      const live = this.state.pools.get(poolId) ?? []
+      if (!pool || live.length < pool.max_live) return false
+
+      const oldest = pool.recycle === 'oldest' ? live.shift() : undefined
+      if (oldest) {
+        live.push(oldest)
+        if (hasArcadeBody(oldest)) {
+          oldest.body.reset(x, y)
+          this.configureBody(oldest, objSpec)
+        } else if (isDisplayObject(oldest)) {
+          oldest.setPosition(x, y)
+        }
+        if (objSpec.health !== undefined) {
+          oldest.setData('health', objSpec.health)
+        }
+      }
+      return true
+    }
+
     private spawnProjectile(shooterSpec: GameObject, projectileTemplate: GameObject) {
       const shooter = this.state.objects.get(shooterSpec.id)
       if (!shooter || !hasBody(shooter)) return
@@ -896,49 +2711,22 @@ function createSceneClass(sceneSpec: SceneSpec, gameSpec: PhaserGameSpec) {
         spawnY = shooter.y as number
       }
 
-      // Create the projectile using the template
-      const projectile = this.createSpawnedObject(projectileTemplate, { x: spawnX, y: spawnY })
+      // A full pool skips this shot or fires its oldest projectile again
+      const poolId = `projectiles_${shooterSpec.id}`
+      const pool = shooterSpec.controls?.projectile_pool
+      if (this.poolIsFull(poolId, pool, projectileTemplate, spawnX, spawnY)) {
+        return
+      }
+
+      // Create the projectile using the template, in the projectiles group
+      // (which should already exist from create())
+      const projectilesGroup = this.state.groups.get('projectiles')
+      const projectile = this.buildObject(projectileTemplate, spawnX, spawnY, projectilesGroup)
 
       if (projectile && hasArcadeBody(projectile)) {
         // Store the projectile
         this.state.objects.set(projectileId, projectile)
 
-        // Add to projectiles group (should already exist from create())
-        const projectilesGroup = this.state.groups.get('projectiles')
-        if (projectilesGroup) {
-          projectilesGroup.add(projectile)
-
-          // IMPORTANT: Re-apply physics settings after adding to group
-          // Adding to group can reset body properties
-          const body = projectile.body
-
-          // Re-apply collision box for emoji projectiles
-          if (projectileTemplate.type === 'emoji' && projectileTemplate.emoji?.collision_box) {
-            const collisionBox = projectileTemplate.emoji.collision_box
-            if (collisionBox.shape === 'rectangle' && collisionBox.width && collisionBox.height) {
-              body.setSize(collisionBox.width, collisionBox.height)
-              if (projectile instanceof Phaser.GameObjects.Text) {
-                const offsetX = (projectile.width - collisionBox.width) / 2
-                const offsetY = (projectile.height - collisionBox.height) / 2
-                body.setOffset(offsetX, offsetY)
-              }
-            } else if (collisionBox.shape === 'circle' && collisionBox.radius) {
-              body.setCircle(collisionBox.radius)
-              if (projectile instanceof Phaser.GameObjects.Text) {
-                const diameter = collisionBox.radius * 2
-                const offsetX = (projectile.width - diameter) / 2
-                const offsetY = (projectile.height - diameter) / 2
-                body.setOffset(offsetX, offsetY)
-              }
-            }
-          }
-
-          // Re-apply velocity (adding to group can reset it)
-          if (projectileTemplate.physics?.velocity) {
-            body.setVelocity(projectileTemplate.physics.velocity.x, projectileTemplate.physics.velocity.y)
-          }
-        }
-
         // Auto-destroy projectile when it leaves the world bounds
         projectile.body.world.on('worldbounds', (body: Phaser.Physics.Arcade.Body) => {
           if (body.gameObject === projectile) {
@@ -946,6 +2734,7 @@ function createSceneClass(sceneSpec: SceneSpec, gameSpec: PhaserGameSpec) {
           }
         })
         projectile.body.setCollideWorldBounds(false)
+        this.addToPool(poolId, projectile)
 
         // Clean up when destroyed
         projectile.once('destroy', () => {
@@ -957,13 +2746,37 @@ function createSceneClass(sceneSpec: SceneSpec, gameSpec: PhaserGameSpec) {
       }
     }
 
+    /**
+     * Play an object's jump, walk, or idle animation for how it is moving
+     */
+    private updateAnimation(objSpec: GameObject) {
+      const obj = this.state.objects.get(objSpec.id)
+      if (!(obj instanceof Phaser.GameObjects.Sprite) || !objSpec.animations) return
+
+      const { idle, walk, jump } = objSpec.animations
+      let animation = idle
+      if (hasArcadeBody(obj)) {
+        const body = obj.body
+        const onGround = body.touching.down || body.blocked.down
+        if (jump && !onGround) {
+          animation = jump
+        } else if (walk && body.velocity.x !== 0) {
+          animation = walk
+        }
+      }
+      if (animation) {
+        obj.anims.play(animation, true)
+      }
+    }
+
     private handleBehavior(objSpec: GameObject) {
-      if (!objSpec.behavior) return
+      const behavior = behaviorOf(objSpec)
+      if (!behavior) return
 
       const obj = this.state.objects.get(objSpec.id)
       if (!obj || !hasArcadeBody(obj)) return
 
-      switch (objSpec.behavior) {
+      switch (behavior) {
         case 'patrol':
           this.handlePatrolBehavior(objSpec.id, obj, obj.body, objSpec.behavior_params)
           break
@@ -1063,14 +2876,16 @@ function createSceneClass(sceneSpec: SceneSpec, gameSpec: PhaserGameSpec) {
 }
 
 /**
- * Create a Phaser game from a PhaserGameSpec
+ * Create a Phaser game from a PhaserGameSpec; pass the id of a library game
+ * to save its high scores
  */
 export function createPhaserGame(
   spec: PhaserGameSpec,
-  parent: string | HTMLElement
+  parent: string | HTMLElement,
+  gameId?: string
 ): Phaser.Game {
   const sceneClasses = spec.scenes.map((sceneSpec) =>
-    createSceneClass(sceneSpec, spec)
+    createSceneClass(sceneSpec, spec, gameId)
   )
 
   const config: Phaser.Types.Core.GameConfig = {
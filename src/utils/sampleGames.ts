@@ -11,6 +11,7 @@ export const samplePlatformer: PhaserGameSpec = {
       enabled: true,
       gravity: { x: 0, y: 300 },
       debug: false,
+      wrap_world: false,
     },
   },
   assets: [],
@@ -194,6 +195,7 @@ export const sampleDodger: PhaserGameSpec = {
       enabled: true,
       gravity: { x: 0, y: 200 },
       debug: false,
+      wrap_world: false,
     },
   },
   assets: [],
@@ -0,0 +1,25 @@
+import { defineConfig } from 'vite'
+import path from 'path'
+
+// Builds the standalone game runtime into public/ so it ships with the app and
+// the backend can inline it into exported HTML files
+export default defineConfig({
+  resolve: {
+    alias: {
+      '@': path.resolve(__dirname, './src'),
+    },
+  },
+
+  publicDir: false,
+
+  build: {
+    outDir: 'public/runtime',
+    emptyOutDir: true,
+    lib: {
+      entry: path.resolve(__dirname, './src/runtime/standalone.ts'),
+      formats: ['iife'],
+      name: 'PueoRuntime',
+      fileName: () => 'pueo-runtime.js',
+    },
+  },
+})